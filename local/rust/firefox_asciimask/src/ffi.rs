@@ -22,6 +22,8 @@
 //! ```
 
 use crate::{ASCIIMaskArray, WHITESPACE_MASK, CRLF_MASK, CRLF_TAB_MASK, ZERO_TO_NINE_MASK};
+use crate::{ALPHANUMERIC_MASK, ALPHA_MASK, HEX_MASK, HTTP_TOKEN_MASK, URI_UNRESERVED_MASK};
+use crate::{strip_masked_slice, strip_masked_u16_slice};
 
 // ============================================================================
 // FFI Exports (C ABI)
@@ -87,6 +89,146 @@ pub extern "C" fn ASCIIMask_Mask0to9() -> *const ASCIIMaskArray {
     &ZERO_TO_NINE_MASK as *const ASCIIMaskArray
 }
 
+/// FFI: Get pointer to hexadecimal digit mask (0-9, a-f, A-F)
+///
+/// # Safety
+/// Returns a pointer to static data with 'static lifetime.
+/// Safe to call from C++. The returned pointer is never null.
+///
+/// # C++ Signature
+/// ```cpp
+/// extern "C" const ASCIIMaskArray* ASCIIMask_MaskHex();
+/// ```
+#[no_mangle]
+pub extern "C" fn ASCIIMask_MaskHex() -> *const ASCIIMaskArray {
+    &HEX_MASK as *const ASCIIMaskArray
+}
+
+/// FFI: Get pointer to ASCII letter mask (a-z, A-Z)
+///
+/// # Safety
+/// Returns a pointer to static data with 'static lifetime.
+/// Safe to call from C++. The returned pointer is never null.
+///
+/// # C++ Signature
+/// ```cpp
+/// extern "C" const ASCIIMaskArray* ASCIIMask_MaskAlpha();
+/// ```
+#[no_mangle]
+pub extern "C" fn ASCIIMask_MaskAlpha() -> *const ASCIIMaskArray {
+    &ALPHA_MASK as *const ASCIIMaskArray
+}
+
+/// FFI: Get pointer to alphanumeric mask (a-z, A-Z, 0-9)
+///
+/// # Safety
+/// Returns a pointer to static data with 'static lifetime.
+/// Safe to call from C++. The returned pointer is never null.
+///
+/// # C++ Signature
+/// ```cpp
+/// extern "C" const ASCIIMaskArray* ASCIIMask_MaskAlphanumeric();
+/// ```
+#[no_mangle]
+pub extern "C" fn ASCIIMask_MaskAlphanumeric() -> *const ASCIIMaskArray {
+    &ALPHANUMERIC_MASK as *const ASCIIMaskArray
+}
+
+/// FFI: Get pointer to URI unreserved character mask (RFC 3986)
+///
+/// # Safety
+/// Returns a pointer to static data with 'static lifetime.
+/// Safe to call from C++. The returned pointer is never null.
+///
+/// # C++ Signature
+/// ```cpp
+/// extern "C" const ASCIIMaskArray* ASCIIMask_MaskURIUnreserved();
+/// ```
+#[no_mangle]
+pub extern "C" fn ASCIIMask_MaskURIUnreserved() -> *const ASCIIMaskArray {
+    &URI_UNRESERVED_MASK as *const ASCIIMaskArray
+}
+
+/// FFI: Get pointer to HTTP token character mask (RFC 7230 tchar)
+///
+/// # Safety
+/// Returns a pointer to static data with 'static lifetime.
+/// Safe to call from C++. The returned pointer is never null.
+///
+/// # C++ Signature
+/// ```cpp
+/// extern "C" const ASCIIMaskArray* ASCIIMask_MaskHTTPToken();
+/// ```
+#[no_mangle]
+pub extern "C" fn ASCIIMask_MaskHTTPToken() -> *const ASCIIMaskArray {
+    &HTTP_TOKEN_MASK as *const ASCIIMaskArray
+}
+
+/// FFI: Strip all masked characters from a raw byte buffer, in place.
+///
+/// Implements the compaction loop of C++ `nsTSubstring::StripTaggedASCII`
+/// over a caller-owned buffer. Bytes past the returned length are
+/// unspecified; the caller is responsible for updating its own length
+/// bookkeeping (and null terminator, if any).
+///
+/// # Safety
+/// - `buf` must point to `len` valid, writable bytes (or be null with len 0).
+/// - `mask` must be a valid pointer to an ASCIIMaskArray (e.g. one returned
+///   by the `ASCIIMask_Mask*` accessors above).
+///
+/// # Returns
+/// The new logical length of the buffer, or 0 if either pointer is null.
+///
+/// # C++ Signature
+/// ```cpp
+/// extern "C" size_t ASCIIMask_StripInPlace(uint8_t* buf, size_t len,
+///                                          const ASCIIMaskArray* mask);
+/// ```
+#[no_mangle]
+pub unsafe extern "C" fn ASCIIMask_StripInPlace(
+    buf: *mut u8,
+    len: usize,
+    mask: *const ASCIIMaskArray,
+) -> usize {
+    if buf.is_null() || mask.is_null() {
+        return 0;
+    }
+    let slice = core::slice::from_raw_parts_mut(buf, len);
+    strip_masked_slice(slice, &*mask)
+}
+
+/// FFI: Strip all masked code units from a raw char16_t buffer, in place.
+///
+/// char16_t counterpart of [`ASCIIMask_StripInPlace`], for the
+/// `nsTSubstring<char16_t>` instantiation. `len` is in code units, not
+/// bytes, and the returned length is likewise in code units.
+///
+/// # Safety
+/// - `buf` must point to `len` valid, writable u16 code units (or be null
+///   with len 0).
+/// - `mask` must be a valid pointer to an ASCIIMaskArray.
+///
+/// # Returns
+/// The new logical length of the buffer, or 0 if either pointer is null.
+///
+/// # C++ Signature
+/// ```cpp
+/// extern "C" size_t ASCIIMask_StripInPlaceU16(char16_t* buf, size_t len,
+///                                             const ASCIIMaskArray* mask);
+/// ```
+#[no_mangle]
+pub unsafe extern "C" fn ASCIIMask_StripInPlaceU16(
+    buf: *mut u16,
+    len: usize,
+    mask: *const ASCIIMaskArray,
+) -> usize {
+    if buf.is_null() || mask.is_null() {
+        return 0;
+    }
+    let slice = core::slice::from_raw_parts_mut(buf, len);
+    strip_masked_u16_slice(slice, &*mask)
+}
+
 // ============================================================================
 // FFI Tests
 // ============================================================================
@@ -101,6 +243,11 @@ mod tests {
         assert!(!ASCIIMask_MaskCRLF().is_null());
         assert!(!ASCIIMask_MaskCRLFTab().is_null());
         assert!(!ASCIIMask_Mask0to9().is_null());
+        assert!(!ASCIIMask_MaskHex().is_null());
+        assert!(!ASCIIMask_MaskAlpha().is_null());
+        assert!(!ASCIIMask_MaskAlphanumeric().is_null());
+        assert!(!ASCIIMask_MaskURIUnreserved().is_null());
+        assert!(!ASCIIMask_MaskHTTPToken().is_null());
     }
 
     #[test]
@@ -120,9 +267,70 @@ mod tests {
             assert!(digit_mask[b'0' as usize]);
             assert!(digit_mask[b'9' as usize]);
             assert!(!digit_mask[b'a' as usize]);
+
+            let hex_mask = &*ASCIIMask_MaskHex();
+            assert!(hex_mask[b'f' as usize]);
+            assert!(!hex_mask[b'g' as usize]);
+
+            let token_mask = &*ASCIIMask_MaskHTTPToken();
+            assert!(token_mask[b'!' as usize]);
+            assert!(!token_mask[b'"' as usize]);
         }
     }
 
+    #[test]
+    fn test_ffi_strip_in_place() {
+        let mut buf = *b"a\r\nb\r\nc";
+        let new_len = unsafe {
+            ASCIIMask_StripInPlace(buf.as_mut_ptr(), buf.len(), ASCIIMask_MaskCRLF())
+        };
+        assert_eq!(new_len, 3);
+        assert_eq!(&buf[..new_len], b"abc");
+    }
+
+    #[test]
+    fn test_ffi_strip_in_place_u16() {
+        let mut buf: Vec<u16> = "a\r\nb\r\nc".encode_utf16().collect();
+        let new_len = unsafe {
+            ASCIIMask_StripInPlaceU16(buf.as_mut_ptr(), buf.len(), ASCIIMask_MaskCRLF())
+        };
+        assert_eq!(new_len, 3);
+        assert_eq!(String::from_utf16(&buf[..new_len]).unwrap(), "abc");
+
+        // Non-BMP data (surrogate pairs) passes through untouched
+        let mut buf: Vec<u16> = "x\t😀".encode_utf16().collect();
+        let new_len = unsafe {
+            ASCIIMask_StripInPlaceU16(buf.as_mut_ptr(), buf.len(), ASCIIMask_MaskWhitespace())
+        };
+        assert_eq!(String::from_utf16(&buf[..new_len]).unwrap(), "x😀");
+    }
+
+    #[test]
+    fn test_ffi_strip_null_safety() {
+        // Null buffer or mask returns 0 rather than crashing
+        let new_len = unsafe {
+            ASCIIMask_StripInPlace(core::ptr::null_mut(), 5, ASCIIMask_MaskCRLF())
+        };
+        assert_eq!(new_len, 0);
+
+        let mut buf = *b"abc";
+        let new_len = unsafe {
+            ASCIIMask_StripInPlace(buf.as_mut_ptr(), buf.len(), core::ptr::null())
+        };
+        assert_eq!(new_len, 0);
+
+        let new_len = unsafe {
+            ASCIIMask_StripInPlaceU16(core::ptr::null_mut(), 5, ASCIIMask_MaskCRLF())
+        };
+        assert_eq!(new_len, 0);
+
+        let mut buf = [b'a' as u16; 3];
+        let new_len = unsafe {
+            ASCIIMask_StripInPlaceU16(buf.as_mut_ptr(), buf.len(), core::ptr::null())
+        };
+        assert_eq!(new_len, 0);
+    }
+
     #[test]
     fn test_ffi_pointers_stable() {
         // Calling multiple times should return the same pointer (static data)
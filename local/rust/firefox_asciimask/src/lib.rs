@@ -33,14 +33,111 @@
 //! - Thread-safe (immutable data)
 //! - Cache-friendly (128-byte arrays fit in L1 cache)
 //! - Zero-cost abstraction (array access compiles to single memory load)
+//!
+//! ## Features
+//!
+//! The crate is `no_std`-capable so it can be linked into minimal
+//! targets and the standalone JS shell:
+//!
+//! - `std` (default): implies `alloc`; also required by `verify`
+//! - `alloc` (implied by `std`): the `Vec`/`String` strip helpers
+//!
+//! With no features enabled, the masks, classification helpers, slice
+//! strip/scan functions (including the SIMD paths, which use only
+//! baseline SSE2/NEON) and the FFI accessors remain available on core
+//! alone.
 
-#![no_std]
+#![cfg_attr(not(feature = "std"), no_std)]
+
+#[cfg(feature = "alloc")]
+extern crate alloc;
+
+#[cfg(feature = "alloc")]
+use alloc::{string::String, vec::Vec};
 
 pub mod ffi;
 
+#[cfg(feature = "verify")]
+pub mod verify;
+
 /// Type alias for ASCII mask arrays (128 booleans, one per ASCII character)
 pub type ASCIIMaskArray = [bool; 128];
 
+/// Type alias for full-byte-range mask arrays (256 booleans, one per byte
+/// value). Unlike [`ASCIIMaskArray`], these can classify non-ASCII bytes,
+/// which the Latin-1 stripping paths need.
+pub type ByteMaskArray = [bool; 256];
+
+// ============================================================================
+// Const Character Tables
+// ============================================================================
+
+/// Generic compile-time character lookup table: `N` entries of `T`, indexed
+/// by byte value.
+///
+/// This is the shared, audited construction path for the lookup tables the
+/// ported string components need: the boolean masks in this crate, the JSON
+/// two-character escape table in `firefox_jsonwriter`, and any future
+/// classification or escape tables. Building every table through the same
+/// few const constructors keeps the subtle parts (index arithmetic,
+/// defaulting) in one place.
+///
+/// # Examples
+/// ```
+/// use firefox_asciimask::CharTable;
+///
+/// // A 256-entry escape table with a handful of populated slots
+/// static ESCAPES: [u8; 256] =
+///     CharTable::with_entries(0, &[(b'\n', b'n'), (b'\t', b't')]).into_array();
+/// assert_eq!(ESCAPES[b'\n' as usize], b'n');
+/// assert_eq!(ESCAPES[b'x' as usize], 0);
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct CharTable<T, const N: usize> {
+    entries: [T; N],
+}
+
+impl<T: Copy, const N: usize> CharTable<T, N> {
+    /// Table with every entry set to `value`.
+    pub const fn filled(value: T) -> Self {
+        Self { entries: [value; N] }
+    }
+
+    /// Table with every entry set to `default`, then the given
+    /// `(index, value)` pairs applied in order.
+    pub const fn with_entries(default: T, entries: &[(u8, T)]) -> Self {
+        let mut table = [default; N];
+        let mut i = 0;
+        while i < entries.len() {
+            table[entries[i].0 as usize] = entries[i].1;
+            i += 1;
+        }
+        Self { entries: table }
+    }
+
+    /// Overwrite one entry. Usable while building a table in const context.
+    pub const fn set(&mut self, index: u8, value: T) {
+        self.entries[index as usize] = value;
+    }
+
+    /// Look up the entry for a byte.
+    #[inline(always)]
+    pub const fn get(&self, index: u8) -> T {
+        self.entries[index as usize]
+    }
+
+    /// Consume the table, yielding the raw array (for `static` declarations
+    /// that need the plain array type, e.g. for FFI export).
+    pub const fn into_array(self) -> [T; N] {
+        self.entries
+    }
+
+    /// Borrow the raw array.
+    pub const fn as_array(&self) -> &[T; N] {
+        &self.entries
+    }
+}
+
 // ============================================================================
 // Compile-Time Mask Generation
 // ============================================================================
@@ -99,6 +196,37 @@ const fn is_zero_to_nine(c: u8) -> bool {
     c == b'5' || c == b'6' || c == b'7' || c == b'8' || c == b'9'
 }
 
+/// Test if character is a hexadecimal digit: 0-9, a-f, A-F
+const fn is_hex(c: u8) -> bool {
+    is_zero_to_nine(c) || (c >= b'a' && c <= b'f') || (c >= b'A' && c <= b'F')
+}
+
+/// Test if character is an ASCII letter: a-z, A-Z
+const fn is_alpha(c: u8) -> bool {
+    (c >= b'a' && c <= b'z') || (c >= b'A' && c <= b'Z')
+}
+
+/// Test if character is an ASCII letter or digit
+const fn is_alphanumeric(c: u8) -> bool {
+    is_alpha(c) || is_zero_to_nine(c)
+}
+
+/// Test if character is unreserved in a URI (RFC 3986 section 2.3):
+/// ALPHA / DIGIT / "-" / "." / "_" / "~"
+const fn is_uri_unreserved(c: u8) -> bool {
+    is_alphanumeric(c) || c == b'-' || c == b'.' || c == b'_' || c == b'~'
+}
+
+/// Test if character is an HTTP token character (RFC 7230 section 3.2.6):
+/// "!" / "#" / "$" / "%" / "&" / "'" / "*" / "+" / "-" / "." / "^" / "_" /
+/// "`" / "|" / "~" / DIGIT / ALPHA
+const fn is_http_token(c: u8) -> bool {
+    is_alphanumeric(c) ||
+    c == b'!' || c == b'#' || c == b'$' || c == b'%' || c == b'&' ||
+    c == b'\'' || c == b'*' || c == b'+' || c == b'-' || c == b'.' ||
+    c == b'^' || c == b'_' || c == b'`' || c == b'|' || c == b'~'
+}
+
 // ============================================================================
 // Static Mask Arrays (Compile-Time Initialized)
 // ============================================================================
@@ -115,6 +243,21 @@ pub static CRLF_TAB_MASK: ASCIIMaskArray = create_mask!(is_crlf_tab);
 /// Mask for digit characters: 0-9
 pub static ZERO_TO_NINE_MASK: ASCIIMaskArray = create_mask!(is_zero_to_nine);
 
+/// Mask for hexadecimal digit characters: 0-9, a-f, A-F
+pub static HEX_MASK: ASCIIMaskArray = create_mask!(is_hex);
+
+/// Mask for ASCII letters: a-z, A-Z
+pub static ALPHA_MASK: ASCIIMaskArray = create_mask!(is_alpha);
+
+/// Mask for ASCII letters and digits
+pub static ALPHANUMERIC_MASK: ASCIIMaskArray = create_mask!(is_alphanumeric);
+
+/// Mask for URI unreserved characters (RFC 3986): ALPHA / DIGIT / - . _ ~
+pub static URI_UNRESERVED_MASK: ASCIIMaskArray = create_mask!(is_uri_unreserved);
+
+/// Mask for HTTP token characters (RFC 7230 tchar)
+pub static HTTP_TOKEN_MASK: ASCIIMaskArray = create_mask!(is_http_token);
+
 // ============================================================================
 // Helper Functions
 // ============================================================================
@@ -144,6 +287,566 @@ pub fn is_masked(mask: &ASCIIMaskArray, ch: u8) -> bool {
     ch < 128 && mask[ch as usize]
 }
 
+// ============================================================================
+// Bit-Packed Masks
+// ============================================================================
+
+/// Bit-packed representation of an ASCII mask: 128 bits in 16 bytes.
+///
+/// An [`ASCIIMaskArray`] trades space for a branchless one-byte load; this
+/// type makes the opposite trade for call sites where cache footprint
+/// matters more (the whole mask fits in a single cache line alongside other
+/// hot data). Membership testing costs a shift and a bit test instead of a
+/// plain byte load.
+///
+/// # Examples
+/// ```
+/// use firefox_asciimask::*;
+///
+/// static PACKED_CRLF: PackedASCIIMask = PackedASCIIMask::from_ascii(&CRLF_MASK);
+/// assert!(PACKED_CRLF.is_masked(b'\n'));
+/// assert!(!PACKED_CRLF.is_masked(b'a'));
+/// assert!(!PACKED_CRLF.is_masked(200)); // > 127, always false
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct PackedASCIIMask([u8; 16]);
+
+impl PackedASCIIMask {
+    /// Pack a 128-entry boolean mask into 16 bytes. Bit `ch & 7` of byte
+    /// `ch >> 3` holds the entry for character `ch`.
+    pub const fn from_ascii(mask: &ASCIIMaskArray) -> Self {
+        let mut bytes = [0u8; 16];
+        let mut ch = 0;
+        while ch < 128 {
+            if mask[ch] {
+                bytes[ch >> 3] |= 1 << (ch & 7);
+            }
+            ch += 1;
+        }
+        Self(bytes)
+    }
+
+    /// Check if a character is masked. Same contract as the free function
+    /// [`is_masked`]: characters >= 128 are never masked.
+    #[inline(always)]
+    pub const fn is_masked(&self, ch: u8) -> bool {
+        ch < 128 && (self.0[(ch >> 3) as usize] >> (ch & 7)) & 1 != 0
+    }
+
+    /// Expand back to the 128-entry boolean representation.
+    pub const fn to_ascii(&self) -> ASCIIMaskArray {
+        let mut mask = [false; 128];
+        let mut ch = 0;
+        while ch < 128 {
+            mask[ch] = (self.0[ch >> 3] >> (ch & 7)) & 1 != 0;
+            ch += 1;
+        }
+        mask
+    }
+
+    /// Raw 16-byte bitmap, in case C++ wants to embed it.
+    pub const fn as_bytes(&self) -> &[u8; 16] {
+        &self.0
+    }
+}
+
+impl From<&ASCIIMaskArray> for PackedASCIIMask {
+    fn from(mask: &ASCIIMaskArray) -> Self {
+        Self::from_ascii(mask)
+    }
+}
+
+// ============================================================================
+// Mask Set Algebra
+// ============================================================================
+
+/// Union of two masks: a character is masked if it is in either input.
+///
+/// Usable in const context so composite masks can be built at compile time
+/// instead of duplicating predicates:
+///
+/// ```
+/// use firefox_asciimask::*;
+///
+/// static WS_OR_DIGITS: ASCIIMaskArray = mask_union(&WHITESPACE_MASK, &ZERO_TO_NINE_MASK);
+/// assert!(is_masked(&WS_OR_DIGITS, b' '));
+/// assert!(is_masked(&WS_OR_DIGITS, b'7'));
+/// assert!(!is_masked(&WS_OR_DIGITS, b'a'));
+/// ```
+pub const fn mask_union(a: &ASCIIMaskArray, b: &ASCIIMaskArray) -> ASCIIMaskArray {
+    let mut out = [false; 128];
+    let mut i = 0;
+    while i < 128 {
+        out[i] = a[i] || b[i];
+        i += 1;
+    }
+    out
+}
+
+/// Intersection of two masks: a character is masked only if it is in both
+/// inputs.
+pub const fn mask_intersect(a: &ASCIIMaskArray, b: &ASCIIMaskArray) -> ASCIIMaskArray {
+    let mut out = [false; 128];
+    let mut i = 0;
+    while i < 128 {
+        out[i] = a[i] && b[i];
+        i += 1;
+    }
+    out
+}
+
+/// Complement of a mask within the ASCII range: every ASCII character not in
+/// the input is masked. Characters >= 128 remain unmasked, as always.
+pub const fn mask_invert(a: &ASCIIMaskArray) -> ASCIIMaskArray {
+    let mut out = [false; 128];
+    let mut i = 0;
+    while i < 128 {
+        out[i] = !a[i];
+        i += 1;
+    }
+    out
+}
+
+// ============================================================================
+// Full-Byte-Range Masks
+// ============================================================================
+
+/// Build a [`ByteMaskArray`] from a list of byte values.
+///
+/// Usable in const context, so composite byte masks can be built at compile
+/// time the same way the 128-entry masks are.
+///
+/// # Examples
+/// ```
+/// use firefox_asciimask::*;
+///
+/// static NBSP_MASK: ByteMaskArray = byte_mask_from_bytes(&[0xA0, b' ']);
+/// assert!(NBSP_MASK[0xA0]);
+/// assert!(NBSP_MASK[b' ' as usize]);
+/// assert!(!NBSP_MASK[b'x' as usize]);
+/// ```
+pub const fn byte_mask_from_bytes(bytes: &[u8]) -> ByteMaskArray {
+    let mut table: CharTable<bool, 256> = CharTable::filled(false);
+    let mut i = 0;
+    while i < bytes.len() {
+        table.set(bytes[i], true);
+        i += 1;
+    }
+    table.into_array()
+}
+
+/// Build a 128-entry [`ASCIIMaskArray`] from a list of ASCII characters.
+///
+/// Companion to [`byte_mask_from_bytes`] for the narrow mask type, for ad
+/// hoc masks that aren't worth a dedicated predicate. Panics at compile
+/// time if any character is >= 128.
+///
+/// # Examples
+/// ```
+/// use firefox_asciimask::*;
+///
+/// static COMMA_OR_SEMI: ASCIIMaskArray = mask_from_chars(b",;");
+/// assert!(is_masked(&COMMA_OR_SEMI, b','));
+/// assert!(!is_masked(&COMMA_OR_SEMI, b'.'));
+/// ```
+pub const fn mask_from_chars(chars: &[u8]) -> ASCIIMaskArray {
+    let mut table: CharTable<bool, 128> = CharTable::filled(false);
+    let mut i = 0;
+    while i < chars.len() {
+        assert!(chars[i] < 128, "mask_from_chars requires ASCII input");
+        table.set(chars[i], true);
+        i += 1;
+    }
+    table.into_array()
+}
+
+/// Widen a 128-entry [`ASCIIMaskArray`] to a full-byte-range mask.
+///
+/// Bytes 128-255 are unmasked, matching the `ch < 128` guard that
+/// [`is_masked`] applies to the narrow masks.
+pub const fn byte_mask_from_ascii(ascii: &ASCIIMaskArray) -> ByteMaskArray {
+    let mut table: CharTable<bool, 256> = CharTable::filled(false);
+    let mut i = 0;
+    while i < 128 {
+        table.set(i as u8, ascii[i]);
+        i += 1;
+    }
+    table.into_array()
+}
+
+/// Check if a byte is masked in a full-byte-range mask.
+///
+/// Unlike [`is_masked`] no range guard is needed: every byte value has an
+/// entry.
+#[inline(always)]
+pub fn is_masked_byte(mask: &ByteMaskArray, ch: u8) -> bool {
+    mask[ch as usize]
+}
+
+/// Remove all masked bytes from a byte slice using a full-byte-range mask,
+/// compacting in place. Counterpart of [`strip_masked_slice`] for Latin-1
+/// data where bytes >= 128 may need stripping too.
+///
+/// # Returns
+/// The new logical length of the buffer (number of bytes kept).
+pub fn strip_masked_bytes_slice(buf: &mut [u8], mask: &ByteMaskArray) -> usize {
+    let mut to = 0;
+    for from in 0..buf.len() {
+        let ch = buf[from];
+        if !is_masked_byte(mask, ch) {
+            buf[to] = ch;
+            to += 1;
+        }
+    }
+    to
+}
+
+// ============================================================================
+// SIMD Scanning
+// ============================================================================
+
+/// Maximum number of distinct masked characters the SIMD path will handle.
+/// The standard masks (whitespace, CRLF, CRLF+tab) all have 5 or fewer;
+/// denser masks fall back to the scalar loop.
+const SIMD_MAX_NEEDLES: usize = 8;
+
+/// Minimum haystack length before the SIMD path pays for its setup cost.
+const SIMD_MIN_LEN: usize = 16;
+
+/// Find the index of the first masked character in a byte slice.
+///
+/// This is the scanning half of a strip operation: callers can memmove the
+/// unmasked prefix wholesale instead of testing one byte at a time. On
+/// x86-64 (SSE2) and AArch64 (NEON) the haystack is compared 16 bytes per
+/// iteration against each masked character, which is a large win for the
+/// sparse masks this crate ships (at most 5 characters each).
+///
+/// # Examples
+/// ```
+/// use firefox_asciimask::*;
+///
+/// assert_eq!(find_first_masked(b"hello\r\nworld", &CRLF_MASK), Some(5));
+/// assert_eq!(find_first_masked(b"no newlines here", &CRLF_MASK), None);
+/// ```
+pub fn find_first_masked(haystack: &[u8], mask: &ASCIIMaskArray) -> Option<usize> {
+    #[cfg(any(target_arch = "x86_64", target_arch = "aarch64"))]
+    if haystack.len() >= SIMD_MIN_LEN {
+        if let Some(needles) = collect_needles(mask) {
+            // SAFETY: SSE2 is baseline on x86-64 and NEON is baseline on
+            // AArch64, so no runtime feature detection is required.
+            return unsafe { find_first_masked_simd(haystack, &needles) };
+        }
+    }
+    find_first_masked_scalar(haystack, mask)
+}
+
+/// Scalar reference implementation: one table lookup per byte.
+fn find_first_masked_scalar(haystack: &[u8], mask: &ASCIIMaskArray) -> Option<usize> {
+    haystack.iter().position(|&ch| is_masked(mask, ch))
+}
+
+/// Gather the masked characters into a small fixed buffer, or `None` if the
+/// mask is too dense for the SIMD compare-per-needle strategy.
+#[cfg(any(target_arch = "x86_64", target_arch = "aarch64"))]
+fn collect_needles(mask: &ASCIIMaskArray) -> Option<([u8; SIMD_MAX_NEEDLES], usize)> {
+    let mut needles = [0u8; SIMD_MAX_NEEDLES];
+    let mut count = 0;
+    for (ch, &masked) in mask.iter().enumerate() {
+        if masked {
+            if count == SIMD_MAX_NEEDLES {
+                return None;
+            }
+            needles[count] = ch as u8;
+            count += 1;
+        }
+    }
+    Some((needles, count))
+}
+
+/// SSE2 path: OR together a compare against each needle, 16 bytes at a time,
+/// then use the movemask bit position to locate the first hit.
+#[cfg(target_arch = "x86_64")]
+unsafe fn find_first_masked_simd(
+    haystack: &[u8],
+    (needles, count): &([u8; SIMD_MAX_NEEDLES], usize),
+) -> Option<usize> {
+    use core::arch::x86_64::*;
+
+    let mut splats = [_mm_setzero_si128(); SIMD_MAX_NEEDLES];
+    for (splat, &needle) in splats[..*count].iter_mut().zip(needles.iter()) {
+        *splat = _mm_set1_epi8(needle as i8);
+    }
+
+    let mut i = 0;
+    while i + 16 <= haystack.len() {
+        let chunk = _mm_loadu_si128(haystack.as_ptr().add(i) as *const __m128i);
+        let mut acc = _mm_setzero_si128();
+        for splat in &splats[..*count] {
+            acc = _mm_or_si128(acc, _mm_cmpeq_epi8(chunk, *splat));
+        }
+        let bits = _mm_movemask_epi8(acc) as u32;
+        if bits != 0 {
+            return Some(i + bits.trailing_zeros() as usize);
+        }
+        i += 16;
+    }
+
+    haystack[i..]
+        .iter()
+        .position(|ch| needles[..*count].contains(ch))
+        .map(|pos| i + pos)
+}
+
+/// NEON path: same strategy as SSE2; a nonzero lane maximum flags a hit and
+/// the exact offset is recovered with a short scalar scan of the chunk.
+#[cfg(target_arch = "aarch64")]
+unsafe fn find_first_masked_simd(
+    haystack: &[u8],
+    (needles, count): &([u8; SIMD_MAX_NEEDLES], usize),
+) -> Option<usize> {
+    use core::arch::aarch64::*;
+
+    let mut splats = [vdupq_n_u8(0); SIMD_MAX_NEEDLES];
+    for (splat, &needle) in splats[..*count].iter_mut().zip(needles.iter()) {
+        *splat = vdupq_n_u8(needle);
+    }
+
+    let mut i = 0;
+    while i + 16 <= haystack.len() {
+        let chunk = vld1q_u8(haystack.as_ptr().add(i));
+        let mut acc = vdupq_n_u8(0);
+        for splat in &splats[..*count] {
+            acc = vorrq_u8(acc, vceqq_u8(chunk, *splat));
+        }
+        if vmaxvq_u8(acc) != 0 {
+            let pos = haystack[i..i + 16]
+                .iter()
+                .position(|ch| needles[..*count].contains(ch))
+                .unwrap();
+            return Some(i + pos);
+        }
+        i += 16;
+    }
+
+    haystack[i..]
+        .iter()
+        .position(|ch| needles[..*count].contains(ch))
+        .map(|pos| i + pos)
+}
+
+// ============================================================================
+// UTF-16 Helpers
+// ============================================================================
+
+/// Check if a UTF-16 code unit is masked (with bounds checking).
+///
+/// Equivalent to C++ `aChar < 128 && aMask[aChar]` instantiated for
+/// char16_t, which is how most Gecko string operations consume these masks.
+///
+/// # Examples
+/// ```
+/// use firefox_asciimask::*;
+///
+/// assert!(is_masked_u16(&CRLF_MASK, '\n' as u16));
+/// assert!(!is_masked_u16(&CRLF_MASK, 'a' as u16));
+/// assert!(!is_masked_u16(&CRLF_MASK, 0x2028)); // LINE SEPARATOR, non-ASCII
+/// ```
+#[inline(always)]
+pub fn is_masked_u16(mask: &ASCIIMaskArray, ch: u16) -> bool {
+    ch < 128 && mask[ch as usize]
+}
+
+/// Find the index of the first masked code unit in a UTF-16 buffer.
+pub fn find_first_masked_u16(haystack: &[u16], mask: &ASCIIMaskArray) -> Option<usize> {
+    haystack.iter().position(|&ch| is_masked_u16(mask, ch))
+}
+
+/// Remove all masked code units from a UTF-16 slice, compacting in place.
+/// UTF-16 counterpart of [`strip_masked_slice`]; surrogates are never
+/// masked (they are >= 128), so pairs cannot be broken.
+///
+/// # Returns
+/// The new logical length of the buffer (number of code units kept).
+pub fn strip_masked_u16_slice(buf: &mut [u16], mask: &ASCIIMaskArray) -> usize {
+    let mut to = 0;
+    for from in 0..buf.len() {
+        let ch = buf[from];
+        if !is_masked_u16(mask, ch) {
+            buf[to] = ch;
+            to += 1;
+        }
+    }
+    to
+}
+
+/// Remove all masked code units from a `Vec<u16>` in place.
+///
+/// Equivalent to C++ `nsTString<char16_t>::StripTaggedASCII`.
+#[cfg(feature = "alloc")]
+pub fn strip_masked_u16(buf: &mut Vec<u16>, mask: &ASCIIMaskArray) {
+    let new_len = strip_masked_u16_slice(buf, mask);
+    buf.truncate(new_len);
+}
+
+// ============================================================================
+// Split and Trim Adapters
+// ============================================================================
+
+/// Iterator over the non-empty runs of unmasked bytes in a slice.
+/// Created by [`split_on_mask`].
+pub struct SplitOnMask<'a> {
+    remainder: &'a [u8],
+    mask: &'a ASCIIMaskArray,
+}
+
+impl<'a> Iterator for SplitOnMask<'a> {
+    type Item = &'a [u8];
+
+    fn next(&mut self) -> Option<&'a [u8]> {
+        // Skip the run of masked separators before the next token
+        while let Some(&ch) = self.remainder.first() {
+            if !is_masked(self.mask, ch) {
+                break;
+            }
+            self.remainder = &self.remainder[1..];
+        }
+        if self.remainder.is_empty() {
+            return None;
+        }
+        let end = find_first_masked(self.remainder, self.mask).unwrap_or(self.remainder.len());
+        let (token, rest) = self.remainder.split_at(end);
+        self.remainder = rest;
+        Some(token)
+    }
+}
+
+/// Split a byte slice on masked characters, yielding the non-empty tokens
+/// between them. Runs of adjacent separators are treated as one, like
+/// `str::split_whitespace`.
+///
+/// # Examples
+/// ```
+/// use firefox_asciimask::*;
+///
+/// let tokens: Vec<&[u8]> = split_on_mask(b"  a\tbc \n d ", &WHITESPACE_MASK).collect();
+/// assert_eq!(tokens, [b"a" as &[u8], b"bc", b"d"]);
+/// ```
+pub fn split_on_mask<'a>(data: &'a [u8], mask: &'a ASCIIMaskArray) -> SplitOnMask<'a> {
+    SplitOnMask { remainder: data, mask }
+}
+
+/// Trim masked characters from the start of a string slice.
+pub fn trim_mask_start<'a>(s: &'a str, mask: &ASCIIMaskArray) -> &'a str {
+    let start = s
+        .bytes()
+        .position(|ch| !is_masked(mask, ch))
+        .unwrap_or(s.len());
+    // Masked characters are ASCII, so `start` always lands on a char boundary
+    &s[start..]
+}
+
+/// Trim masked characters from the end of a string slice.
+pub fn trim_mask_end<'a>(s: &'a str, mask: &ASCIIMaskArray) -> &'a str {
+    let end = s.len()
+        - s.bytes()
+            .rev()
+            .position(|ch| !is_masked(mask, ch))
+            .unwrap_or(s.len());
+    &s[..end]
+}
+
+/// Trim masked characters from both ends of a string slice.
+///
+/// Equivalent to C++ `nsTSubstring::Trim` with a mask-defined character set.
+///
+/// # Examples
+/// ```
+/// use firefox_asciimask::*;
+///
+/// assert_eq!(trim_mask("\r\n value \r\n", &WHITESPACE_MASK), "value");
+/// assert_eq!(trim_mask("123abc456", &ZERO_TO_NINE_MASK), "abc");
+/// ```
+pub fn trim_mask<'a>(s: &'a str, mask: &ASCIIMaskArray) -> &'a str {
+    trim_mask_end(trim_mask_start(s, mask), mask)
+}
+
+// ============================================================================
+// Strip Functions (nsTString::StripTaggedASCII)
+// ============================================================================
+
+/// Remove all masked characters from a byte slice, compacting in place.
+///
+/// This is the core loop of C++ `nsTString::StripTaggedASCII`: a read cursor
+/// walks the buffer and a write cursor copies back only the characters that
+/// are not in the mask. Bytes past the returned length are left unspecified.
+///
+/// # Returns
+/// The new logical length of the buffer (number of bytes kept).
+///
+/// # Examples
+/// ```
+/// use firefox_asciimask::*;
+///
+/// let mut buf = *b"a\r\nb\r\nc";
+/// let len = strip_masked_slice(&mut buf, &CRLF_MASK);
+/// assert_eq!(&buf[..len], b"abc");
+/// ```
+pub fn strip_masked_slice(buf: &mut [u8], mask: &ASCIIMaskArray) -> usize {
+    let mut to = 0;
+    for from in 0..buf.len() {
+        let ch = buf[from];
+        if !is_masked(mask, ch) {
+            buf[to] = ch;
+            to += 1;
+        }
+    }
+    to
+}
+
+/// Remove all masked characters from a `Vec<u8>` in place.
+///
+/// Equivalent to C++ `nsTCString::StripTaggedASCII`: the vector is compacted
+/// with a single pass and truncated to the surviving length. No reallocation
+/// occurs.
+///
+/// # Examples
+/// ```
+/// use firefox_asciimask::*;
+///
+/// let mut buf = b" hello \t world \n".to_vec();
+/// strip_masked(&mut buf, &WHITESPACE_MASK);
+/// assert_eq!(buf, b"helloworld");
+/// ```
+#[cfg(feature = "alloc")]
+pub fn strip_masked(buf: &mut Vec<u8>, mask: &ASCIIMaskArray) {
+    let new_len = strip_masked_slice(buf, mask);
+    buf.truncate(new_len);
+}
+
+/// Remove all masked characters from a `String` in place.
+///
+/// Equivalent to C++ `nsTString<char>::StripTaggedASCII` operating on UTF-8
+/// data. Masked characters are always ASCII (< 128), and ASCII bytes never
+/// appear inside multi-byte UTF-8 sequences, so removing them preserves
+/// UTF-8 validity.
+///
+/// # Examples
+/// ```
+/// use firefox_asciimask::*;
+///
+/// let mut s = String::from("line1\r\nline2\r\n");
+/// strip_masked_str(&mut s, &CRLF_MASK);
+/// assert_eq!(s, "line1line2");
+/// ```
+#[cfg(feature = "alloc")]
+pub fn strip_masked_str(s: &mut String, mask: &ASCIIMaskArray) {
+    // SAFETY: strip_masked only removes bytes < 128, which are standalone
+    // ASCII code points in UTF-8, so the string remains valid UTF-8.
+    unsafe {
+        strip_masked(s.as_mut_vec(), mask);
+    }
+}
+
 // ============================================================================
 // Compile-Time Assertions
 // ============================================================================
@@ -172,6 +875,28 @@ const _: () = {
     assert!(ZERO_TO_NINE_MASK[b'9' as usize]);
     assert!(ZERO_TO_NINE_MASK[b'5' as usize]);
     assert!(!ZERO_TO_NINE_MASK[b'a' as usize]);
+
+    assert!(HEX_MASK[b'0' as usize]);
+    assert!(HEX_MASK[b'a' as usize]);
+    assert!(HEX_MASK[b'F' as usize]);
+    assert!(!HEX_MASK[b'g' as usize]);
+
+    assert!(ALPHA_MASK[b'a' as usize]);
+    assert!(ALPHA_MASK[b'Z' as usize]);
+    assert!(!ALPHA_MASK[b'0' as usize]);
+
+    assert!(ALPHANUMERIC_MASK[b'a' as usize]);
+    assert!(ALPHANUMERIC_MASK[b'0' as usize]);
+    assert!(!ALPHANUMERIC_MASK[b'-' as usize]);
+
+    assert!(URI_UNRESERVED_MASK[b'~' as usize]);
+    assert!(URI_UNRESERVED_MASK[b'-' as usize]);
+    assert!(!URI_UNRESERVED_MASK[b'/' as usize]);
+
+    assert!(HTTP_TOKEN_MASK[b'!' as usize]);
+    assert!(HTTP_TOKEN_MASK[b'|' as usize]);
+    assert!(!HTTP_TOKEN_MASK[b' ' as usize]);
+    assert!(!HTTP_TOKEN_MASK[b'"' as usize]);
 };
 
 // ============================================================================
@@ -242,6 +967,58 @@ mod tests {
         assert!(!ZERO_TO_NINE_MASK[0]);
     }
 
+    #[test]
+    fn test_hex_mask() {
+        for c in b'0'..=b'9' {
+            assert!(HEX_MASK[c as usize]);
+        }
+        for c in b'a'..=b'f' {
+            assert!(HEX_MASK[c as usize]);
+        }
+        for c in b'A'..=b'F' {
+            assert!(HEX_MASK[c as usize]);
+        }
+        assert!(!HEX_MASK[b'g' as usize]);
+        assert!(!HEX_MASK[b'G' as usize]);
+        assert!(!HEX_MASK[b' ' as usize]);
+    }
+
+    #[test]
+    fn test_alpha_and_alphanumeric_masks() {
+        for c in 0u8..128 {
+            assert_eq!(ALPHA_MASK[c as usize], c.is_ascii_alphabetic());
+            assert_eq!(ALPHANUMERIC_MASK[c as usize], c.is_ascii_alphanumeric());
+        }
+    }
+
+    #[test]
+    fn test_uri_unreserved_mask() {
+        // RFC 3986: unreserved = ALPHA / DIGIT / "-" / "." / "_" / "~"
+        for c in [b'a', b'Z', b'0', b'-', b'.', b'_', b'~'] {
+            assert!(URI_UNRESERVED_MASK[c as usize], "0x{:02X} should be unreserved", c);
+        }
+        for c in [b'/', b'?', b'#', b'%', b' ', b'+', b'='] {
+            assert!(!URI_UNRESERVED_MASK[c as usize], "0x{:02X} should be reserved", c);
+        }
+    }
+
+    #[test]
+    fn test_http_token_mask() {
+        // RFC 7230 tchar set
+        for c in b"!#$%&'*+-.^_`|~" {
+            assert!(HTTP_TOKEN_MASK[*c as usize], "0x{:02X} should be a tchar", c);
+        }
+        for c in 0u8..128 {
+            if c.is_ascii_alphanumeric() {
+                assert!(HTTP_TOKEN_MASK[c as usize]);
+            }
+        }
+        // Separators are not token characters
+        for c in b"\"(),/:;<=>?@[\\]{} \t" {
+            assert!(!HTTP_TOKEN_MASK[*c as usize], "0x{:02X} must not be a tchar", c);
+        }
+    }
+
     #[test]
     fn test_is_masked_helper() {
         // Valid ASCII range
@@ -266,6 +1043,347 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_char_table_constructors() {
+        static FILLED: CharTable<u8, 4> = CharTable::filled(7);
+        assert_eq!(FILLED.as_array(), &[7, 7, 7, 7]);
+
+        static ESCAPES: CharTable<u8, 256> =
+            CharTable::with_entries(0, &[(b'\n', b'n'), (b'\t', b't')]);
+        assert_eq!(ESCAPES.get(b'\n'), b'n');
+        assert_eq!(ESCAPES.get(b'\t'), b't');
+        assert_eq!(ESCAPES.get(b'x'), 0);
+        assert_eq!(ESCAPES.get(0xFF), 0);
+    }
+
+    #[test]
+    fn test_char_table_set_and_into_array() {
+        const TABLE: [bool; 128] = {
+            let mut t: CharTable<bool, 128> = CharTable::filled(false);
+            t.set(b'a', true);
+            t.into_array()
+        };
+        assert!(TABLE[b'a' as usize]);
+        assert!(!TABLE[b'b' as usize]);
+    }
+
+    #[test]
+    fn test_mask_from_chars() {
+        static COMMA_OR_SEMI: ASCIIMaskArray = mask_from_chars(b",;");
+        for c in 0u8..128 {
+            assert_eq!(COMMA_OR_SEMI[c as usize], c == b',' || c == b';');
+        }
+        // Agrees with the predicate-built masks for the same character set
+        static CRLF_FROM_CHARS: ASCIIMaskArray = mask_from_chars(b"\r\n");
+        assert_eq!(CRLF_FROM_CHARS, CRLF_MASK);
+    }
+
+    #[test]
+    fn test_packed_mask_size() {
+        assert_eq!(core::mem::size_of::<PackedASCIIMask>(), 16);
+    }
+
+    #[test]
+    fn test_packed_mask_equivalence() {
+        // Packed and unpacked representations must agree for every character
+        for mask in [&WHITESPACE_MASK, &CRLF_MASK, &CRLF_TAB_MASK, &ZERO_TO_NINE_MASK,
+                     &HEX_MASK, &ALPHA_MASK, &ALPHANUMERIC_MASK, &HTTP_TOKEN_MASK] {
+            let packed = PackedASCIIMask::from_ascii(mask);
+            for ch in 0u8..=255 {
+                assert_eq!(packed.is_masked(ch), is_masked(mask, ch), "mismatch at 0x{:02X}", ch);
+            }
+        }
+    }
+
+    #[test]
+    fn test_packed_mask_round_trip() {
+        let packed = PackedASCIIMask::from_ascii(&WHITESPACE_MASK);
+        assert_eq!(packed.to_ascii(), WHITESPACE_MASK);
+
+        // From impl matches const constructor
+        assert_eq!(PackedASCIIMask::from(&WHITESPACE_MASK), packed);
+    }
+
+    #[test]
+    #[ignore] // benchmark; run with: cargo test --release -- --ignored bench_
+    fn bench_packed_vs_unpacked() {
+        use std::time::Instant;
+
+        let data: Vec<u8> = (0..10_000_000u32).map(|i| (i % 251) as u8).collect();
+        let packed = PackedASCIIMask::from_ascii(&WHITESPACE_MASK);
+
+        let start = Instant::now();
+        let unpacked_hits = data.iter().filter(|&&c| is_masked(&WHITESPACE_MASK, c)).count();
+        let unpacked_time = start.elapsed();
+
+        let start = Instant::now();
+        let packed_hits = data.iter().filter(|&&c| packed.is_masked(c)).count();
+        let packed_time = start.elapsed();
+
+        assert_eq!(unpacked_hits, packed_hits);
+        println!(
+            "unpacked: {:?} ({} hits), packed: {:?} ({} hits)",
+            unpacked_time, unpacked_hits, packed_time, packed_hits
+        );
+    }
+
+    #[test]
+    fn test_mask_union() {
+        static WS_OR_DIGITS: ASCIIMaskArray = mask_union(&WHITESPACE_MASK, &ZERO_TO_NINE_MASK);
+        for c in 0u8..128 {
+            assert_eq!(
+                WS_OR_DIGITS[c as usize],
+                WHITESPACE_MASK[c as usize] || ZERO_TO_NINE_MASK[c as usize]
+            );
+        }
+    }
+
+    #[test]
+    fn test_mask_intersect() {
+        // Hex ∩ alpha = a-f, A-F
+        static HEX_LETTERS: ASCIIMaskArray = mask_intersect(&HEX_MASK, &ALPHA_MASK);
+        for c in 0u8..128 {
+            let expected = matches!(c, b'a'..=b'f' | b'A'..=b'F');
+            assert_eq!(HEX_LETTERS[c as usize], expected, "mismatch at 0x{:02X}", c);
+        }
+    }
+
+    #[test]
+    fn test_mask_invert() {
+        static NON_DIGITS: ASCIIMaskArray = mask_invert(&ZERO_TO_NINE_MASK);
+        for c in 0u8..128 {
+            assert_eq!(NON_DIGITS[c as usize], !ZERO_TO_NINE_MASK[c as usize]);
+        }
+        // Double inversion round-trips
+        static BACK: ASCIIMaskArray = mask_invert(&NON_DIGITS);
+        assert_eq!(BACK, ZERO_TO_NINE_MASK);
+    }
+
+    #[test]
+    fn test_byte_mask_from_bytes() {
+        static LATIN1_WS: ByteMaskArray = byte_mask_from_bytes(&[b' ', b'\t', 0xA0]);
+        assert!(LATIN1_WS[b' ' as usize]);
+        assert!(LATIN1_WS[b'\t' as usize]);
+        assert!(LATIN1_WS[0xA0]); // non-breaking space, unreachable for ASCIIMaskArray
+        assert!(!LATIN1_WS[b'x' as usize]);
+        assert!(!LATIN1_WS[0xFF]);
+    }
+
+    #[test]
+    fn test_byte_mask_from_ascii() {
+        static WIDE_CRLF: ByteMaskArray = byte_mask_from_ascii(&CRLF_MASK);
+        // Low half matches the source mask exactly
+        for c in 0..128usize {
+            assert_eq!(WIDE_CRLF[c], CRLF_MASK[c]);
+        }
+        // High half is all unmasked
+        for &masked in &WIDE_CRLF[128..] {
+            assert!(!masked);
+        }
+    }
+
+    #[test]
+    fn test_is_masked_byte() {
+        static MASK: ByteMaskArray = byte_mask_from_bytes(&[0x00, 0x7F, 0x80, 0xFF]);
+        assert!(is_masked_byte(&MASK, 0x00));
+        assert!(is_masked_byte(&MASK, 0x7F));
+        assert!(is_masked_byte(&MASK, 0x80));
+        assert!(is_masked_byte(&MASK, 0xFF));
+        assert!(!is_masked_byte(&MASK, b'a'));
+    }
+
+    #[test]
+    fn test_strip_masked_bytes_slice() {
+        static MASK: ByteMaskArray = byte_mask_from_bytes(&[0xA0, b' ']);
+        let mut buf = [b'a', 0xA0, b'b', b' ', b'c', 0xFF];
+        let len = strip_masked_bytes_slice(&mut buf, &MASK);
+        assert_eq!(&buf[..len], &[b'a', b'b', b'c', 0xFF]);
+    }
+
+    #[test]
+    fn test_find_first_masked_basic() {
+        assert_eq!(find_first_masked(b"hello\r\nworld", &CRLF_MASK), Some(5));
+        assert_eq!(find_first_masked(b"\rimmediate", &CRLF_MASK), Some(0));
+        assert_eq!(find_first_masked(b"no newlines here at all", &CRLF_MASK), None);
+        assert_eq!(find_first_masked(b"", &CRLF_MASK), None);
+    }
+
+    #[test]
+    fn test_find_first_masked_long_input() {
+        // Long enough to exercise the SIMD path on x86-64/AArch64
+        let mut data = vec![b'x'; 1000];
+        assert_eq!(find_first_masked(&data, &WHITESPACE_MASK), None);
+
+        // Hit in the middle of a 16-byte chunk
+        data[517] = b'\t';
+        assert_eq!(find_first_masked(&data, &WHITESPACE_MASK), Some(517));
+
+        // Hit in the scalar tail (length not a multiple of 16)
+        let mut data = vec![b'x'; 1003];
+        data[1001] = b'\n';
+        assert_eq!(find_first_masked(&data, &CRLF_MASK), Some(1001));
+    }
+
+    #[test]
+    fn test_find_first_masked_matches_scalar() {
+        // SIMD and scalar paths must agree on every offset and mask
+        let mut data = vec![b'a'; 64];
+        for pos in 0..64 {
+            for ch in [b'\r', b'\n', b'\t', b' ', b'\x0C', b'5'] {
+                data[pos] = ch;
+                for mask in [&WHITESPACE_MASK, &CRLF_MASK, &CRLF_TAB_MASK, &ZERO_TO_NINE_MASK] {
+                    let expected = data.iter().position(|&c| is_masked(mask, c));
+                    assert_eq!(find_first_masked(&data, mask), expected);
+                }
+                data[pos] = b'a';
+            }
+        }
+    }
+
+    #[test]
+    fn test_find_first_masked_dense_mask() {
+        // A mask with more than 8 entries takes the scalar fallback
+        let mut dense: ASCIIMaskArray = [false; 128];
+        for ch in b'a'..=b'z' {
+            dense[ch as usize] = true;
+        }
+        let mut data = vec![b'0'; 100];
+        data[73] = b'q';
+        assert_eq!(find_first_masked(&data, &dense), Some(73));
+    }
+
+    #[test]
+    fn test_is_masked_u16() {
+        assert!(is_masked_u16(&CRLF_MASK, '\n' as u16));
+        assert!(is_masked_u16(&CRLF_MASK, '\r' as u16));
+        assert!(!is_masked_u16(&CRLF_MASK, 'a' as u16));
+        // Non-ASCII code units are never masked, including Unicode line
+        // separators and surrogate halves
+        assert!(!is_masked_u16(&CRLF_MASK, 0x2028));
+        assert!(!is_masked_u16(&WHITESPACE_MASK, 0x00A0));
+        assert!(!is_masked_u16(&CRLF_MASK, 0xD800));
+        assert!(!is_masked_u16(&CRLF_MASK, 0xFFFF));
+    }
+
+    #[test]
+    fn test_find_first_masked_u16() {
+        let data: Vec<u16> = "hello\r\nworld".encode_utf16().collect();
+        assert_eq!(find_first_masked_u16(&data, &CRLF_MASK), Some(5));
+        assert_eq!(find_first_masked_u16(&data, &ZERO_TO_NINE_MASK), None);
+        assert_eq!(find_first_masked_u16(&[], &CRLF_MASK), None);
+    }
+
+    #[test]
+    fn test_strip_masked_u16() {
+        let mut data: Vec<u16> = "line1\r\nline2\r\n".encode_utf16().collect();
+        strip_masked_u16(&mut data, &CRLF_MASK);
+        assert_eq!(String::from_utf16(&data).unwrap(), "line1line2");
+
+        // Surrogate pairs (non-BMP characters) survive stripping
+        let mut data: Vec<u16> = "a\t😀\tb".encode_utf16().collect();
+        strip_masked_u16(&mut data, &WHITESPACE_MASK);
+        assert_eq!(String::from_utf16(&data).unwrap(), "a😀b");
+    }
+
+    #[test]
+    fn test_strip_masked_u16_slice() {
+        let mut buf: Vec<u16> = "a\rb\nc".encode_utf16().collect();
+        let len = strip_masked_u16_slice(&mut buf, &CRLF_MASK);
+        assert_eq!(len, 3);
+        assert_eq!(String::from_utf16(&buf[..len]).unwrap(), "abc");
+    }
+
+    #[test]
+    fn test_split_on_mask() {
+        let tokens: Vec<&[u8]> = split_on_mask(b"a\tbc \n d", &WHITESPACE_MASK).collect();
+        assert_eq!(tokens, [b"a" as &[u8], b"bc", b"d"]);
+
+        // Leading/trailing separators produce no empty tokens
+        let tokens: Vec<&[u8]> = split_on_mask(b"  x  ", &WHITESPACE_MASK).collect();
+        assert_eq!(tokens, [b"x" as &[u8]]);
+
+        // All separators, or empty input: no tokens
+        assert_eq!(split_on_mask(b" \t\n ", &WHITESPACE_MASK).count(), 0);
+        assert_eq!(split_on_mask(b"", &WHITESPACE_MASK).count(), 0);
+
+        // No separators: one token covering the whole input
+        let tokens: Vec<&[u8]> = split_on_mask(b"abc", &WHITESPACE_MASK).collect();
+        assert_eq!(tokens, [b"abc" as &[u8]]);
+    }
+
+    #[test]
+    fn test_trim_mask() {
+        assert_eq!(trim_mask("\r\n value \r\n", &WHITESPACE_MASK), "value");
+        assert_eq!(trim_mask("123abc456", &ZERO_TO_NINE_MASK), "abc");
+        assert_eq!(trim_mask("no-trim", &CRLF_MASK), "no-trim");
+        assert_eq!(trim_mask("", &WHITESPACE_MASK), "");
+        assert_eq!(trim_mask(" \t ", &WHITESPACE_MASK), "");
+        // Interior masked characters are untouched
+        assert_eq!(trim_mask(" a b ", &WHITESPACE_MASK), "a b");
+        // Multi-byte UTF-8 at the boundary is preserved
+        assert_eq!(trim_mask(" café ", &WHITESPACE_MASK), "café");
+    }
+
+    #[test]
+    fn test_trim_mask_one_sided() {
+        assert_eq!(trim_mask_start("  x  ", &WHITESPACE_MASK), "x  ");
+        assert_eq!(trim_mask_end("  x  ", &WHITESPACE_MASK), "  x");
+        assert_eq!(trim_mask_start("", &WHITESPACE_MASK), "");
+        assert_eq!(trim_mask_end("", &WHITESPACE_MASK), "");
+    }
+
+    #[test]
+    fn test_strip_masked_slice() {
+        let mut buf = *b"a\rb\nc";
+        let len = strip_masked_slice(&mut buf, &CRLF_MASK);
+        assert_eq!(len, 3);
+        assert_eq!(&buf[..len], b"abc");
+
+        // Nothing masked: buffer unchanged
+        let mut buf = *b"abc";
+        let len = strip_masked_slice(&mut buf, &CRLF_MASK);
+        assert_eq!(len, 3);
+        assert_eq!(&buf[..len], b"abc");
+
+        // Everything masked: empty result
+        let mut buf = *b"\r\n\r\n";
+        let len = strip_masked_slice(&mut buf, &CRLF_MASK);
+        assert_eq!(len, 0);
+    }
+
+    #[test]
+    fn test_strip_masked_vec() {
+        let mut buf = b" hello \t world \n".to_vec();
+        strip_masked(&mut buf, &WHITESPACE_MASK);
+        assert_eq!(buf, b"helloworld");
+
+        // Empty vector is a no-op
+        let mut buf: Vec<u8> = Vec::new();
+        strip_masked(&mut buf, &WHITESPACE_MASK);
+        assert!(buf.is_empty());
+
+        // Non-ASCII bytes (> 127) are never stripped
+        let mut buf = vec![0xC3, 0xA9, b'\r', 0xFF];
+        strip_masked(&mut buf, &CRLF_MASK);
+        assert_eq!(buf, vec![0xC3, 0xA9, 0xFF]);
+    }
+
+    #[test]
+    fn test_strip_masked_str() {
+        let mut s = String::from("line1\r\nline2\r\n");
+        strip_masked_str(&mut s, &CRLF_MASK);
+        assert_eq!(s, "line1line2");
+
+        // Multi-byte UTF-8 survives stripping intact
+        let mut s = String::from("café\r\nnaïve\n");
+        strip_masked_str(&mut s, &CRLF_MASK);
+        assert_eq!(s, "cafénaïve");
+
+        let mut s = String::from("abc123def");
+        strip_masked_str(&mut s, &ZERO_TO_NINE_MASK);
+        assert_eq!(s, "abcdef");
+    }
+
     #[test]
     fn test_all_whitespace() {
         let whitespace_chars = [b' ', b'\t', b'\n', b'\r', b'\x0C'];
@@ -0,0 +1,197 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+//! Exhaustive mask verification (feature = "verify")
+//!
+//! Every mask in this crate is a compile-time table derived from a predicate.
+//! A table edit that drifts from its predicate is silent data corruption, so
+//! this module re-checks each mask against its source predicate for all
+//! 0..=255 inputs and cross-checks that the FFI accessors hand out pointers
+//! to the same static data. The result is a machine-readable JSON report
+//! suitable for gating table edits in automation.
+//!
+//! ## Usage
+//! ```text
+//! cargo test --features verify
+//! ```
+//! or programmatically:
+//! ```
+//! use firefox_asciimask::verify::verify_all;
+//!
+//! let report = verify_all();
+//! assert!(report.passed());
+//! println!("{}", report.to_json());
+//! ```
+
+use crate::ffi::*;
+use crate::*;
+
+/// Result of verifying one mask against its predicate and FFI accessor.
+pub struct MaskCheck {
+    /// Name of the mask static, e.g. `"WHITESPACE_MASK"`.
+    pub name: &'static str,
+    /// Byte values (0..=255) where the table disagrees with the predicate.
+    pub mismatches: Vec<u8>,
+    /// Whether the FFI accessor returned a pointer to this mask's static.
+    pub ffi_pointer_ok: bool,
+}
+
+impl MaskCheck {
+    /// True if the table matches its predicate everywhere and the FFI
+    /// pointer checks out.
+    pub fn passed(&self) -> bool {
+        self.mismatches.is_empty() && self.ffi_pointer_ok
+    }
+}
+
+/// Aggregate verification report over all masks in the crate.
+pub struct VerificationReport {
+    pub checks: Vec<MaskCheck>,
+}
+
+impl VerificationReport {
+    /// True if every individual check passed.
+    pub fn passed(&self) -> bool {
+        self.checks.iter().all(MaskCheck::passed)
+    }
+
+    /// Render the report as JSON. No external dependencies: the shape is
+    /// flat and every value is a name, bool, or byte list.
+    pub fn to_json(&self) -> String {
+        let mut out = String::from("{\"passed\":");
+        out.push_str(if self.passed() { "true" } else { "false" });
+        out.push_str(",\"checks\":[");
+        for (i, check) in self.checks.iter().enumerate() {
+            if i > 0 {
+                out.push(',');
+            }
+            out.push_str("{\"name\":\"");
+            out.push_str(check.name);
+            out.push_str("\",\"passed\":");
+            out.push_str(if check.passed() { "true" } else { "false" });
+            out.push_str(",\"ffi_pointer_ok\":");
+            out.push_str(if check.ffi_pointer_ok { "true" } else { "false" });
+            out.push_str(",\"mismatches\":[");
+            for (j, ch) in check.mismatches.iter().enumerate() {
+                if j > 0 {
+                    out.push(',');
+                }
+                out.push_str(&ch.to_string());
+            }
+            out.push_str("]}");
+        }
+        out.push_str("]}");
+        out
+    }
+}
+
+/// Check one mask against its predicate for every byte value, and confirm
+/// the FFI accessor returns a pointer to the same static.
+fn check_mask(
+    name: &'static str,
+    mask: &'static ASCIIMaskArray,
+    predicate: fn(u8) -> bool,
+    accessor: extern "C" fn() -> *const ASCIIMaskArray,
+) -> MaskCheck {
+    let mut mismatches = Vec::new();
+    for ch in 0u8..=255 {
+        let expected = ch < 128 && predicate(ch);
+        if is_masked(mask, ch) != expected {
+            mismatches.push(ch);
+        }
+    }
+    let ffi_pointer_ok = std::ptr::eq(accessor(), mask);
+    MaskCheck { name, mismatches, ffi_pointer_ok }
+}
+
+/// Verify every mask shipped by this crate.
+pub fn verify_all() -> VerificationReport {
+    let checks = vec![
+        check_mask(
+            "WHITESPACE_MASK",
+            &WHITESPACE_MASK,
+            is_whitespace,
+            ASCIIMask_MaskWhitespace,
+        ),
+        check_mask("CRLF_MASK", &CRLF_MASK, is_crlf, ASCIIMask_MaskCRLF),
+        check_mask(
+            "CRLF_TAB_MASK",
+            &CRLF_TAB_MASK,
+            is_crlf_tab,
+            ASCIIMask_MaskCRLFTab,
+        ),
+        check_mask(
+            "ZERO_TO_NINE_MASK",
+            &ZERO_TO_NINE_MASK,
+            is_zero_to_nine,
+            ASCIIMask_Mask0to9,
+        ),
+        check_mask("HEX_MASK", &HEX_MASK, is_hex, ASCIIMask_MaskHex),
+        check_mask("ALPHA_MASK", &ALPHA_MASK, is_alpha, ASCIIMask_MaskAlpha),
+        check_mask(
+            "ALPHANUMERIC_MASK",
+            &ALPHANUMERIC_MASK,
+            is_alphanumeric,
+            ASCIIMask_MaskAlphanumeric,
+        ),
+        check_mask(
+            "URI_UNRESERVED_MASK",
+            &URI_UNRESERVED_MASK,
+            is_uri_unreserved,
+            ASCIIMask_MaskURIUnreserved,
+        ),
+        check_mask(
+            "HTTP_TOKEN_MASK",
+            &HTTP_TOKEN_MASK,
+            is_http_token,
+            ASCIIMask_MaskHTTPToken,
+        ),
+    ];
+    VerificationReport { checks }
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_verify_all_passes() {
+        let report = verify_all();
+        assert!(report.passed(), "report: {}", report.to_json());
+        assert_eq!(report.checks.len(), 9);
+        for check in &report.checks {
+            assert!(check.mismatches.is_empty(), "{} has mismatches", check.name);
+            assert!(check.ffi_pointer_ok, "{} FFI pointer mismatch", check.name);
+        }
+    }
+
+    #[test]
+    fn test_report_json_shape() {
+        let report = verify_all();
+        let json = report.to_json();
+        assert!(json.starts_with("{\"passed\":true"));
+        assert!(json.contains("\"name\":\"WHITESPACE_MASK\""));
+        assert!(json.contains("\"mismatches\":[]"));
+        assert!(json.ends_with("]}"));
+    }
+
+    #[test]
+    fn test_failing_check_is_reported() {
+        // A deliberately wrong check must show up in the report
+        let mut report = verify_all();
+        report.checks.push(MaskCheck {
+            name: "BROKEN_MASK",
+            mismatches: vec![65, 66],
+            ffi_pointer_ok: true,
+        });
+        assert!(!report.passed());
+        let json = report.to_json();
+        assert!(json.starts_with("{\"passed\":false"));
+        assert!(json.contains("\"mismatches\":[65,66]"));
+    }
+}
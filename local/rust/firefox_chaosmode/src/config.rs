@@ -0,0 +1,347 @@
+// -*- Mode: Rust; tab-width: 4; indent-tabs-mode: nil; c-basic-offset: 4 -*-
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+//! Typed chaos configuration
+//!
+//! [`ChaosConfig`] gathers everything that shapes a chaos run — the
+//! feature set, the replay seed, per-feature weights, and thread-name
+//! allow/deny lists — into one value that is built up with a typed
+//! builder, applied in one step, serialized to a line of text for logs
+//! and CI artifacts, and parsed back. It replaces sequences of ad-hoc
+//! [`crate::set_chaos_feature`] / [`crate::set_feature_probability`]
+//! calls that could be observed half-applied.
+
+use crate::{ChaosFeature, FEATURE_COUNT};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Mutex;
+
+/// A complete chaos configuration, built with the builder methods and
+/// installed with [`ChaosConfig::apply`].
+///
+/// # Example
+/// ```
+/// use firefox_chaosmode::{ChaosConfig, ChaosFeature};
+///
+/// let config = ChaosConfig::new()
+///     .features(ChaosFeature::TimerScheduling as u32 | ChaosFeature::IOAmounts as u32)
+///     .seed(0xDECAF)
+///     .probability(ChaosFeature::IOAmounts, 250)
+///     .deny_thread("Compositor");
+/// let line = config.to_config_string();
+/// assert_eq!(ChaosConfig::from_config_string(&line), Some(config));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ChaosConfig {
+    /// Enabled feature bitmask
+    features: u32,
+    /// Replay seed; `None` keeps the current (or lazily chosen) seed
+    seed: Option<u64>,
+    /// Per-feature permille overrides; `None` means the default of 1000
+    probabilities: [Option<u32>; FEATURE_COUNT],
+    /// If non-empty, chaos only applies on threads with these names
+    allowed_threads: Vec<String>,
+    /// Chaos never applies on threads with these names
+    denied_threads: Vec<String>,
+}
+
+impl Default for ChaosConfig {
+    fn default() -> Self {
+        Self {
+            features: ChaosFeature::Any as u32,
+            seed: None,
+            probabilities: [None; FEATURE_COUNT],
+            allowed_threads: Vec::new(),
+            denied_threads: Vec::new(),
+        }
+    }
+}
+
+impl ChaosConfig {
+    /// Start from the default configuration (all features, default
+    /// weights, no thread filter, keep the current seed).
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the enabled feature bitmask.
+    pub fn features(mut self, features: u32) -> Self {
+        self.features = features;
+        self
+    }
+
+    /// Enable one feature in addition to whatever is already enabled.
+    pub fn feature(mut self, feature: ChaosFeature) -> Self {
+        if self.features == ChaosFeature::Any as u32 {
+            // Narrow from the everything-default to just the named bits
+            self.features = 0;
+        }
+        self.features |= feature as u32;
+        self
+    }
+
+    /// Set the replay seed applied with the configuration.
+    pub fn seed(mut self, seed: u64) -> Self {
+        self.seed = Some(seed);
+        self
+    }
+
+    /// Set one feature's trigger probability in permille (clamped to
+    /// 1000). Multi-bit feature sets apply the weight to every covered
+    /// feature, like [`crate::set_feature_probability`].
+    pub fn probability(mut self, feature: ChaosFeature, permille: u32) -> Self {
+        let permille = permille.min(1000);
+        let bits = feature as u32;
+        for (index, slot) in self.probabilities.iter_mut().enumerate() {
+            if bits & (1 << index) != 0 {
+                *slot = Some(permille);
+            }
+        }
+        self
+    }
+
+    /// Restrict chaos to threads with this name (repeatable). Threads
+    /// without a name are never filtered.
+    ///
+    /// Names must not contain the serialization separators `;`, `=`, `,`.
+    pub fn allow_thread(mut self, name: &str) -> Self {
+        self.allowed_threads.push(name.to_string());
+        self
+    }
+
+    /// Exempt threads with this name from chaos (repeatable).
+    ///
+    /// Names must not contain the serialization separators `;`, `=`, `,`.
+    pub fn deny_thread(mut self, name: &str) -> Self {
+        self.denied_threads.push(name.to_string());
+        self
+    }
+
+    /// Install this configuration.
+    ///
+    /// All fields are applied under one lock, so a concurrent
+    /// [`ChaosConfig::current`] (or another `apply`) never observes a
+    /// half-applied mix of two configurations. Individual `is_active`
+    /// checks on other threads still transition field-by-field — the
+    /// atomicity guarantee is about configuration state, not about
+    /// in-flight queries.
+    ///
+    /// Does not enter chaos mode; activation stays with
+    /// [`crate::enter_chaos_mode`] and friends.
+    pub fn apply(&self) {
+        let mut applied = applied_config().lock().unwrap_or_else(|e| e.into_inner());
+        crate::CHAOS_FEATURES.store(self.features, Ordering::Relaxed);
+        for (index, slot) in crate::FEATURE_PERMILLE.iter().enumerate() {
+            slot.store(self.probabilities[index].unwrap_or(1000), Ordering::Relaxed);
+        }
+        if let Some(seed) = self.seed {
+            crate::set_chaos_seed(seed);
+        }
+        {
+            let mut filters = THREAD_FILTERS.lock().unwrap_or_else(|e| e.into_inner());
+            filters.0 = self.allowed_threads.clone();
+            filters.1 = self.denied_threads.clone();
+            FILTERS_PRESENT.store(
+                !self.allowed_threads.is_empty() || !self.denied_threads.is_empty(),
+                Ordering::Relaxed,
+            );
+        }
+        *applied = Some(self.clone());
+    }
+
+    /// Snapshot the most recently applied configuration (the default one
+    /// if [`ChaosConfig::apply`] was never called).
+    pub fn current() -> ChaosConfig {
+        applied_config()
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .clone()
+            .unwrap_or_default()
+    }
+
+    /// Serialize to a single `key=value;...` line, suitable for a log
+    /// message or CI artifact. [`ChaosConfig::from_config_string`] parses
+    /// it back.
+    pub fn to_config_string(&self) -> String {
+        let mut out = format!("features={:#x}", self.features);
+        if let Some(seed) = self.seed {
+            out.push_str(&format!(";seed={seed:#x}"));
+        }
+        for (index, permille) in self.probabilities.iter().enumerate() {
+            if let Some(permille) = permille {
+                let name = crate::leading_feature_name(1 << index);
+                out.push_str(&format!(";prob.{name}={permille}"));
+            }
+        }
+        if !self.allowed_threads.is_empty() {
+            out.push_str(&format!(";allow={}", self.allowed_threads.join(",")));
+        }
+        if !self.denied_threads.is_empty() {
+            out.push_str(&format!(";deny={}", self.denied_threads.join(",")));
+        }
+        out
+    }
+
+    /// Parse a configuration serialized by
+    /// [`ChaosConfig::to_config_string`].
+    ///
+    /// # Returns
+    /// The parsed configuration, or `None` if any field is malformed
+    pub fn from_config_string(value: &str) -> Option<ChaosConfig> {
+        let mut config = ChaosConfig::new();
+        for entry in value.split(';') {
+            let entry = entry.trim();
+            if entry.is_empty() {
+                continue;
+            }
+            let (key, value) = entry.split_once('=')?;
+            match key {
+                "features" => config.features = parse_u32(value)?,
+                "seed" => config.seed = Some(parse_u64(value)?),
+                "allow" => {
+                    config.allowed_threads =
+                        value.split(',').map(str::to_string).collect();
+                }
+                "deny" => {
+                    config.denied_threads =
+                        value.split(',').map(str::to_string).collect();
+                }
+                prob_key => {
+                    let name = prob_key.strip_prefix("prob.")?;
+                    let bits = crate::parse_chaos_features(name)?;
+                    let index = bits.trailing_zeros() as usize;
+                    if bits.count_ones() != 1 || index >= FEATURE_COUNT {
+                        return None;
+                    }
+                    config.probabilities[index] = Some(value.parse::<u32>().ok()?.min(1000));
+                }
+            }
+        }
+        Some(config)
+    }
+}
+
+/// Parse a decimal or `0x`-prefixed hex u32
+fn parse_u32(value: &str) -> Option<u32> {
+    match value.strip_prefix("0x").or_else(|| value.strip_prefix("0X")) {
+        Some(hex) => u32::from_str_radix(hex, 16).ok(),
+        None => value.parse().ok(),
+    }
+}
+
+/// Parse a decimal or `0x`-prefixed hex u64
+fn parse_u64(value: &str) -> Option<u64> {
+    match value.strip_prefix("0x").or_else(|| value.strip_prefix("0X")) {
+        Some(hex) => u64::from_str_radix(hex, 16).ok(),
+        None => value.parse().ok(),
+    }
+}
+
+/// The most recently applied configuration, for [`ChaosConfig::current`]
+fn applied_config() -> &'static Mutex<Option<ChaosConfig>> {
+    static APPLIED: Mutex<Option<ChaosConfig>> = Mutex::new(None);
+    &APPLIED
+}
+
+/// (allow, deny) thread-name lists from the applied configuration
+static THREAD_FILTERS: Mutex<(Vec<String>, Vec<String>)> =
+    Mutex::new((Vec::new(), Vec::new()));
+
+/// Fast path: skip the filter lock entirely while no filter is configured
+static FILTERS_PRESENT: AtomicBool = AtomicBool::new(false);
+
+/// Does the applied thread filter let chaos act on the calling thread?
+///
+/// Unnamed threads are never filtered (there is nothing to match), deny
+/// wins over allow, and a non-empty allow list denies every other named
+/// thread.
+pub(crate) fn thread_allowed() -> bool {
+    if !FILTERS_PRESENT.load(Ordering::Relaxed) {
+        return true;
+    }
+    let current = std::thread::current();
+    let Some(name) = current.name() else {
+        return true;
+    };
+    let filters = THREAD_FILTERS.lock().unwrap_or_else(|e| e.into_inner());
+    if filters.1.iter().any(|denied| denied == name) {
+        return false;
+    }
+    filters.0.is_empty() || filters.0.iter().any(|allowed| allowed == name)
+}
+
+#[cfg(test)]
+mod tests {
+    // Only pure builder/serialization logic is tested here; apply() and
+    // the thread filter mutate process-global state and are covered in
+    // the chaos_config_tests binary (separate process)
+    use super::*;
+
+    #[test]
+    fn test_builder_accumulates_fields() {
+        let config = ChaosConfig::new()
+            .feature(ChaosFeature::TimerScheduling)
+            .feature(ChaosFeature::IOAmounts)
+            .seed(42)
+            .probability(ChaosFeature::IOAmounts, 250)
+            .allow_thread("main")
+            .deny_thread("Compositor");
+        assert_eq!(
+            config.features,
+            ChaosFeature::TimerScheduling as u32 | ChaosFeature::IOAmounts as u32
+        );
+        assert_eq!(config.seed, Some(42));
+        assert_eq!(config.probabilities[3], Some(250));
+        assert_eq!(config.probabilities[2], None);
+        assert_eq!(config.allowed_threads, vec!["main"]);
+        assert_eq!(config.denied_threads, vec!["Compositor"]);
+    }
+
+    #[test]
+    fn test_probability_clamps_and_spreads() {
+        let config = ChaosConfig::new().probability(ChaosFeature::Any, 5000);
+        assert!(config.probabilities.iter().all(|p| *p == Some(1000)));
+    }
+
+    #[test]
+    fn test_config_string_round_trip() {
+        let config = ChaosConfig::new()
+            .features(0x14)
+            .seed(0xDECAF)
+            .probability(ChaosFeature::TimerScheduling, 500)
+            .allow_thread("main")
+            .allow_thread("DOM Worker")
+            .deny_thread("Compositor");
+        let line = config.to_config_string();
+        assert_eq!(
+            line,
+            "features=0x14;seed=0xdecaf;prob.timer=500;allow=main,DOM Worker;deny=Compositor"
+        );
+        assert_eq!(ChaosConfig::from_config_string(&line), Some(config));
+
+        // The default configuration round-trips too
+        let default = ChaosConfig::new();
+        assert_eq!(
+            ChaosConfig::from_config_string(&default.to_config_string()),
+            Some(default)
+        );
+    }
+
+    #[test]
+    fn test_config_string_rejects_malformed_input() {
+        assert!(ChaosConfig::from_config_string("features").is_none());
+        assert!(ChaosConfig::from_config_string("features=0xzz").is_none());
+        assert!(ChaosConfig::from_config_string("prob.bogus=500").is_none());
+        assert!(ChaosConfig::from_config_string("prob.any=500").is_none());
+        assert!(ChaosConfig::from_config_string("prob.timer=abc").is_none());
+        assert!(ChaosConfig::from_config_string("unknown=1").is_none());
+    }
+
+    #[test]
+    fn test_config_string_accepts_decimal_and_whitespace() {
+        let config = ChaosConfig::from_config_string(" features=5 ; seed=99 ").unwrap();
+        assert_eq!(config.features, 0x5);
+        assert_eq!(config.seed, Some(99));
+    }
+}
@@ -0,0 +1,81 @@
+// -*- Mode: Rust; tab-width: 4; indent-tabs-mode: nil; c-basic-offset: 4 -*-
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+//! ChaosConfig application tests
+//!
+//! Applying a configuration replaces the process-global feature set,
+//! weights, and thread filter, so these run in their own test binary
+//! where no other test depends on that state. The single test keeps the
+//! apply/restore sequence serial.
+
+use firefox_chaosmode::*;
+
+#[test]
+fn test_apply_and_query_config() {
+    // The applied config is queryable and fully replaces global state
+    let config = ChaosConfig::new()
+        .features(ChaosFeature::TimerScheduling as u32 | ChaosFeature::IOAmounts as u32)
+        .seed(0xC0FFEE)
+        .probability(ChaosFeature::IOAmounts, 0);
+    config.apply();
+    assert_eq!(ChaosConfig::current(), config);
+    assert_eq!(get_chaos_seed(), 0xC0FFEE);
+
+    enter_chaos_mode_for_current_thread();
+    assert!(is_active(ChaosFeature::TimerScheduling));
+    assert!(!is_active(ChaosFeature::ImageCache));
+    // The weight-0 override suppresses an otherwise enabled feature
+    assert!(!should_apply(ChaosFeature::IOAmounts));
+    assert!(should_apply(ChaosFeature::TimerScheduling));
+
+    // Re-applying the default restores weights without touching the seed
+    ChaosConfig::new().apply();
+    assert!(should_apply(ChaosFeature::IOAmounts));
+    assert_eq!(get_chaos_seed(), 0xC0FFEE);
+
+    // A deny entry for this thread turns chaos off here...
+    let name = std::thread::current().name().unwrap().to_string();
+    ChaosConfig::new().deny_thread(&name).apply();
+    assert!(!is_active(ChaosFeature::Any));
+    assert!(!should_apply(ChaosFeature::TimerScheduling));
+
+    // ...an allow list for another thread does too...
+    ChaosConfig::new().allow_thread("SomeOtherThread").apply();
+    assert!(!is_active(ChaosFeature::Any));
+
+    // ...and an allow list naming this thread turns it back on
+    ChaosConfig::new().allow_thread(&name).apply();
+    assert!(is_active(ChaosFeature::Any));
+
+    // The filter is per-thread: an allowed spawn observes process-wide
+    // activation that the denied parent would not
+    ChaosConfig::new().deny_thread(&name).apply();
+    enter_chaos_mode();
+    assert!(!is_active(ChaosFeature::Any));
+    std::thread::Builder::new()
+        .name("ChaosWorker".into())
+        .spawn(|| assert!(is_active(ChaosFeature::Any)))
+        .unwrap()
+        .join()
+        .unwrap();
+    leave_chaos_mode();
+
+    // The FFI round trip: serialize, clear, re-apply from the string
+    let config = ChaosConfig::new().features(0x5).probability(ChaosFeature::TimerScheduling, 500);
+    config.apply();
+    let needed = unsafe { ffi::mozilla_chaosmode_config_to_string(std::ptr::null_mut(), 0) };
+    let mut buf = vec![0u8; needed];
+    unsafe { ffi::mozilla_chaosmode_config_to_string(buf.as_mut_ptr(), buf.len()) };
+    let line = String::from_utf8(buf).unwrap();
+    assert_eq!(line, "features=0x5;prob.timer=500");
+
+    ChaosConfig::new().apply();
+    assert!(unsafe { ffi::mozilla_chaosmode_apply_config_string(line.as_ptr(), line.len()) });
+    assert_eq!(ChaosConfig::current(), config);
+    assert!(!unsafe { ffi::mozilla_chaosmode_apply_config_string("bogus".as_ptr(), 5) });
+
+    leave_chaos_mode_for_current_thread();
+    ChaosConfig::new().apply();
+}
@@ -8,38 +8,38 @@
 //! This module provides C-compatible functions that can be called from C++.
 //! All functions use #[no_mangle] and extern "C" for ABI compatibility.
 
-use crate::{enter_chaos_mode, leave_chaos_mode, random_i32_in_range, random_u32_less_than};
+use crate::{
+    enter_chaos_mode, get_chaos_seed, leave_chaos_mode, random_i32_in_range,
+    random_u32_less_than, set_chaos_seed,
+};
 
 /// Set which chaos features should be active.
-/// 
-/// # Safety
-/// Must be called before threading starts. Not thread-safe.
-/// 
+///
+/// Thread-safe: a single atomic store. Call before threading starts for
+/// deterministic behavior; later calls are safe but racy-by-design.
+///
 /// # Arguments
 /// * `feature` - The chaos feature flags to enable (as u32)
 #[no_mangle]
 pub extern "C" fn mozilla_chaosmode_set_chaos_feature(feature: u32) {
     // We don't convert to enum - just set the raw u32 value directly
     // This allows arbitrary bit combinations like 0x3 (ThreadScheduling | NetworkScheduling)
-    unsafe {
-        crate::CHAOS_FEATURES = feature;
-    }
+    crate::CHAOS_FEATURES.store(feature, std::sync::atomic::Ordering::Relaxed);
 }
 
 /// Check if a specific chaos feature is currently active.
-/// 
-/// Thread-safe: Uses atomic operations.
-/// 
+///
+/// Thread-safe. Consults both the process-wide counter and the calling
+/// thread's counter.
+///
 /// # Arguments
 /// * `feature` - The chaos feature to check (as u32)
-/// 
+///
 /// # Returns
 /// true if the feature is active, false otherwise
 #[no_mangle]
 pub extern "C" fn mozilla_chaosmode_is_active(feature: u32) -> bool {
-    let counter = crate::CHAOS_MODE_COUNTER.load(std::sync::atomic::Ordering::Relaxed);
-    let features = unsafe { crate::CHAOS_FEATURES };
-    counter > 0 && (features & feature) != 0
+    crate::is_active_bits(feature)
 }
 
 /// Increase the chaos mode activation level.
@@ -61,16 +61,394 @@ pub extern "C" fn mozilla_chaosmode_leave_chaos_mode() {
     leave_chaos_mode();
 }
 
+/// Increase the chaos activation level for the calling thread only.
+///
+/// Other threads are unaffected; see mozilla_chaosmode_enter_chaos_mode
+/// for the process-wide variant. Must be balanced by
+/// mozilla_chaosmode_leave_chaos_mode_for_current_thread on this thread.
+#[no_mangle]
+pub extern "C" fn mozilla_chaosmode_enter_chaos_mode_for_current_thread() {
+    crate::enter_chaos_mode_for_current_thread();
+}
+
+/// Decrease the calling thread's chaos activation level.
+///
+/// # Safety
+/// Will panic in debug builds if this thread's counter is 0.
+#[no_mangle]
+pub extern "C" fn mozilla_chaosmode_leave_chaos_mode_for_current_thread() {
+    crate::leave_chaos_mode_for_current_thread();
+}
+
+/// Set how often a feature fires when chaos mode is active.
+///
+/// Thread-safe. `permille` is out of 1000 and clamped; multi-bit feature
+/// masks apply the weight to every covered feature.
+///
+/// # Arguments
+/// * `feature` - The chaos feature flags to weight (as u32)
+/// * `permille` - Trigger probability out of 1000
+#[no_mangle]
+pub extern "C" fn mozilla_chaosmode_set_feature_probability(feature: u32, permille: u32) {
+    let permille = permille.min(1000);
+    for (index, slot) in crate::FEATURE_PERMILLE.iter().enumerate() {
+        if feature & (1 << index) != 0 {
+            slot.store(permille, std::sync::atomic::Ordering::Relaxed);
+        }
+    }
+}
+
+/// Should this call site apply its chaos perturbation right now?
+///
+/// Combines the is_active check with the feature's configured probability.
+/// Thread-safe.
+///
+/// # Arguments
+/// * `feature` - A single chaos feature flag (as u32)
+///
+/// # Returns
+/// true if chaos mode is active for the feature and its weight fired
+#[no_mangle]
+pub extern "C" fn mozilla_chaosmode_should_apply(feature: u32) -> bool {
+    crate::should_apply_bits(feature)
+}
+
+/// Register an observer callback for chaos events.
+///
+/// The callback receives (event, feature, user_data) where event is 0 for
+/// enter, 1 for leave, and 2 for a feature trigger. It runs synchronously
+/// on the thread producing the event.
+///
+/// # Safety
+/// `user_data` must remain valid (and be safe to use from any thread)
+/// until the observer is unregistered.
+///
+/// # Returns
+/// An id for mozilla_chaosmode_unregister_observer
+#[no_mangle]
+pub unsafe extern "C" fn mozilla_chaosmode_register_observer(
+    callback: crate::ChaosObserverCallback,
+    user_data: *mut std::ffi::c_void,
+) -> u64 {
+    crate::register_chaos_observer(callback, user_data)
+}
+
+/// Remove a previously registered chaos observer.
+///
+/// # Arguments
+/// * `id` - The id returned by mozilla_chaosmode_register_observer
+///
+/// # Returns
+/// true if the id was found and removed
+#[no_mangle]
+pub extern "C" fn mozilla_chaosmode_unregister_observer(id: u64) -> bool {
+    crate::unregister_chaos_observer(id)
+}
+
+/// Should this fallible allocation be made to fail?
+///
+/// Thread-safe. Returns true only while MemoryAllocation chaos is active
+/// and its configured weight fired; the caller then reports OOM.
+///
+/// # Returns
+/// true if the allocation should fail
+#[no_mangle]
+pub extern "C" fn mozilla_chaosmode_should_fail_alloc() -> bool {
+    crate::should_fail_alloc()
+}
+
+/// Possibly shrink an I/O request to inject a short read or write.
+///
+/// Thread-safe. While FileIO chaos is active and its weight fires, returns
+/// a random amount in [1, requested]; otherwise returns requested
+/// unchanged. Never returns 0 for a non-zero request.
+///
+/// # Arguments
+/// * `requested` - The byte count the caller intended to read or write
+///
+/// # Returns
+/// The (possibly truncated) byte count to actually use
+#[no_mangle]
+pub extern "C" fn mozilla_chaosmode_should_truncate_io(requested: usize) -> usize {
+    crate::should_truncate_io(requested)
+}
+
+/// Fill a caller-provided buffer with a (possibly randomized) visit order.
+///
+/// Writes a permutation of 0..len into `indices`: the identity order
+/// normally, or a uniformly random one when HashTableIteration chaos is
+/// active and its weight fires.
+///
+/// # Safety
+/// `indices` must be valid for writes of `len` u32 values, or null (in
+/// which case nothing is written).
+///
+/// # Arguments
+/// * `indices` - Output buffer for the visit order
+/// * `len` - Number of slots to order
+#[no_mangle]
+pub unsafe extern "C" fn mozilla_chaosmode_shuffle_indices(indices: *mut u32, len: u32) {
+    if indices.is_null() {
+        return;
+    }
+    let order = crate::shuffle_indices(len);
+    unsafe {
+        std::ptr::copy_nonoverlapping(order.as_ptr(), indices, len as usize);
+    }
+}
+
+/// Sleep for a random duration up to max_micros if the feature fires.
+///
+/// Thread-safe. No-op when chaos mode is off for the feature or max_micros
+/// is 0. Intended for TaskDispatching/TaskRunning call sites.
+///
+/// # Arguments
+/// * `feature` - A single chaos feature flag (as u32)
+/// * `max_micros` - Upper bound on the injected delay, in microseconds
+#[no_mangle]
+pub extern "C" fn mozilla_chaosmode_maybe_delay(feature: u32, max_micros: u32) {
+    if max_micros == 0 || !crate::should_apply_bits(feature) {
+        return;
+    }
+    // saturating_add keeps max_micros == u32::MAX from wrapping to a
+    // zero bound
+    let micros = random_u32_less_than(max_micros.saturating_add(1));
+    if micros > 0 {
+        std::thread::sleep(std::time::Duration::from_micros(micros as u64));
+    }
+}
+
+/// Yield the current thread's timeslice if the feature fires.
+///
+/// Thread-safe. The cheap sibling of mozilla_chaosmode_maybe_delay.
+///
+/// # Arguments
+/// * `feature` - A single chaos feature flag (as u32)
+#[no_mangle]
+pub extern "C" fn mozilla_chaosmode_maybe_yield(feature: u32) {
+    if crate::should_apply_bits(feature) {
+        std::thread::yield_now();
+    }
+}
+
+/// Dump the per-feature trigger statistics into caller-provided arrays.
+///
+/// Fills up to `len` entries of `queried` and `fired`, indexed by feature
+/// bit position (entry 0 is ThreadScheduling = 0x1, entry 1 is
+/// NetworkScheduling = 0x2, ...). A first call with null buffers sizes the
+/// arrays.
+///
+/// # Safety
+/// `queried` and `fired` must each be valid for writes of `len` u64
+/// values, or null.
+///
+/// # Returns
+/// The number of weightable features (the full array length)
+#[no_mangle]
+pub unsafe extern "C" fn mozilla_chaosmode_get_stats(
+    queried: *mut u64,
+    fired: *mut u64,
+    len: usize,
+) -> usize {
+    let stats = crate::get_stats();
+    for (index, entry) in stats.iter().enumerate().take(len) {
+        unsafe {
+            if !queried.is_null() {
+                *queried.add(index) = entry.queried;
+            }
+            if !fired.is_null() {
+                *fired.add(index) = entry.fired;
+            }
+        }
+    }
+    stats.len()
+}
+
+/// Zero all per-feature trigger statistics.
+///
+/// Thread-safe; mainly for test harnesses that want per-test accounting.
+#[no_mangle]
+pub extern "C" fn mozilla_chaosmode_reset_stats() {
+    crate::reset_stats();
+}
+
+/// Dump the chaos decision trace as JSON into a caller-provided buffer.
+///
+/// Writes up to `len` bytes of UTF-8 JSON (not NUL-terminated) into `buf`
+/// and returns the full length of the JSON document, so a first call with
+/// a null buffer sizes the allocation:
+///
+/// ```cpp
+/// size_t needed = mozilla_chaosmode_trace_to_json(nullptr, 0);
+/// std::vector<char> buf(needed);
+/// mozilla_chaosmode_trace_to_json(buf.data(), buf.size());
+/// ```
+///
+/// # Safety
+/// `buf` must be valid for writes of `len` bytes, or null.
+///
+/// # Returns
+/// The full JSON length in bytes (regardless of how much was copied)
+#[no_mangle]
+pub unsafe extern "C" fn mozilla_chaosmode_trace_to_json(buf: *mut u8, len: usize) -> usize {
+    let json = crate::trace::to_json();
+    if !buf.is_null() {
+        let n = len.min(json.len());
+        unsafe {
+            std::ptr::copy_nonoverlapping(json.as_ptr(), buf, n);
+        }
+    }
+    json.len()
+}
+
+/// Forget all recorded chaos decisions.
+///
+/// Thread-safe; mainly for test harnesses that want a per-test trace.
+#[no_mangle]
+pub extern "C" fn mozilla_chaosmode_trace_clear() {
+    crate::trace::clear();
+}
+
+/// Apply a serialized chaos configuration (see ChaosConfig).
+///
+/// Parses a `key=value;...` line as produced by
+/// mozilla_chaosmode_config_to_string and applies it in one step.
+///
+/// # Safety
+/// `value` must be valid for reads of `len` bytes of UTF-8.
+///
+/// # Returns
+/// true if the string parsed and was applied
+#[no_mangle]
+pub unsafe extern "C" fn mozilla_chaosmode_apply_config_string(
+    value: *const u8,
+    len: usize,
+) -> bool {
+    if value.is_null() {
+        return false;
+    }
+    let bytes = unsafe { std::slice::from_raw_parts(value, len) };
+    let Ok(text) = std::str::from_utf8(bytes) else {
+        return false;
+    };
+    match crate::ChaosConfig::from_config_string(text) {
+        Some(config) => {
+            config.apply();
+            true
+        }
+        None => false,
+    }
+}
+
+/// Serialize the applied chaos configuration into a caller buffer.
+///
+/// Same size-then-fill contract as mozilla_chaosmode_trace_to_json: writes
+/// up to `len` bytes and returns the full serialized length.
+///
+/// # Safety
+/// `buf` must be valid for writes of `len` bytes, or null.
+///
+/// # Returns
+/// The full serialized length in bytes
+#[no_mangle]
+pub unsafe extern "C" fn mozilla_chaosmode_config_to_string(buf: *mut u8, len: usize) -> usize {
+    let line = crate::ChaosConfig::current().to_config_string();
+    if !buf.is_null() {
+        let n = len.min(line.len());
+        unsafe {
+            std::ptr::copy_nonoverlapping(line.as_ptr(), buf, n);
+        }
+    }
+    line.len()
+}
+
+/// Write the chaos configuration, seed, and decision trace to a file.
+///
+/// See export_session; the file can be attached to a CI failure and fed
+/// back through mozilla_chaosmode_import_session locally.
+///
+/// # Safety
+/// `path` must be valid for reads of `len` bytes of UTF-8.
+///
+/// # Returns
+/// true if the file was written
+#[no_mangle]
+pub unsafe extern "C" fn mozilla_chaosmode_export_session(path: *const u8, len: usize) -> bool {
+    if path.is_null() {
+        return false;
+    }
+    let bytes = unsafe { std::slice::from_raw_parts(path, len) };
+    let Ok(path) = std::str::from_utf8(bytes) else {
+        return false;
+    };
+    crate::export_session(path).is_ok()
+}
+
+/// Apply the configuration and seed recorded in a session file.
+///
+/// # Safety
+/// `path` must be valid for reads of `len` bytes of UTF-8.
+///
+/// # Returns
+/// true if the file parsed and its configuration was applied
+#[no_mangle]
+pub unsafe extern "C" fn mozilla_chaosmode_import_session(path: *const u8, len: usize) -> bool {
+    if path.is_null() {
+        return false;
+    }
+    let bytes = unsafe { std::slice::from_raw_parts(path, len) };
+    let Ok(path) = std::str::from_utf8(bytes) else {
+        return false;
+    };
+    crate::import_session(path).is_ok()
+}
+
+/// Configure chaos mode from MOZ_CHAOSMODE / MOZ_CHAOSMODE_SEED.
+///
+/// For use by startup code before threading begins. Accepts `0xN` hex flags
+/// or a `thread,timer,io` style name list; see the crate documentation.
+/// Only configures — the caller still enters chaos mode explicitly.
+///
+/// # Returns
+/// true if MOZ_CHAOSMODE was present and yielded a feature set
+#[no_mangle]
+pub extern "C" fn mozilla_chaosmode_init_from_env() -> bool {
+    crate::init_from_env()
+}
+
+/// Seed the chaos RNG for deterministic, replayable randomness.
+///
+/// Thread-safe. The seed is logged; a seed of 0 is valid.
+///
+/// # Arguments
+/// * `seed` - The replay seed (e.g. copied from a failing run's log)
+#[no_mangle]
+pub extern "C" fn mozilla_chaosmode_set_chaos_seed(seed: u64) {
+    set_chaos_seed(seed);
+}
+
+/// Return the seed behind the chaos RNG, choosing one if none was set.
+///
+/// Thread-safe. Test harnesses include this in failure logs so the run can
+/// be replayed with mozilla_chaosmode_set_chaos_seed.
+///
+/// # Returns
+/// The seed currently in use
+#[no_mangle]
+pub extern "C" fn mozilla_chaosmode_get_chaos_seed() -> u64 {
+    get_chaos_seed()
+}
+
 /// Return a pseudo-random u32 < bound.
-/// 
+///
 /// # Arguments
 /// * `bound` - Upper bound (exclusive)
-/// 
+///
 /// # Returns
 /// A pseudo-random u32 in range [0, bound)
-/// 
+///
 /// # Safety
-/// Not thread-safe - uses C rand().
+/// Thread-safe: draws from the calling thread's own chaos RNG.
 /// Will panic in debug builds if bound is 0.
 #[no_mangle]
 pub extern "C" fn mozilla_chaosmode_random_u32_less_than(bound: u32) -> u32 {
@@ -78,16 +456,16 @@ pub extern "C" fn mozilla_chaosmode_random_u32_less_than(bound: u32) -> u32 {
 }
 
 /// Return a pseudo-random i32 between low and high (inclusive).
-/// 
+///
 /// # Arguments
 /// * `low` - Lower bound (inclusive)
 /// * `high` - Upper bound (inclusive)
-/// 
+///
 /// # Returns
 /// A pseudo-random i32 in range [low, high]
-/// 
+///
 /// # Safety
-/// Not thread-safe - uses C rand().
+/// Thread-safe: draws from the calling thread's own chaos RNG.
 /// Will panic in debug builds if high < low.
 #[no_mangle]
 pub extern "C" fn mozilla_chaosmode_random_i32_in_range(low: i32, high: i32) -> i32 {
@@ -133,7 +511,7 @@ mod ffi_tests {
         // Test random i32
         for _ in 0..10 {
             let val = mozilla_chaosmode_random_i32_in_range(-50, 50);
-            assert!(val >= -50 && val <= 50);
+            assert!((-50..=50).contains(&val));
         }
     }
 
@@ -10,14 +10,31 @@
 //!
 //! # Safety
 //! - Uses atomic operations with Relaxed ordering (matches C++ implementation)
-//! - Random functions are NOT thread-safe (intentional, matches C++ behavior)
-//! - SetChaosFeature must be called before threading starts
+//! - Random functions are thread-safe (each thread owns a xorshift128+
+//!   generator derived from the chaos seed and its thread id) and
+//!   deterministic per thread under [`set_chaos_seed`]
+//! - SetChaosFeature is intended to be called before threading starts, but is
+//!   now atomic, so late reconfiguration is defined behavior (other threads
+//!   observe the change at some point; no tearing)
 
-use std::sync::atomic::{AtomicU32, Ordering};
+use firefox_xorshift128plus::XorShift128PlusRNG;
+use std::sync::atomic::{AtomicU32, AtomicU64, Ordering};
 
 // FFI layer for C++ interop
 pub mod ffi;
 
+// Chaos decision trace ring buffer
+pub mod trace;
+
+// Typed configuration builder
+pub mod config;
+
+// Session capture and replay files
+pub mod session;
+
+pub use config::ChaosConfig;
+pub use session::{export_session, import_session};
+
 /// Chaos features that can be enabled for testing.
 /// These are bit flags that can be combined.
 #[repr(u32)]
@@ -40,6 +57,11 @@ pub enum ChaosFeature {
     TaskDispatching = 0x40,
     /// Delay task running to encourage sending threads to run
     TaskRunning = 0x80,
+    /// Randomly fail memory allocations to exercise OOM handling
+    MemoryAllocation = 0x100,
+    /// Randomly truncate file reads and writes to exercise short-I/O
+    /// handling
+    FileIO = 0x200,
     /// All features enabled
     Any = 0xffffffff,
 }
@@ -49,30 +71,47 @@ pub enum ChaosFeature {
 static CHAOS_MODE_COUNTER: AtomicU32 = AtomicU32::new(0);
 
 /// Global chaos features configuration
-/// This is NOT atomic - must be set before threading starts
-static mut CHAOS_FEATURES: u32 = ChaosFeature::Any as u32;
+///
+/// Atomic so reads from arbitrary threads are defined behavior. The intended
+/// usage is still set-once-before-threading (a single Relaxed store that
+/// every later load observes, making the atomic effectively free), but
+/// runtime reconfiguration is now merely racy-by-design rather than UB.
+static CHAOS_FEATURES: AtomicU32 = AtomicU32::new(ChaosFeature::Any as u32);
 
 /// Set which chaos features should be active when chaos mode is enabled.
-/// 
-/// # Safety
-/// Must be called before any threads are started. Not thread-safe.
+///
+/// Thread-safe: a single atomic store. For deterministic behavior call this
+/// before threading starts; calls made while other threads are running take
+/// effect at some point without tearing, but in-flight `is_active` checks
+/// may see either value.
 pub fn set_chaos_feature(feature: ChaosFeature) {
-    unsafe {
-        CHAOS_FEATURES = feature as u32;
-    }
+    CHAOS_FEATURES.store(feature as u32, Ordering::Relaxed);
 }
 
 /// Check if a specific chaos feature is currently active.
-/// 
+///
 /// A feature is active when:
-/// 1. Chaos mode counter > 0 (enterChaosMode has been called)
+/// 1. The process-wide counter > 0 ([`enter_chaos_mode`]) or the calling
+///    thread's counter > 0 ([`enter_chaos_mode_for_current_thread`])
 /// 2. The feature is enabled in CHAOS_FEATURES
-/// 
-/// Thread-safe: Uses atomic load with Relaxed ordering.
+/// 3. The applied [`ChaosConfig`]'s thread filter (if any) permits the
+///    calling thread
+///
+/// Thread-safe: Uses atomic loads with Relaxed ordering.
 pub fn is_active(feature: ChaosFeature) -> bool {
-    let counter = CHAOS_MODE_COUNTER.load(Ordering::Relaxed);
-    let features = unsafe { CHAOS_FEATURES };
-    counter > 0 && (features & (feature as u32)) != 0
+    is_active_bits(feature as u32)
+}
+
+/// Raw-bitmask implementation behind [`is_active`], shared with the FFI.
+pub(crate) fn is_active_bits(feature: u32) -> bool {
+    let features = CHAOS_FEATURES.load(Ordering::Relaxed);
+    chaos_counter_active() && (features & feature) != 0 && config::thread_allowed()
+}
+
+/// Is any activation (process-wide or for the calling thread) in effect?
+fn chaos_counter_active() -> bool {
+    CHAOS_MODE_COUNTER.load(Ordering::Relaxed) > 0
+        || THREAD_CHAOS_COUNTER.with(|counter| counter.get() > 0)
 }
 
 /// Increase the chaos mode activation level.
@@ -83,6 +122,7 @@ pub fn is_active(feature: ChaosFeature) -> bool {
 /// Thread-safe: Uses atomic increment with Relaxed ordering.
 pub fn enter_chaos_mode() {
     CHAOS_MODE_COUNTER.fetch_add(1, Ordering::Relaxed);
+    notify_observers(ChaosEvent::Enter, 0);
 }
 
 /// Decrease the chaos mode activation level.
@@ -95,44 +135,723 @@ pub fn enter_chaos_mode() {
 pub fn leave_chaos_mode() {
     let prev = CHAOS_MODE_COUNTER.fetch_sub(1, Ordering::Relaxed);
     debug_assert!(prev > 0, "leaveChaosMode called without matching enterChaosMode");
+    notify_observers(ChaosEvent::Leave, 0);
+}
+
+thread_local! {
+    /// Per-thread chaos nesting depth, consulted by [`is_active`] alongside
+    /// the process-wide counter
+    static THREAD_CHAOS_COUNTER: std::cell::Cell<u32> = const { std::cell::Cell::new(0) };
+}
+
+/// Increase the chaos activation level for the calling thread only.
+///
+/// A single test can run chaotically without perturbing every other
+/// thread in the process: [`is_active`] (and therefore [`should_apply`])
+/// reports active on this thread while every other thread still sees the
+/// process-wide counter alone. Nests like [`enter_chaos_mode`]; each call
+/// must be matched by [`leave_chaos_mode_for_current_thread`] on the same
+/// thread.
+pub fn enter_chaos_mode_for_current_thread() {
+    THREAD_CHAOS_COUNTER.with(|counter| counter.set(counter.get() + 1));
+    notify_observers(ChaosEvent::Enter, 0);
+}
+
+/// Decrease the calling thread's chaos activation level.
+///
+/// # Panics
+/// Debug builds will panic if the thread's counter is already 0.
+pub fn leave_chaos_mode_for_current_thread() {
+    THREAD_CHAOS_COUNTER.with(|counter| {
+        let prev = counter.get();
+        debug_assert!(
+            prev > 0,
+            "leave_chaos_mode_for_current_thread called without matching enter on this thread"
+        );
+        counter.set(prev.wrapping_sub(1));
+    });
+    notify_observers(ChaosEvent::Leave, 0);
+}
+
+/// Chaos lifecycle events delivered to registered observers
+#[repr(u32)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ChaosEvent {
+    /// enter_chaos_mode was called (feature argument is 0)
+    Enter = 0,
+    /// leave_chaos_mode was called (feature argument is 0)
+    Leave = 1,
+    /// A feature decision fired in should_apply (feature argument is the
+    /// queried bitmask)
+    FeatureTrigger = 2,
+}
+
+/// Observer callback: `event` is a [`ChaosEvent`] value, `feature` the
+/// relevant bitmask (0 for enter/leave), `user_data` the pointer passed at
+/// registration. Called synchronously on the thread producing the event, so
+/// implementations must be quick and reentrancy-safe.
+pub type ChaosObserverCallback = extern "C" fn(event: u32, feature: u32, user_data: *mut std::ffi::c_void);
+
+struct ChaosObserver {
+    id: u64,
+    callback: ChaosObserverCallback,
+    user_data: *mut std::ffi::c_void,
+}
+
+// The user_data pointer is supplied by the registrant, who promises it is
+// safe to use from whichever thread produces chaos events (profiler and
+// test-harness annotation buffers are themselves thread-safe).
+unsafe impl Send for ChaosObserver {}
+
+/// Registered observers. Guarded by a mutex; the hot paths skip it entirely
+/// while OBSERVER_COUNT is 0, so the common no-observer case stays lock-free.
+static OBSERVERS: std::sync::Mutex<Vec<ChaosObserver>> = std::sync::Mutex::new(Vec::new());
+static OBSERVER_COUNT: AtomicU32 = AtomicU32::new(0);
+static NEXT_OBSERVER_ID: AtomicU64 = AtomicU64::new(1);
+
+/// Register a callback invoked on chaos enter/leave and on each feature
+/// trigger, so the profiler or a test harness can annotate its timeline
+/// with chaos activity.
+///
+/// # Returns
+/// An id for [`unregister_chaos_observer`]
+pub fn register_chaos_observer(
+    callback: ChaosObserverCallback,
+    user_data: *mut std::ffi::c_void,
+) -> u64 {
+    let id = NEXT_OBSERVER_ID.fetch_add(1, Ordering::Relaxed);
+    let mut observers = OBSERVERS.lock().unwrap_or_else(|e| e.into_inner());
+    observers.push(ChaosObserver { id, callback, user_data });
+    OBSERVER_COUNT.store(observers.len() as u32, Ordering::Relaxed);
+    id
+}
+
+/// Remove a previously registered observer.
+///
+/// # Returns
+/// true if the id was found and removed
+pub fn unregister_chaos_observer(id: u64) -> bool {
+    let mut observers = OBSERVERS.lock().unwrap_or_else(|e| e.into_inner());
+    let before = observers.len();
+    observers.retain(|o| o.id != id);
+    OBSERVER_COUNT.store(observers.len() as u32, Ordering::Relaxed);
+    observers.len() != before
+}
+
+/// Deliver an event to every registered observer (no-op without observers)
+fn notify_observers(event: ChaosEvent, feature: u32) {
+    if OBSERVER_COUNT.load(Ordering::Relaxed) == 0 {
+        return;
+    }
+    // Copy the entries out so callbacks run without the lock held; a
+    // callback may itself (un)register observers
+    let entries: Vec<(ChaosObserverCallback, usize)> = {
+        let observers = OBSERVERS.lock().unwrap_or_else(|e| e.into_inner());
+        observers.iter().map(|o| (o.callback, o.user_data as usize)).collect()
+    };
+    for (callback, user_data) in entries {
+        callback(event as u32, feature, user_data as *mut std::ffi::c_void);
+    }
+}
+
+/// Number of individually weightable features (bits 0x1 through 0x200)
+const FEATURE_COUNT: usize = 10;
+
+/// Per-feature trigger probability in permille (0..=1000)
+///
+/// Defaults to 1000 (always fire), preserving the historical all-or-nothing
+/// behavior for call sites that never configure a weight.
+static FEATURE_PERMILLE: [AtomicU32; FEATURE_COUNT] = [
+    AtomicU32::new(1000),
+    AtomicU32::new(1000),
+    AtomicU32::new(1000),
+    AtomicU32::new(1000),
+    AtomicU32::new(1000),
+    AtomicU32::new(1000),
+    AtomicU32::new(1000),
+    AtomicU32::new(1000),
+    AtomicU32::new(1000),
+    AtomicU32::new(1000),
+];
+
+/// Human-readable name of the lowest feature bit in a mask, for trace and
+/// log output. Multi-bit masks report their leading (lowest) feature.
+pub(crate) fn leading_feature_name(feature: u32) -> &'static str {
+    if feature == 0 {
+        return "none";
+    }
+    match 1u32 << feature.trailing_zeros() {
+        0x1 => "thread",
+        0x2 => "network",
+        0x4 => "timer",
+        0x8 => "io",
+        0x10 => "hash",
+        0x20 => "image",
+        0x40 => "taskdispatch",
+        0x80 => "taskrun",
+        0x100 => "memalloc",
+        0x200 => "fileio",
+        _ => "unknown",
+    }
+}
+
+/// Per-feature count of [`should_apply`] queries (indexed like
+/// [`FEATURE_PERMILLE`])
+static FEATURE_QUERIED: [AtomicU64; FEATURE_COUNT] = [
+    AtomicU64::new(0),
+    AtomicU64::new(0),
+    AtomicU64::new(0),
+    AtomicU64::new(0),
+    AtomicU64::new(0),
+    AtomicU64::new(0),
+    AtomicU64::new(0),
+    AtomicU64::new(0),
+    AtomicU64::new(0),
+    AtomicU64::new(0),
+];
+
+/// Per-feature count of queries whose perturbation actually fired
+static FEATURE_FIRED: [AtomicU64; FEATURE_COUNT] = [
+    AtomicU64::new(0),
+    AtomicU64::new(0),
+    AtomicU64::new(0),
+    AtomicU64::new(0),
+    AtomicU64::new(0),
+    AtomicU64::new(0),
+    AtomicU64::new(0),
+    AtomicU64::new(0),
+    AtomicU64::new(0),
+    AtomicU64::new(0),
+];
+
+/// Query and fire counts for one feature, from [`get_stats`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FeatureStats {
+    /// The feature's bitmask (always a single bit)
+    pub feature: u32,
+    /// How many times [`should_apply`] was asked about this feature,
+    /// whether or not chaos mode was active
+    pub queried: u64,
+    /// How many of those queries fired the perturbation
+    pub fired: u64,
+}
+
+/// Snapshot the per-feature trigger statistics.
+///
+/// One entry per weightable feature, in bit order. Tests use this to
+/// assert chaos actually exercised the intended code paths — a fired count
+/// of 0 for a feature the test meant to stress is a setup bug, not a pass.
+pub fn get_stats() -> Vec<FeatureStats> {
+    (0..FEATURE_COUNT)
+        .map(|index| FeatureStats {
+            feature: 1 << index,
+            queried: FEATURE_QUERIED[index].load(Ordering::Relaxed),
+            fired: FEATURE_FIRED[index].load(Ordering::Relaxed),
+        })
+        .collect()
+}
+
+/// Zero all trigger statistics, for per-test accounting.
+pub fn reset_stats() {
+    for index in 0..FEATURE_COUNT {
+        FEATURE_QUERIED[index].store(0, Ordering::Relaxed);
+        FEATURE_FIRED[index].store(0, Ordering::Relaxed);
+    }
+}
+
+/// Map a single-feature bitmask to its index in [`FEATURE_PERMILLE`]
+fn feature_index(feature: u32) -> Option<usize> {
+    if feature.count_ones() == 1 && feature.trailing_zeros() < FEATURE_COUNT as u32 {
+        Some(feature.trailing_zeros() as usize)
+    } else {
+        None
+    }
+}
+
+/// Set how often a feature should actually fire when chaos mode is active.
+///
+/// `permille` is out of 1000: `set_feature_probability(IOAmounts, 50)` makes
+/// I/O truncation trigger 5% of the time while e.g. TimerScheduling can stay
+/// at 50% — previously chaos was all-or-nothing per feature. Values above
+/// 1000 are clamped. Passing a multi-bit feature set (including
+/// [`ChaosFeature::Any`]) applies the weight to every covered feature.
+///
+/// Thread-safe; weights may be adjusted at any time.
+pub fn set_feature_probability(feature: ChaosFeature, permille: u32) {
+    let permille = permille.min(1000);
+    let bits = feature as u32;
+    for (index, slot) in FEATURE_PERMILLE.iter().enumerate() {
+        if bits & (1 << index) != 0 {
+            slot.store(permille, Ordering::Relaxed);
+        }
+    }
+}
+
+/// Should this call site apply its chaos perturbation right now?
+///
+/// Combines [`is_active`] with the feature's configured probability: returns
+/// false when chaos mode is off or the feature isn't enabled, and otherwise
+/// fires with the weight set via [`set_feature_probability`] (default:
+/// always). Call sites should prefer this over bare `is_active` so weights
+/// apply uniformly.
+///
+/// Every decision made while chaos mode is active is recorded in the
+/// [`trace`] ring buffer for later inspection.
+pub fn should_apply(feature: ChaosFeature) -> bool {
+    should_apply_bits(feature as u32)
+}
+
+/// Raw-bitmask implementation behind [`should_apply`], shared with the FFI.
+pub(crate) fn should_apply_bits(feature: u32) -> bool {
+    let index = feature_index(feature);
+    if let Some(index) = index {
+        // Queries are counted whether or not chaos mode is active, so a
+        // test can tell "feature never reached" from "feature never fired"
+        FEATURE_QUERIED[index].fetch_add(1, Ordering::Relaxed);
+    }
+    if !is_active_bits(feature) {
+        // Chaos mode off: no decision was made, so nothing to trace
+        return false;
+    }
+    let Some(index) = index else {
+        // Multi-bit queries have no single weight; keep is_active semantics
+        trace::record_decision(feature, 0, true);
+        notify_observers(ChaosEvent::FeatureTrigger, feature);
+        return true;
+    };
+    let permille = FEATURE_PERMILLE[index].load(Ordering::Relaxed);
+    let (random_value, fired) = match permille {
+        0 => (0, false),
+        1000.. => (0, true),
+        p => {
+            let draw = random_u32_less_than(1000);
+            (draw, draw < p)
+        }
+    };
+    trace::record_decision(feature, random_value, fired);
+    if fired {
+        FEATURE_FIRED[index].fetch_add(1, Ordering::Relaxed);
+        notify_observers(ChaosEvent::FeatureTrigger, feature);
+    }
+    fired
+}
+
+/// Sleep for a random duration up to `max_micros` if the feature fires.
+///
+/// The shared implementation for TaskDispatching/TaskRunning-style call
+/// sites that want to perturb scheduling: checks [`should_apply`] (so
+/// per-feature weights and the decision trace all apply) and, when it
+/// fires, sleeps a uniformly random number of microseconds in
+/// `[0, max_micros]`. No-op when chaos mode is off or `max_micros` is 0.
+pub fn maybe_delay(feature: ChaosFeature, max_micros: u32) {
+    if max_micros == 0 || !should_apply(feature) {
+        return;
+    }
+    // saturating_add keeps max_micros == u32::MAX from wrapping to a
+    // zero bound
+    let micros = random_u32_less_than(max_micros.saturating_add(1));
+    if micros > 0 {
+        std::thread::sleep(std::time::Duration::from_micros(micros as u64));
+    }
+}
+
+/// Yield the current thread's timeslice if the feature fires.
+///
+/// The cheap sibling of [`maybe_delay`] for call sites where any sleep is
+/// too heavy but encouraging a different interleaving is still useful.
+pub fn maybe_yield(feature: ChaosFeature) {
+    if should_apply(feature) {
+        std::thread::yield_now();
+    }
+}
+
+/// Should this allocation be made to fail?
+///
+/// The [`ChaosFeature::MemoryAllocation`] entry point for fallible-alloc
+/// call sites: returns true when the feature is active and its weight
+/// fired, in which case the caller reports OOM instead of allocating.
+/// Configure the failure rate with [`set_feature_probability`] — the
+/// default weight of 1000 fails *every* fallible allocation, which is
+/// rarely what a test wants.
+pub fn should_fail_alloc() -> bool {
+    should_apply(ChaosFeature::MemoryAllocation)
+}
+
+/// Possibly shrink an I/O request to inject a short read or write.
+///
+/// The [`ChaosFeature::FileIO`] entry point for read/write call sites:
+/// when the feature is active and its weight fires, returns a uniformly
+/// random amount in `[1, requested]`; otherwise returns `requested`
+/// unchanged. Never returns 0, so truncation can't be mistaken for EOF,
+/// and requests of 0 or 1 bytes pass through untouched.
+pub fn should_truncate_io(requested: usize) -> usize {
+    if requested <= 1 || !should_apply(ChaosFeature::FileIO) {
+        return requested;
+    }
+    // Draw in [0, requested); +1 shifts to [1, requested]. Requests past
+    // u32 range are clamped before drawing — a 4 GiB truncation bound is
+    // more than enough chaos.
+    let bound = requested.min(u32::MAX as usize) as u32;
+    1 + random_u32_less_than(bound) as usize
+}
+
+/// Return the order in which `len` slots should be visited.
+///
+/// The [`ChaosFeature::HashTableIteration`] entry point for
+/// PLDHashTable-style consumers: when the feature is active and its weight
+/// fires, returns a uniformly random permutation of `0..len` (Fisher-Yates
+/// over the per-thread chaos RNG, so the order replays from the seed);
+/// otherwise returns the identity order. See [`iteration_order`] for the
+/// iterator form.
+pub fn shuffle_indices(len: u32) -> Vec<u32> {
+    let mut indices: Vec<u32> = (0..len).collect();
+    if len > 1 && should_apply(ChaosFeature::HashTableIteration) {
+        // Backwards Fisher-Yates: each prefix draw is uniform over the
+        // remaining slots
+        for i in (1..len as usize).rev() {
+            let j = random_u32_less_than(i as u32 + 1) as usize;
+            indices.swap(i, j);
+        }
+    }
+    indices
+}
+
+/// Iterator over a (possibly randomized) visit order for `len` slots.
+///
+/// Returned by [`iteration_order`]; yields each index in `0..len` exactly
+/// once.
+pub struct IterationOrder {
+    indices: std::vec::IntoIter<u32>,
+}
+
+impl Iterator for IterationOrder {
+    type Item = u32;
+
+    fn next(&mut self) -> Option<u32> {
+        self.indices.next()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.indices.size_hint()
+    }
+}
+
+impl ExactSizeIterator for IterationOrder {}
+
+/// Iterate `0..len` in the order chosen by [`shuffle_indices`].
+///
+/// ```
+/// use firefox_chaosmode::iteration_order;
+///
+/// // With HashTableIteration chaos off this is simply 0..4
+/// let visited: Vec<u32> = iteration_order(4).collect();
+/// assert_eq!(visited.len(), 4);
+/// ```
+pub fn iteration_order(len: u32) -> IterationOrder {
+    IterationOrder {
+        indices: shuffle_indices(len).into_iter(),
+    }
+}
+
+/// Parse a `MOZ_CHAOSMODE` value into a feature bitmask.
+///
+/// Two forms are accepted, matching how Gecko enables chaos mode:
+///
+/// - hex flags: `0xN` (e.g. `0x5` = ThreadScheduling | TimerScheduling)
+/// - a comma-separated name list: `thread,timer,io` — names are
+///   `thread`, `network`, `timer`, `io`, `hash`, `image`, `taskdispatch`,
+///   `taskrun`, `memalloc`, `fileio`, and `any`
+///
+/// An empty string means "everything" (setting the variable at all opts
+/// into chaos). Unknown names are skipped with a warning rather than
+/// aborting startup.
+///
+/// # Returns
+/// The parsed feature bitmask, or `None` if nothing in the value parsed
+pub fn parse_chaos_features(value: &str) -> Option<u32> {
+    let value = value.trim();
+    if value.is_empty() {
+        return Some(ChaosFeature::Any as u32);
+    }
+
+    if let Some(hex) = value.strip_prefix("0x").or_else(|| value.strip_prefix("0X")) {
+        return u32::from_str_radix(hex, 16).ok();
+    }
+
+    let mut features = 0u32;
+    let mut any_parsed = false;
+    for name in value.split(',') {
+        let flag = match name.trim().to_ascii_lowercase().as_str() {
+            "" => continue,
+            "thread" => ChaosFeature::ThreadScheduling,
+            "network" => ChaosFeature::NetworkScheduling,
+            "timer" => ChaosFeature::TimerScheduling,
+            "io" => ChaosFeature::IOAmounts,
+            "hash" => ChaosFeature::HashTableIteration,
+            "image" => ChaosFeature::ImageCache,
+            "taskdispatch" => ChaosFeature::TaskDispatching,
+            "taskrun" => ChaosFeature::TaskRunning,
+            "memalloc" => ChaosFeature::MemoryAllocation,
+            "fileio" => ChaosFeature::FileIO,
+            "any" => ChaosFeature::Any,
+            unknown => {
+                eprintln!("ChaosMode: ignoring unknown feature name '{unknown}' in MOZ_CHAOSMODE");
+                continue;
+            }
+        };
+        features |= flag as u32;
+        any_parsed = true;
+    }
+    any_parsed.then_some(features)
+}
+
+/// Configure chaos mode from the environment.
+///
+/// Reads `MOZ_CHAOSMODE` (see [`parse_chaos_features`] for the accepted
+/// forms) and, if set, applies the parsed feature flags. `MOZ_CHAOSMODE_SEED`
+/// (decimal or `0x`-prefixed hex) additionally seeds the chaos RNG for a
+/// replayable run.
+///
+/// This only configures; the caller (startup code) still decides when to
+/// [`enter_chaos_mode`].
+///
+/// # Returns
+/// `true` if `MOZ_CHAOSMODE` was present and yielded a feature set
+pub fn init_from_env() -> bool {
+    if let Ok(seed_str) = std::env::var("MOZ_CHAOSMODE_SEED") {
+        let seed_str = seed_str.trim();
+        let parsed = match seed_str.strip_prefix("0x").or_else(|| seed_str.strip_prefix("0X")) {
+            Some(hex) => u64::from_str_radix(hex, 16).ok(),
+            None => seed_str.parse::<u64>().ok(),
+        };
+        match parsed {
+            Some(seed) => set_chaos_seed(seed),
+            None => eprintln!("ChaosMode: could not parse MOZ_CHAOSMODE_SEED '{seed_str}'"),
+        }
+    }
+
+    let Ok(value) = std::env::var("MOZ_CHAOSMODE") else {
+        return false;
+    };
+    match parse_chaos_features(&value) {
+        Some(features) => {
+            CHAOS_FEATURES.store(features, Ordering::Relaxed);
+            true
+        }
+        None => {
+            eprintln!("ChaosMode: could not parse MOZ_CHAOSMODE '{value}'");
+            false
+        }
+    }
+}
+
+/// RAII guard for a chaos mode activation.
+///
+/// The constructor calls [`enter_chaos_mode`] and the destructor calls
+/// [`leave_chaos_mode`], so the enter/leave pairing can never be unbalanced
+/// in Rust callers — early returns, `?`, and panics all unwind through the
+/// drop. Guards nest freely, mirroring the counter semantics.
+///
+/// # Example
+/// ```
+/// use firefox_chaosmode::{ChaosFeature, ChaosModeGuard, is_active};
+///
+/// {
+///     let _guard = ChaosModeGuard::new();
+///     assert!(is_active(ChaosFeature::Any));
+/// } // leave_chaos_mode runs here
+/// ```
+#[must_use = "chaos mode is left again as soon as the guard is dropped"]
+pub struct ChaosModeGuard {
+    // Not Send: leaving on a different thread than entering is legal for
+    // the global counter, but keeping the guard thread-bound matches how
+    // the C++ callers scope AutoEnterChaosMode-style helpers.
+    _not_send: std::marker::PhantomData<*const ()>,
+}
+
+impl ChaosModeGuard {
+    /// Enter chaos mode for the lifetime of the returned guard.
+    pub fn new() -> Self {
+        enter_chaos_mode();
+        Self { _not_send: std::marker::PhantomData }
+    }
+}
+
+impl Default for ChaosModeGuard {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Drop for ChaosModeGuard {
+    fn drop(&mut self) {
+        leave_chaos_mode();
+    }
+}
+
+/// Run a closure with chaos mode active and the given feature set.
+///
+/// The previous feature configuration is restored afterwards (also on
+/// panic), so a test can scope chaos to one block:
+///
+/// ```
+/// use firefox_chaosmode::{with_chaos, ChaosFeature, is_active};
+///
+/// let result = with_chaos(ChaosFeature::TimerScheduling, || {
+///     assert!(is_active(ChaosFeature::TimerScheduling));
+///     42
+/// });
+/// assert_eq!(result, 42);
+/// ```
+///
+/// Note the feature set is process-global: other threads that are already
+/// inside chaos mode observe `features` for the duration of the closure.
+pub fn with_chaos<R>(features: ChaosFeature, f: impl FnOnce() -> R) -> R {
+    // Restores the previous feature set on drop, before the outer guard
+    // leaves chaos mode
+    struct RestoreFeatures(u32);
+    impl Drop for RestoreFeatures {
+        fn drop(&mut self) {
+            CHAOS_FEATURES.store(self.0, Ordering::Relaxed);
+        }
+    }
+
+    let _restore = RestoreFeatures(CHAOS_FEATURES.swap(features as u32, Ordering::Relaxed));
+    let _guard = ChaosModeGuard::new();
+    f()
+}
+
+/// The seed behind the chaos RNGs, for replaying failures
+///
+/// The first random draw (or explicit [`set_chaos_seed`]) establishes this;
+/// lazily chosen seeds are logged. A chaos-mode test failure can then be
+/// replayed bit-for-bit by passing the logged seed to [`set_chaos_seed`].
+static CHAOS_SEED: AtomicU64 = AtomicU64::new(0);
+
+/// Bumped by every [`set_chaos_seed`] call; 0 means "never explicitly or
+/// lazily seeded". Thread-local generators compare their cached epoch
+/// against this and reseed themselves when it moved.
+static SEED_EPOCH: AtomicU64 = AtomicU64::new(0);
+
+/// Derive a non-zero seed from ambient entropy (used when no explicit seed
+/// was configured). Mixes a hasher-randomized value with the clock.
+fn entropy_seed() -> u64 {
+    use std::hash::{BuildHasher, Hasher};
+    let hasher_bits = std::collections::hash_map::RandomState::new().build_hasher().finish();
+    let time_bits = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.subsec_nanos() as u64 ^ d.as_secs())
+        .unwrap_or(0);
+    // 0 is the "unseeded" sentinel; remap it
+    match hasher_bits ^ time_bits {
+        0 => 1,
+        seed => seed,
+    }
+}
+
+/// Return the established chaos seed, choosing and logging one on first use
+fn ensure_seed() -> u64 {
+    if SEED_EPOCH.load(Ordering::Relaxed) == 0 {
+        let candidate = entropy_seed();
+        if CHAOS_SEED
+            .compare_exchange(0, candidate, Ordering::Relaxed, Ordering::Relaxed)
+            .is_ok()
+        {
+            // Surface the seed so an intermittent failure's log is enough
+            // to replay the exact sequence
+            eprintln!(
+                "ChaosMode: randomly chosen seed {candidate:#018x} (replay with set_chaos_seed)"
+            );
+        }
+        let _ = SEED_EPOCH.compare_exchange(0, 1, Ordering::Relaxed, Ordering::Relaxed);
+    }
+    CHAOS_SEED.load(Ordering::Relaxed)
+}
+
+thread_local! {
+    /// This thread's chaos generator with the seed epoch it was built from
+    static THREAD_CHAOS_RNG: std::cell::RefCell<Option<(u64, XorShift128PlusRNG)>> =
+        const { std::cell::RefCell::new(None) };
+}
+
+/// Run a closure against the calling thread's chaos RNG, (re)seeding it
+/// first if [`set_chaos_seed`] moved the epoch since the last draw.
+///
+/// Each thread owns an independent generator derived from the global chaos
+/// seed and the thread's compact id, so draws never contend on a shared
+/// generator and each thread's sequence is a pure function of (seed, its
+/// own draw order) — concurrent draws on other threads cannot perturb it.
+fn with_thread_rng<R>(f: impl FnOnce(&mut XorShift128PlusRNG) -> R) -> R {
+    let epoch = SEED_EPOCH.load(Ordering::Relaxed).max(1);
+    THREAD_CHAOS_RNG.with(|cell| {
+        let mut slot = cell.borrow_mut();
+        if slot.as_ref().map(|(cached, _)| *cached) != Some(epoch) {
+            let seed = ensure_seed();
+            // Spread the (small, sequential) thread ids across the seed
+            // space with the SplitMix64 increment before mixing them in
+            let thread_bits = trace::current_thread_id().wrapping_mul(0x9E3779B97F4A7C15);
+            *slot = Some((epoch, XorShift128PlusRNG::from_seed_u64(seed ^ thread_bits)));
+        }
+        let (_, rng) = slot.as_mut().unwrap();
+        f(rng)
+    })
+}
+
+/// Seed the chaos RNGs for deterministic, replayable randomness.
+///
+/// Replaces the previous (or lazily chosen) seed and resets every thread's
+/// generator, so each thread's subsequent [`random_u32_less_than`] /
+/// [`random_i32_in_range`] draws are a pure function of this seed and that
+/// thread's draw order. The seed is logged so failure reports always carry
+/// it.
+///
+/// A seed of 0 is valid (it is expanded through SplitMix64 internally).
+pub fn set_chaos_seed(seed: u64) {
+    CHAOS_SEED.store(seed, Ordering::Relaxed);
+    // Moving the epoch invalidates every thread's cached generator; each
+    // reseeds itself from the new value on its next draw
+    SEED_EPOCH.fetch_add(1, Ordering::Relaxed);
+    eprintln!("ChaosMode: seed set to {seed:#018x}");
+}
+
+/// Return the seed behind the chaos RNGs, choosing one if none was set yet.
+///
+/// Test harnesses include this in failure logs so the run can be replayed
+/// with [`set_chaos_seed`].
+pub fn get_chaos_seed() -> u64 {
+    ensure_seed()
 }
 
 /// Return a pseudo-random uint32_t < aBound.
-/// 
-/// Uses C's rand() function for compatibility with C++ implementation.
-/// NOT thread-safe, NOT cryptographically secure.
-/// Only for chaos testing where deterministic results aren't needed.
-/// 
+///
+/// Draws from the calling thread's xorshift128+ generator (derived from
+/// the seed set via [`set_chaos_seed`], or lazily from entropy, plus the
+/// thread's id), using unbiased bounded generation. NOT cryptographically
+/// secure — chaos testing only.
+///
+/// Thread-safe with no shared state on the draw path: each thread's
+/// sequence replays bit-for-bit from the seed regardless of what other
+/// threads draw concurrently.
+///
 /// # Panics
-/// Debug builds will panic if aBound is 0.
-/// 
-/// # Safety
-/// Uses unsafe FFI call to libc::rand().
-/// Not thread-safe - matches C++ behavior.
+/// Panics if aBound is 0.
 pub fn random_u32_less_than(bound: u32) -> u32 {
     debug_assert!(bound != 0, "bound must not be zero");
-    unsafe {
-        (libc::rand() as u32) % bound
-    }
+    with_thread_rng(|rng| rng.next_u32_below(bound))
 }
 
 /// Return a pseudo-random int32_t between aLow and aHigh (inclusive).
-/// 
-/// Uses C's rand() function for compatibility with C++ implementation.
-/// NOT thread-safe, NOT cryptographically secure.
-/// 
+///
+/// Same generator and seeding as [`random_u32_less_than`].
+///
 /// # Panics
 /// Debug builds will panic if aHigh < aLow.
-/// 
-/// # Safety
-/// Uses unsafe FFI call to libc::rand().
-/// Not thread-safe - matches C++ behavior.
 pub fn random_i32_in_range(low: i32, high: i32) -> i32 {
     debug_assert!(high >= low, "high must be >= low");
-    let range = high - low + 1;
-    unsafe {
-        ((libc::rand() as i32) % range) + low
-    }
+    // Width computed in i64 so extreme ranges (e.g. i32::MIN..=i32::MAX)
+    // don't overflow
+    let range = (high as i64 - low as i64 + 1) as u64;
+    (low as i64 + with_thread_rng(|rng| rng.next_u64_below(range)) as i64) as i32
 }
 
 #[cfg(test)]
@@ -209,7 +928,7 @@ mod tests {
         // Test range bounds
         for _ in 0..100 {
             let val = random_i32_in_range(-10, 10);
-            assert!(val >= -10 && val <= 10);
+            assert!((-10..=10).contains(&val));
         }
         
         // Test single value range
@@ -220,8 +939,378 @@ mod tests {
         // Test positive range
         for _ in 0..100 {
             let val = random_i32_in_range(0, 100);
-            assert!(val >= 0 && val <= 100);
+            assert!((0..=100).contains(&val));
+        }
+    }
+
+    #[test]
+    fn test_guard_balances_counter() {
+        let initial = CHAOS_MODE_COUNTER.load(Ordering::Relaxed);
+        {
+            let _g = ChaosModeGuard::new();
+            assert_eq!(CHAOS_MODE_COUNTER.load(Ordering::Relaxed), initial + 1);
+            {
+                let _inner = ChaosModeGuard::default();
+                assert_eq!(CHAOS_MODE_COUNTER.load(Ordering::Relaxed), initial + 2);
+            }
+            assert_eq!(CHAOS_MODE_COUNTER.load(Ordering::Relaxed), initial + 1);
         }
+        assert_eq!(CHAOS_MODE_COUNTER.load(Ordering::Relaxed), initial);
+    }
+
+    #[test]
+    fn test_guard_balances_on_panic() {
+        let initial = CHAOS_MODE_COUNTER.load(Ordering::Relaxed);
+        let result = std::panic::catch_unwind(|| {
+            let _g = ChaosModeGuard::new();
+            panic!("boom");
+        });
+        assert!(result.is_err());
+        assert_eq!(CHAOS_MODE_COUNTER.load(Ordering::Relaxed), initial);
+    }
+
+    #[test]
+    fn test_with_chaos_scopes_activation_and_returns_value() {
+        let initial = CHAOS_MODE_COUNTER.load(Ordering::Relaxed);
+        let out = with_chaos(ChaosFeature::TimerScheduling, || {
+            assert!(is_active(ChaosFeature::TimerScheduling));
+            "result"
+        });
+        assert_eq!(out, "result");
+        assert_eq!(CHAOS_MODE_COUNTER.load(Ordering::Relaxed), initial);
+    }
+
+    #[test]
+    fn test_with_chaos_restores_features() {
+        set_chaos_feature(ChaosFeature::ImageCache);
+        with_chaos(ChaosFeature::TimerScheduling, || {
+            assert_eq!(
+                CHAOS_FEATURES.load(Ordering::Relaxed),
+                ChaosFeature::TimerScheduling as u32
+            );
+        });
+        assert_eq!(
+            CHAOS_FEATURES.load(Ordering::Relaxed),
+            ChaosFeature::ImageCache as u32
+        );
+        // Restore the default so other tests see the usual baseline
+        set_chaos_feature(ChaosFeature::Any);
+    }
+
+    #[test]
+    fn test_should_apply_respects_activation_and_weights() {
+        // Inactive chaos mode never applies, regardless of weight
+        assert!(!should_apply(ChaosFeature::IOAmounts));
+
+        set_chaos_feature(ChaosFeature::Any);
+        let _guard = ChaosModeGuard::new();
+
+        // Default weight (1000) always fires
+        for _ in 0..50 {
+            assert!(should_apply(ChaosFeature::IOAmounts));
+        }
+
+        // Weight 0 never fires even while active
+        set_feature_probability(ChaosFeature::IOAmounts, 0);
+        for _ in 0..50 {
+            assert!(!should_apply(ChaosFeature::IOAmounts));
+        }
+
+        // An intermediate weight fires roughly proportionally
+        set_feature_probability(ChaosFeature::IOAmounts, 500);
+        let fired = (0..2000).filter(|_| should_apply(ChaosFeature::IOAmounts)).count();
+        assert!(
+            (600..=1400).contains(&fired),
+            "weight 500 fired {fired}/2000 times"
+        );
+
+        // Values above 1000 clamp to always
+        set_feature_probability(ChaosFeature::IOAmounts, 5000);
+        assert!(should_apply(ChaosFeature::IOAmounts));
+
+        // Other features keep their own (default) weight
+        assert!(should_apply(ChaosFeature::TimerScheduling));
+
+        // Restore the default weight for other tests
+        set_feature_probability(ChaosFeature::Any, 1000);
+    }
+
+    #[test]
+    fn test_maybe_delay_and_yield() {
+        // With chaos mode off these are no-ops that return immediately
+        // (nothing observable to assert beyond "does not hang or panic")
+        maybe_delay(ChaosFeature::TaskDispatching, 1_000_000);
+        maybe_yield(ChaosFeature::TaskRunning);
+
+        set_chaos_feature(ChaosFeature::Any);
+        let _guard = ChaosModeGuard::new();
+
+        // Zero max delay never sleeps or draws
+        maybe_delay(ChaosFeature::TaskDispatching, 0);
+
+        // Active delay stays within the requested bound (with generous
+        // scheduler slack so the test cannot flake)
+        let start = std::time::Instant::now();
+        maybe_delay(ChaosFeature::TaskDispatching, 1000);
+        assert!(start.elapsed() < std::time::Duration::from_secs(1));
+
+        maybe_yield(ChaosFeature::TaskRunning);
+    }
+
+    #[test]
+    fn test_thread_local_chaos_scope() {
+        set_chaos_feature(ChaosFeature::Any);
+
+        // The thread-local counter nests independently of the global one
+        let global_before = CHAOS_MODE_COUNTER.load(Ordering::Relaxed);
+        enter_chaos_mode_for_current_thread();
+        enter_chaos_mode_for_current_thread();
+        assert_eq!(THREAD_CHAOS_COUNTER.with(|c| c.get()), 2);
+        assert_eq!(CHAOS_MODE_COUNTER.load(Ordering::Relaxed), global_before);
+        assert!(is_active(ChaosFeature::Any));
+        assert!(should_apply(ChaosFeature::TimerScheduling));
+
+        // A freshly spawned thread starts with its own zeroed counter
+        std::thread::spawn(|| {
+            assert_eq!(THREAD_CHAOS_COUNTER.with(|c| c.get()), 0);
+        })
+        .join()
+        .unwrap();
+
+        leave_chaos_mode_for_current_thread();
+        leave_chaos_mode_for_current_thread();
+        assert_eq!(THREAD_CHAOS_COUNTER.with(|c| c.get()), 0);
+
+        // The FFI pair reaches the same counter
+        ffi::mozilla_chaosmode_enter_chaos_mode_for_current_thread();
+        assert_eq!(THREAD_CHAOS_COUNTER.with(|c| c.get()), 1);
+        ffi::mozilla_chaosmode_leave_chaos_mode_for_current_thread();
+        assert_eq!(THREAD_CHAOS_COUNTER.with(|c| c.get()), 0);
+    }
+
+    #[test]
+    fn test_should_fail_alloc() {
+        set_chaos_feature(ChaosFeature::Any);
+        let _guard = ChaosModeGuard::new();
+
+        // Weight 0 never fails allocations even while active
+        set_feature_probability(ChaosFeature::MemoryAllocation, 0);
+        for _ in 0..50 {
+            assert!(!should_fail_alloc());
+        }
+
+        // Full weight fails every fallible allocation
+        set_feature_probability(ChaosFeature::MemoryAllocation, 1000);
+        for _ in 0..50 {
+            assert!(should_fail_alloc());
+        }
+    }
+
+    #[test]
+    fn test_should_truncate_io() {
+        set_chaos_feature(ChaosFeature::Any);
+        let _guard = ChaosModeGuard::new();
+
+        // Weight 0 passes every request through untouched
+        set_feature_probability(ChaosFeature::FileIO, 0);
+        assert_eq!(should_truncate_io(4096), 4096);
+
+        // Full weight truncates into [1, requested] and never to 0
+        set_feature_probability(ChaosFeature::FileIO, 1000);
+        for _ in 0..100 {
+            let got = should_truncate_io(100);
+            assert!((1..=100).contains(&got), "truncated to {got}");
+        }
+
+        // 0- and 1-byte requests can't be shrunk
+        assert_eq!(should_truncate_io(0), 0);
+        assert_eq!(should_truncate_io(1), 1);
+    }
+
+    #[test]
+    fn test_feature_stats() {
+        // NetworkScheduling is the one feature no other test queries, so
+        // delta assertions on its counters can't race
+        set_chaos_feature(ChaosFeature::Any);
+        let slot = feature_index(ChaosFeature::NetworkScheduling as u32).unwrap();
+        let before = get_stats()[slot];
+        assert_eq!(before.feature, ChaosFeature::NetworkScheduling as u32);
+
+        enter_chaos_mode_for_current_thread();
+        set_feature_probability(ChaosFeature::NetworkScheduling, 1000);
+        for _ in 0..10 {
+            assert!(should_apply(ChaosFeature::NetworkScheduling));
+        }
+        // Suppressed queries count as queried but not fired
+        set_feature_probability(ChaosFeature::NetworkScheduling, 0);
+        for _ in 0..5 {
+            assert!(!should_apply(ChaosFeature::NetworkScheduling));
+        }
+        leave_chaos_mode_for_current_thread();
+        // Queries while chaos mode is off still count as queried
+        should_apply(ChaosFeature::NetworkScheduling);
+        set_feature_probability(ChaosFeature::NetworkScheduling, 1000);
+
+        let after = get_stats()[slot];
+        assert_eq!(after.queried - before.queried, 16);
+        assert_eq!(after.fired - before.fired, 10);
+
+        // The FFI dump sizes with null buffers, then fills
+        let count =
+            unsafe { ffi::mozilla_chaosmode_get_stats(std::ptr::null_mut(), std::ptr::null_mut(), 0) };
+        assert_eq!(count, FEATURE_COUNT);
+        let mut queried = vec![0u64; count];
+        let mut fired = vec![0u64; count];
+        unsafe {
+            ffi::mozilla_chaosmode_get_stats(queried.as_mut_ptr(), fired.as_mut_ptr(), count)
+        };
+        assert!(queried[slot] >= after.queried);
+        assert!(fired[slot] >= after.fired);
+
+        // Reset zeroes the counters (nothing else queries this feature)
+        ffi::mozilla_chaosmode_reset_stats();
+        let cleared = get_stats()[slot];
+        assert_eq!(cleared.queried, 0);
+        assert_eq!(cleared.fired, 0);
+    }
+
+    #[test]
+    fn test_shuffle_indices() {
+        set_chaos_feature(ChaosFeature::Any);
+        enter_chaos_mode_for_current_thread();
+
+        // Weight 0 keeps the identity order even while active
+        set_feature_probability(ChaosFeature::HashTableIteration, 0);
+        assert_eq!(shuffle_indices(5), vec![0, 1, 2, 3, 4]);
+
+        // Full weight yields a permutation of 0..len
+        set_feature_probability(ChaosFeature::HashTableIteration, 1000);
+        let order = shuffle_indices(100);
+        let mut sorted = order.clone();
+        sorted.sort_unstable();
+        assert_eq!(sorted, (0..100).collect::<Vec<u32>>());
+
+        // ...that is overwhelmingly unlikely to still be the identity
+        let identity: Vec<u32> = (0..100).collect();
+        let shuffled = (0..10).any(|_| shuffle_indices(100) != identity);
+        assert!(shuffled);
+
+        // Degenerate lengths don't draw at all
+        assert!(shuffle_indices(0).is_empty());
+        assert_eq!(shuffle_indices(1), vec![0]);
+
+        // The iterator form visits every index exactly once
+        let iter = iteration_order(50);
+        assert_eq!(iter.len(), 50);
+        let mut visited: Vec<u32> = iter.collect();
+        visited.sort_unstable();
+        assert_eq!(visited, (0..50).collect::<Vec<u32>>());
+
+        // The FFI form fills the caller's buffer with the same shape
+        let mut buf = [u32::MAX; 20];
+        unsafe { ffi::mozilla_chaosmode_shuffle_indices(buf.as_mut_ptr(), buf.len() as u32) };
+        let mut sorted = buf;
+        sorted.sort_unstable();
+        assert_eq!(sorted, std::array::from_fn::<u32, 20, _>(|i| i as u32));
+
+        leave_chaos_mode_for_current_thread();
+    }
+
+    #[test]
+    fn test_feature_index() {
+        assert_eq!(feature_index(ChaosFeature::ThreadScheduling as u32), Some(0));
+        assert_eq!(feature_index(ChaosFeature::TaskRunning as u32), Some(7));
+        assert_eq!(feature_index(ChaosFeature::MemoryAllocation as u32), Some(8));
+        assert_eq!(feature_index(ChaosFeature::FileIO as u32), Some(9));
+        // Multi-bit and out-of-range masks have no single slot
+        assert_eq!(feature_index(0x3), None);
+        assert_eq!(feature_index(ChaosFeature::Any as u32), None);
+        assert_eq!(feature_index(0x400), None);
+        assert_eq!(feature_index(0), None);
+    }
+
+    #[test]
+    fn test_parse_chaos_features() {
+        // Hex form
+        assert_eq!(parse_chaos_features("0x1"), Some(0x1));
+        assert_eq!(parse_chaos_features("0X10"), Some(0x10));
+        assert_eq!(parse_chaos_features("0xffffffff"), Some(0xffffffff));
+        assert_eq!(parse_chaos_features("0xzz"), None);
+
+        // Name list form
+        assert_eq!(
+            parse_chaos_features("thread,timer,io"),
+            Some(
+                ChaosFeature::ThreadScheduling as u32
+                    | ChaosFeature::TimerScheduling as u32
+                    | ChaosFeature::IOAmounts as u32
+            )
+        );
+        assert_eq!(parse_chaos_features("any"), Some(ChaosFeature::Any as u32));
+        assert_eq!(
+            parse_chaos_features("memalloc,fileio"),
+            Some(ChaosFeature::MemoryAllocation as u32 | ChaosFeature::FileIO as u32)
+        );
+        assert_eq!(
+            parse_chaos_features(" Hash , IMAGE "),
+            Some(ChaosFeature::HashTableIteration as u32 | ChaosFeature::ImageCache as u32)
+        );
+
+        // Unknown names are skipped, not fatal
+        assert_eq!(
+            parse_chaos_features("thread,bogus"),
+            Some(ChaosFeature::ThreadScheduling as u32)
+        );
+        assert_eq!(parse_chaos_features("bogus"), None);
+
+        // Empty means everything (the variable being set is the opt-in)
+        assert_eq!(parse_chaos_features(""), Some(ChaosFeature::Any as u32));
+    }
+
+    #[test]
+    fn test_init_from_env() {
+        // All env interaction stays in this single test so parallel tests
+        // never race on the variables
+        std::env::remove_var("MOZ_CHAOSMODE");
+        std::env::remove_var("MOZ_CHAOSMODE_SEED");
+        assert!(!init_from_env());
+
+        std::env::set_var("MOZ_CHAOSMODE", "0x3");
+        assert!(init_from_env());
+        assert_eq!(CHAOS_FEATURES.load(Ordering::Relaxed), 0x3);
+
+        std::env::set_var("MOZ_CHAOSMODE", "timer");
+        assert!(init_from_env());
+        assert_eq!(
+            CHAOS_FEATURES.load(Ordering::Relaxed),
+            ChaosFeature::TimerScheduling as u32
+        );
+
+        // Unparseable value leaves the previous configuration alone
+        std::env::set_var("MOZ_CHAOSMODE", "bogus");
+        assert!(!init_from_env());
+        assert_eq!(
+            CHAOS_FEATURES.load(Ordering::Relaxed),
+            ChaosFeature::TimerScheduling as u32
+        );
+
+        std::env::remove_var("MOZ_CHAOSMODE");
+        std::env::remove_var("MOZ_CHAOSMODE_SEED");
+        // Restore the default so other tests see the usual baseline
+        set_chaos_feature(ChaosFeature::Any);
+    }
+
+    #[test]
+    fn test_set_chaos_feature_atomic_round_trip() {
+        // The store must be observable through the same atomic without
+        // any unsafe access
+        set_chaos_feature(ChaosFeature::ImageCache);
+        assert_eq!(
+            CHAOS_FEATURES.load(Ordering::Relaxed),
+            ChaosFeature::ImageCache as u32
+        );
+        // Restore the default so other tests see the usual baseline
+        set_chaos_feature(ChaosFeature::Any);
     }
 
     #[test]
@@ -236,6 +1325,8 @@ mod tests {
         assert_eq!(ChaosFeature::ImageCache as u32, 0x20);
         assert_eq!(ChaosFeature::TaskDispatching as u32, 0x40);
         assert_eq!(ChaosFeature::TaskRunning as u32, 0x80);
+        assert_eq!(ChaosFeature::MemoryAllocation as u32, 0x100);
+        assert_eq!(ChaosFeature::FileIO as u32, 0x200);
         assert_eq!(ChaosFeature::Any as u32, 0xffffffff);
     }
 }
@@ -0,0 +1,82 @@
+// -*- Mode: Rust; tab-width: 4; indent-tabs-mode: nil; c-basic-offset: 4 -*-
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+//! Observer hook tests for ChaosMode
+//!
+//! These run in their own test binary so no other test produces chaos
+//! events (enter/leave/trigger) while an observer is registered — exact
+//! event-count assertions would be racy in the main test binaries.
+
+use firefox_chaosmode::*;
+use std::sync::atomic::{AtomicU32, Ordering};
+
+/// Per-event counters shared with the callback through user_data
+#[derive(Default)]
+struct EventCounts {
+    enter: AtomicU32,
+    leave: AtomicU32,
+    trigger: AtomicU32,
+    last_trigger_feature: AtomicU32,
+}
+
+extern "C" fn counting_observer(event: u32, feature: u32, user_data: *mut std::ffi::c_void) {
+    let counts = unsafe { &*(user_data as *const EventCounts) };
+    match event {
+        e if e == ChaosEvent::Enter as u32 => counts.enter.fetch_add(1, Ordering::Relaxed),
+        e if e == ChaosEvent::Leave as u32 => counts.leave.fetch_add(1, Ordering::Relaxed),
+        e if e == ChaosEvent::FeatureTrigger as u32 => {
+            counts.last_trigger_feature.store(feature, Ordering::Relaxed);
+            counts.trigger.fetch_add(1, Ordering::Relaxed)
+        }
+        other => panic!("unexpected chaos event {other}"),
+    };
+}
+
+#[test]
+fn test_observer_hooks() {
+    let counts = EventCounts::default();
+    let user_data = &counts as *const EventCounts as *mut std::ffi::c_void;
+
+    // Events before registration are not delivered
+    enter_chaos_mode();
+    leave_chaos_mode();
+    assert_eq!(counts.enter.load(Ordering::Relaxed), 0);
+
+    let id = register_chaos_observer(counting_observer, user_data);
+
+    set_chaos_feature(ChaosFeature::Any);
+    {
+        let _guard = ChaosModeGuard::new();
+        assert!(should_apply(ChaosFeature::TimerScheduling));
+        assert!(should_apply(ChaosFeature::IOAmounts));
+
+        // Suppressed decisions do not produce trigger events
+        set_feature_probability(ChaosFeature::ImageCache, 0);
+        assert!(!should_apply(ChaosFeature::ImageCache));
+        set_feature_probability(ChaosFeature::ImageCache, 1000);
+    }
+
+    assert_eq!(counts.enter.load(Ordering::Relaxed), 1);
+    assert_eq!(counts.leave.load(Ordering::Relaxed), 1);
+    assert_eq!(counts.trigger.load(Ordering::Relaxed), 2);
+    assert_eq!(
+        counts.last_trigger_feature.load(Ordering::Relaxed),
+        ChaosFeature::IOAmounts as u32
+    );
+
+    // Unregistered observers stop receiving events
+    assert!(unregister_chaos_observer(id));
+    assert!(!unregister_chaos_observer(id));
+    enter_chaos_mode();
+    leave_chaos_mode();
+    assert_eq!(counts.enter.load(Ordering::Relaxed), 1);
+
+    // Same flow through the FFI
+    let id = unsafe { ffi::mozilla_chaosmode_register_observer(counting_observer, user_data) };
+    ffi::mozilla_chaosmode_enter_chaos_mode();
+    ffi::mozilla_chaosmode_leave_chaos_mode();
+    assert_eq!(counts.enter.load(Ordering::Relaxed), 2);
+    assert!(ffi::mozilla_chaosmode_unregister_observer(id));
+}
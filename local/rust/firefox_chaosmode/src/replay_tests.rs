@@ -0,0 +1,124 @@
+// -*- Mode: Rust; tab-width: 4; indent-tabs-mode: nil; c-basic-offset: 4 -*-
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+//! Seed replay tests for ChaosMode
+//!
+//! These run in their own test binary so no other test calls
+//! set_chaos_seed (which invalidates every thread's generator) or fills
+//! the trace ring concurrently — such assertions would be racy in the
+//! main test binaries. Keep every test that asserts exact draw sequences
+//! in the single test below.
+
+use firefox_chaosmode::*;
+
+#[test]
+fn test_trace_ring_buffer() {
+    // This is the only test in this binary that records decisions (the
+    // replay test below never calls should_apply), so exact-content
+    // assertions on the global ring buffer are race-free here
+    trace::clear();
+    assert!(trace::snapshot().is_empty());
+    assert_eq!(trace::to_json(), "{\"decisions\":[]}");
+
+    set_chaos_feature(ChaosFeature::Any);
+    let _guard = ChaosModeGuard::new();
+
+    // Inactive features and default weights both trace their decisions
+    assert!(should_apply(ChaosFeature::IOAmounts));
+    set_feature_probability(ChaosFeature::TimerScheduling, 0);
+    assert!(!should_apply(ChaosFeature::TimerScheduling));
+    set_feature_probability(ChaosFeature::TimerScheduling, 1000);
+
+    let records = trace::snapshot();
+    assert_eq!(records.len(), 2);
+    assert_eq!(records[0].feature, ChaosFeature::IOAmounts as u32);
+    assert!(records[0].fired);
+    assert_eq!(records[1].feature, ChaosFeature::TimerScheduling as u32);
+    assert!(!records[1].fired);
+    assert!(records[0].seq < records[1].seq);
+    assert!(records[0].timestamp_us <= records[1].timestamp_us);
+    assert_eq!(records[0].thread_id, records[1].thread_id);
+
+    let json = trace::to_json();
+    assert!(json.contains("\"feature\":\"0x8\""));
+    assert!(json.contains("\"feature_name\":\"io\""));
+    assert!(json.contains("\"fired\":false"));
+
+    // Overflow keeps only the newest TRACE_CAPACITY entries
+    for _ in 0..(trace::TRACE_CAPACITY + 50) {
+        should_apply(ChaosFeature::HashTableIteration);
+    }
+    let records = trace::snapshot();
+    assert_eq!(records.len(), trace::TRACE_CAPACITY);
+    assert!(records.windows(2).all(|w| w[0].seq + 1 == w[1].seq));
+    assert_eq!(
+        records.last().unwrap().feature,
+        ChaosFeature::HashTableIteration as u32
+    );
+
+    // FFI dump: size, then fill
+    let needed = unsafe { ffi::mozilla_chaosmode_trace_to_json(std::ptr::null_mut(), 0) };
+    let mut buf = vec![0u8; needed];
+    let written = unsafe { ffi::mozilla_chaosmode_trace_to_json(buf.as_mut_ptr(), buf.len()) };
+    assert_eq!(written, needed);
+    let json = String::from_utf8(buf).unwrap();
+    assert!(json.starts_with("{\"decisions\":["));
+    assert!(json.ends_with("]}"));
+
+    ffi::mozilla_chaosmode_trace_clear();
+    assert!(trace::snapshot().is_empty());
+}
+
+#[test]
+fn test_seed_replay() {
+    // Seeding is reflected by the getter
+    set_chaos_seed(0xDECAF);
+    assert_eq!(get_chaos_seed(), 0xDECAF);
+
+    // Record a sequence...
+    let first: Vec<u32> = (0..50).map(|_| random_u32_less_than(1000)).collect();
+    let first_i32: Vec<i32> = (0..50).map(|_| random_i32_in_range(-100, 100)).collect();
+
+    // ...re-seed and replay it bit-for-bit
+    set_chaos_seed(0xDECAF);
+    let second: Vec<u32> = (0..50).map(|_| random_u32_less_than(1000)).collect();
+    let second_i32: Vec<i32> = (0..50).map(|_| random_i32_in_range(-100, 100)).collect();
+
+    assert_eq!(first, second);
+    assert_eq!(first_i32, second_i32);
+
+    // A different seed yields a different sequence
+    set_chaos_seed(0xBEEF);
+    let third: Vec<u32> = (0..50).map(|_| random_u32_less_than(1000)).collect();
+    assert_ne!(first, third);
+
+    // FFI seed functions reach the same generator
+    ffi::mozilla_chaosmode_set_chaos_seed(0xDECAF);
+    assert_eq!(ffi::mozilla_chaosmode_get_chaos_seed(), 0xDECAF);
+    let via_ffi: Vec<u32> = (0..50)
+        .map(|_| ffi::mozilla_chaosmode_random_u32_less_than(1000))
+        .collect();
+    assert_eq!(first, via_ffi);
+
+    // Draws on other threads must not perturb this thread's sequence:
+    // each thread owns a generator derived from (seed, thread id)
+    set_chaos_seed(0xDECAF);
+    std::thread::spawn(|| {
+        for _ in 0..100 {
+            random_u32_less_than(1000);
+        }
+    })
+    .join()
+    .unwrap();
+    let with_noise: Vec<u32> = (0..50).map(|_| random_u32_less_than(1000)).collect();
+    set_chaos_seed(0xDECAF);
+    let without_noise: Vec<u32> = (0..50).map(|_| random_u32_less_than(1000)).collect();
+    assert_eq!(with_noise, without_noise);
+
+    // Extreme i32 range must not overflow internally
+    set_chaos_seed(1);
+    let v = random_i32_in_range(i32::MIN, i32::MAX);
+    let _ = v; // any value is in range by construction
+}
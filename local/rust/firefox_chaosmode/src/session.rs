@@ -0,0 +1,78 @@
+// -*- Mode: Rust; tab-width: 4; indent-tabs-mode: nil; c-basic-offset: 4 -*-
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+//! Chaos session capture and replay files
+//!
+//! A failing chaos-mode CI run is only useful if it can be reproduced.
+//! [`export_session`] writes everything that shaped the run — the applied
+//! [`ChaosConfig`], the seed actually in use, and the decision trace — to
+//! one file suitable for a CI artifact. [`import_session`] reads the file
+//! back and applies the configuration (including the seed), so the same
+//! chaos behavior replays locally. The recorded trace rides along for
+//! inspection; replay itself is driven by the seed, not the log.
+
+use crate::config::ChaosConfig;
+use crate::{get_chaos_seed, trace};
+use std::io;
+use std::path::Path;
+
+/// First line of a session file; bump the version on format changes
+const SESSION_HEADER: &str = "# ChaosMode session v1";
+
+/// Write the current chaos configuration, seed, and decision trace to
+/// `path`.
+///
+/// The file is line-oriented text: a header comment, a `config=` line in
+/// [`ChaosConfig::to_config_string`] form (always carrying the in-use
+/// seed), and a `trace=` line holding the decision log as JSON.
+///
+/// # Returns
+/// `Ok(())` on success, or the underlying I/O error
+pub fn export_session(path: impl AsRef<Path>) -> io::Result<()> {
+    // current() may have no explicit seed; embed the one actually in use
+    // so the import replays the same sequences
+    let config = ChaosConfig::current().seed(get_chaos_seed());
+    let contents = format!(
+        "{SESSION_HEADER}\nconfig={}\ntrace={}\n",
+        config.to_config_string(),
+        trace::to_json()
+    );
+    std::fs::write(path, contents)
+}
+
+/// Read a session file written by [`export_session`] and apply its
+/// configuration, including the seed.
+///
+/// The `trace=` line (the exporting run's decision log) is not replayed —
+/// determinism comes from the seed — but the file is rejected if no
+/// `config=` line parses. Comment lines (`#`) and unknown keys are
+/// skipped so the format can grow.
+///
+/// # Returns
+/// The applied configuration, or an `InvalidData` error for a malformed
+/// file (I/O errors pass through)
+pub fn import_session(path: impl AsRef<Path>) -> io::Result<ChaosConfig> {
+    let text = std::fs::read_to_string(path)?;
+    let mut config = None;
+    for line in text.lines() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        if let Some(value) = line.strip_prefix("config=") {
+            config = Some(ChaosConfig::from_config_string(value).ok_or_else(|| {
+                io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    format!("unparseable chaos config line: {value}"),
+                )
+            })?);
+        }
+    }
+    let config = config.ok_or_else(|| {
+        io::Error::new(io::ErrorKind::InvalidData, "session file has no config line")
+    })?;
+    config.apply();
+    Ok(config)
+}
@@ -0,0 +1,67 @@
+// -*- Mode: Rust; tab-width: 4; indent-tabs-mode: nil; c-basic-offset: 4 -*-
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+//! Session export/import tests
+//!
+//! Importing a session applies a ChaosConfig and seed, and exporting
+//! snapshots the decision trace, all of which is process-global state —
+//! so these run in their own test binary, as one serial test.
+
+use firefox_chaosmode::*;
+
+#[test]
+fn test_session_round_trip() {
+    let dir = std::env::temp_dir();
+    let path = dir.join(format!("chaos_session_{}.txt", std::process::id()));
+
+    // Capture a configured run with a few traced decisions
+    let config = ChaosConfig::new()
+        .features(ChaosFeature::TimerScheduling as u32 | ChaosFeature::IOAmounts as u32)
+        .seed(0xCAB00D1E)
+        .probability(ChaosFeature::IOAmounts, 500);
+    config.apply();
+    trace::clear();
+    enter_chaos_mode_for_current_thread();
+    let original: Vec<u32> = (0..20).map(|_| random_u32_less_than(1000)).collect();
+    for _ in 0..10 {
+        should_apply(ChaosFeature::IOAmounts);
+    }
+    export_session(&path).unwrap();
+    leave_chaos_mode_for_current_thread();
+
+    // The file carries the header, the config (with seed), and the trace
+    let contents = std::fs::read_to_string(&path).unwrap();
+    assert!(contents.starts_with("# ChaosMode session v1\n"));
+    assert!(contents.contains("config=features=0xc;seed=0xcab00d1e;prob.io=500\n"));
+    assert!(contents.contains("trace={\"decisions\":["));
+    assert!(contents.contains("\"feature_name\":\"io\""));
+
+    // Perturb everything, then import: config, seed, and weights return
+    ChaosConfig::new().seed(1).apply();
+    let imported = import_session(&path).unwrap();
+    assert_eq!(imported, ChaosConfig::current());
+    assert_eq!(get_chaos_seed(), 0xCAB00D1E);
+
+    // The replayed draw sequence matches the captured run's
+    let replayed: Vec<u32> = (0..20).map(|_| random_u32_less_than(1000)).collect();
+    assert_eq!(original, replayed);
+
+    // The FFI pair reaches the same files and state
+    ChaosConfig::new().seed(7).apply();
+    let path_str = path.to_str().unwrap();
+    assert!(unsafe { ffi::mozilla_chaosmode_import_session(path_str.as_ptr(), path_str.len()) });
+    assert_eq!(get_chaos_seed(), 0xCAB00D1E);
+    assert!(unsafe { ffi::mozilla_chaosmode_export_session(path_str.as_ptr(), path_str.len()) });
+
+    // Malformed and missing files are errors, not panics
+    std::fs::write(&path, "config=features=0xzz\n").unwrap();
+    assert!(import_session(&path).is_err());
+    std::fs::write(&path, "# just a comment\n").unwrap();
+    assert!(import_session(&path).is_err());
+    std::fs::remove_file(&path).unwrap();
+    assert!(import_session(&path).is_err());
+
+    ChaosConfig::new().apply();
+}
@@ -101,7 +101,7 @@ fn test_random_edge_cases() {
     // Test negative range
     for _ in 0..100 {
         let val = mozilla_chaosmode_random_i32_in_range(-100, -50);
-        assert!(val >= -100 && val <= -50);
+        assert!((-100..=-50).contains(&val));
     }
 }
 
@@ -0,0 +1,229 @@
+// -*- Mode: Rust; tab-width: 4; indent-tabs-mode: nil; c-basic-offset: 4 -*-
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+//! Chaos decision trace ring buffer
+//!
+//! Intermittent-failure investigations need to know what chaos mode
+//! actually did, not just that it was enabled. This module keeps the last
+//! [`TRACE_CAPACITY`] chaos decisions (feature, thread, timestamp, random
+//! value, fired) in a fixed-size lock-free ring buffer that
+//! [`crate::should_apply`] feeds, and renders it as JSON for log
+//! attachments.
+//!
+//! # Lock-freedom
+//!
+//! Writers claim a slot with one `fetch_add` and publish it with a seqlock
+//! scheme: the slot's sequence number is zeroed, the fields are written,
+//! then the sequence is stored with Release. Readers accept a slot only if
+//! the sequence read with Acquire before and after the fields agree, so a
+//! torn (concurrently rewritten) slot is skipped rather than misreported.
+//! Recording never blocks and never allocates.
+
+use crate::leading_feature_name;
+use std::sync::atomic::{AtomicU32, AtomicU64, Ordering};
+
+/// Number of decisions retained; older entries are overwritten
+pub const TRACE_CAPACITY: usize = 1024;
+
+/// One slot of the ring buffer (all-atomic so writers never lock)
+struct TraceSlot {
+    /// 1-based global sequence number; 0 means "never written"
+    seq: AtomicU64,
+    feature: AtomicU32,
+    /// 1 if the decision fired, 0 if the weight suppressed it
+    fired: AtomicU32,
+    thread_id: AtomicU64,
+    timestamp_us: AtomicU64,
+    random_value: AtomicU32,
+}
+
+impl TraceSlot {
+    const fn new() -> Self {
+        Self {
+            seq: AtomicU64::new(0),
+            feature: AtomicU32::new(0),
+            fired: AtomicU32::new(0),
+            thread_id: AtomicU64::new(0),
+            timestamp_us: AtomicU64::new(0),
+            random_value: AtomicU32::new(0),
+        }
+    }
+}
+
+static SLOTS: [TraceSlot; TRACE_CAPACITY] = [const { TraceSlot::new() }; TRACE_CAPACITY];
+
+/// Total number of decisions ever recorded (also the next sequence number)
+static CURSOR: AtomicU64 = AtomicU64::new(0);
+
+/// Compact per-thread id (ThreadId has no stable numeric form). Also used
+/// to derive the per-thread chaos RNG seed.
+pub(crate) fn current_thread_id() -> u64 {
+    static NEXT_THREAD_ID: AtomicU64 = AtomicU64::new(1);
+    thread_local! {
+        static THREAD_ID: u64 = NEXT_THREAD_ID.fetch_add(1, Ordering::Relaxed);
+    }
+    THREAD_ID.with(|id| *id)
+}
+
+/// Microseconds since the Unix epoch (0 if the clock is unavailable)
+fn now_micros() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_micros() as u64)
+        .unwrap_or(0)
+}
+
+/// A decoded trace entry, oldest-first in [`snapshot`] order.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TraceRecord {
+    /// Global 1-based sequence number of the decision
+    pub seq: u64,
+    /// The queried feature bitmask
+    pub feature: u32,
+    /// Whether the perturbation was applied
+    pub fired: bool,
+    /// Compact id of the querying thread
+    pub thread_id: u64,
+    /// Microseconds since the Unix epoch
+    pub timestamp_us: u64,
+    /// The random draw that decided (0 for deterministic 0/1000 weights)
+    pub random_value: u32,
+}
+
+/// Record one chaos decision. Lock-free; called by [`crate::should_apply`].
+pub(crate) fn record_decision(feature: u32, random_value: u32, fired: bool) {
+    let seq = CURSOR.fetch_add(1, Ordering::Relaxed) + 1;
+    let slot = &SLOTS[(seq - 1) as usize % TRACE_CAPACITY];
+
+    // Invalidate, write fields, publish (seqlock write side)
+    slot.seq.store(0, Ordering::Release);
+    slot.feature.store(feature, Ordering::Relaxed);
+    slot.fired.store(fired as u32, Ordering::Relaxed);
+    slot.thread_id.store(current_thread_id(), Ordering::Relaxed);
+    slot.timestamp_us.store(now_micros(), Ordering::Relaxed);
+    slot.random_value.store(random_value, Ordering::Relaxed);
+    slot.seq.store(seq, Ordering::Release);
+}
+
+/// Snapshot the ring buffer, oldest entry first.
+///
+/// Entries being overwritten concurrently are skipped (their before/after
+/// sequence reads disagree), so the result is always internally consistent.
+pub fn snapshot() -> Vec<TraceRecord> {
+    let newest = CURSOR.load(Ordering::Relaxed);
+    let span = newest.min(TRACE_CAPACITY as u64);
+    let mut records = Vec::with_capacity(span as usize);
+    for seq in (newest - span + 1)..=newest {
+        if seq == 0 {
+            continue;
+        }
+        let slot = &SLOTS[(seq - 1) as usize % TRACE_CAPACITY];
+        let before = slot.seq.load(Ordering::Acquire);
+        if before != seq {
+            continue; // already overwritten or mid-write
+        }
+        let record = TraceRecord {
+            seq,
+            feature: slot.feature.load(Ordering::Relaxed),
+            fired: slot.fired.load(Ordering::Relaxed) != 0,
+            thread_id: slot.thread_id.load(Ordering::Relaxed),
+            timestamp_us: slot.timestamp_us.load(Ordering::Relaxed),
+            random_value: slot.random_value.load(Ordering::Relaxed),
+        };
+        if slot.seq.load(Ordering::Acquire) == seq {
+            records.push(record);
+        }
+    }
+    records
+}
+
+/// Forget all recorded decisions (primarily for tests and session capture).
+pub fn clear() {
+    // New sequence numbers restart from 1; stale slots are unreachable
+    // because their stored seq no longer matches any probed value
+    CURSOR.store(0, Ordering::Relaxed);
+    for slot in &SLOTS {
+        slot.seq.store(0, Ordering::Relaxed);
+    }
+}
+
+/// Append a JSON string literal, escaping via the shared
+/// `firefox_jsonwriter` two-character escape table (control characters
+/// without a two-char form use `\u00XX`).
+fn push_json_str(out: &mut String, s: &str) {
+    out.push('"');
+    for &b in s.as_bytes() {
+        let escape = firefox_jsonwriter::TWO_CHAR_ESCAPES[b as usize];
+        if escape != 0 {
+            out.push('\\');
+            out.push(escape as u8 as char);
+        } else if b < 0x20 {
+            out.push_str(&format!("\\u{b:04x}"));
+        } else {
+            out.push(b as char);
+        }
+    }
+    out.push('"');
+}
+
+/// Render the current trace as JSON, oldest entry first.
+///
+/// The shape is stable for tooling: an object with a `decisions` array
+/// whose entries carry `seq`, `feature` (hex string), `feature_name`,
+/// `fired`, `thread`, `timestamp_us`, and `random`.
+pub fn to_json() -> String {
+    let records = snapshot();
+    let mut out = String::from("{\"decisions\":[");
+    for (i, r) in records.iter().enumerate() {
+        if i > 0 {
+            out.push(',');
+        }
+        out.push_str("{\"seq\":");
+        out.push_str(&r.seq.to_string());
+        out.push_str(",\"feature\":");
+        push_json_str(&mut out, &format!("{:#x}", r.feature));
+        out.push_str(",\"feature_name\":");
+        push_json_str(&mut out, leading_feature_name(r.feature));
+        out.push_str(",\"fired\":");
+        out.push_str(if r.fired { "true" } else { "false" });
+        out.push_str(",\"thread\":");
+        out.push_str(&r.thread_id.to_string());
+        out.push_str(",\"timestamp_us\":");
+        out.push_str(&r.timestamp_us.to_string());
+        out.push_str(",\"random\":");
+        out.push_str(&r.random_value.to_string());
+        out.push('}');
+    }
+    out.push_str("]}");
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Behavioral tests that assert on the global ring buffer contents live
+    // in src/replay_tests.rs (a separate test process), because lib tests
+    // exercising should_apply record into the same buffer concurrently.
+
+    #[test]
+    fn test_push_json_str_escapes() {
+        let mut out = String::new();
+        push_json_str(&mut out, "plain");
+        assert_eq!(out, "\"plain\"");
+
+        let mut out = String::new();
+        push_json_str(&mut out, "a\"b\\c\nd\u{1}");
+        assert_eq!(out, "\"a\\\"b\\\\c\\nd\\u0001\"");
+    }
+
+    #[test]
+    fn test_thread_ids_are_distinct() {
+        let mine = current_thread_id();
+        assert_eq!(mine, current_thread_id(), "id must be stable per thread");
+        let other = std::thread::spawn(current_thread_id).join().unwrap();
+        assert_ne!(mine, other);
+    }
+}
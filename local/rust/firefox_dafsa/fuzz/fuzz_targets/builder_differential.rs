@@ -0,0 +1,74 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+//! Differential fuzz target: DafsaBuilder against a BTreeMap oracle.
+//! The input is cut into lines; each line becomes a printable key with
+//! a value derived from its bytes. The built table must answer exactly
+//! like the map — for every stored key, for mutated probes, through
+//! iteration, and after a round trip through the checked container —
+//! which pins the binary format against builder or reader regressions.
+
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use std::collections::BTreeMap;
+
+use firefox_dafsa::{Dafsa, DafsaBuilder, KEY_NOT_FOUND};
+
+fuzz_target!(|data: &[u8]| {
+    let mut oracle: BTreeMap<String, i32> = BTreeMap::new();
+    let mut builder = DafsaBuilder::new();
+
+    for line in data.split(|&b| b == b'\n').take(256) {
+        let Some((&value_byte, key_bytes)) = line.split_first() else {
+            continue;
+        };
+        if key_bytes.is_empty() || key_bytes.len() > 64 {
+            continue;
+        }
+        // Fold every byte into the printable range the format allows
+        let key: String = key_bytes
+            .iter()
+            .map(|&b| (0x20 + (b % 0x5F)) as char)
+            .collect();
+        // Exercise compact and variable-width terminals alike
+        let value = (value_byte as i32) * (value_byte as i32);
+        if oracle.contains_key(&key) {
+            continue;
+        }
+        builder.insert(&key, value).expect("printable key rejected");
+        oracle.insert(key, value);
+    }
+
+    let bytes = builder.build().expect("build failed");
+    let dafsa = Dafsa::new(bytes);
+
+    // Every stored key answers its value; probes derived from stored
+    // keys answer whatever the oracle says
+    for (key, &value) in &oracle {
+        assert_eq!(dafsa.lookup(key), value, "key {key:?}");
+        let probes = [
+            format!("{key}!"),
+            key[..key.len() - 1].to_string(),
+            format!("!{key}"),
+        ];
+        for probe in probes {
+            let expected = oracle.get(&probe).copied().unwrap_or(KEY_NOT_FOUND);
+            assert_eq!(dafsa.lookup(&probe), expected, "probe {probe:?}");
+        }
+    }
+
+    // Iteration reproduces the full key set in lexicographic order
+    let entries: Vec<(String, i32)> = dafsa.iter().collect();
+    let reference: Vec<(String, i32)> =
+        oracle.iter().map(|(k, &v)| (k.clone(), v)).collect();
+    assert_eq!(entries, reference);
+
+    // The checked container round-trips losslessly
+    let reloaded = Dafsa::from_slice_checked(&dafsa.to_checked_bytes())
+        .expect("checked round trip failed");
+    for (key, &value) in &oracle {
+        assert_eq!(reloaded.lookup(key), value);
+    }
+});
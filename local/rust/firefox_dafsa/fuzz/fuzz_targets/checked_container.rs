@@ -0,0 +1,35 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+//! Fuzz target: checked container loading on arbitrary bytes.
+//! `from_slice_checked` is the entry point that faces untrusted table
+//! bytes (remote settings, caches), so header parsing must never
+//! panic, whatever the input. Lookups are only exercised on tables the
+//! builder produced — a forged payload with a matching checksum is
+//! outside the integrity check's threat model, like the C++ reader's.
+
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+
+use firefox_dafsa::Dafsa;
+
+fuzz_target!(|data: &[u8]| {
+    let _ = Dafsa::from_slice_checked(data);
+
+    // Truncations of a genuine container must all be rejected cleanly
+    let mut builder = firefox_dafsa::DafsaBuilder::new();
+    for (index, chunk) in data.chunks(8).take(16).enumerate() {
+        let key: String = chunk.iter().map(|&b| (0x20 + (b % 0x5F)) as char).collect();
+        if key.is_empty() {
+            continue;
+        }
+        let _ = builder.insert(&key, index as i32);
+    }
+    let container = Dafsa::new(builder.build().expect("build failed")).to_checked_bytes();
+    for length in 0..container.len() {
+        assert!(Dafsa::from_slice_checked(&container[..length]).is_err());
+    }
+    assert!(Dafsa::from_slice_checked(&container).is_ok());
+});
@@ -0,0 +1,666 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+//! Runtime DAFSA construction.
+//!
+//! A port of the `make_dafsa.py` generation pipeline: words go into a
+//! trie, common suffixes are shared by bottom-up minimization, chains
+//! of one-to-one nodes are joined into multi-character labels, and the
+//! result is serialized to the exact byte format [`Dafsa::from_slice`]
+//! consumes — so tables can be built at runtime or in build scripts
+//! without the Python step.
+//!
+//! The byte format (as read by the lookup code in `lib.rs`):
+//!
+//! - the array starts with the root's offset list
+//! - an offset list is a sequence of 1-3 byte records; each adds a
+//!   positive delta to the running target address (the first is
+//!   relative to the list's own start), and bit 0x80 on a record's
+//!   first byte marks the end of the list
+//! - a child record is its label's characters in order, all with the
+//!   high bit clear except the last, which either carries bit 0x80 and
+//!   is followed by the node's offset list, or is a return byte
+//!   `0x80 | value` terminating a key; values above
+//!   [`MAX_COMPACT_VALUE`] set bit 0x10 on the return byte and spill
+//!   their remaining bits into varint continuation bytes
+//!
+//! Children are laid out after their parents, so every delta stays
+//! positive; a delta must fit in 21 bits, which bounds the table size
+//! the same way `make_dafsa.py`'s assert does.
+
+use std::collections::{BTreeMap, HashMap};
+
+/// The largest value a single return byte can store. `make_dafsa.py`
+/// only ever emits 0-4 of this range; values above it use the
+/// variable-width terminal encoding, which tables readable by the C++
+/// reader must avoid.
+pub const MAX_COMPACT_VALUE: i32 = 0x0F;
+
+/// Why [`DafsaBuilder`] rejected its input.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum BuildError {
+    /// Keys must be at least one character; the format has no way to
+    /// tag the empty string.
+    EmptyKey,
+    /// Keys must be printable 7-bit ASCII (0x20-0x7E), the same
+    /// restriction `make_dafsa.py` enforces.
+    InvalidKeyByte {
+        /// The offending byte.
+        byte: u8,
+    },
+    /// The value does not fit the format (values are non-negative;
+    /// `KEY_NOT_FOUND` is reserved for misses).
+    ValueOutOfRange {
+        /// The rejected value.
+        value: i32,
+    },
+    /// The same key was inserted twice with different values.
+    DuplicateKey {
+        /// The conflicting key.
+        key: String,
+    },
+    /// An encoded offset exceeded the 21-bit limit; the key set is too
+    /// large for one table.
+    OffsetTooLarge,
+}
+
+/// Builds the binary DAFSA encoding from (key, value) pairs.
+///
+/// Insertion order does not matter and inserting the same pair twice
+/// is harmless; the output is a pure function of the final key/value
+/// set. `build` produces bytes that [`Dafsa::from_slice`] (and the C++
+/// `Dafsa`) accept directly.
+///
+/// # Examples
+///
+/// ```
+/// use firefox_dafsa::{Dafsa, DafsaBuilder};
+///
+/// let mut builder = DafsaBuilder::new();
+/// builder.insert("example.com", 1).unwrap();
+/// builder.insert("example.org", 2).unwrap();
+/// let dafsa = Dafsa::new(builder.build().unwrap());
+/// assert_eq!(dafsa.lookup("example.com"), 1);
+/// assert_eq!(dafsa.lookup("example.net"), firefox_dafsa::KEY_NOT_FOUND);
+/// ```
+///
+/// [`Dafsa::from_slice`]: crate::Dafsa::from_slice
+#[derive(Default)]
+pub struct DafsaBuilder {
+    entries: BTreeMap<Vec<u8>, i32>,
+}
+
+/// A node of the minimized automaton: either a labeled interior node
+/// or the shared terminal carrying a key's value.
+enum Node {
+    Chars { label: Vec<u8>, children: Vec<usize> },
+    Value(i32),
+}
+
+/// Encodes a return value: a single byte for values up to
+/// [`MAX_COMPACT_VALUE`], otherwise the 0x10-flagged byte with the low
+/// nibble followed by 7-bit little-endian continuation bytes.
+fn encode_value(value: i32) -> Vec<u8> {
+    debug_assert!(value >= 0);
+    if value <= MAX_COMPACT_VALUE {
+        return vec![0x80 | value as u8];
+    }
+    let mut bytes = vec![0x90 | (value & 0x0F) as u8];
+    let mut rest = (value as u32) >> 4;
+    loop {
+        let septet = (rest & 0x7F) as u8;
+        rest >>= 7;
+        if rest == 0 {
+            bytes.push(septet);
+            return bytes;
+        }
+        bytes.push(septet | 0x80);
+    }
+}
+
+impl DafsaBuilder {
+    /// Creates an empty builder.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a key with its value.
+    ///
+    /// Keys are printable ASCII; values are any non-negative `i32`,
+    /// though values above [`MAX_COMPACT_VALUE`] produce tables only
+    /// the Rust reader understands. Re-inserting a key with the same
+    /// value is a no-op; a different value is an error, since the
+    /// table could only answer one of them.
+    pub fn insert(&mut self, key: &str, value: i32) -> Result<(), BuildError> {
+        if key.is_empty() {
+            return Err(BuildError::EmptyKey);
+        }
+        if let Some(byte) = key.bytes().find(|b| !(0x20..=0x7E).contains(b)) {
+            return Err(BuildError::InvalidKeyByte { byte });
+        }
+        if value < 0 {
+            return Err(BuildError::ValueOutOfRange { value });
+        }
+
+        match self.entries.insert(key.as_bytes().to_vec(), value) {
+            Some(previous) if previous != value => Err(BuildError::DuplicateKey {
+                key: key.to_string(),
+            }),
+            _ => Ok(()),
+        }
+    }
+
+    /// Serializes the accumulated keys to the DAFSA byte format.
+    ///
+    /// An empty builder produces an empty table, which looks up
+    /// nothing — the same behavior as `Dafsa::new(vec![])`.
+    pub fn build(&self) -> Result<Vec<u8>, BuildError> {
+        if self.entries.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let (mut nodes, root_children) = self.minimized_nodes();
+        let in_degrees = join_labels(&mut nodes, &root_children);
+        encode(&nodes, &root_children, &in_degrees)
+    }
+
+    /// Builds the suffix-sharing automaton: a node per distinct
+    /// (character, residual key set) pair, hash-consed bottom-up so
+    /// identical subtrees collapse into one.
+    fn minimized_nodes(&self) -> (Vec<Node>, Vec<usize>) {
+        struct TrieNode {
+            edges: BTreeMap<u8, usize>,
+            value: Option<i32>,
+        }
+
+        let mut trie = vec![TrieNode {
+            edges: BTreeMap::new(),
+            value: None,
+        }];
+        for (key, &value) in &self.entries {
+            let mut current = 0;
+            for &byte in key {
+                current = match trie[current].edges.get(&byte) {
+                    Some(&next) => next,
+                    None => {
+                        let next = trie.len();
+                        trie.push(TrieNode {
+                            edges: BTreeMap::new(),
+                            value: None,
+                        });
+                        trie[current].edges.insert(byte, next);
+                        next
+                    }
+                };
+            }
+            trie[current].value = Some(value);
+        }
+
+        let mut nodes = Vec::new();
+        let mut value_memo: HashMap<i32, usize> = HashMap::new();
+        let mut chars_memo: HashMap<(u8, Vec<usize>), usize> = HashMap::new();
+
+        // Recursion depth is bounded by the longest key
+        fn reduce(
+            trie: &[TrieNode],
+            trie_id: usize,
+            byte: u8,
+            nodes: &mut Vec<Node>,
+            value_memo: &mut HashMap<i32, usize>,
+            chars_memo: &mut HashMap<(u8, Vec<usize>), usize>,
+        ) -> usize {
+            let mut children = Vec::new();
+            if let Some(value) = trie[trie_id].value {
+                let value_node = *value_memo.entry(value).or_insert_with(|| {
+                    nodes.push(Node::Value(value));
+                    nodes.len() - 1
+                });
+                children.push(value_node);
+            }
+            for (&child_byte, &child_id) in &trie[trie_id].edges {
+                children.push(reduce(
+                    trie, child_id, child_byte, nodes, value_memo, chars_memo,
+                ));
+            }
+
+            *chars_memo
+                .entry((byte, children.clone()))
+                .or_insert_with(|| {
+                    nodes.push(Node::Chars {
+                        label: vec![byte],
+                        children,
+                    });
+                    nodes.len() - 1
+                })
+        }
+
+        let root_children: Vec<usize> = trie[0]
+            .edges
+            .iter()
+            .map(|(&byte, &child_id)| {
+                reduce(
+                    &trie,
+                    child_id,
+                    byte,
+                    &mut nodes,
+                    &mut value_memo,
+                    &mut chars_memo,
+                )
+            })
+            .collect();
+
+        (nodes, root_children)
+    }
+}
+
+/// Joins one-to-one chains into multi-character labels, as
+/// `make_dafsa.py`'s `join_labels` does: a node absorbs its only child
+/// when that child has no other parent. Returns the in-degree of every
+/// node (after joining), which the encoder uses to decide whether a
+/// value byte can be inlined after a label.
+fn join_labels(nodes: &mut [Node], root_children: &[usize]) -> Vec<usize> {
+    let count_in_degrees = |nodes: &[Node], root_children: &[usize]| {
+        let mut in_degrees = vec![0usize; nodes.len()];
+        for &child in root_children {
+            in_degrees[child] += 1;
+        }
+        for node in nodes.iter() {
+            if let Node::Chars { children, .. } = node {
+                for &child in children {
+                    in_degrees[child] += 1;
+                }
+            }
+        }
+        in_degrees
+    };
+
+    let mut in_degrees = count_in_degrees(nodes, root_children);
+    for id in 0..nodes.len() {
+        loop {
+            // The only child, if this node has exactly one and it is
+            // absorbable (an interior node with no other parent)
+            let absorbable = match &nodes[id] {
+                Node::Chars { children, .. } if children.len() == 1 => {
+                    let child = children[0];
+                    match &nodes[child] {
+                        Node::Chars { .. } if in_degrees[child] == 1 => Some(child),
+                        _ => None,
+                    }
+                }
+                _ => None,
+            };
+            let Some(child) = absorbable else { break };
+
+            // Move the child's label and children up; the child is now
+            // unreachable
+            let (child_label, child_children) = match &nodes[child] {
+                Node::Chars { label, children } => (label.clone(), children.clone()),
+                Node::Value(_) => unreachable!("absorbable is only ever a Chars node"),
+            };
+            in_degrees[child] = 0;
+            let Node::Chars { label, children } = &mut nodes[id] else {
+                unreachable!("only Chars nodes absorb children");
+            };
+            label.extend_from_slice(&child_label);
+            *children = child_children;
+        }
+    }
+
+    in_degrees
+}
+
+/// Serializes the automaton back-to-front so that every child's
+/// address is known when its parent's offset list is encoded.
+///
+/// Positions are tracked as distances from the end of the (reversed)
+/// output, which makes them independent of the still-unknown total
+/// size; the final `reverse` turns them into real addresses with
+/// parents before children.
+fn encode(
+    nodes: &[Node],
+    root_children: &[usize],
+    in_degrees: &[usize],
+) -> Result<Vec<u8>, BuildError> {
+    let mut reversed_output: Vec<u8> = Vec::new();
+    // Position of each emitted record, as distance from the array end
+    let mut positions: HashMap<usize, usize> = HashMap::new();
+    let mut emit_order = Vec::new();
+
+    // Post-order walk: children always emitted (and thus positioned)
+    // before any parent that links to them
+    fn post_order(
+        nodes: &[Node],
+        id: usize,
+        visited: &mut [bool],
+        emit_order: &mut Vec<usize>,
+    ) {
+        if visited[id] {
+            return;
+        }
+        visited[id] = true;
+        if let Node::Chars { children, .. } = &nodes[id] {
+            for &child in children {
+                post_order(nodes, child, visited, emit_order);
+            }
+        }
+        emit_order.push(id);
+    }
+    let mut visited = vec![false; nodes.len()];
+    for &child in root_children {
+        post_order(nodes, child, &mut visited, &mut emit_order);
+    }
+
+    // Value terminals whose only parent inlines them as a return byte
+    // never need a standalone record
+    let mut inlined = vec![false; nodes.len()];
+    for node in nodes {
+        if let Node::Chars { children, .. } = node {
+            if let [child] = children[..] {
+                if matches!(nodes[child], Node::Value(_)) && in_degrees[child] == 1 {
+                    inlined[child] = true;
+                }
+            }
+        }
+    }
+
+    for &id in &emit_order {
+        if inlined[id] {
+            continue;
+        }
+        let record = match &nodes[id] {
+            Node::Value(value) => encode_value(*value),
+            Node::Chars { label, children } => {
+                // A label whose only continuation is an unshared value
+                // terminal inlines the return bytes: <char>+ value
+                if let [child] = children[..] {
+                    if let Node::Value(value) = nodes[child] {
+                        if in_degrees[child] == 1 {
+                            let mut record = label.clone();
+                            record.extend(encode_value(value));
+                            reversed_output.extend(record.iter().rev());
+                            positions.insert(id, reversed_output.len());
+                            continue;
+                        }
+                    }
+                }
+
+                // <char>* end_char offsets
+                let mut record = label.clone();
+                *record.last_mut().expect("labels are never empty") |= 0x80;
+                let links =
+                    encode_links(children, &positions, reversed_output.len())?;
+                record.extend(links);
+                record
+            }
+        };
+        reversed_output.extend(record.iter().rev());
+        positions.insert(id, reversed_output.len());
+    }
+
+    let root_links = encode_links(root_children, &positions, reversed_output.len())?;
+    reversed_output.extend(root_links.iter().rev());
+    reversed_output.reverse();
+    Ok(reversed_output)
+}
+
+/// Encodes one offset list in final byte order.
+///
+/// `current` is the distance from the array end at which the list will
+/// be placed. The list length feeds back into the first delta, so the
+/// encoding starts from the widest guess and re-encodes until the
+/// length stabilizes — the same fixed point `make_dafsa.py` iterates
+/// to.
+fn encode_links(
+    children: &[usize],
+    positions: &HashMap<usize, usize>,
+    current: usize,
+) -> Result<Vec<u8>, BuildError> {
+    // Ascending target address = descending distance from the end
+    let mut sorted: Vec<usize> = children.iter().map(|&child| positions[&child]).collect();
+    sorted.sort_unstable_by(|a, b| b.cmp(a));
+
+    let mut guess = 3 * sorted.len();
+    loop {
+        let mut buffer = Vec::new();
+        let mut last_record_start = 0;
+        // The running target starts at the list's own start address
+        let mut previous = current + guess;
+        for &position in &sorted {
+            let distance = previous - position;
+            last_record_start = buffer.len();
+            if distance < (1 << 6) {
+                buffer.push(distance as u8);
+            } else if distance < (1 << 13) {
+                buffer.push(0x40 | (distance >> 8) as u8);
+                buffer.push((distance & 0xFF) as u8);
+            } else if distance < (1 << 21) {
+                buffer.push(0x60 | (distance >> 16) as u8);
+                buffer.push(((distance >> 8) & 0xFF) as u8);
+                buffer.push((distance & 0xFF) as u8);
+            } else {
+                return Err(BuildError::OffsetTooLarge);
+            }
+            previous = position;
+        }
+        if buffer.len() != guess {
+            guess = buffer.len();
+            continue;
+        }
+        // Mark the last record so the reader stops after it
+        buffer[last_record_start] |= 0x80;
+        return Ok(buffer);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Dafsa, KEY_NOT_FOUND};
+
+    fn build(entries: &[(&str, i32)]) -> Dafsa {
+        let mut builder = DafsaBuilder::new();
+        for &(key, value) in entries {
+            builder.insert(key, value).unwrap();
+        }
+        Dafsa::new(builder.build().unwrap())
+    }
+
+    #[test]
+    fn test_single_key() {
+        let dafsa = build(&[("foo", 2)]);
+        assert_eq!(dafsa.lookup("foo"), 2);
+        assert_eq!(dafsa.lookup("fo"), KEY_NOT_FOUND);
+        assert_eq!(dafsa.lookup("foob"), KEY_NOT_FOUND);
+        assert_eq!(dafsa.lookup(""), KEY_NOT_FOUND);
+        assert_eq!(dafsa.lookup("bar"), KEY_NOT_FOUND);
+    }
+
+    #[test]
+    fn test_single_char_keys() {
+        let dafsa = build(&[("a", 0), ("b", 4)]);
+        assert_eq!(dafsa.lookup("a"), 0);
+        assert_eq!(dafsa.lookup("b"), 4);
+        assert_eq!(dafsa.lookup("c"), KEY_NOT_FOUND);
+        assert_eq!(dafsa.lookup("ab"), KEY_NOT_FOUND);
+    }
+
+    #[test]
+    fn test_shared_prefixes_and_suffixes() {
+        let entries = [
+            ("example.com", 1),
+            ("example.org", 2),
+            ("sample.com", 1),
+            ("sample.org", 2),
+            ("example", 0),
+        ];
+        let dafsa = build(&entries);
+        for (key, value) in entries {
+            assert_eq!(dafsa.lookup(key), value, "key {key}");
+        }
+        assert_eq!(dafsa.lookup("example."), KEY_NOT_FOUND);
+        assert_eq!(dafsa.lookup("ample.com"), KEY_NOT_FOUND);
+        assert_eq!(dafsa.lookup("example.net"), KEY_NOT_FOUND);
+    }
+
+    #[test]
+    fn test_prefix_of_another_key() {
+        let dafsa = build(&[("a", 0), ("ab", 1), ("abc", 2)]);
+        assert_eq!(dafsa.lookup("a"), 0);
+        assert_eq!(dafsa.lookup("ab"), 1);
+        assert_eq!(dafsa.lookup("abc"), 2);
+        assert_eq!(dafsa.lookup("abcd"), KEY_NOT_FOUND);
+    }
+
+    #[test]
+    fn test_all_compact_values() {
+        let entries: Vec<(String, i32)> = (0..=MAX_COMPACT_VALUE)
+            .map(|v| (format!("key{v:02}"), v))
+            .collect();
+        let mut builder = DafsaBuilder::new();
+        for (key, value) in &entries {
+            builder.insert(key, *value).unwrap();
+        }
+        let dafsa = Dafsa::new(builder.build().unwrap());
+        for (key, value) in &entries {
+            assert_eq!(dafsa.lookup(key), *value);
+        }
+    }
+
+    #[test]
+    fn test_wide_values_round_trip() {
+        let values = [
+            MAX_COMPACT_VALUE + 1,
+            0x7F,
+            0x80,
+            0x7FF,
+            0x800,
+            123_456,
+            i32::MAX,
+        ];
+        let entries: Vec<(String, i32)> = values
+            .iter()
+            .map(|&v| (format!("wide{v}"), v))
+            .collect();
+        let mut builder = DafsaBuilder::new();
+        for (key, value) in &entries {
+            builder.insert(key, *value).unwrap();
+        }
+        // Mix in compact values to exercise both terminal forms at once
+        builder.insert("compact", 3).unwrap();
+        let dafsa = Dafsa::new(builder.build().unwrap());
+        for (key, value) in &entries {
+            assert_eq!(dafsa.lookup(key), *value, "value {value}");
+        }
+        assert_eq!(dafsa.lookup("compact"), 3);
+        assert_eq!(dafsa.lookup("wide"), KEY_NOT_FOUND);
+    }
+
+    #[test]
+    fn test_encode_value_widths() {
+        // Compact values stay single-byte for C++ reader compatibility
+        for value in 0..=MAX_COMPACT_VALUE {
+            assert_eq!(encode_value(value), vec![0x80 | value as u8]);
+        }
+        // 4 bits in the terminal, then 7 bits per continuation byte
+        assert_eq!(encode_value(0x10), vec![0x90, 0x01]);
+        assert_eq!(encode_value(0x7FF), vec![0x9F, 0x7F]);
+        assert_eq!(encode_value(0x800), vec![0x90, 0x80, 0x01]);
+    }
+
+    #[test]
+    fn test_empty_builder() {
+        let dafsa = Dafsa::new(DafsaBuilder::new().build().unwrap());
+        assert_eq!(dafsa.lookup("anything"), KEY_NOT_FOUND);
+    }
+
+    #[test]
+    fn test_suffix_sharing_shrinks_output() {
+        // Ten keys ending in the same long suffix with the same value
+        // must share that suffix's encoding
+        let mut shared = DafsaBuilder::new();
+        for prefix in ["alpha", "bravo", "delta", "gamma", "omega"] {
+            shared.insert(&format!("{prefix}.public.suffix.example"), 1).unwrap();
+        }
+        let shared_size = shared.build().unwrap().len();
+        let key_bytes: usize = 5 * "alpha.public.suffix.example".len();
+        assert!(
+            shared_size < key_bytes,
+            "suffix sharing failed: {shared_size} >= {key_bytes}"
+        );
+    }
+
+    #[test]
+    fn test_insert_validation() {
+        let mut builder = DafsaBuilder::new();
+        assert_eq!(builder.insert("", 0), Err(BuildError::EmptyKey));
+        assert_eq!(
+            builder.insert("tab\tkey", 0),
+            Err(BuildError::InvalidKeyByte { byte: b'\t' })
+        );
+        assert_eq!(
+            builder.insert("café", 0),
+            Err(BuildError::InvalidKeyByte { byte: 0xC3 })
+        );
+        assert_eq!(
+            builder.insert("key", -1),
+            Err(BuildError::ValueOutOfRange { value: -1 })
+        );
+        assert_eq!(
+            builder.insert("key", KEY_NOT_FOUND),
+            Err(BuildError::ValueOutOfRange { value: KEY_NOT_FOUND })
+        );
+
+        builder.insert("key", 1).unwrap();
+        builder.insert("key", 1).unwrap(); // same value: no-op
+        assert_eq!(
+            builder.insert("key", 2),
+            Err(BuildError::DuplicateKey {
+                key: "key".to_string()
+            })
+        );
+    }
+
+    #[test]
+    fn test_differential_against_map() {
+        use firefox_xorshift128plus::XorShift128PlusRNG;
+        use std::collections::HashMap;
+
+        let mut rng = XorShift128PlusRNG::from_seed_u64(0xDAF5_A000);
+        let alphabet = b"abcdef.-";
+
+        for _ in 0..50 {
+            let mut expected: HashMap<String, i32> = HashMap::new();
+            let mut builder = DafsaBuilder::new();
+            for _ in 0..(1 + rng.next() % 40) {
+                let length = 1 + (rng.next() % 12) as usize;
+                let key: String = (0..length)
+                    .map(|_| alphabet[(rng.next() % alphabet.len() as u64) as usize] as char)
+                    .collect();
+                let value = (rng.next() % 5) as i32;
+                if expected.contains_key(&key) {
+                    continue;
+                }
+                builder.insert(&key, value).unwrap();
+                expected.insert(key, value);
+            }
+            let dafsa = Dafsa::new(builder.build().unwrap());
+
+            for (key, &value) in &expected {
+                assert_eq!(dafsa.lookup(key), value, "key {key}");
+            }
+            // Probe non-keys: mutations and truncations of real keys
+            for key in expected.keys() {
+                let probes = [
+                    format!("{key}x"),
+                    key[..key.len() - 1].to_string(),
+                    format!("x{key}"),
+                ];
+                for probe in probes {
+                    let wanted = expected.get(&probe).copied().unwrap_or(KEY_NOT_FOUND);
+                    assert_eq!(dafsa.lookup(&probe), wanted, "probe {probe}");
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,300 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+//! Build-time code generation for embedded DAFSA tables.
+//!
+//! Replaces the Python codegen step for crates that embed DAFSA data:
+//! a build script reads a word list, serializes it with
+//! [`DafsaBuilder`](crate::DafsaBuilder) and writes a Rust source file
+//! containing the byte array plus a typed accessor, which the crate
+//! pulls in with [`include_dafsa!`](crate::include_dafsa).
+//!
+//! The word list format matches the `make_dafsa.py` input convention:
+//! one key per line with its value appended as a single digit (so
+//! `example.com1` stores key `example.com` with value 1). Blank lines
+//! and lines starting with `#` are ignored.
+//!
+//! A typical build script:
+//!
+//! ```no_run
+//! # fn main() -> Result<(), firefox_dafsa::CodegenError> {
+//! let out = std::path::Path::new(&std::env::var_os("OUT_DIR").unwrap())
+//!     .join("etld_dafsa.rs");
+//! firefox_dafsa::generate_dafsa_file("etld_dafsa", "data/effective_tld_names.dat", out)?;
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! and in the crate:
+//!
+//! ```ignore
+//! firefox_dafsa::include_dafsa!("etld_dafsa.rs");
+//!
+//! assert_eq!(etld_dafsa().lookup("com"), 0);
+//! ```
+
+use std::fmt::Write as _;
+use std::fs;
+use std::path::Path;
+
+use crate::builder::{BuildError, DafsaBuilder};
+
+/// Why code generation failed.
+#[derive(Debug)]
+pub enum CodegenError {
+    /// Reading the word list or writing the output failed.
+    Io(std::io::Error),
+    /// A line did not end with a single value digit.
+    MissingValue {
+        /// One-based line number in the word list.
+        line: usize,
+    },
+    /// The key or value on a line was rejected by the builder.
+    Build {
+        /// One-based line number in the word list.
+        line: usize,
+        /// The builder's reason.
+        error: BuildError,
+    },
+    /// The requested accessor name is not a plain Rust identifier.
+    InvalidName {
+        /// The rejected name.
+        name: String,
+    },
+}
+
+impl From<std::io::Error> for CodegenError {
+    fn from(error: std::io::Error) -> Self {
+        CodegenError::Io(error)
+    }
+}
+
+/// Parses a `make_dafsa.py`-style word list into (key, value) pairs.
+///
+/// Each non-blank, non-`#` line is a key with its value as a trailing
+/// digit. Returned line numbers are not preserved; parse errors carry
+/// them instead.
+pub fn parse_word_list(input: &str) -> Result<Vec<(String, i32)>, CodegenError> {
+    let mut entries = Vec::new();
+    for (index, raw_line) in input.lines().enumerate() {
+        let line = raw_line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let (key, value) = line.split_at(line.len() - 1);
+        let value = match value.chars().next() {
+            Some(digit @ '0'..='9') => digit as i32 - '0' as i32,
+            _ => return Err(CodegenError::MissingValue { line: index + 1 }),
+        };
+        entries.push((key.to_string(), value));
+    }
+    Ok(entries)
+}
+
+/// Generates Rust source embedding the word list as a DAFSA.
+///
+/// The output defines `pub static {NAME}: [u8; _]` holding the encoded
+/// table and `pub fn {name}() -> Dafsa` as the typed accessor, where
+/// `name` must be a plain lowercase identifier (letters, digits,
+/// underscores, not starting with a digit).
+pub fn generate_dafsa_source(name: &str, word_list: &str) -> Result<String, CodegenError> {
+    let valid = !name.is_empty()
+        && !name.starts_with(|c: char| c.is_ascii_digit())
+        && name
+            .chars()
+            .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_');
+    if !valid {
+        return Err(CodegenError::InvalidName {
+            name: name.to_string(),
+        });
+    }
+
+    let mut builder = DafsaBuilder::new();
+    for (index, (key, value)) in parse_word_list(word_list)?.iter().enumerate() {
+        builder.insert(key, *value).map_err(|error| CodegenError::Build {
+            // Best effort: entry index, not the raw line, once comments
+            // are stripped
+            line: index + 1,
+            error,
+        })?;
+    }
+    let bytes = builder.build().map_err(|error| CodegenError::Build {
+        line: 0,
+        error,
+    })?;
+
+    let static_name = name.to_uppercase();
+    let mut source = String::new();
+    let _ = writeln!(source, "// Generated by firefox_dafsa codegen. Do not edit.");
+    let _ = writeln!(source);
+    let _ = writeln!(
+        source,
+        "pub static {static_name}: [u8; {}] = [",
+        bytes.len()
+    );
+    for chunk in bytes.chunks(12) {
+        let mut line = String::from("   ");
+        for byte in chunk {
+            let _ = write!(line, " 0x{byte:02X},");
+        }
+        let _ = writeln!(source, "{line}");
+    }
+    let _ = writeln!(source, "];");
+    let _ = writeln!(source);
+    let _ = writeln!(
+        source,
+        "pub fn {name}() -> ::firefox_dafsa::Dafsa {{"
+    );
+    let _ = writeln!(
+        source,
+        "    ::firefox_dafsa::Dafsa::from_slice(&{static_name})"
+    );
+    let _ = writeln!(source, "}}");
+    Ok(source)
+}
+
+/// Build-script entry point: reads a word list file and writes the
+/// generated source, typically into `OUT_DIR` for
+/// [`include_dafsa!`](crate::include_dafsa).
+pub fn generate_dafsa_file(
+    name: &str,
+    input: impl AsRef<Path>,
+    output: impl AsRef<Path>,
+) -> Result<(), CodegenError> {
+    let word_list = fs::read_to_string(input)?;
+    let source = generate_dafsa_source(name, &word_list)?;
+    fs::write(output, source)?;
+    Ok(())
+}
+
+/// Includes a table generated into `OUT_DIR` by
+/// [`generate_dafsa_file`], bringing its static and accessor into the
+/// current module.
+#[macro_export]
+macro_rules! include_dafsa {
+    ($file:expr) => {
+        include!(concat!(env!("OUT_DIR"), "/", $file));
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Dafsa, KEY_NOT_FOUND};
+
+    const WORD_LIST: &str = "\
+# Top sites, tagged
+example.com1
+example.org2
+
+sample.net0
+";
+
+    #[test]
+    fn test_parse_word_list() {
+        let entries = parse_word_list(WORD_LIST).unwrap();
+        assert_eq!(
+            entries,
+            vec![
+                ("example.com".to_string(), 1),
+                ("example.org".to_string(), 2),
+                ("sample.net".to_string(), 0),
+            ]
+        );
+        assert_eq!(parse_word_list("").unwrap(), vec![]);
+        assert_eq!(parse_word_list("# only comments\n").unwrap(), vec![]);
+    }
+
+    #[test]
+    fn test_parse_word_list_rejects_missing_value() {
+        assert!(matches!(
+            parse_word_list("example.com1\nno-digit\n"),
+            Err(CodegenError::MissingValue { line: 2 })
+        ));
+    }
+
+    #[test]
+    fn test_generated_source_shape() {
+        let mut builder = DafsaBuilder::new();
+        builder.insert("example.com", 1).unwrap();
+        builder.insert("example.org", 2).unwrap();
+        builder.insert("sample.net", 0).unwrap();
+        let bytes = builder.build().unwrap();
+
+        let source = generate_dafsa_source("top_sites", WORD_LIST).unwrap();
+        assert!(source.contains(&format!("pub static TOP_SITES: [u8; {}] = [", bytes.len())));
+        assert!(source.contains("pub fn top_sites() -> ::firefox_dafsa::Dafsa {"));
+        // The embedded bytes are exactly the builder's encoding
+        assert_eq!(source.matches("0x").count(), bytes.len());
+        for byte in &bytes {
+            let rendered = format!("0x{byte:02X},");
+            assert!(source.contains(&rendered));
+        }
+    }
+
+    #[test]
+    fn test_generated_bytes_round_trip() {
+        // Re-extract the array literal and check lookups work on it
+        let source = generate_dafsa_source("t", WORD_LIST).unwrap();
+        let start = source.find('[').unwrap();
+        let open = source[start..].find("[\n").map(|i| start + i).unwrap();
+        let close = source[open..].find(']').map(|i| open + i).unwrap();
+        let bytes: Vec<u8> = source[open + 1..close]
+            .split(',')
+            .filter_map(|token| {
+                let token = token.trim();
+                token
+                    .strip_prefix("0x")
+                    .map(|hex| u8::from_str_radix(hex, 16).unwrap())
+            })
+            .collect();
+
+        let dafsa = Dafsa::new(bytes);
+        assert_eq!(dafsa.lookup("example.com"), 1);
+        assert_eq!(dafsa.lookup("example.org"), 2);
+        assert_eq!(dafsa.lookup("sample.net"), 0);
+        assert_eq!(dafsa.lookup("example.net"), KEY_NOT_FOUND);
+    }
+
+    #[test]
+    fn test_invalid_names_rejected() {
+        for name in ["", "0day", "Upper", "has-dash", "spa ce"] {
+            assert!(matches!(
+                generate_dafsa_source(name, WORD_LIST),
+                Err(CodegenError::InvalidName { .. })
+            ));
+        }
+    }
+
+    #[test]
+    fn test_builder_errors_carry_entry_number() {
+        // The same key with two different values is a builder rejection
+        match generate_dafsa_source("t", "good.com1\ngood.com2\n") {
+            Err(CodegenError::Build { line: 2, error }) => {
+                assert_eq!(
+                    error,
+                    BuildError::DuplicateKey {
+                        key: "good.com".to_string()
+                    }
+                );
+            }
+            other => panic!("expected build error, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn test_generate_dafsa_file() {
+        let dir = std::env::temp_dir().join("firefox_dafsa_codegen_test");
+        fs::create_dir_all(&dir).unwrap();
+        let input = dir.join("words.dat");
+        let output = dir.join("words.rs");
+        fs::write(&input, WORD_LIST).unwrap();
+
+        generate_dafsa_file("words", &input, &output).unwrap();
+        let generated = fs::read_to_string(&output).unwrap();
+        assert_eq!(generated, generate_dafsa_source("words", WORD_LIST).unwrap());
+
+        let _ = fs::remove_dir_all(&dir);
+    }
+}
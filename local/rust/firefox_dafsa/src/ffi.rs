@@ -5,8 +5,14 @@
 //! FFI bindings for C++ interoperability.
 //!
 //! This module provides C-compatible exports that match the C++ Dafsa API.
+//! The nsACString-based entry points need the `nsstring` feature (on by
+//! default); the raw pointer/length entry points work without it for
+//! consumers outside Gecko.
 
+#[cfg(feature = "nsstring")]
+use crate::psl::PublicSuffix;
 use crate::{Dafsa, KEY_NOT_FOUND};
+#[cfg(feature = "nsstring")]
 use nsstring::nsACString;
 use std::slice;
 
@@ -47,11 +53,40 @@ pub unsafe extern "C" fn rust_dafsa_destroy(dafsa: *mut RustDafsa) {
     }
 }
 
+/// Looks up a key given as a raw pointer and length, so callers without
+/// nsstring (tests, non-Gecko embedders) can query the table. A null
+/// `key_ptr` with a zero length is the empty key.
+///
+/// # Safety
+/// - `dafsa` must be a valid pointer returned from `rust_dafsa_new`
+/// - `key_ptr` must point to `key_len` readable bytes, or be null with
+///   `key_len == 0`
+#[no_mangle]
+pub unsafe extern "C" fn rust_dafsa_lookup_raw(
+    dafsa: *const RustDafsa,
+    key_ptr: *const u8,
+    key_len: usize,
+) -> i32 {
+    if dafsa.is_null() || (key_ptr.is_null() && key_len != 0) {
+        return KEY_NOT_FOUND;
+    }
+
+    let dafsa = &(*dafsa).inner;
+    let key = if key_len == 0 {
+        &[][..]
+    } else {
+        slice::from_raw_parts(key_ptr, key_len)
+    };
+
+    crate::lookup_string(dafsa.data.as_slice(), key)
+}
+
 /// Looks up a key in the DAFSA.
 ///
 /// # Safety
 /// - `dafsa` must be a valid pointer returned from `rust_dafsa_new`
 /// - `key` must be a valid pointer to an nsACString
+#[cfg(feature = "nsstring")]
 #[no_mangle]
 pub unsafe extern "C" fn rust_dafsa_lookup(
     dafsa: *const RustDafsa,
@@ -72,3 +107,97 @@ pub unsafe extern "C" fn rust_dafsa_lookup(
 pub extern "C" fn rust_dafsa_key_not_found() -> i32 {
     KEY_NOT_FOUND
 }
+
+/// Returns the byte offset at which the public suffix of `host` starts,
+/// treating the DAFSA as a public suffix rule table (see `psl.rs`).
+/// Returns -1 on null arguments so nsEffectiveTLDService can tell a
+/// degenerate call from a host that is entirely a public suffix.
+///
+/// # Safety
+/// - `dafsa` must be a valid pointer returned from `rust_dafsa_new`
+/// - `host` must be a valid pointer to an nsACString holding a
+///   normalized (lowercase ASCII, punycoded) host
+#[cfg(feature = "nsstring")]
+#[no_mangle]
+pub unsafe extern "C" fn rust_dafsa_public_suffix_start(
+    dafsa: *const RustDafsa,
+    host: *const nsACString,
+) -> i32 {
+    if dafsa.is_null() || host.is_null() {
+        return -1;
+    }
+
+    let dafsa = &(*dafsa).inner;
+    let host_str = (*host).as_str_unchecked();
+
+    PublicSuffix::new(dafsa).public_suffix_start(host_str) as i32
+}
+
+/// Returns the byte offset at which the registrable domain (eTLD+1) of
+/// `host` starts, or -1 when the host is itself a public suffix (or on
+/// null arguments) and therefore has no registrable domain.
+///
+/// # Safety
+/// - `dafsa` must be a valid pointer returned from `rust_dafsa_new`
+/// - `host` must be a valid pointer to an nsACString holding a
+///   normalized (lowercase ASCII, punycoded) host
+#[cfg(feature = "nsstring")]
+#[no_mangle]
+pub unsafe extern "C" fn rust_dafsa_registrable_domain_start(
+    dafsa: *const RustDafsa,
+    host: *const nsACString,
+) -> i32 {
+    if dafsa.is_null() || host.is_null() {
+        return -1;
+    }
+
+    let dafsa = &(*dafsa).inner;
+    let host_str = (*host).as_str_unchecked();
+
+    match PublicSuffix::new(dafsa).registrable_domain(host_str) {
+        Some(domain) => (host_str.len() - domain.len()) as i32,
+        None => -1,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::DafsaBuilder;
+
+    #[test]
+    fn test_lookup_raw() {
+        let mut builder = DafsaBuilder::new();
+        builder.insert("example.com", 1).unwrap();
+        let bytes = builder.build().unwrap();
+
+        unsafe {
+            let dafsa = rust_dafsa_new(bytes.as_ptr(), bytes.len());
+            assert!(!dafsa.is_null());
+
+            let key = b"example.com";
+            assert_eq!(rust_dafsa_lookup_raw(dafsa, key.as_ptr(), key.len()), 1);
+            let miss = b"example.org";
+            assert_eq!(
+                rust_dafsa_lookup_raw(dafsa, miss.as_ptr(), miss.len()),
+                KEY_NOT_FOUND
+            );
+            // Empty and null keys
+            assert_eq!(rust_dafsa_lookup_raw(dafsa, key.as_ptr(), 0), KEY_NOT_FOUND);
+            assert_eq!(
+                rust_dafsa_lookup_raw(dafsa, std::ptr::null(), 0),
+                KEY_NOT_FOUND
+            );
+            assert_eq!(
+                rust_dafsa_lookup_raw(dafsa, std::ptr::null(), 4),
+                KEY_NOT_FOUND
+            );
+            assert_eq!(
+                rust_dafsa_lookup_raw(std::ptr::null(), key.as_ptr(), key.len()),
+                KEY_NOT_FOUND
+            );
+
+            rust_dafsa_destroy(dafsa);
+        }
+    }
+}
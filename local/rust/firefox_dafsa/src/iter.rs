@@ -0,0 +1,215 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+//! Iteration over every key stored in a DAFSA.
+//!
+//! The encoded automaton orders a node's children by their record
+//! address, which the encoder chooses for offset compactness, not
+//! lexicographically. The iterator therefore decodes each offset list
+//! up front and sorts the children by their leading character, which
+//! makes the depth-first walk yield keys in lexicographic order — the
+//! stable order debugging dumps and table diffs want.
+
+use crate::{get_next_offset, get_return_value, is_eol, Dafsa};
+
+/// Iterator over `(key, value)` pairs of a [`Dafsa`], in lexicographic
+/// key order. Created by [`Dafsa::iter`].
+pub struct DafsaIter<'a> {
+    data: &'a [u8],
+    /// The key bytes accumulated along the current path.
+    key: Vec<u8>,
+    stack: Vec<Frame>,
+}
+
+/// One offset list being walked: its child record addresses (sorted),
+/// the next child to visit, and how much of `key` belongs to the path
+/// above this list.
+struct Frame {
+    children: Vec<usize>,
+    next: usize,
+    prefix_len: usize,
+}
+
+/// Decodes the offset list starting at `list_pos` into child record
+/// addresses, sorted by each record's leading character so the walk is
+/// lexicographic. A return-value record (leading byte below 0x20 after
+/// masking) is the empty continuation and sorts first.
+fn sorted_list_targets(data: &[u8], list_pos: usize) -> Vec<usize> {
+    let mut pos = list_pos;
+    let end = data.len();
+    let mut offset = list_pos;
+    let mut targets = Vec::new();
+    while get_next_offset(&mut pos, end, &mut offset, data) {
+        targets.push(offset);
+    }
+    targets.sort_unstable_by_key(|&target| data[target] & 0x7F);
+    targets
+}
+
+impl<'a> DafsaIter<'a> {
+    pub(crate) fn new(dafsa: &'a Dafsa) -> Self {
+        let data = dafsa.data.as_slice();
+        let stack = if data.is_empty() {
+            Vec::new()
+        } else {
+            vec![Frame {
+                children: sorted_list_targets(data, 0),
+                next: 0,
+                prefix_len: 0,
+            }]
+        };
+        DafsaIter {
+            data,
+            key: Vec::new(),
+            stack,
+        }
+    }
+}
+
+impl Iterator for DafsaIter<'_> {
+    type Item = (String, i32);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let frame = self.stack.last_mut()?;
+            if frame.next >= frame.children.len() {
+                self.stack.pop();
+                continue;
+            }
+            let mut target = frame.children[frame.next];
+            frame.next += 1;
+            self.key.truncate(frame.prefix_len);
+
+            // Walk one record: plain label characters, then either a
+            // return value (a complete key) or an end character whose
+            // offset list continues the walk
+            while !is_eol(target, self.data) {
+                self.key.push(self.data[target]);
+                target += 1;
+            }
+            if let Some(value) = get_return_value(target, self.data) {
+                let key = String::from_utf8(self.key.clone())
+                    .expect("DAFSA keys are printable ASCII");
+                return Some((key, value));
+            }
+            self.key.push(self.data[target] & 0x7F);
+            self.stack.push(Frame {
+                children: sorted_list_targets(self.data, target + 1),
+                next: 0,
+                prefix_len: self.key.len(),
+            });
+        }
+    }
+}
+
+impl Dafsa {
+    /// Iterates over every stored key with its value, in lexicographic
+    /// key order.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use firefox_dafsa::{Dafsa, DafsaBuilder};
+    ///
+    /// let mut builder = DafsaBuilder::new();
+    /// builder.insert("beta", 1).unwrap();
+    /// builder.insert("alpha", 0).unwrap();
+    /// let dafsa = Dafsa::new(builder.build().unwrap());
+    ///
+    /// let entries: Vec<_> = dafsa.iter().collect();
+    /// assert_eq!(entries, vec![("alpha".to_string(), 0), ("beta".to_string(), 1)]);
+    /// ```
+    pub fn iter(&self) -> DafsaIter<'_> {
+        DafsaIter::new(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Dafsa, DafsaBuilder};
+
+    fn build(entries: &[(&str, i32)]) -> Dafsa {
+        let mut builder = DafsaBuilder::new();
+        for &(key, value) in entries {
+            builder.insert(key, value).unwrap();
+        }
+        Dafsa::new(builder.build().unwrap())
+    }
+
+    #[test]
+    fn test_iter_empty() {
+        assert_eq!(Dafsa::new(vec![]).iter().count(), 0);
+    }
+
+    #[test]
+    fn test_iter_yields_all_entries_sorted() {
+        // Inserted out of order; iteration is lexicographic regardless
+        let dafsa = build(&[
+            ("example.org", 2),
+            ("a", 0),
+            ("example.com", 1),
+            ("ab", 3),
+            ("b", 4),
+        ]);
+        let entries: Vec<_> = dafsa.iter().collect();
+        assert_eq!(
+            entries,
+            vec![
+                ("a".to_string(), 0),
+                ("ab".to_string(), 3),
+                ("b".to_string(), 4),
+                ("example.com".to_string(), 1),
+                ("example.org".to_string(), 2),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_iter_shared_suffixes_expand() {
+        // Minimization shares suffix records; iteration must still
+        // produce every full key once
+        let keys = ["alpha.example", "beta.example", "gamma.example"];
+        let dafsa = build(&keys.map(|key| (key, 1)));
+        let entries: Vec<_> = dafsa.iter().collect();
+        assert_eq!(entries.len(), keys.len());
+        let mut expected: Vec<_> = keys.iter().map(|key| (key.to_string(), 1)).collect();
+        expected.sort();
+        assert_eq!(entries, expected);
+    }
+
+    #[test]
+    fn test_iter_differential_against_map() {
+        use firefox_xorshift128plus::XorShift128PlusRNG;
+        use std::collections::BTreeMap;
+
+        let mut rng = XorShift128PlusRNG::from_seed_u64(0x17E8_A7E0);
+        let alphabet = b"ab.z-";
+
+        for _ in 0..30 {
+            let mut expected: BTreeMap<String, i32> = BTreeMap::new();
+            let mut builder = DafsaBuilder::new();
+            for _ in 0..(1 + rng.next() % 30) {
+                let length = 1 + (rng.next() % 10) as usize;
+                let key: String = (0..length)
+                    .map(|_| alphabet[(rng.next() % alphabet.len() as u64) as usize] as char)
+                    .collect();
+                let value = (rng.next() % 5) as i32;
+                if expected.contains_key(&key) {
+                    continue;
+                }
+                builder.insert(&key, value).unwrap();
+                expected.insert(key, value);
+            }
+            let dafsa = Dafsa::new(builder.build().unwrap());
+
+            // BTreeMap iteration order is the lexicographic reference
+            let entries: Vec<_> = dafsa.iter().collect();
+            let reference: Vec<_> = expected
+                .iter()
+                .map(|(key, &value)| (key.clone(), value))
+                .collect();
+            assert_eq!(entries, reference);
+        }
+    }
+}
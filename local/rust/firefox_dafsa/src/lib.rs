@@ -7,17 +7,100 @@
 //! This is a port of the C++ Dafsa class from xpcom/ds/Dafsa.{h,cpp}.
 //! The implementation maintains API compatibility with the C++ version.
 
+pub mod builder;
+pub mod codegen;
 pub mod ffi;
+pub mod iter;
+#[cfg(unix)]
+mod mmap;
+pub mod psl;
+
+pub use builder::{BuildError, DafsaBuilder, MAX_COMPACT_VALUE};
+pub use codegen::{generate_dafsa_file, generate_dafsa_source, CodegenError};
+pub use iter::DafsaIter;
+pub use psl::{PublicSuffix, PSL_EXCEPTION, PSL_PRIVATE, PSL_WILDCARD};
 
 /// The value returned when a key is not found in the DAFSA.
 pub const KEY_NOT_FOUND: i32 = -1;
 
+/// Magic bytes opening a checked DAFSA container.
+const DAFSA_MAGIC: [u8; 4] = *b"DAFS";
+
+/// Version of the checked container format written by
+/// [`Dafsa::to_checked_bytes`]. Version 1 tables only carry
+/// single-byte return values (0-15); version 2 adds the variable-width
+/// terminal encoding for larger values. The reader accepts both.
+pub const FORMAT_VERSION: u8 = 2;
+
+/// Size of the checked container header: magic, version, payload
+/// length (u32 LE) and FNV-1a checksum (u32 LE).
+const HEADER_LEN: usize = 4 + 1 + 4 + 4;
+
+/// Why [`Dafsa::from_slice_checked`] rejected a buffer.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LoadError {
+    /// The buffer is shorter than the container header.
+    TooShort,
+    /// The buffer does not start with the DAFSA container magic.
+    BadMagic,
+    /// The container was written by an unknown format version.
+    UnsupportedVersion {
+        /// The version byte found in the header.
+        version: u8,
+    },
+    /// The header's payload length disagrees with the buffer — a
+    /// truncated or padded table.
+    LengthMismatch {
+        /// Payload length recorded in the header.
+        expected: usize,
+        /// Payload bytes actually present.
+        actual: usize,
+    },
+    /// The payload does not hash to the recorded checksum.
+    ChecksumMismatch {
+        /// Checksum recorded in the header.
+        expected: u32,
+        /// Checksum computed over the payload.
+        actual: u32,
+    },
+}
+
+/// 32-bit FNV-1a over the table payload; cheap, dependency-free and
+/// plenty to catch truncation and bit rot (this is an integrity check,
+/// not an authenticity one).
+fn fnv1a32(bytes: &[u8]) -> u32 {
+    let mut hash: u32 = 0x811C_9DC5;
+    for &byte in bytes {
+        hash ^= byte as u32;
+        hash = hash.wrapping_mul(0x0100_0193);
+    }
+    hash
+}
+
 /// A deterministic acyclic finite state automaton suitable for storing static
 /// dictionaries of tagged ASCII strings.
 ///
 /// The DAFSA is initialized with a binary encoding generated by `make_dafsa.py`.
 pub struct Dafsa {
-    data: Vec<u8>,
+    data: Storage,
+}
+
+/// Where the encoded table lives: copied onto the heap, or a read-only
+/// file mapping owned by the `Dafsa` (see [`Dafsa::from_file`]).
+enum Storage {
+    Heap(Vec<u8>),
+    #[cfg(unix)]
+    Mapped(mmap::Mmap),
+}
+
+impl Storage {
+    fn as_slice(&self) -> &[u8] {
+        match self {
+            Storage::Heap(data) => data,
+            #[cfg(unix)]
+            Storage::Mapped(mapping) => mapping.as_slice(),
+        }
+    }
 }
 
 impl Dafsa {
@@ -26,14 +109,98 @@ impl Dafsa {
     /// # Arguments
     /// * `data` - Binary encoding generated by `make_dafsa.py`
     pub fn new(data: Vec<u8>) -> Self {
-        Dafsa { data }
+        Dafsa {
+            data: Storage::Heap(data),
+        }
     }
 
     /// Creates a DAFSA from a slice (used for FFI).
     pub fn from_slice(data: &[u8]) -> Self {
-        Dafsa {
-            data: data.to_vec(),
+        Dafsa::new(data.to_vec())
+    }
+
+    /// Creates a DAFSA backed by a read-only memory mapping of `path`,
+    /// so large tables are paged in on demand instead of being copied
+    /// onto the heap. The mapping is owned by the returned `Dafsa` and
+    /// released when it is dropped. On platforms without memory
+    /// mapping the file is read into the heap instead.
+    ///
+    /// The file contents are the raw table encoding, as written by
+    /// `make_dafsa.py` or [`DafsaBuilder`](builder::DafsaBuilder) —
+    /// not the checked container format.
+    pub fn from_file(path: impl AsRef<std::path::Path>) -> std::io::Result<Self> {
+        let file = std::fs::File::open(path)?;
+        let length = file.metadata()?.len();
+        if length == 0 {
+            return Ok(Dafsa::new(Vec::new()));
+        }
+        #[cfg(unix)]
+        {
+            Ok(Dafsa {
+                data: Storage::Mapped(mmap::Mmap::map(&file, length)?),
+            })
+        }
+        #[cfg(not(unix))]
+        {
+            use std::io::Read;
+            let mut data = Vec::with_capacity(length as usize);
+            let mut file = file;
+            file.read_to_end(&mut data)?;
+            Ok(Dafsa::new(data))
+        }
+    }
+
+    /// Creates a DAFSA from a checked container written by
+    /// [`to_checked_bytes`](Dafsa::to_checked_bytes), verifying magic,
+    /// format version, length and checksum.
+    ///
+    /// Raw `make_dafsa.py` tables have no framing, so a truncated or
+    /// corrupted table silently degrades into garbage lookups; tables
+    /// that travel (remote settings, caches) should use this entry
+    /// point instead of [`from_slice`](Dafsa::from_slice).
+    pub fn from_slice_checked(data: &[u8]) -> Result<Self, LoadError> {
+        if data.len() < HEADER_LEN {
+            return Err(LoadError::TooShort);
         }
+        if data[..4] != DAFSA_MAGIC {
+            return Err(LoadError::BadMagic);
+        }
+        let version = data[4];
+        if !(1..=FORMAT_VERSION).contains(&version) {
+            return Err(LoadError::UnsupportedVersion { version });
+        }
+        let expected_len =
+            u32::from_le_bytes([data[5], data[6], data[7], data[8]]) as usize;
+        let payload = &data[HEADER_LEN..];
+        if payload.len() != expected_len {
+            return Err(LoadError::LengthMismatch {
+                expected: expected_len,
+                actual: payload.len(),
+            });
+        }
+        let expected_checksum =
+            u32::from_le_bytes([data[9], data[10], data[11], data[12]]);
+        let actual_checksum = fnv1a32(payload);
+        if actual_checksum != expected_checksum {
+            return Err(LoadError::ChecksumMismatch {
+                expected: expected_checksum,
+                actual: actual_checksum,
+            });
+        }
+        Ok(Dafsa::from_slice(payload))
+    }
+
+    /// Serializes the table into the checked container format that
+    /// [`from_slice_checked`](Dafsa::from_slice_checked) validates.
+    pub fn to_checked_bytes(&self) -> Vec<u8> {
+        let data = self.data.as_slice();
+        let mut bytes = Vec::with_capacity(HEADER_LEN + data.len());
+        bytes.extend_from_slice(&DAFSA_MAGIC);
+        bytes.push(FORMAT_VERSION);
+        bytes.extend_from_slice(&(data.len() as u32).to_le_bytes());
+        bytes.extend_from_slice(&fnv1a32(data).to_le_bytes());
+        bytes.extend_from_slice(data);
+        bytes
     }
 
     /// Searches for the given string in the DAFSA.
@@ -44,7 +211,42 @@ impl Dafsa {
     /// # Returns
     /// * `KEY_NOT_FOUND` if not found, otherwise the associated tag
     pub fn lookup(&self, key: &str) -> i32 {
-        lookup_string(&self.data, key.as_bytes())
+        lookup_string(self.data.as_slice(), key.as_bytes())
+    }
+
+    /// Finds the longest prefix of `key` that is stored in the DAFSA.
+    ///
+    /// # Returns
+    /// * `Some((length, tag))` for the longest stored key that is a prefix
+    ///   of `key`, where `length` is that prefix's length in bytes
+    /// * `None` if no prefix of `key` is stored
+    pub fn lookup_longest_prefix(&self, key: &str) -> Option<(usize, i32)> {
+        lookup_longest_prefix_string(self.data.as_slice(), key.as_bytes())
+    }
+
+    /// Finds the longest stored key that is a suffix of `key`, aligned to
+    /// `.`-separated labels as the public suffix list requires: a stored
+    /// key matches if it equals `key` or if `key` ends with `.` followed
+    /// by the stored key. A stored key such as `le.com` therefore does
+    /// not match `example.com`.
+    ///
+    /// # Returns
+    /// * `Some((length, tag))` where `length` is the matched suffix's
+    ///   length in bytes (not counting the separating `.`)
+    /// * `None` if no label-aligned suffix of `key` is stored
+    pub fn lookup_longest_suffix(&self, key: &str) -> Option<(usize, i32)> {
+        let bytes = key.as_bytes();
+        let mut start = 0;
+        loop {
+            let value = lookup_string(self.data.as_slice(), &bytes[start..]);
+            if value != KEY_NOT_FOUND {
+                return Some((key.len() - start, value));
+            }
+            match bytes[start..].iter().position(|&b| b == b'.') {
+                Some(dot) => start += dot + 1,
+                None => return None,
+            }
+        }
     }
 }
 
@@ -55,31 +257,29 @@ fn get_next_offset(pos: &mut usize, end: usize, offset: &mut usize, data: &[u8])
         return false;
     }
 
-    // When reading an offset the byte array must always contain at least
-    // three more bytes to consume. First the offset to read, then a node
-    // to skip over and finally a destination node. No object can be smaller
-    // than one byte.
-    debug_assert!(*pos + 2 < end);
-
-    let bytes_consumed;
-    match data[*pos] & 0x60 {
+    // An offset record is one to three bytes; each match arm below only
+    // indexes the bytes its width covers. Tables from make_dafsa.py always
+    // leave at least three readable bytes here (an offset, a node to skip
+    // and a destination node), but minimal tables from `DafsaBuilder` can
+    // legitimately end within two bytes of an offset list.
+    let bytes_consumed = match data[*pos] & 0x60 {
         0x60 => {
             // Read three byte offset
             *offset += (((data[*pos] & 0x1F) as usize) << 16)
                 | ((data[*pos + 1] as usize) << 8)
                 | (data[*pos + 2] as usize);
-            bytes_consumed = 3;
+            3
         }
         0x40 => {
             // Read two byte offset
             *offset += (((data[*pos] & 0x1F) as usize) << 8) | (data[*pos + 1] as usize);
-            bytes_consumed = 2;
+            2
         }
         _ => {
             *offset += (data[*pos] & 0x3F) as usize;
-            bytes_consumed = 1;
+            1
         }
-    }
+    };
 
     if (data[*pos] & 0x80) != 0 {
         *pos = end;
@@ -114,12 +314,31 @@ fn is_end_char_match(offset: usize, key_byte: u8, data: &[u8]) -> bool {
 
 /// Read return value at offset.
 /// Returns Some(value) if a return value could be read, None otherwise.
+///
+/// A terminal byte is 100vnnnn: bit 0x10 clear means the value is the low
+/// nibble (the classic make_dafsa.py encoding, 0-15), set means the nibble
+/// holds the least significant four bits and continuation bytes follow,
+/// each carrying seven more bits with the high bit flagging another byte.
 fn get_return_value(offset: usize, data: &[u8]) -> Option<i32> {
     debug_assert!(offset < data.len());
-    if (data[offset] & 0xE0) == 0x80 {
-        Some((data[offset] & 0x0F) as i32)
-    } else {
-        None
+    if (data[offset] & 0xE0) != 0x80 {
+        return None;
+    }
+    if (data[offset] & 0x10) == 0 {
+        return Some((data[offset] & 0x0F) as i32);
+    }
+
+    let mut value = (data[offset] & 0x0F) as u32;
+    let mut shift = 4;
+    let mut pos = offset + 1;
+    loop {
+        let byte = data[pos];
+        value |= ((byte & 0x7F) as u32) << shift;
+        if (byte & 0x80) == 0 {
+            return Some(value as i32);
+        }
+        shift += 7;
+        pos += 1;
     }
 }
 
@@ -197,6 +416,73 @@ fn lookup_string(graph: &[u8], key: &[u8]) -> i32 {
     KEY_NOT_FOUND // No match
 }
 
+/// Find the longest stored key that is a prefix of `key`.
+///
+/// Walks the same automaton as `lookup_string`, but instead of requiring the
+/// key to be consumed exactly, it records a match every time a return value
+/// becomes reachable along the way and keeps descending while characters
+/// still match. The list iteration uses a probe cursor so that sibling
+/// return-value records are still seen after the descending child is found.
+fn lookup_longest_prefix_string(graph: &[u8], key: &[u8]) -> Option<(usize, i32)> {
+    let mut best = None;
+    let mut pos = 0;
+    let end = graph.len();
+    let mut offset = 0;
+    let mut key_idx = 0;
+
+    loop {
+        // The offset list to dive into next, with the key index after the
+        // consumed label. At most one sibling can match the next character.
+        let mut next = None;
+
+        while get_next_offset(&mut pos, end, &mut offset, graph) {
+            // A bare return value means key[..key_idx] is a stored key.
+            // Siblings may already have produced a longer match, so only
+            // ever extend the best match.
+            if let Some(return_value) = get_return_value(offset, graph) {
+                if best.is_none_or(|(length, _)| key_idx > length) {
+                    best = Some((key_idx, return_value));
+                }
+                continue;
+            }
+            if next.is_some() {
+                continue;
+            }
+
+            // Consume matching <char> elements without disturbing the
+            // cumulative list offset
+            let mut probe = offset;
+            let mut idx = key_idx;
+            while idx < key.len() && !is_eol(probe, graph) && is_match(probe, key[idx], graph) {
+                probe += 1;
+                idx += 1;
+            }
+
+            if is_eol(probe, graph) {
+                if let Some(return_value) = get_return_value(probe, graph) {
+                    // <char>+ return value: the whole label matched
+                    if best.is_none_or(|(length, _)| idx > length) {
+                        best = Some((idx, return_value));
+                    }
+                } else if idx < key.len() && is_end_char_match(probe, key[idx], graph) {
+                    next = Some((probe + 1, idx + 1));
+                }
+            }
+            // Otherwise the key ended or mismatched mid-label; this child
+            // is a dead end but siblings may still hold a return value
+        }
+
+        match next {
+            Some((list, idx)) => {
+                pos = list;
+                offset = list;
+                key_idx = idx;
+            }
+            None => return best,
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -218,4 +504,271 @@ mod tests {
         assert_eq!(dafsa.lookup(""), KEY_NOT_FOUND);
         assert_eq!(dafsa.lookup("foo"), KEY_NOT_FOUND);
     }
+
+    fn build(entries: &[(&str, i32)]) -> Dafsa {
+        let mut builder = DafsaBuilder::new();
+        for &(key, value) in entries {
+            builder.insert(key, value).unwrap();
+        }
+        Dafsa::new(builder.build().unwrap())
+    }
+
+    #[test]
+    fn test_lookup_longest_prefix() {
+        let dafsa = build(&[("a", 0), ("abc", 1), ("abcde", 2), ("ax", 3)]);
+
+        assert_eq!(dafsa.lookup_longest_prefix(""), None);
+        assert_eq!(dafsa.lookup_longest_prefix("b"), None);
+        assert_eq!(dafsa.lookup_longest_prefix("a"), Some((1, 0)));
+        assert_eq!(dafsa.lookup_longest_prefix("ab"), Some((1, 0)));
+        assert_eq!(dafsa.lookup_longest_prefix("abc"), Some((3, 1)));
+        assert_eq!(dafsa.lookup_longest_prefix("abcd"), Some((3, 1)));
+        assert_eq!(dafsa.lookup_longest_prefix("abcdef"), Some((5, 2)));
+        assert_eq!(dafsa.lookup_longest_prefix("axe"), Some((2, 3)));
+        assert_eq!(dafsa.lookup_longest_prefix("aycde"), Some((1, 0)));
+
+        let empty = Dafsa::new(vec![]);
+        assert_eq!(empty.lookup_longest_prefix("abc"), None);
+    }
+
+    #[test]
+    fn test_lookup_longest_prefix_agrees_with_exact() {
+        // The longest prefix result must always be the longest prefix on
+        // which exact lookup succeeds
+        let entries = [
+            ("com", 0),
+            ("example.com", 1),
+            ("www.example.com", 2),
+            ("examples", 3),
+        ];
+        let dafsa = build(&entries);
+        for probe in [
+            "com",
+            "common",
+            "example.commerce",
+            "www.example.com/path",
+            "examples.com",
+            "none",
+        ] {
+            let expected = (1..=probe.len())
+                .rev()
+                .find_map(|length| match dafsa.lookup(&probe[..length]) {
+                    KEY_NOT_FOUND => None,
+                    value => Some((length, value)),
+                });
+            assert_eq!(dafsa.lookup_longest_prefix(probe), expected, "probe {probe}");
+        }
+    }
+
+    #[test]
+    fn test_lookup_longest_suffix() {
+        let dafsa = build(&[("com", 0), ("co.uk", 1), ("example.com", 2)]);
+
+        assert_eq!(dafsa.lookup_longest_suffix("com"), Some((3, 0)));
+        assert_eq!(dafsa.lookup_longest_suffix("example.com"), Some((11, 2)));
+        assert_eq!(dafsa.lookup_longest_suffix("www.example.com"), Some((11, 2)));
+        assert_eq!(dafsa.lookup_longest_suffix("sample.com"), Some((3, 0)));
+        assert_eq!(dafsa.lookup_longest_suffix("example.co.uk"), Some((5, 1)));
+        // Matches align to label boundaries: "le.com" style partial labels
+        // never match even though the bytes are a suffix
+        assert_eq!(dafsa.lookup_longest_suffix("notexample.com"), Some((3, 0)));
+        assert_eq!(dafsa.lookup_longest_suffix("org"), None);
+        assert_eq!(dafsa.lookup_longest_suffix("example.org"), None);
+        assert_eq!(dafsa.lookup_longest_suffix(""), None);
+    }
+
+    #[test]
+    fn test_from_file_maps_table() {
+        let entries = [("example.com", 1), ("example.org", 2)];
+        let bytes = {
+            let mut builder = DafsaBuilder::new();
+            for &(key, value) in &entries {
+                builder.insert(key, value).unwrap();
+            }
+            builder.build().unwrap()
+        };
+
+        let dir = std::env::temp_dir().join("firefox_dafsa_mmap_test");
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("table.bin");
+        std::fs::write(&path, &bytes).unwrap();
+
+        let dafsa = Dafsa::from_file(&path).unwrap();
+        for (key, value) in entries {
+            assert_eq!(dafsa.lookup(key), value);
+        }
+        assert_eq!(dafsa.lookup("example.net"), KEY_NOT_FOUND);
+        // The mapping is independent of the file entry once open
+        let _ = std::fs::remove_file(&path);
+        assert_eq!(dafsa.lookup("example.com"), 1);
+
+        // Empty and missing files
+        let empty = dir.join("empty.bin");
+        std::fs::write(&empty, b"").unwrap();
+        assert_eq!(Dafsa::from_file(&empty).unwrap().lookup("x"), KEY_NOT_FOUND);
+        assert!(Dafsa::from_file(dir.join("missing.bin")).is_err());
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_checked_round_trip() {
+        let dafsa = build(&[("example.com", 1), ("example.org", 2)]);
+        let container = dafsa.to_checked_bytes();
+        let loaded = Dafsa::from_slice_checked(&container).unwrap();
+        assert_eq!(loaded.lookup("example.com"), 1);
+        assert_eq!(loaded.lookup("example.org"), 2);
+        assert_eq!(loaded.lookup("example.net"), KEY_NOT_FOUND);
+
+        // The empty table round-trips too
+        let empty = Dafsa::new(vec![]).to_checked_bytes();
+        assert_eq!(
+            Dafsa::from_slice_checked(&empty).unwrap().lookup("x"),
+            KEY_NOT_FOUND
+        );
+
+        // Version-1 containers (single-byte terminals only) still load
+        let mut v1 = dafsa.to_checked_bytes();
+        v1[4] = 1;
+        assert_eq!(Dafsa::from_slice_checked(&v1).unwrap().lookup("example.com"), 1);
+    }
+
+    #[test]
+    fn test_checked_rejects_corruption() {
+        let dafsa = build(&[("example.com", 1)]);
+        let container = dafsa.to_checked_bytes();
+
+        // Too short: any prefix of the header
+        for length in 0..HEADER_LEN {
+            assert!(matches!(
+                Dafsa::from_slice_checked(&container[..length]),
+                Err(LoadError::TooShort)
+            ));
+        }
+
+        // Wrong magic
+        let mut bad = container.clone();
+        bad[0] = b'X';
+        assert!(matches!(
+            Dafsa::from_slice_checked(&bad),
+            Err(LoadError::BadMagic)
+        ));
+
+        // Unknown version
+        let mut bad = container.clone();
+        bad[4] = FORMAT_VERSION + 1;
+        assert!(matches!(
+            Dafsa::from_slice_checked(&bad),
+            Err(LoadError::UnsupportedVersion { version }) if version == FORMAT_VERSION + 1
+        ));
+
+        // Truncated payload
+        let truncated = &container[..container.len() - 1];
+        assert!(matches!(
+            Dafsa::from_slice_checked(truncated),
+            Err(LoadError::LengthMismatch { .. })
+        ));
+
+        // Flipped payload bit
+        let mut bad = container.clone();
+        *bad.last_mut().unwrap() ^= 0x01;
+        assert!(matches!(
+            Dafsa::from_slice_checked(&bad),
+            Err(LoadError::ChecksumMismatch { .. })
+        ));
+    }
+
+    #[test]
+    fn test_lookup_longest_suffix_label_alignment() {
+        let dafsa = build(&[("le.com", 1)]);
+        assert_eq!(dafsa.lookup_longest_suffix("le.com"), Some((6, 1)));
+        assert_eq!(dafsa.lookup_longest_suffix("peop.le.com"), Some((6, 1)));
+        assert_eq!(dafsa.lookup_longest_suffix("example.com"), None);
+    }
+
+    /// Generates a PSL-shaped corpus of `count` dotted ASCII keys.
+    fn synthetic_domains(count: usize) -> Vec<String> {
+        use firefox_xorshift128plus::XorShift128PlusRNG;
+
+        let mut rng = XorShift128PlusRNG::from_seed_u64(0xDAF5_ABE4);
+        let labels = [
+            "com", "org", "net", "example", "static", "cdn", "mail", "www", "app", "eu",
+        ];
+        let mut keys = Vec::with_capacity(count);
+        while keys.len() < count {
+            let depth = 1 + (rng.next() % 3) as usize;
+            let mut key = String::new();
+            for level in 0..depth {
+                if level > 0 {
+                    key.push('.');
+                }
+                key.push_str(labels[(rng.next() % labels.len() as u64) as usize]);
+            }
+            key.push_str(&format!(".{}", rng.next() % 10_000));
+            keys.push(key);
+        }
+        keys.sort();
+        keys.dedup();
+        keys
+    }
+
+    /// Rough builder and lookup timings over a PSL-sized corpus; run
+    /// with `cargo test -- --ignored --nocapture`. Not a criterion
+    /// harness — the workspace builds without network access, so the
+    /// timings stay dependency-free like the floating point crate's.
+    #[test]
+    #[ignore]
+    fn bench_builder_and_lookup() {
+        use std::time::Instant;
+
+        let keys = synthetic_domains(10_000);
+
+        let start = Instant::now();
+        let mut builder = DafsaBuilder::new();
+        for (index, key) in keys.iter().enumerate() {
+            builder.insert(key, (index % 5) as i32).unwrap();
+        }
+        let bytes = builder.build().unwrap();
+        let build_time = start.elapsed();
+        println!(
+            "build: {:?} for {} keys -> {} bytes",
+            build_time,
+            keys.len(),
+            bytes.len()
+        );
+
+        let dafsa = Dafsa::new(bytes);
+        let misses: Vec<String> = keys.iter().map(|key| format!("{key}x")).collect();
+
+        let start = Instant::now();
+        let mut hits = 0usize;
+        for _ in 0..100 {
+            for key in &keys {
+                if dafsa.lookup(key) != KEY_NOT_FOUND {
+                    hits += 1;
+                }
+            }
+        }
+        let hit_time = start.elapsed();
+
+        let start = Instant::now();
+        let mut found = 0usize;
+        for _ in 0..100 {
+            for key in &misses {
+                if dafsa.lookup(key) != KEY_NOT_FOUND {
+                    found += 1;
+                }
+            }
+        }
+        let miss_time = start.elapsed();
+
+        assert_eq!(hits, keys.len() * 100);
+        assert_eq!(found, 0);
+        println!(
+            "lookup: {:?} for {} hits, {:?} for as many misses",
+            hit_time,
+            hits,
+            miss_time
+        );
+    }
 }
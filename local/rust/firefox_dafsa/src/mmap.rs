@@ -0,0 +1,66 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+//! Read-only memory mapping for DAFSA tables.
+//!
+//! Large remote-settings-delivered tables should not be copied onto
+//! the heap just to be searched; a private read-only mapping lets the
+//! kernel page the table in on demand and share it across processes.
+//! The bindings come from the `libc` crate so the prototypes (notably
+//! the `off_t` offset parameter, which is 32-bit on 32-bit targets)
+//! match the platform's C library exactly.
+
+use libc::{c_void, MAP_FAILED, MAP_PRIVATE, PROT_READ};
+use std::fs::File;
+use std::io;
+use std::os::unix::io::AsRawFd;
+
+/// An owned read-only file mapping; unmapped on drop.
+pub(crate) struct Mmap {
+    ptr: *mut c_void,
+    len: usize,
+}
+
+// The mapping is PROT_READ and owned: no alias can write through it,
+// so sharing it across threads is as safe as sharing a &[u8]
+unsafe impl Send for Mmap {}
+unsafe impl Sync for Mmap {}
+
+impl Mmap {
+    /// Maps `len` bytes of `file` starting at offset 0. `len` must be
+    /// non-zero (mmap rejects empty mappings; callers special-case
+    /// empty files).
+    pub(crate) fn map(file: &File, len: u64) -> io::Result<Mmap> {
+        debug_assert!(len > 0);
+        let len = usize::try_from(len)
+            .map_err(|_| io::Error::new(io::ErrorKind::InvalidData, "table too large to map"))?;
+
+        let ptr = unsafe {
+            libc::mmap(
+                std::ptr::null_mut(),
+                len,
+                PROT_READ,
+                MAP_PRIVATE,
+                file.as_raw_fd(),
+                0,
+            )
+        };
+        if ptr == MAP_FAILED {
+            return Err(io::Error::last_os_error());
+        }
+        Ok(Mmap { ptr, len })
+    }
+
+    pub(crate) fn as_slice(&self) -> &[u8] {
+        unsafe { std::slice::from_raw_parts(self.ptr as *const u8, self.len) }
+    }
+}
+
+impl Drop for Mmap {
+    fn drop(&mut self) {
+        unsafe {
+            libc::munmap(self.ptr, self.len);
+        }
+    }
+}
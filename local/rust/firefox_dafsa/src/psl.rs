@@ -0,0 +1,220 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+//! Public suffix computation over a DAFSA-encoded public suffix list.
+//!
+//! nsEffectiveTLDService answers two questions about a host: where its
+//! public suffix (eTLD) starts, and what its registrable domain
+//! (eTLD+1) is. Both come from matching the host's label-aligned
+//! suffixes against the embedded rule table, where each rule's value
+//! carries the flags below. Wildcard rules are stored without their
+//! `*.` prefix and exception rules without their `!`, exactly as
+//! `prepare_tlds.py` emits them.
+//!
+//! Hosts are expected in normalized form: lowercase ASCII (punycoded
+//! if needed) with no trailing dot; normalization is the caller's job,
+//! as it is for the C++ service.
+
+use crate::{Dafsa, KEY_NOT_FOUND};
+
+/// Rule value bit: an exception rule (`!city.kobe.jp`), which exempts
+/// its domain from a covering wildcard.
+pub const PSL_EXCEPTION: i32 = 1;
+
+/// Rule value bit: a wildcard rule (`*.kobe.jp`); every direct child
+/// label of the stored domain is a public suffix.
+pub const PSL_WILDCARD: i32 = 2;
+
+/// Rule value bit: the rule comes from the PSL's private section.
+/// Matching ignores it; callers that distinguish ICANN from private
+/// registries can test the bit on [`PublicSuffix::lookup_flags`].
+pub const PSL_PRIVATE: i32 = 4;
+
+/// Computes public suffixes and registrable domains against a
+/// DAFSA-encoded rule table.
+pub struct PublicSuffix<'a> {
+    dafsa: &'a Dafsa,
+}
+
+impl<'a> PublicSuffix<'a> {
+    /// Wraps a rule table. The DAFSA's keys are PSL rules with flag
+    /// values as described in the module docs.
+    pub fn new(dafsa: &'a Dafsa) -> Self {
+        PublicSuffix { dafsa }
+    }
+
+    /// Returns the raw rule flags stored for `rule`, or `KEY_NOT_FOUND`.
+    pub fn lookup_flags(&self, rule: &str) -> i32 {
+        self.dafsa.lookup(rule)
+    }
+
+    /// Returns the public suffix of `host` as a subslice of it.
+    ///
+    /// Follows the PSL algorithm: among all matching rules an exception
+    /// rule prevails, otherwise the longest match; a host matching no
+    /// rule falls back to the implicit `*` rule, making its rightmost
+    /// label the public suffix. The host itself can be the public
+    /// suffix (`com` is its own eTLD).
+    pub fn public_suffix<'h>(&self, host: &'h str) -> &'h str {
+        &host[self.public_suffix_start(host)..]
+    }
+
+    /// Returns the registrable domain (eTLD+1) of `host`: the public
+    /// suffix plus one more label. `None` when the host *is* a public
+    /// suffix and so cannot be registered.
+    pub fn registrable_domain<'h>(&self, host: &'h str) -> Option<&'h str> {
+        let suffix_start = self.public_suffix_start(host);
+        if suffix_start == 0 {
+            return None;
+        }
+        // The label immediately left of the public suffix; its start is
+        // just past the previous dot, if any
+        let head = &host[..suffix_start - 1];
+        let domain_start = match head.rfind('.') {
+            Some(dot) => dot + 1,
+            None => 0,
+        };
+        Some(&host[domain_start..])
+    }
+
+    /// Byte offset at which the public suffix of `host` starts.
+    pub fn public_suffix_start(&self, host: &str) -> usize {
+        if host.is_empty() {
+            return 0;
+        }
+        let host = host.strip_suffix('.').unwrap_or(host);
+
+        // Start offset of every label, leftmost first
+        let mut starts = vec![0];
+        for (index, byte) in host.bytes().enumerate() {
+            if byte == b'.' {
+                starts.push(index + 1);
+            }
+        }
+
+        // Scan suffixes longest first. The first normal or wildcard
+        // match is the longest one, but exception rules prevail over
+        // everything, so the scan always runs to the end.
+        let mut prevailing = None;
+        for (index, &start) in starts.iter().enumerate() {
+            let flags = self.dafsa.lookup(&host[start..]);
+            if flags == KEY_NOT_FOUND {
+                continue;
+            }
+            if flags & PSL_EXCEPTION != 0 {
+                // The exception's own domain is registrable: the public
+                // suffix is the rule minus its leftmost label
+                return match starts.get(index + 1) {
+                    Some(&next) => next,
+                    None => start,
+                };
+            }
+            if prevailing.is_none() {
+                if flags & PSL_WILDCARD != 0 && index > 0 {
+                    // *.rule: the label left of the match is public too
+                    prevailing = Some(starts[index - 1]);
+                } else {
+                    prevailing = Some(start);
+                }
+            }
+        }
+
+        // No rule matched: the implicit * rule makes the rightmost
+        // label the public suffix
+        prevailing.unwrap_or_else(|| *starts.last().expect("at least one label"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::DafsaBuilder;
+
+    fn rules(entries: &[(&str, i32)]) -> Dafsa {
+        let mut builder = DafsaBuilder::new();
+        for &(key, value) in entries {
+            builder.insert(key, value).unwrap();
+        }
+        Dafsa::new(builder.build().unwrap())
+    }
+
+    fn psl_table() -> Dafsa {
+        rules(&[
+            ("com", 0),
+            ("co.uk", 0),
+            ("uk", 0),
+            ("jp", 0),
+            ("kobe.jp", PSL_WILDCARD),
+            ("city.kobe.jp", PSL_EXCEPTION),
+            ("github.io", PSL_PRIVATE),
+        ])
+    }
+
+    #[test]
+    fn test_public_suffix_normal_rules() {
+        let table = psl_table();
+        let psl = PublicSuffix::new(&table);
+
+        assert_eq!(psl.public_suffix("example.com"), "com");
+        assert_eq!(psl.public_suffix("www.example.com"), "com");
+        assert_eq!(psl.public_suffix("example.co.uk"), "co.uk");
+        assert_eq!(psl.public_suffix("com"), "com");
+        // Unknown TLDs fall back to the implicit * rule
+        assert_eq!(psl.public_suffix("example.zz"), "zz");
+        assert_eq!(psl.public_suffix("a.b.example.zz"), "zz");
+    }
+
+    #[test]
+    fn test_public_suffix_wildcard_and_exception() {
+        let table = psl_table();
+        let psl = PublicSuffix::new(&table);
+
+        // *.kobe.jp: one extra label is public...
+        assert_eq!(psl.public_suffix("foo.kobe.jp"), "foo.kobe.jp");
+        assert_eq!(psl.public_suffix("bar.foo.kobe.jp"), "foo.kobe.jp");
+        // ...but the wildcard needs that label to be present
+        assert_eq!(psl.public_suffix("kobe.jp"), "kobe.jp");
+        // !city.kobe.jp overrides the wildcard
+        assert_eq!(psl.public_suffix("city.kobe.jp"), "kobe.jp");
+        assert_eq!(psl.public_suffix("www.city.kobe.jp"), "kobe.jp");
+    }
+
+    #[test]
+    fn test_registrable_domain() {
+        let table = psl_table();
+        let psl = PublicSuffix::new(&table);
+
+        assert_eq!(psl.registrable_domain("www.example.com"), Some("example.com"));
+        assert_eq!(psl.registrable_domain("example.com"), Some("example.com"));
+        assert_eq!(psl.registrable_domain("a.b.example.co.uk"), Some("example.co.uk"));
+        assert_eq!(psl.registrable_domain("bar.foo.kobe.jp"), Some("bar.foo.kobe.jp"));
+        assert_eq!(psl.registrable_domain("www.city.kobe.jp"), Some("city.kobe.jp"));
+        // Private-section registries behave like normal rules
+        assert_eq!(psl.registrable_domain("user.github.io"), Some("user.github.io"));
+
+        // A public suffix has no registrable domain
+        assert_eq!(psl.registrable_domain("com"), None);
+        assert_eq!(psl.registrable_domain("co.uk"), None);
+        assert_eq!(psl.registrable_domain("foo.kobe.jp"), None);
+        assert_eq!(psl.registrable_domain("zz"), None);
+        assert_eq!(psl.registrable_domain(""), None);
+    }
+
+    #[test]
+    fn test_trailing_dot_is_ignored() {
+        let table = psl_table();
+        let psl = PublicSuffix::new(&table);
+        assert_eq!(psl.public_suffix_start("www.example.com."), 12);
+        assert_eq!(psl.public_suffix("www.example.com"), "com");
+    }
+
+    #[test]
+    fn test_lookup_flags_passthrough() {
+        let table = psl_table();
+        let psl = PublicSuffix::new(&table);
+        assert_eq!(psl.lookup_flags("kobe.jp"), PSL_WILDCARD);
+        assert_eq!(psl.lookup_flags("github.io"), PSL_PRIVATE);
+        assert_eq!(psl.lookup_flags("nope"), KEY_NOT_FOUND);
+    }
+}
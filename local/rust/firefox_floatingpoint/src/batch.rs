@@ -0,0 +1,192 @@
+//! Batch float32-representability checking.
+//!
+//! Typed-array and WebGL paths validate whole buffers before
+//! downcasting doubles to floats; doing that one call at a time leaves
+//! throughput on the table. The entry points here walk a slice with
+//! the bit-classification check and, as in `firefox_hashbytes`, compile
+//! the shared loop body a second time for AVX2-capable cores so the
+//! compiler can use wider encodings; a runtime dispatch picks the best
+//! path. Results are identical on every path.
+
+use crate::is_float32_representable;
+
+/// The shared counting loop, compiled per dispatch target.
+///
+/// The per-element check is branch-free enough for the compiler to
+/// if-convert, so the loop reduces to a running sum the vectorizer can
+/// widen.
+#[inline(always)]
+fn count_impl(values: &[f64]) -> usize {
+    values
+        .iter()
+        .map(|&value| usize::from(!is_float32_representable(value)))
+        .sum()
+}
+
+/// The shared mask loop, compiled per dispatch target.
+#[inline(always)]
+fn mask_impl(values: &[f64]) -> Vec<u64> {
+    let mut mask = vec![0u64; values.len().div_ceil(64)];
+    for (index, &value) in values.iter().enumerate() {
+        if !is_float32_representable(value) {
+            mask[index / 64] |= 1 << (index % 64);
+        }
+    }
+    mask
+}
+
+/// The counting body compiled for AVX2-capable cores.
+///
+/// # Safety
+///
+/// Only call when the CPU supports AVX2 (checked at dispatch).
+#[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+#[target_feature(enable = "avx2")]
+unsafe fn count_avx2(values: &[f64]) -> usize {
+    count_impl(values)
+}
+
+/// The mask body compiled for AVX2-capable cores.
+///
+/// # Safety
+///
+/// Only call when the CPU supports AVX2 (checked at dispatch).
+#[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+#[target_feature(enable = "avx2")]
+unsafe fn mask_avx2(values: &[f64]) -> Vec<u64> {
+    mask_impl(values)
+}
+
+/// Counts the values in the slice that are NOT exactly representable
+/// as float32.
+///
+/// Zero means the whole buffer can be downcast losslessly. Equivalent
+/// to filtering with [`is_float32_representable`] per element, but
+/// vectorization-friendly.
+///
+/// # Examples
+///
+/// ```
+/// use firefox_floatingpoint::count_non_float32_representable;
+///
+/// let values = [1.0, 2.5, 2147483647.0, f64::NAN];
+/// assert_eq!(count_non_float32_representable(&values), 1);
+/// ```
+pub fn count_non_float32_representable(values: &[f64]) -> usize {
+    #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+    {
+        if std::is_x86_feature_detected!("avx2") {
+            // SAFETY: the AVX2 requirement was just verified
+            return unsafe { count_avx2(values) };
+        }
+    }
+    count_impl(values)
+}
+
+/// Produces a packed bitmask of the non-representable values.
+///
+/// Bit `i % 64` of word `i / 64` is set when `values[i]` is not
+/// exactly representable as float32; unused trailing bits of the last
+/// word are zero. Callers that need the offending indices — e.g. for
+/// a diagnostic about which buffer elements would round — scan the
+/// mask words rather than re-checking every element.
+///
+/// # Examples
+///
+/// ```
+/// use firefox_floatingpoint::non_float32_representable_mask;
+///
+/// let values = [1.0, 2147483647.0, 0.5];
+/// assert_eq!(non_float32_representable_mask(&values), vec![0b010]);
+/// ```
+pub fn non_float32_representable_mask(values: &[f64]) -> Vec<u64> {
+    #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+    {
+        if std::is_x86_feature_detected!("avx2") {
+            // SAFETY: the AVX2 requirement was just verified
+            return unsafe { mask_avx2(values) };
+        }
+    }
+    mask_impl(values)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_count_basic() {
+        assert_eq!(count_non_float32_representable(&[]), 0);
+        assert_eq!(count_non_float32_representable(&[1.0, 0.5, -2.0]), 0);
+        assert_eq!(
+            count_non_float32_representable(&[1.0, 2147483647.0, 16777217.0]),
+            2
+        );
+        // NaN and infinities are representable, matching the scalar check
+        assert_eq!(
+            count_non_float32_representable(&[f64::NAN, f64::INFINITY]),
+            0
+        );
+    }
+
+    #[test]
+    fn test_mask_basic() {
+        assert!(non_float32_representable_mask(&[]).is_empty());
+        assert_eq!(non_float32_representable_mask(&[1.0, 0.5]), vec![0]);
+        assert_eq!(
+            non_float32_representable_mask(&[1.0, 2147483647.0, 0.5, 1e-300]),
+            vec![0b1010]
+        );
+    }
+
+    #[test]
+    fn test_mask_word_boundaries() {
+        // A bad value on each side of the 64-element word boundary
+        let mut values = vec![1.0f64; 130];
+        values[63] = 2147483647.0;
+        values[64] = 2147483647.0;
+        values[129] = 2147483647.0;
+
+        let mask = non_float32_representable_mask(&values);
+        assert_eq!(mask.len(), 3); // ceil(130 / 64)
+        assert_eq!(mask[0], 1 << 63);
+        assert_eq!(mask[1], 1);
+        assert_eq!(mask[2], 1 << 1);
+    }
+
+    #[test]
+    fn test_batch_agrees_with_scalar() {
+        use firefox_xorshift128plus::XorShift128PlusRNG;
+
+        let mut rng = XorShift128PlusRNG::from_seed_u64(0xba7c_ba7c);
+        for len in [0usize, 1, 63, 64, 65, 1000] {
+            // Mix raw bit patterns with guaranteed-representable values
+            let values: Vec<f64> = (0..len)
+                .map(|i| {
+                    if i % 3 == 0 {
+                        rng.next_float() as f64
+                    } else {
+                        f64::from_bits(rng.next())
+                    }
+                })
+                .collect();
+
+            let expected: Vec<bool> = values
+                .iter()
+                .map(|&v| !crate::is_float32_representable(v))
+                .collect();
+            let expected_count = expected.iter().filter(|&&bad| bad).count();
+
+            assert_eq!(count_non_float32_representable(&values), expected_count);
+
+            let mask = non_float32_representable_mask(&values);
+            assert_eq!(mask.len(), len.div_ceil(64));
+            for (i, &bad) in expected.iter().enumerate() {
+                assert_eq!(mask[i / 64] >> (i % 64) & 1 == 1, bad, "bit {} wrong", i);
+            }
+            // Trailing bits beyond len are clear
+            let total_set: u32 = mask.iter().map(|w| w.count_ones()).sum();
+            assert_eq!(total_set as usize, expected_count);
+        }
+    }
+}
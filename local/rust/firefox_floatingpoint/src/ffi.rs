@@ -66,60 +66,299 @@ pub extern "C" fn IsFloat32Representable(value: f64) -> bool {
     }
 }
 
+/// C-compatible export of NumberIsInt32.
+///
+/// Writes the integer value through `out` and returns true when the
+/// double is exactly an int32 (-0 does not qualify); otherwise returns
+/// false and leaves `out` untouched.
+///
+/// # Safety
+///
+/// `out` must be null or point to writable memory for an int32_t.
+///
+/// # Examples (from C++)
+///
+/// ```cpp
+/// int32_t i;
+/// if (NumberIsInt32(d, &i)) { /* use i */ }
+/// ```
+#[no_mangle]
+pub unsafe extern "C" fn NumberIsInt32(value: f64, out: *mut i32) -> bool {
+    panic::catch_unwind(|| match crate::number_is_int32(value) {
+        Some(int_value) => {
+            if !out.is_null() {
+                *out = int_value;
+            }
+            true
+        }
+        None => false,
+    })
+    .unwrap_or(false)
+}
+
+/// C-compatible export of NumberEqualsInt32.
+///
+/// Like NumberIsInt32, but -0 counts as 0.
+///
+/// # Safety
+///
+/// `out` must be null or point to writable memory for an int32_t.
+#[no_mangle]
+pub unsafe extern "C" fn NumberEqualsInt32(value: f64, out: *mut i32) -> bool {
+    panic::catch_unwind(|| match crate::number_equals_int32(value) {
+        Some(int_value) => {
+            if !out.is_null() {
+                *out = int_value;
+            }
+            true
+        }
+        None => false,
+    })
+    .unwrap_or(false)
+}
+
+/// C-compatible export of NumberIsInt64.
+///
+/// # Safety
+///
+/// `out` must be null or point to writable memory for an int64_t.
+#[no_mangle]
+pub unsafe extern "C" fn NumberIsInt64(value: f64, out: *mut i64) -> bool {
+    panic::catch_unwind(|| match crate::number_is_int64(value) {
+        Some(int_value) => {
+            if !out.is_null() {
+                *out = int_value;
+            }
+            true
+        }
+        None => false,
+    })
+    .unwrap_or(false)
+}
+
+/// C-compatible export of ToZeroIfNonfinite.
+///
+/// Returns the value unchanged if finite, +0 for NaN and infinities.
+#[no_mangle]
+pub extern "C" fn ToZeroIfNonfinite(value: f64) -> f64 {
+    panic::catch_unwind(|| crate::to_zero_if_nonfinite(value)).unwrap_or(0.0)
+}
+
+/// C-compatible export of ExponentComponent.
+///
+/// Returns the unbiased exponent; see
+/// [`exponent_component`](crate::exponent_component) for the
+/// zero/denormal and nonfinite conventions. Returns `i32::MIN` if a
+/// panic occurs (it never should for bit inspection).
+#[no_mangle]
+pub extern "C" fn ExponentComponent(value: f64) -> i32 {
+    panic::catch_unwind(|| crate::exponent_component(value)).unwrap_or(i32::MIN)
+}
+
+/// C-compatible export of IsNegativeZero.
+#[no_mangle]
+pub extern "C" fn IsNegativeZero(value: f64) -> bool {
+    panic::catch_unwind(|| crate::is_negative_zero(value)).unwrap_or(false)
+}
+
+/// C-compatible export of IsPositiveZero.
+#[no_mangle]
+pub extern "C" fn IsPositiveZero(value: f64) -> bool {
+    panic::catch_unwind(|| crate::is_positive_zero(value)).unwrap_or(false)
+}
+
+/// C-compatible export of SpecificNaN<double>.
+///
+/// Writes the constructed NaN through `out` and returns true. The C++
+/// template asserts on a zero or too-wide significand; here those
+/// panics are caught by the FFI guard, which returns false and leaves
+/// `out` untouched.
+///
+/// # Safety
+///
+/// `out` must be null or point to writable memory for a double.
+#[no_mangle]
+pub unsafe extern "C" fn SpecificNaNDouble(signbit: bool, significand: u64, out: *mut f64) -> bool {
+    panic::catch_unwind(|| {
+        let nan = crate::specific_nan_f64(signbit, significand);
+        if !out.is_null() {
+            *out = nan;
+        }
+        true
+    })
+    .unwrap_or(false)
+}
+
+/// C-compatible export of SpecificNaN<float>.
+///
+/// # Safety
+///
+/// `out` must be null or point to writable memory for a float.
+#[no_mangle]
+pub unsafe extern "C" fn SpecificNaNFloat(signbit: bool, significand: u32, out: *mut f32) -> bool {
+    panic::catch_unwind(|| {
+        let nan = crate::specific_nan_f32(signbit, significand);
+        if !out.is_null() {
+            *out = nan;
+        }
+        true
+    })
+    .unwrap_or(false)
+}
+
+/// C-compatible export of FuzzyEqualsAdditive.
+///
+/// The C++ default epsilon (2^-40) does not carry across FFI; callers
+/// pass it explicitly.
+#[no_mangle]
+pub extern "C" fn FuzzyEqualsAdditive(value1: f64, value2: f64, epsilon: f64) -> bool {
+    panic::catch_unwind(|| crate::fuzzy_equals_additive(value1, value2, epsilon)).unwrap_or(false)
+}
+
+/// C-compatible export of FuzzyEqualsMultiplicative.
+#[no_mangle]
+pub extern "C" fn FuzzyEqualsMultiplicative(value1: f64, value2: f64, epsilon: f64) -> bool {
+    panic::catch_unwind(|| crate::fuzzy_equals_multiplicative(value1, value2, epsilon))
+        .unwrap_or(false)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_ffi_number_is_int32() {
+        unsafe {
+            let mut out = 0i32;
+            assert!(NumberIsInt32(42.0, &mut out));
+            assert_eq!(out, 42);
+
+            // Failure leaves the out-param untouched
+            out = 99;
+            assert!(!NumberIsInt32(1.5, &mut out));
+            assert_eq!(out, 99);
+            assert!(!NumberIsInt32(-0.0, &mut out));
+
+            // Null out-param is tolerated
+            assert!(NumberIsInt32(7.0, std::ptr::null_mut()));
+        }
+    }
+
+    #[test]
+    fn test_ffi_number_equals_int32() {
+        unsafe {
+            let mut out = 99i32;
+            assert!(NumberEqualsInt32(-0.0, &mut out));
+            assert_eq!(out, 0);
+            assert!(!NumberEqualsInt32(f64::NAN, &mut out));
+        }
+    }
+
+    #[test]
+    fn test_ffi_number_is_int64() {
+        unsafe {
+            let mut out = 0i64;
+            assert!(NumberIsInt64(4294967296.0, &mut out));
+            assert_eq!(out, 1i64 << 32);
+            assert!(!NumberIsInt64(2f64.powi(63), &mut out));
+        }
+    }
+
+    #[test]
+    fn test_ffi_to_zero_if_nonfinite() {
+        assert_eq!(ToZeroIfNonfinite(2.5), 2.5);
+        assert_eq!(ToZeroIfNonfinite(f64::NAN), 0.0);
+        assert_eq!(ToZeroIfNonfinite(f64::NEG_INFINITY), 0.0);
+    }
+
+    #[test]
+    fn test_ffi_exponent_and_zero_predicates() {
+        assert_eq!(ExponentComponent(10.0), 3);
+        assert_eq!(ExponentComponent(0.0), -1023);
+        assert_eq!(ExponentComponent(f64::NAN), 1024);
+        assert!(IsNegativeZero(-0.0));
+        assert!(!IsNegativeZero(0.0));
+        assert!(IsPositiveZero(0.0));
+        assert!(!IsPositiveZero(-0.0));
+    }
+
+    #[test]
+    fn test_ffi_specific_nan() {
+        unsafe {
+            let mut out = 0.0f64;
+            assert!(SpecificNaNDouble(true, 1, &mut out));
+            assert_eq!(out.to_bits(), 0xFFF0_0000_0000_0001);
+
+            // The invalid-significand panic is caught; out is untouched
+            let before = out;
+            assert!(!SpecificNaNDouble(false, 0, &mut out));
+            assert_eq!(out.to_bits(), before.to_bits());
+
+            let mut out32 = 0.0f32;
+            assert!(SpecificNaNFloat(false, 0x40_0000, &mut out32));
+            assert_eq!(out32.to_bits(), 0x7FC0_0000);
+        }
+    }
+
+    #[test]
+    fn test_ffi_fuzzy_equals() {
+        let eps = crate::FUZZY_EQUALS_EPSILON_F64;
+        assert!(FuzzyEqualsAdditive(1.0, 1.0 + eps / 2.0, eps));
+        assert!(!FuzzyEqualsAdditive(1.0, 1.1, eps));
+        assert!(FuzzyEqualsMultiplicative(1e20, 1e20 * (1.0 + eps / 2.0), eps));
+        assert!(!FuzzyEqualsMultiplicative(0.0, 1e-300, eps));
+    }
+
     #[test]
     fn test_ffi_basic() {
         // Test that FFI function works identically to Rust function
-        assert_eq!(IsFloat32Representable(1.0), true);
-        assert_eq!(IsFloat32Representable(2147483647.0), false);
-        assert_eq!(IsFloat32Representable(f64::NAN), true);
-        assert_eq!(IsFloat32Representable(f64::INFINITY), true);
+        assert!(IsFloat32Representable(1.0));
+        assert!(!(IsFloat32Representable(2147483647.0)));
+        assert!(IsFloat32Representable(f64::NAN));
+        assert!(IsFloat32Representable(f64::INFINITY));
     }
 
     #[test]
     fn test_ffi_special_values() {
         // Zeroes
-        assert_eq!(IsFloat32Representable(0.0), true);
-        assert_eq!(IsFloat32Representable(-0.0), true);
+        assert!(IsFloat32Representable(0.0));
+        assert!(IsFloat32Representable(-0.0));
 
         // Infinities
-        assert_eq!(IsFloat32Representable(f64::INFINITY), true);
-        assert_eq!(IsFloat32Representable(f64::NEG_INFINITY), true);
+        assert!(IsFloat32Representable(f64::INFINITY));
+        assert!(IsFloat32Representable(f64::NEG_INFINITY));
 
         // NaN
-        assert_eq!(IsFloat32Representable(f64::NAN), true);
+        assert!(IsFloat32Representable(f64::NAN));
     }
 
     #[test]
     fn test_ffi_overflow() {
         let max_as_f64 = f32::MAX as f64;
-        assert_eq!(IsFloat32Representable(max_as_f64 * 2.0), false);
-        assert_eq!(IsFloat32Representable(-max_as_f64 * 2.0), false);
+        assert!(!(IsFloat32Representable(max_as_f64 * 2.0)));
+        assert!(!(IsFloat32Representable(-max_as_f64 * 2.0)));
     }
 
     #[test]
     fn test_ffi_precision() {
         // Exact values
-        assert_eq!(IsFloat32Representable(1.0), true);
-        assert_eq!(IsFloat32Representable(2.5), true);
+        assert!(IsFloat32Representable(1.0));
+        assert!(IsFloat32Representable(2.5));
 
         // Precision loss
-        assert_eq!(IsFloat32Representable(2147483647.0), false);
-        assert_eq!(IsFloat32Representable(16777217.0), false);
+        assert!(!(IsFloat32Representable(2147483647.0)));
+        assert!(!(IsFloat32Representable(16777217.0)));
     }
 
     #[test]
     fn test_ffi_powers_of_two() {
         // Representable powers of 2
-        assert_eq!(IsFloat32Representable(2.0_f64.powi(0)), true);
-        assert_eq!(IsFloat32Representable(2.0_f64.powi(10)), true);
-        assert_eq!(IsFloat32Representable(2.0_f64.powi(127)), true);
+        assert!(IsFloat32Representable(2.0_f64.powi(0)));
+        assert!(IsFloat32Representable(2.0_f64.powi(10)));
+        assert!(IsFloat32Representable(2.0_f64.powi(127)));
 
         // Non-representable powers of 2
-        assert_eq!(IsFloat32Representable(2.0_f64.powi(128)), false);
-        assert_eq!(IsFloat32Representable(2.0_f64.powi(-150)), false);
+        assert!(!(IsFloat32Representable(2.0_f64.powi(128))));
+        assert!(!(IsFloat32Representable(2.0_f64.powi(-150))));
     }
 }
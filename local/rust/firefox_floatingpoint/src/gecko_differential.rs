@@ -0,0 +1,165 @@
+//! Differential harness against the original C++ implementations.
+//!
+//! Only compiled with the `gecko-differential` feature, which is off
+//! by default: the extern symbols below must be provided by a small
+//! C++ shim compiled into the test binary when linking against Gecko,
+//! each wrapper forwarding to the mfbt inline it is named after, e.g.
+//!
+//! ```cpp
+//! extern "C" bool gecko_IsFloat32Representable(double v) {
+//!   return mozilla::IsFloat32Representable(v);
+//! }
+//! ```
+//!
+//! The harness runs every port in this crate against its C++
+//! counterpart over a large corpus — structured values hitting every
+//! classification boundary plus uniform random bit patterns — and
+//! fails with a report listing every divergence (function, input bits,
+//! both results) rather than stopping at the first.
+
+use firefox_xorshift128plus::XorShift128PlusRNG;
+
+extern "C" {
+    fn gecko_IsFloat32Representable(value: f64) -> bool;
+    fn gecko_ExponentComponent(value: f64) -> i32;
+    fn gecko_NumberIsInt32(value: f64, out: *mut i32) -> bool;
+    fn gecko_NumberEqualsInt32(value: f64, out: *mut i32) -> bool;
+    fn gecko_NumberIsInt64(value: f64, out: *mut i64) -> bool;
+    fn gecko_ToZeroIfNonfinite(value: f64) -> f64;
+    fn gecko_ToInt32(value: f64) -> i32;
+}
+
+/// Structured doubles covering every branch of the ports: specials,
+/// signed zeros, power-of-two exponent sweep with one-ULP neighbors,
+/// int32/int64 boundaries, and float32 boundary values.
+fn corpus() -> Vec<f64> {
+    let mut values = vec![
+        0.0,
+        -0.0,
+        f64::NAN,
+        f64::INFINITY,
+        f64::NEG_INFINITY,
+        f64::MAX,
+        f64::MIN,
+        f64::MIN_POSITIVE,
+        f32::MAX as f64,
+        f32::MIN_POSITIVE as f64,
+        2147483647.0,
+        2147483648.0,
+        -2147483648.0,
+        -2147483649.0,
+        2f64.powi(63),
+        -(2f64.powi(63)),
+        2f64.powi(63) - 2048.0,
+        9007199254740991.0,
+        3735928559.0,
+        std::f64::consts::PI,
+        0.5,
+        -1.5,
+    ];
+    for exp in -1074..=1023i32 {
+        let base = if exp < -1022 {
+            f64::from_bits(1u64 << (exp + 1074))
+        } else {
+            2f64.powi(exp)
+        };
+        for value in [base, -base] {
+            values.push(value);
+            values.push(f64::from_bits(value.to_bits() + 1));
+            values.push(f64::from_bits(value.to_bits() - 1));
+        }
+    }
+    let mut rng = XorShift128PlusRNG::from_seed_u64(0x6ec0_d1ff);
+    for _ in 0..500_000 {
+        values.push(f64::from_bits(rng.next()));
+    }
+    for _ in 0..100_000 {
+        // Concentrate on the int32/int64 band where the conversion
+        // ports have their interesting boundaries
+        values.push((rng.next_double() - 0.5) * 2f64.powi(54));
+    }
+    values
+}
+
+/// Compares one value across every port, appending any divergence to
+/// the report.
+fn check_value(value: f64, report: &mut Vec<String>) {
+    let bits = value.to_bits();
+    let mut divergence = |name: &str, rust: String, cpp: String| {
+        report.push(format!(
+            "{}({:e} / {:#018x}): rust={} cpp={}",
+            name, value, bits, rust, cpp
+        ));
+    };
+
+    unsafe {
+        let rust = crate::is_float32_representable(value);
+        let cpp = gecko_IsFloat32Representable(value);
+        if rust != cpp {
+            divergence("IsFloat32Representable", rust.to_string(), cpp.to_string());
+        }
+
+        let rust = crate::exponent_component(value);
+        let cpp = gecko_ExponentComponent(value);
+        if rust != cpp {
+            divergence("ExponentComponent", rust.to_string(), cpp.to_string());
+        }
+
+        let rust = crate::number_is_int32(value);
+        let mut out = 0i32;
+        let cpp = gecko_NumberIsInt32(value, &mut out).then_some(out);
+        if rust != cpp {
+            divergence("NumberIsInt32", format!("{:?}", rust), format!("{:?}", cpp));
+        }
+
+        let rust = crate::number_equals_int32(value);
+        let mut out = 0i32;
+        let cpp = gecko_NumberEqualsInt32(value, &mut out).then_some(out);
+        if rust != cpp {
+            divergence(
+                "NumberEqualsInt32",
+                format!("{:?}", rust),
+                format!("{:?}", cpp),
+            );
+        }
+
+        let rust = crate::number_is_int64(value);
+        let mut out = 0i64;
+        let cpp = gecko_NumberIsInt64(value, &mut out).then_some(out);
+        if rust != cpp {
+            divergence("NumberIsInt64", format!("{:?}", rust), format!("{:?}", cpp));
+        }
+
+        let rust = crate::to_zero_if_nonfinite(value);
+        let cpp = gecko_ToZeroIfNonfinite(value);
+        if rust.to_bits() != cpp.to_bits() {
+            divergence("ToZeroIfNonfinite", rust.to_string(), cpp.to_string());
+        }
+
+        let rust = crate::to_int32(value);
+        let cpp = gecko_ToInt32(value);
+        if rust != cpp {
+            divergence("ToInt32", rust.to_string(), cpp.to_string());
+        }
+    }
+}
+
+#[test]
+fn differential_against_gecko() {
+    let mut report = Vec::new();
+    for value in corpus() {
+        check_value(value, &mut report);
+        // An implementation bug tends to diverge on a whole class of
+        // inputs; cap the report so the failure stays readable
+        if report.len() >= 100 {
+            report.push("... report truncated at 100 divergences".to_string());
+            break;
+        }
+    }
+    assert!(
+        report.is_empty(),
+        "{} divergence(s) from the C++ implementations:\n{}",
+        report.len(),
+        report.join("\n")
+    );
+}
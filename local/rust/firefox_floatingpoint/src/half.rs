@@ -0,0 +1,218 @@
+//! Half-precision (IEEE-754 binary16) support.
+//!
+//! Rust has no stable `f16` type, so half values are handled as raw
+//! `u16` bit patterns: 1 sign bit, 5 exponent bits (bias 15), 10
+//! significand bits. The conversions here use round-to-nearest-even,
+//! matching hardware float conversion and the behavior canvas/WebGPU
+//! code expects.
+//!
+//! # Examples
+//!
+//! ```
+//! use firefox_floatingpoint::{f32_to_f16_bits, f16_bits_to_f32, is_float16_representable};
+//!
+//! assert_eq!(f32_to_f16_bits(1.0), 0x3C00);
+//! assert_eq!(f16_bits_to_f32(0x3C00), 1.0);
+//!
+//! assert!(is_float16_representable(2048.0));
+//! assert!(!is_float16_representable(2049.0)); // needs 12 significand bits
+//! ```
+
+use crate::is_float32_representable;
+
+/// Converts a float32 to half-precision bits, rounding to nearest even.
+///
+/// Overflow produces ±infinity (`0x7C00`/`0xFC00`); values below the
+/// smallest subnormal half round to ±0. NaN inputs stay NaN: the top
+/// significand bits carry over, and a significand bit is forced if the
+/// payload would otherwise vanish, so a NaN can never collapse to
+/// infinity.
+pub fn f32_to_f16_bits(value: f32) -> u16 {
+    let bits = value.to_bits();
+    let sign = ((bits >> 16) & 0x8000) as u16;
+    let exp = (bits >> 23) & 0xFF;
+    let mant = bits & 0x007F_FFFF;
+
+    if exp == 0xFF {
+        if mant == 0 {
+            return sign | 0x7C00;
+        }
+        let payload = (mant >> 13) as u16;
+        return sign | 0x7C00 | payload | u16::from(payload == 0);
+    }
+
+    if exp == 0 {
+        // Float32 zeros and subnormals are all far below half range
+        return sign;
+    }
+
+    let unbiased = exp as i32 - 127;
+
+    if unbiased > 15 {
+        // At least 2^16: beyond the largest finite half (65504)
+        return sign | 0x7C00;
+    }
+
+    if unbiased >= -14 {
+        // Normal half. Assemble first, then round: a carry out of the
+        // significand bumps the exponent field, which is exactly the
+        // right behavior (including overflow to infinity at 0x7C00).
+        let mut half = sign | (((unbiased + 15) as u16) << 10) | (mant >> 13) as u16;
+        let round = mant & 0x1FFF;
+        if round > 0x1000 || (round == 0x1000 && (half & 1) == 1) {
+            half += 1;
+        }
+        return half;
+    }
+
+    // Subnormal half: the significand (with its implicit bit made
+    // explicit) is shifted right until the exponent reaches -14. The
+    // smallest subnormal is 2^-24, so anything needing a shift past
+    // the whole significand rounds to zero.
+    let significand = mant | 0x0080_0000;
+    let shift = (-unbiased - 1) as u32;
+    if shift > 24 {
+        return sign;
+    }
+    let half_mant = significand >> shift;
+    let round = significand & ((1 << shift) - 1);
+    let halfway = 1 << (shift - 1);
+    let mut half = sign | half_mant as u16;
+    if round > halfway || (round == halfway && (half & 1) == 1) {
+        half += 1;
+    }
+    half
+}
+
+/// Converts half-precision bits to a float32.
+///
+/// Every half value is exactly representable in float32, so this
+/// conversion is lossless; NaN payloads shift into the top float32
+/// significand bits.
+pub fn f16_bits_to_f32(bits: u16) -> f32 {
+    let sign = ((bits & 0x8000) as u32) << 16;
+    let exp = (bits >> 10) & 0x1F;
+    let mant = (bits & 0x3FF) as u32;
+
+    if exp == 0x1F {
+        return f32::from_bits(sign | 0x7F80_0000 | (mant << 13));
+    }
+
+    if exp == 0 {
+        if mant == 0 {
+            return f32::from_bits(sign);
+        }
+        // Subnormal half: normalize. The MSB of the significand
+        // becomes the implicit bit of a normal float32.
+        let msb = 31 - mant.leading_zeros();
+        let exp_field = msb + 103; // (msb - 24) + 127
+        let significand = (mant << (23 - msb)) & 0x007F_FFFF;
+        return f32::from_bits(sign | (exp_field << 23) | significand);
+    }
+
+    f32::from_bits(sign | ((exp as u32 + 112) << 23) | (mant << 13))
+}
+
+/// Determines whether a double can be losslessly represented as a
+/// half-precision (float16) value.
+///
+/// The half-precision counterpart of
+/// [`is_float32_representable`](crate::is_float32_representable), with
+/// the same conventions: NaN and the infinities are representable,
+/// anything that would round — by range or by precision — is not.
+pub fn is_float16_representable(value: f64) -> bool {
+    if !value.is_finite() {
+        return true;
+    }
+    // Half values are a subset of float32 values, so anything that is
+    // not float32-representable cannot be a half; for the rest, the
+    // float32 round-trip through half is exact iff the value is a half.
+    if !is_float32_representable(value) {
+        return false;
+    }
+    let as_f32 = value as f32;
+    f16_bits_to_f32(f32_to_f16_bits(as_f32)) == as_f32
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_known_conversions() {
+        assert_eq!(f32_to_f16_bits(0.0), 0x0000);
+        assert_eq!(f32_to_f16_bits(-0.0), 0x8000);
+        assert_eq!(f32_to_f16_bits(1.0), 0x3C00);
+        assert_eq!(f32_to_f16_bits(-2.0), 0xC000);
+        assert_eq!(f32_to_f16_bits(65504.0), 0x7BFF); // largest finite half
+        assert_eq!(f32_to_f16_bits(f32::INFINITY), 0x7C00);
+        assert_eq!(f32_to_f16_bits(f32::NEG_INFINITY), 0xFC00);
+        assert_eq!(f32_to_f16_bits(2.0_f32.powi(-24)), 0x0001); // smallest subnormal
+        assert_eq!(f32_to_f16_bits(2.0_f32.powi(-14)), 0x0400); // smallest normal
+    }
+
+    #[test]
+    fn test_round_to_nearest_even() {
+        // 1 + 2^-11 is exactly halfway between 0x3C00 and 0x3C01:
+        // ties go to the even significand
+        assert_eq!(f32_to_f16_bits(1.0 + 2.0_f32.powi(-11)), 0x3C00);
+        // Halfway between 0x3C01 and 0x3C02 rounds up to even
+        assert_eq!(f32_to_f16_bits(1.0 + 3.0 * 2.0_f32.powi(-11)), 0x3C02);
+        // Just past halfway rounds up
+        assert_eq!(f32_to_f16_bits(1.0 + 2.0_f32.powi(-11) + 2.0_f32.powi(-20)), 0x3C01);
+
+        // 2^-25 is exactly halfway between 0 and the smallest
+        // subnormal: ties-to-even gives 0
+        assert_eq!(f32_to_f16_bits(2.0_f32.powi(-25)), 0x0000);
+        assert_eq!(f32_to_f16_bits(1.5 * 2.0_f32.powi(-25)), 0x0001);
+
+        // 65520 is exactly halfway between 65504 and 2^16: rounding up
+        // overflows to infinity
+        assert_eq!(f32_to_f16_bits(65520.0), 0x7C00);
+        assert_eq!(f32_to_f16_bits(65519.0), 0x7BFF);
+    }
+
+    #[test]
+    fn test_exhaustive_half_round_trip() {
+        // Every one of the 65536 half bit patterns — zeros, subnormals,
+        // normals, infinities, and every NaN payload — must survive the
+        // trip through float32 bit-exactly
+        for bits in 0..=u16::MAX {
+            let as_f32 = f16_bits_to_f32(bits);
+            assert_eq!(
+                f32_to_f16_bits(as_f32),
+                bits,
+                "half bits {:#06x} did not round-trip (f32 = {})",
+                bits,
+                as_f32
+            );
+        }
+    }
+
+    #[test]
+    fn test_is_float16_representable() {
+        assert!(is_float16_representable(0.0));
+        assert!(is_float16_representable(-0.0));
+        assert!(is_float16_representable(1.0));
+        assert!(is_float16_representable(0.5));
+        assert!(is_float16_representable(65504.0));
+        assert!(is_float16_representable(f64::NAN));
+        assert!(is_float16_representable(f64::INFINITY));
+        assert!(is_float16_representable(2.0_f64.powi(-24)));
+
+        // 11-bit integers fit, 12-bit ones do not
+        assert!(is_float16_representable(2048.0));
+        assert!(!is_float16_representable(2049.0));
+
+        // Range and precision failures
+        assert!(!is_float16_representable(65505.0));
+        assert!(!is_float16_representable(65536.0));
+        assert!(!is_float16_representable(0.1));
+        assert!(!is_float16_representable(2.0_f64.powi(-25)));
+
+        // Float32-representable but not half-representable
+        assert!(is_float32_representable(2049.0));
+        // Not even float32-representable
+        assert!(!is_float16_representable(std::f64::consts::PI));
+    }
+}
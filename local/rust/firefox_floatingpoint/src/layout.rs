@@ -0,0 +1,358 @@
+//! IEEE-754 bit-layout constants, generic over float width.
+//!
+//! This is the Rust counterpart of the C++ `mozilla::FloatingPoint<T>`
+//! traits struct from mfbt/FloatingPoint.h: one trait describing the
+//! sign/exponent/significand split of a format, implemented for `f32`
+//! and `f64`, so bit-inspection code can be written once instead of
+//! duplicated per type.
+//!
+//! # Examples
+//!
+//! ```
+//! use firefox_floatingpoint::FloatingPoint;
+//!
+//! assert_eq!(f64::EXPONENT_BIAS, 1023);
+//! assert_eq!(f32::EXPONENT_BIAS, 127);
+//!
+//! // Default methods work on both widths
+//! assert_eq!(10.0f32.exponent_component(), 3);
+//! assert_eq!(10.0f64.exponent_component(), 3);
+//! ```
+
+/// Bit-layout description of an IEEE-754 binary format.
+///
+/// The constants mirror the C++ `FloatingPoint<T>` members
+/// (`kExponentBias`, `kExponentShift`, `kSignBit`, `kExponentBits`,
+/// `kSignificandBits`). The raw bits are exposed as `u64` regardless of
+/// width — `f32` bits occupy the low 32 — so generic code needs no
+/// integer-type gymnastics.
+pub trait FloatingPoint: Copy {
+    /// The unsigned integer type with the same width as the format.
+    type Bits: Copy + PartialEq + Into<u64>;
+
+    /// Number of explicitly stored significand bits (52 for f64, 23 for f32).
+    const SIGNIFICAND_WIDTH: u32;
+
+    /// Number of exponent bits (11 for f64, 8 for f32).
+    const EXPONENT_WIDTH: u32;
+
+    /// Bias subtracted from the raw exponent field (1023 for f64, 127 for f32).
+    const EXPONENT_BIAS: i32;
+
+    /// Shift from bit 0 to the exponent field; equals [`Self::SIGNIFICAND_WIDTH`].
+    const EXPONENT_SHIFT: u32 = Self::SIGNIFICAND_WIDTH;
+
+    /// Mask selecting the sign bit.
+    const SIGN_BIT: u64 = 1 << (Self::EXPONENT_SHIFT + Self::EXPONENT_WIDTH);
+
+    /// Mask selecting the exponent field.
+    const EXPONENT_BITS: u64 = ((1 << Self::EXPONENT_WIDTH) - 1) << Self::EXPONENT_SHIFT;
+
+    /// Mask selecting the significand field.
+    const SIGNIFICAND_BITS: u64 = (1 << Self::SIGNIFICAND_WIDTH) - 1;
+
+    /// The raw bits of the value.
+    fn to_bits(self) -> Self::Bits;
+
+    /// Reconstructs a value from raw bits.
+    fn from_bits(bits: Self::Bits) -> Self;
+
+    /// Reconstructs a value from `u64` bits; the inverse of
+    /// [`bits_u64`](Self::bits_u64). Bits above the format's width are
+    /// ignored.
+    fn from_bits_u64(bits: u64) -> Self;
+
+    /// The raw bits widened to `u64`, for width-independent mask logic.
+    #[inline]
+    fn bits_u64(self) -> u64 {
+        self.to_bits().into()
+    }
+
+    /// True when the value is a NaN of this format, from bits alone.
+    #[inline]
+    fn is_nan_bits(self) -> bool {
+        let bits = self.bits_u64();
+        bits & Self::EXPONENT_BITS == Self::EXPONENT_BITS && bits & Self::SIGNIFICAND_BITS != 0
+    }
+
+    /// The unbiased exponent: the raw exponent field minus the bias,
+    /// with no significand normalization. See the f64-specific
+    /// [`exponent_component`](crate::exponent_component) for the
+    /// resulting edge-case values.
+    #[inline]
+    fn exponent_component(self) -> i32 {
+        ((self.bits_u64() & Self::EXPONENT_BITS) >> Self::EXPONENT_SHIFT) as i32
+            - Self::EXPONENT_BIAS
+    }
+
+    /// True for exactly -0 in this format.
+    #[inline]
+    fn is_negative_zero(self) -> bool {
+        self.bits_u64() == Self::SIGN_BIT
+    }
+
+    /// True for exactly +0 in this format.
+    #[inline]
+    fn is_positive_zero(self) -> bool {
+        self.bits_u64() == 0
+    }
+}
+
+impl FloatingPoint for f32 {
+    type Bits = u32;
+
+    const SIGNIFICAND_WIDTH: u32 = 23;
+    const EXPONENT_WIDTH: u32 = 8;
+    const EXPONENT_BIAS: i32 = 127;
+
+    #[inline]
+    fn to_bits(self) -> u32 {
+        self.to_bits()
+    }
+
+    #[inline]
+    fn from_bits(bits: u32) -> f32 {
+        f32::from_bits(bits)
+    }
+
+    #[inline]
+    fn from_bits_u64(bits: u64) -> f32 {
+        f32::from_bits(bits as u32)
+    }
+}
+
+impl FloatingPoint for f64 {
+    type Bits = u64;
+
+    const SIGNIFICAND_WIDTH: u32 = 52;
+    const EXPONENT_WIDTH: u32 = 11;
+    const EXPONENT_BIAS: i32 = 1023;
+
+    #[inline]
+    fn to_bits(self) -> u64 {
+        self.to_bits()
+    }
+
+    #[inline]
+    fn from_bits(bits: u64) -> f64 {
+        f64::from_bits(bits)
+    }
+
+    #[inline]
+    fn from_bits_u64(bits: u64) -> f64 {
+        f64::from_bits(bits)
+    }
+}
+
+/// Maps bits into a space where unsigned order matches numeric order:
+/// positives get the sign bit set, negatives are complemented. -0 and
+/// +0 land adjacent, so they are one ULP apart, not zero.
+fn ordered_bits<T: FloatingPoint>(value: T) -> u64 {
+    let width_mask = T::SIGN_BIT | T::EXPONENT_BITS | T::SIGNIFICAND_BITS;
+    let bits = value.bits_u64();
+    if bits & T::SIGN_BIT != 0 {
+        !bits & width_mask
+    } else {
+        bits | T::SIGN_BIT
+    }
+}
+
+/// Counts the representable values strictly between `a` and `b`, plus
+/// one — i.e. how many [`next_up`] steps separate them.
+///
+/// Works across zero (the distance from -0 to +0 is 1) and up to the
+/// infinities. Returns `None` if either value is NaN. Useful for fuzzy
+/// comparisons with a bounded-ULP tolerance and for precise tests of
+/// the other floating-point ports.
+///
+/// # Examples
+///
+/// ```
+/// use firefox_floatingpoint::ulp_distance;
+///
+/// assert_eq!(ulp_distance(1.0f64, 1.0), Some(0));
+/// assert_eq!(ulp_distance(1.0f64, 1.0 + f64::EPSILON), Some(1));
+/// assert_eq!(ulp_distance(-0.0f64, 0.0), Some(1));
+/// assert_eq!(ulp_distance(f64::NAN, 1.0), None);
+/// ```
+pub fn ulp_distance<T: FloatingPoint>(a: T, b: T) -> Option<u64> {
+    if a.is_nan_bits() || b.is_nan_bits() {
+        return None;
+    }
+    Some(ordered_bits(a).abs_diff(ordered_bits(b)))
+}
+
+/// The smallest representable value greater than the input.
+///
+/// IEEE-754 `nextUp`: NaN and +∞ return themselves, -0 steps to the
+/// smallest positive subnormal, the largest finite value steps to +∞.
+///
+/// # Examples
+///
+/// ```
+/// use firefox_floatingpoint::next_up;
+///
+/// assert_eq!(next_up(1.0f64), 1.0 + f64::EPSILON);
+/// assert_eq!(next_up(f64::MAX), f64::INFINITY);
+/// ```
+pub fn next_up<T: FloatingPoint>(value: T) -> T {
+    let bits = value.bits_u64();
+    if value.is_nan_bits() || bits == T::EXPONENT_BITS {
+        // NaN, or already +∞
+        return value;
+    }
+    let next = if bits == T::SIGN_BIT {
+        // -0 steps over +0 to the smallest positive subnormal
+        1
+    } else if bits & T::SIGN_BIT != 0 {
+        bits - 1
+    } else {
+        bits + 1
+    };
+    T::from_bits_u64(next)
+}
+
+/// The largest representable value smaller than the input.
+///
+/// IEEE-754 `nextDown`; the mirror of [`next_up`].
+pub fn next_down<T: FloatingPoint>(value: T) -> T {
+    let bits = value.bits_u64();
+    if value.is_nan_bits() || bits == T::SIGN_BIT | T::EXPONENT_BITS {
+        // NaN, or already -∞
+        return value;
+    }
+    let next = if bits == 0 {
+        // +0 steps over -0 to the smallest negative subnormal
+        T::SIGN_BIT | 1
+    } else if bits & T::SIGN_BIT != 0 {
+        bits + 1
+    } else {
+        bits - 1
+    };
+    T::from_bits_u64(next)
+}
+
+// The three masks must partition the format's bits exactly, with no
+// overlap and no gaps — checked at compile time, like the C++
+// static_asserts in the traits struct.
+const _: () = {
+    assert!(
+        <f64 as FloatingPoint>::SIGN_BIT
+            ^ <f64 as FloatingPoint>::EXPONENT_BITS
+            ^ <f64 as FloatingPoint>::SIGNIFICAND_BITS
+            == u64::MAX
+    );
+    assert!(
+        <f32 as FloatingPoint>::SIGN_BIT
+            ^ <f32 as FloatingPoint>::EXPONENT_BITS
+            ^ <f32 as FloatingPoint>::SIGNIFICAND_BITS
+            == u32::MAX as u64
+    );
+};
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_f64_layout_constants() {
+        assert_eq!(<f64 as FloatingPoint>::SIGN_BIT, 0x8000_0000_0000_0000);
+        assert_eq!(<f64 as FloatingPoint>::EXPONENT_BITS, 0x7FF0_0000_0000_0000);
+        assert_eq!(
+            <f64 as FloatingPoint>::SIGNIFICAND_BITS,
+            0x000F_FFFF_FFFF_FFFF
+        );
+        assert_eq!(<f64 as FloatingPoint>::EXPONENT_SHIFT, 52);
+    }
+
+    #[test]
+    fn test_f32_layout_constants() {
+        assert_eq!(<f32 as FloatingPoint>::SIGN_BIT, 0x8000_0000);
+        assert_eq!(<f32 as FloatingPoint>::EXPONENT_BITS, 0x7F80_0000);
+        assert_eq!(<f32 as FloatingPoint>::SIGNIFICAND_BITS, 0x007F_FFFF);
+        assert_eq!(<f32 as FloatingPoint>::EXPONENT_SHIFT, 23);
+    }
+
+    #[test]
+    fn test_generic_methods_agree_with_f64_functions() {
+        for value in [0.0, -0.0, 1.0, -1.0, 10.0, f64::NAN, f64::INFINITY] {
+            assert_eq!(value.exponent_component(), crate::exponent_component(value));
+            assert_eq!(
+                FloatingPoint::is_negative_zero(value),
+                crate::is_negative_zero(value)
+            );
+            assert_eq!(
+                FloatingPoint::is_positive_zero(value),
+                crate::is_positive_zero(value)
+            );
+        }
+    }
+
+    #[test]
+    fn test_f32_methods() {
+        assert_eq!(1.0f32.exponent_component(), 0);
+        assert_eq!(0.5f32.exponent_component(), -1);
+        assert_eq!(0.0f32.exponent_component(), -127);
+        assert_eq!(f32::INFINITY.exponent_component(), 128);
+        assert!((-0.0f32).is_negative_zero());
+        assert!(0.0f32.is_positive_zero());
+        assert!(!1.0f32.is_negative_zero());
+    }
+
+    #[test]
+    fn test_ulp_distance() {
+        assert_eq!(ulp_distance(1.0f64, 1.0), Some(0));
+        assert_eq!(ulp_distance(1.0f64, 1.0 + f64::EPSILON), Some(1));
+        assert_eq!(ulp_distance(1.0 + f64::EPSILON, 1.0f64), Some(1));
+        assert_eq!(ulp_distance(-0.0f64, 0.0), Some(1));
+        assert_eq!(ulp_distance(0.0f64, 0.0), Some(0));
+
+        // Crossing zero counts both sides
+        let tiny = f64::from_bits(3); // third positive subnormal
+        assert_eq!(ulp_distance(-tiny, tiny), Some(7));
+
+        // f32 distances use f32 ULPs
+        assert_eq!(ulp_distance(1.0f32, 1.0 + f32::EPSILON), Some(1));
+        assert_eq!(ulp_distance(f32::MAX, f32::INFINITY), Some(1));
+
+        assert_eq!(ulp_distance(f64::NAN, 1.0), None);
+        assert_eq!(ulp_distance(1.0, f64::NAN), None);
+    }
+
+    #[test]
+    fn test_next_up_next_down() {
+        assert_eq!(next_up(1.0f64), 1.0 + f64::EPSILON);
+        assert_eq!(next_down(1.0 + f64::EPSILON), 1.0f64);
+        assert_eq!(next_up(f64::MAX), f64::INFINITY);
+        assert_eq!(next_down(f64::MIN), f64::NEG_INFINITY);
+        assert_eq!(next_up(f64::INFINITY), f64::INFINITY);
+        assert_eq!(next_down(f64::NEG_INFINITY), f64::NEG_INFINITY);
+        assert!(next_up(f64::NAN).is_nan_bits());
+
+        // The zero crossing: -0 → smallest positive subnormal,
+        // +0 → smallest negative subnormal
+        assert_eq!(next_up(-0.0f64).to_bits(), 1);
+        assert_eq!(next_down(0.0f64).to_bits(), 0x8000_0000_0000_0001);
+        assert_eq!(next_up(-f64::from_bits(1)).to_bits(), (-0.0f64).to_bits());
+
+        // next_up and next_down invert each other away from zero
+        for value in [1.5f64, -1.5, f64::MIN_POSITIVE, 1e300] {
+            assert_eq!(next_down(next_up(value)), value);
+            assert_eq!(next_up(next_down(value)), value);
+        }
+
+        // And every step is exactly one ULP
+        assert_eq!(ulp_distance(1.5f32, next_up(1.5f32)), Some(1));
+        assert_eq!(ulp_distance(-1.5f64, next_down(-1.5f64)), Some(1));
+    }
+
+    #[test]
+    fn test_bits_round_trip() {
+        fn round_trips<T: FloatingPoint + PartialEq>(value: T) -> bool {
+            T::from_bits(value.to_bits()) == value
+        }
+        assert!(round_trips(1.5f32));
+        assert!(round_trips(1.5f64));
+        assert!(round_trips(f64::MAX));
+    }
+}
@@ -10,9 +10,14 @@
 //! The function implements IEEE-754 representability checking:
 //! 1. NaN and ±∞ values are representable (same in float32 and float64)
 //! 2. Values exceeding FLT_MAX are not representable
-//! 3. For finite values, a round-trip conversion test detects precision loss:
-//!    - If value is exactly representable, round-trip preserves it
-//!    - If value is between two adjacent float32 values, round-trip changes it
+//! 3. For finite values, the float32 format must hold the value exactly:
+//!    the exponent must be in range and no dropped significand bit may
+//!    be set
+//!
+//! Two interchangeable implementations exist — the original float
+//! round-trip port ([`is_float32_representable_roundtrip`]) and a
+//! bit-classification form ([`is_float32_representable_bits`]) — tested
+//! to agree on every input; the faster bit-based one is the default.
 //!
 //! # Examples
 //!
@@ -36,7 +41,16 @@
 //! ```
 
 // FFI layer for C++ interoperability
+pub mod batch;
 pub mod ffi;
+#[cfg(all(test, feature = "gecko-differential"))]
+mod gecko_differential;
+pub mod half;
+pub mod layout;
+
+pub use batch::{count_non_float32_representable, non_float32_representable_mask};
+pub use half::{f16_bits_to_f32, f32_to_f16_bits, is_float16_representable};
+pub use layout::{next_down, next_up, ulp_distance, FloatingPoint};
 
 /// Determines whether a double-precision value can be losslessly represented as float32.
 ///
@@ -75,7 +89,21 @@ pub mod ffi;
 /// assert!(!is_float32_representable(f64::from(f32::MAX) * 2.0)); // overflow
 /// ```
 #[inline]
-pub fn is_float32_representable(value: f64) -> bool {
+pub const fn is_float32_representable(value: f64) -> bool {
+    // The bit-classification form measured faster than the original
+    // float round-trip (kept as is_float32_representable_roundtrip and
+    // differentially tested against this one), so it is the default.
+    is_float32_representable_bits(value)
+}
+
+/// Round-trip implementation of [`is_float32_representable`].
+///
+/// The direct port of the C++ algorithm: cast to float32 and back, and
+/// compare. Retained as an independent cross-check of the bit-based
+/// default — the two are verified to agree in the differential tests —
+/// and for the benchmark comparison.
+#[inline]
+pub fn is_float32_representable_roundtrip(value: f64) -> bool {
     // Step 1: NaN and infinities are representable in both formats
     // IEEE-754 guarantees compatible NaN and ±∞ representations
     if !value.is_finite() {
@@ -105,10 +133,807 @@ pub fn is_float32_representable(value: f64) -> bool {
     round_trip == value
 }
 
+/// Bit-manipulation implementation of [`is_float32_representable`].
+///
+/// Classifies the value purely from its bit pattern: the unbiased
+/// exponent must lie in float32's range, and the significand bits that
+/// float32 would drop — 29 for normal targets, more as the value
+/// descends into float32's subnormal range — must all be zero. No
+/// float round-trip is performed.
+///
+/// The two implementations agree on every input (see the differential
+/// tests). This form benchmarked faster overall than the cast-based
+/// [`is_float32_representable_roundtrip`] — decisively so on mixed
+/// input, where the early exits fire — so [`is_float32_representable`]
+/// delegates here. It also performs no float operations, useful for
+/// code audited for FP exceptions, and is usable in const contexts.
+///
+/// # Examples
+///
+/// ```
+/// use firefox_floatingpoint::is_float32_representable_bits;
+///
+/// assert!(is_float32_representable_bits(2.5));
+/// assert!(!is_float32_representable_bits(2147483647.0));
+/// ```
+#[inline]
+pub const fn is_float32_representable_bits(value: f64) -> bool {
+    let bits = value.to_bits();
+    let exp_field = ((bits & <f64 as FloatingPoint>::EXPONENT_BITS)
+        >> <f64 as FloatingPoint>::EXPONENT_SHIFT) as i32;
+    let mant = bits & <f64 as FloatingPoint>::SIGNIFICAND_BITS;
+
+    // NaN and the infinities are representable
+    if exp_field == 0x7FF {
+        return true;
+    }
+    // ±0 is representable; nonzero double subnormals (below 2^-1022)
+    // are far beneath float32's smallest subnormal
+    if exp_field == 0 {
+        return mant == 0;
+    }
+
+    let exponent = exp_field - <f64 as FloatingPoint>::EXPONENT_BIAS;
+    // Above float32's exponent range. The e == 127 boundary needs no
+    // special casing against f32::MAX: any e == 127 value passing the
+    // precision check below is <= f32::MAX by construction.
+    if exponent > 127 {
+        return false;
+    }
+    // Below even float32's subnormal range
+    if exponent < -149 {
+        return false;
+    }
+
+    // Normal float32 targets keep 24 significand bits, dropping 29 of
+    // the double's 52. Subnormal targets lose one more bit of
+    // precision per exponent step below -126.
+    let dropped = if exponent >= -126 {
+        29
+    } else {
+        29 + (-126 - exponent)
+    };
+    mant & ((1u64 << dropped) - 1) == 0
+}
+
+/// Determines whether a double is exactly an int32 value.
+///
+/// Matches `mozilla::NumberIsInt32`: the value must be a whole number in
+/// `i32` range, and negative zero does NOT qualify — `-0.0` is a
+/// distinct double even though it compares equal to `0.0`. Use
+/// [`number_equals_int32`] when -0 should count as 0.
+///
+/// # Returns
+///
+/// `Some(i)` with the exact integer value, or `None` for NaN,
+/// infinities, -0, fractional values, and out-of-range values.
+///
+/// # Examples
+///
+/// ```
+/// use firefox_floatingpoint::number_is_int32;
+///
+/// assert_eq!(number_is_int32(42.0), Some(42));
+/// assert_eq!(number_is_int32(-0.0), None);
+/// assert_eq!(number_is_int32(2147483648.0), None); // INT32_MAX + 1
+/// ```
+#[inline]
+pub fn number_is_int32(value: f64) -> Option<i32> {
+    if is_negative_zero(value) {
+        return None;
+    }
+    number_equals_int32(value)
+}
+
+/// Determines whether a double compares equal to an int32 value.
+///
+/// Matches `mozilla::NumberEqualsInt32`: like [`number_is_int32`], but
+/// `-0.0` counts as 0 because the two compare equal under IEEE-754.
+///
+/// # Examples
+///
+/// ```
+/// use firefox_floatingpoint::number_equals_int32;
+///
+/// assert_eq!(number_equals_int32(-0.0), Some(0));
+/// assert_eq!(number_equals_int32(1.5), None);
+/// ```
+#[inline]
+pub fn number_equals_int32(value: f64) -> Option<i32> {
+    // The cast saturates out-of-range values (and maps NaN to 0), so
+    // converting back and comparing rejects everything that is not an
+    // exact in-range integer
+    let as_int = value as i32;
+    if as_int as f64 == value {
+        Some(as_int)
+    } else {
+        None
+    }
+}
+
+/// Determines whether a double is exactly an int64 value.
+///
+/// The int64 counterpart of [`number_is_int32`]; -0 does not qualify.
+/// Note that near the top of the range whole doubles are spaced more
+/// than 1 apart, so an in-range whole double may still map to an `i64`
+/// no other double maps to.
+#[inline]
+pub fn number_is_int64(value: f64) -> Option<i64> {
+    if is_negative_zero(value) {
+        return None;
+    }
+    // The round-trip comparison used for int32 is unsound here: both
+    // 2^63 and i64::MAX round to the same double, so saturation would
+    // falsely accept 2^63. Bound the range explicitly first — both
+    // bounds are exactly representable.
+    if value < -(2f64.powi(63)) || value >= 2f64.powi(63) {
+        return None;
+    }
+    let as_int = value as i64;
+    if as_int as f64 == value {
+        Some(as_int)
+    } else {
+        None
+    }
+}
+
+/// Converts a double to an int32 with ECMAScript modular semantics.
+///
+/// The same algorithm as `js::ToInt32` (ECMA-262 ToInt32): NaN,
+/// infinities, and values with no bits below 2^32 map to 0; everything
+/// else is truncated toward zero and reduced modulo 2^32 into int32
+/// range. Unlike a saturating cast, out-of-range values wrap.
+///
+/// Implemented by bit manipulation — the significand is shifted into
+/// place by the exponent — so it is exact and usable in const
+/// contexts.
+///
+/// # Examples
+///
+/// ```
+/// use firefox_floatingpoint::to_int32;
+///
+/// assert_eq!(to_int32(-1.5), -1);
+/// assert_eq!(to_int32(2147483648.0), i32::MIN); // 2^31 wraps
+/// assert_eq!(to_int32(4294967296.0), 0);        // 2^32 wraps to 0
+/// assert_eq!(to_int32(f64::NAN), 0);
+/// ```
+#[inline]
+pub const fn to_int32(value: f64) -> i32 {
+    let bits = value.to_bits();
+    let exp_field = ((bits & <f64 as FloatingPoint>::EXPONENT_BITS)
+        >> <f64 as FloatingPoint>::EXPONENT_SHIFT) as i32;
+
+    // |value| < 1 (including zeros and subnormals) truncates to 0
+    if exp_field < <f64 as FloatingPoint>::EXPONENT_BIAS {
+        return 0;
+    }
+    let exponent = exp_field - <f64 as FloatingPoint>::EXPONENT_BIAS;
+    // The significand spans bit positions exponent-52 ..= exponent of
+    // the value. Once the lowest is at 2^32 or above, the residue mod
+    // 2^32 is zero; this also disposes of NaN and the infinities
+    // (exponent 1024).
+    if exponent >= 84 {
+        return 0;
+    }
+
+    let significand = (bits & <f64 as FloatingPoint>::SIGNIFICAND_BITS) | (1 << 52);
+    // Shift the significand so bit 0 holds the value's 2^0 bit,
+    // truncating any fraction. Left shifts stay below 32, so the low
+    // 32 bits we keep are exact.
+    let aligned = if exponent >= 52 {
+        significand << (exponent - 52)
+    } else {
+        significand >> (52 - exponent)
+    };
+    let low = aligned as u32 as i32;
+    // Modular negation of the low 32 bits matches truncating the
+    // negative value and reducing mod 2^32
+    if (bits & <f64 as FloatingPoint>::SIGN_BIT) != 0 {
+        low.wrapping_neg()
+    } else {
+        low
+    }
+}
+
+/// Converts a double to a uint32 with ECMAScript modular semantics.
+///
+/// ECMA-262 ToUint32; identical bit result to [`to_int32`]
+/// reinterpreted as unsigned.
+///
+/// # Examples
+///
+/// ```
+/// use firefox_floatingpoint::to_uint32;
+///
+/// assert_eq!(to_uint32(-1.0), u32::MAX);
+/// assert_eq!(to_uint32(4294967297.0), 1);
+/// ```
+#[inline]
+pub const fn to_uint32(value: f64) -> u32 {
+    to_int32(value) as u32
+}
+
+/// Returns the value, or 0 if it is NaN or infinite.
+///
+/// Matches `mozilla::ToZeroIfNonfinite`, used by JS value conversions
+/// where nonfinite inputs collapse to +0. The sign of a finite value —
+/// including -0 — is preserved.
+///
+/// # Examples
+///
+/// ```
+/// use firefox_floatingpoint::to_zero_if_nonfinite;
+///
+/// assert_eq!(to_zero_if_nonfinite(3.5), 3.5);
+/// assert_eq!(to_zero_if_nonfinite(f64::INFINITY), 0.0);
+/// assert_eq!(to_zero_if_nonfinite(f64::NAN), 0.0);
+/// ```
+#[inline]
+pub fn to_zero_if_nonfinite(value: f64) -> f64 {
+    if value.is_finite() {
+        value
+    } else {
+        0.0
+    }
+}
+
+/// Returns the unbiased exponent of a double.
+///
+/// Matches `mozilla::ExponentComponent`: the raw exponent field minus
+/// the bias (1023), with no significand normalization. Consequently:
+///
+/// - Zero and denormals return `-1023` (the minimum)
+/// - Infinities and NaN return `1024` (the maximum)
+/// - Normal values return the true binary exponent, e.g. 1.0 → 0,
+///   10.0 → 3
+///
+/// Usable in const contexts.
+///
+/// # Examples
+///
+/// ```
+/// use firefox_floatingpoint::exponent_component;
+///
+/// assert_eq!(exponent_component(1.0), 0);
+/// assert_eq!(exponent_component(10.0), 3);
+/// assert_eq!(exponent_component(0.0), -1023);
+/// assert_eq!(exponent_component(f64::INFINITY), 1024);
+/// ```
+#[inline]
+pub const fn exponent_component(value: f64) -> i32 {
+    ((value.to_bits() & <f64 as FloatingPoint>::EXPONENT_BITS)
+        >> <f64 as FloatingPoint>::EXPONENT_SHIFT) as i32
+        - <f64 as FloatingPoint>::EXPONENT_BIAS
+}
+
+/// Returns true for exactly `-0.0`.
+///
+/// Matches `mozilla::IsNegativeZero`. Bit inspection is required
+/// because -0 compares equal to +0; only the sign bit distinguishes
+/// them. Usable in const contexts.
+///
+/// # Examples
+///
+/// ```
+/// use firefox_floatingpoint::is_negative_zero;
+///
+/// assert!(is_negative_zero(-0.0));
+/// assert!(!is_negative_zero(0.0));
+/// ```
+#[inline]
+pub const fn is_negative_zero(value: f64) -> bool {
+    value.to_bits() == (-0.0f64).to_bits()
+}
+
+/// Returns true for exactly `+0.0`.
+///
+/// Matches `mozilla::IsPositiveZero`; the counterpart of
+/// [`is_negative_zero`]. Usable in const contexts.
+#[inline]
+pub const fn is_positive_zero(value: f64) -> bool {
+    value.to_bits() == 0
+}
+
+/// Default epsilon for [`fuzzy_equals_additive`] and
+/// [`fuzzy_equals_multiplicative`] on doubles, matching the C++
+/// `detail::FuzzyEqualsEpsilon<double>` (2^-40).
+pub const FUZZY_EQUALS_EPSILON_F64: f64 = 1.0 / (1u64 << 40) as f64;
+
+/// Default fuzzy-equals epsilon for floats, matching
+/// `detail::FuzzyEqualsEpsilon<float>` (2^-17).
+pub const FUZZY_EQUALS_EPSILON_F32: f32 = 1.0 / (1u32 << 17) as f32;
+
+/// Constructs a double NaN with the given sign and significand bits.
+///
+/// Matches `mozilla::SpecificNaN<double>`, used where NaN encodings
+/// must be exact (e.g. JS value boxing). The significand must fit in
+/// the 52 significand bits and must be nonzero — an all-zero
+/// significand would encode infinity — and violations panic, mirroring
+/// the C++ assertions.
+///
+/// # Examples
+///
+/// ```
+/// use firefox_floatingpoint::specific_nan_f64;
+///
+/// let nan = specific_nan_f64(true, 0x8000_0000_0000);
+/// assert!(nan.is_nan());
+/// assert!(nan.is_sign_negative());
+/// ```
+#[inline]
+pub const fn specific_nan_f64(negative: bool, significand: u64) -> f64 {
+    assert!(
+        significand & !<f64 as FloatingPoint>::SIGNIFICAND_BITS == 0,
+        "significand must fit in 52 bits"
+    );
+    assert!(significand != 0, "a zero significand would encode infinity");
+    let sign = if negative {
+        <f64 as FloatingPoint>::SIGN_BIT
+    } else {
+        0
+    };
+    f64::from_bits(sign | <f64 as FloatingPoint>::EXPONENT_BITS | significand)
+}
+
+/// Constructs a float NaN with the given sign and significand bits.
+///
+/// The float32 counterpart of [`specific_nan_f64`]; the significand
+/// must fit in 23 bits and be nonzero.
+#[inline]
+pub const fn specific_nan_f32(negative: bool, significand: u32) -> f32 {
+    assert!(
+        significand as u64 & !<f32 as FloatingPoint>::SIGNIFICAND_BITS == 0,
+        "significand must fit in 23 bits"
+    );
+    assert!(significand != 0, "a zero significand would encode infinity");
+    let sign = if negative {
+        <f32 as FloatingPoint>::SIGN_BIT as u32
+    } else {
+        0
+    };
+    f32::from_bits(sign | <f32 as FloatingPoint>::EXPONENT_BITS as u32 | significand)
+}
+
+/// Determines whether an integer survives a round trip through f64.
+///
+/// Generic over any integer that widens to `i128`, covering both
+/// `i64` and `u64` (and the smaller widths, which always pass). The
+/// complement of [`is_float32_representable`] for the integer
+/// direction: telemetry and IPC serialization use it to validate that
+/// a count or identifier can travel in a double field losslessly.
+///
+/// # Examples
+///
+/// ```
+/// use firefox_floatingpoint::is_exactly_representable_as_f64;
+///
+/// assert!(is_exactly_representable_as_f64(1i64 << 53));
+/// assert!(!is_exactly_representable_as_f64((1i64 << 53) + 1));
+/// assert!(!is_exactly_representable_as_f64(u64::MAX));
+/// ```
+#[inline]
+pub fn is_exactly_representable_as_f64<T: Copy + Into<i128>>(value: T) -> bool {
+    let wide: i128 = value.into();
+    // The cast rounds to nearest; casting back is exact for anything
+    // in i64/u64 range (far from i128's saturation bounds), so the
+    // round trip detects any rounding
+    let as_f64 = wide as f64;
+    as_f64 as i128 == wide
+}
+
+/// Determines whether an integer survives a round trip through f32.
+///
+/// The float32 counterpart of [`is_exactly_representable_as_f64`]:
+/// only 24 significand bits are available, so e.g. 2^24 + 1 fails.
+///
+/// # Examples
+///
+/// ```
+/// use firefox_floatingpoint::is_exactly_representable_as_f32;
+///
+/// assert!(is_exactly_representable_as_f32(1i64 << 24));
+/// assert!(!is_exactly_representable_as_f32((1i64 << 24) + 1));
+/// ```
+#[inline]
+pub fn is_exactly_representable_as_f32<T: Copy + Into<i128>>(value: T) -> bool {
+    let wide: i128 = value.into();
+    let as_f32 = wide as f32;
+    as_f32 as i128 == wide
+}
+
+/// Returns the double NaN mfbt uses when no particular NaN is needed.
+///
+/// Matches `mozilla::UnspecifiedNaN<double>`: sign bit clear, every
+/// significand bit set (bits `0x7FFF_FFFF_FFFF_FFFF`). Code that ships
+/// NaN across FFI or serialization compares against exactly this
+/// pattern, so it is pinned by compile-time assertions below.
+///
+/// # Examples
+///
+/// ```
+/// use firefox_floatingpoint::unspecified_nan_f64;
+///
+/// assert!(unspecified_nan_f64().is_nan());
+/// assert_eq!(unspecified_nan_f64().to_bits(), 0x7FFF_FFFF_FFFF_FFFF);
+/// ```
+#[inline]
+pub const fn unspecified_nan_f64() -> f64 {
+    specific_nan_f64(false, <f64 as FloatingPoint>::SIGNIFICAND_BITS)
+}
+
+/// Returns the float NaN mfbt uses when no particular NaN is needed.
+///
+/// Matches `mozilla::UnspecifiedNaN<float>`: bits `0x7FFF_FFFF`.
+#[inline]
+pub const fn unspecified_nan_f32() -> f32 {
+    specific_nan_f32(false, <f32 as FloatingPoint>::SIGNIFICAND_BITS as u32)
+}
+
+/// Returns true when the value is bit-identical to
+/// [`unspecified_nan_f64`].
+///
+/// An ordinary `is_nan()` accepts every NaN encoding; consumers that
+/// normalize NaN across FFI need to know whether a value carries the
+/// canonical pattern or a payload that must be preserved.
+#[inline]
+pub const fn is_canonical_nan_f64(value: f64) -> bool {
+    value.to_bits() == unspecified_nan_f64().to_bits()
+}
+
+/// Float32 counterpart of [`is_canonical_nan_f64`].
+#[inline]
+pub const fn is_canonical_nan_f32(value: f32) -> bool {
+    value.to_bits() == unspecified_nan_f32().to_bits()
+}
+
+// The canonical patterns are load-bearing for FFI consumers: pin the
+// exact bits at compile time.
+const _: () = {
+    assert!(unspecified_nan_f64().to_bits() == 0x7FFF_FFFF_FFFF_FFFF);
+    assert!(unspecified_nan_f32().to_bits() == 0x7FFF_FFFF);
+    assert!(is_canonical_nan_f64(unspecified_nan_f64()));
+    assert!(is_canonical_nan_f32(unspecified_nan_f32()));
+};
+
+/// Compares two doubles for absolute-error equality.
+///
+/// Matches `mozilla::FuzzyEqualsAdditive`: true when
+/// `|a - b| <= epsilon`. Appropriate when the inputs' magnitudes are
+/// near 1; pass [`FUZZY_EQUALS_EPSILON_F64`] for the C++ default.
+/// NaN compares unequal to everything.
+#[inline]
+pub fn fuzzy_equals_additive(a: f64, b: f64, epsilon: f64) -> bool {
+    (a - b).abs() <= epsilon
+}
+
+/// Compares two doubles for relative-error equality.
+///
+/// Matches `mozilla::FuzzyEqualsMultiplicative`: true when
+/// `|a - b| <= epsilon * min(|a|, |b|)`. Scale-independent, but never
+/// true for a zero compared against a nonzero value; pass
+/// [`FUZZY_EQUALS_EPSILON_F64`] for the C++ default.
+#[inline]
+pub fn fuzzy_equals_multiplicative(a: f64, b: f64, epsilon: f64) -> bool {
+    let smaller = if a.abs() < b.abs() { a.abs() } else { b.abs() };
+    (a - b).abs() <= epsilon * smaller
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_number_is_int32() {
+        assert_eq!(number_is_int32(0.0), Some(0));
+        assert_eq!(number_is_int32(42.0), Some(42));
+        assert_eq!(number_is_int32(-42.0), Some(-42));
+        assert_eq!(number_is_int32(2147483647.0), Some(i32::MAX));
+        assert_eq!(number_is_int32(-2147483648.0), Some(i32::MIN));
+
+        // -0 is a distinct double and does not qualify
+        assert_eq!(number_is_int32(-0.0), None);
+
+        // Fractions, out-of-range, and nonfinite values
+        assert_eq!(number_is_int32(1.5), None);
+        assert_eq!(number_is_int32(2147483648.0), None);
+        assert_eq!(number_is_int32(-2147483649.0), None);
+        assert_eq!(number_is_int32(f64::NAN), None);
+        assert_eq!(number_is_int32(f64::INFINITY), None);
+        assert_eq!(number_is_int32(f64::NEG_INFINITY), None);
+    }
+
+    #[test]
+    fn test_number_equals_int32() {
+        // The only difference from number_is_int32: -0 counts as 0
+        assert_eq!(number_equals_int32(-0.0), Some(0));
+        assert_eq!(number_equals_int32(0.0), Some(0));
+        assert_eq!(number_equals_int32(7.0), Some(7));
+        assert_eq!(number_equals_int32(7.5), None);
+        assert_eq!(number_equals_int32(f64::NAN), None);
+    }
+
+    #[test]
+    fn test_number_is_int64() {
+        assert_eq!(number_is_int64(0.0), Some(0));
+        assert_eq!(number_is_int64(-0.0), None);
+        assert_eq!(number_is_int64(4294967296.0), Some(1i64 << 32));
+        assert_eq!(number_is_int64(-(2f64.powi(63))), Some(i64::MIN));
+
+        // 2^63 rounds to the same double as i64::MAX; it must be rejected,
+        // not saturated into range
+        assert_eq!(number_is_int64(2f64.powi(63)), None);
+        // The largest whole double below 2^63 is accepted exactly
+        let below = 2f64.powi(63) - 2048.0;
+        assert_eq!(number_is_int64(below), Some(below as i64));
+
+        assert_eq!(number_is_int64(0.5), None);
+        assert_eq!(number_is_int64(f64::NAN), None);
+        assert_eq!(number_is_int64(f64::INFINITY), None);
+    }
+
+    #[test]
+    fn test_exponent_component() {
+        assert_eq!(exponent_component(1.0), 0);
+        assert_eq!(exponent_component(-1.0), 0);
+        assert_eq!(exponent_component(2.0), 1);
+        assert_eq!(exponent_component(0.5), -1);
+        assert_eq!(exponent_component(10.0), 3);
+        assert_eq!(exponent_component(2f64.powi(100)), 100);
+        assert_eq!(exponent_component(2f64.powi(-100)), -100);
+
+        // Zero and denormals report the minimum raw exponent
+        assert_eq!(exponent_component(0.0), -1023);
+        assert_eq!(exponent_component(-0.0), -1023);
+        assert_eq!(exponent_component(f64::MIN_POSITIVE / 2.0), -1023);
+
+        // Nonfinite values report the maximum
+        assert_eq!(exponent_component(f64::INFINITY), 1024);
+        assert_eq!(exponent_component(f64::NEG_INFINITY), 1024);
+        assert_eq!(exponent_component(f64::NAN), 1024);
+
+        // Largest and smallest normal exponents
+        assert_eq!(exponent_component(f64::MAX), 1023);
+        assert_eq!(exponent_component(f64::MIN_POSITIVE), -1022);
+    }
+
+    #[test]
+    fn test_signed_zero_predicates() {
+        assert!(is_negative_zero(-0.0));
+        assert!(!is_negative_zero(0.0));
+        assert!(is_positive_zero(0.0));
+        assert!(!is_positive_zero(-0.0));
+
+        // Nonzero values, including ones that compare equal to nothing
+        for value in [1.0, -1.0, f64::MIN_POSITIVE, f64::NAN, f64::INFINITY] {
+            assert!(!is_negative_zero(value));
+            assert!(!is_positive_zero(value));
+        }
+    }
+
+    // All three bit-inspection helpers are usable in const contexts;
+    // these assertions are checked at compile time.
+    const _: () = assert!(exponent_component(8.0) == 3);
+    const _: () = assert!(is_negative_zero(-0.0));
+    const _: () = assert!(is_positive_zero(0.0));
+
+    #[test]
+    fn test_to_zero_if_nonfinite() {
+        assert_eq!(to_zero_if_nonfinite(3.5), 3.5);
+        assert_eq!(to_zero_if_nonfinite(-3.5), -3.5);
+        assert_eq!(to_zero_if_nonfinite(f64::NAN), 0.0);
+        assert_eq!(to_zero_if_nonfinite(f64::INFINITY), 0.0);
+        assert_eq!(to_zero_if_nonfinite(f64::NEG_INFINITY), 0.0);
+
+        // Finite -0 passes through with its sign bit intact
+        assert_eq!(to_zero_if_nonfinite(-0.0).to_bits(), (-0.0f64).to_bits());
+    }
+
+    /// Direct transcription of the ECMA-262 ToInt32 spec text, used as
+    /// the reference for the bit-based implementation. fmod on exact
+    /// integers is exact, so no precision is lost.
+    fn spec_to_int32(value: f64) -> i32 {
+        if !value.is_finite() {
+            return 0;
+        }
+        let truncated = value.trunc();
+        let modulus = 4294967296.0; // 2^32
+        let mut residue = truncated % modulus;
+        if residue < 0.0 {
+            residue += modulus;
+        }
+        let unsigned = residue as u32;
+        unsigned as i32
+    }
+
+    #[test]
+    fn test_to_int32_spec_vectors() {
+        assert_eq!(to_int32(0.0), 0);
+        assert_eq!(to_int32(-0.0), 0);
+        assert_eq!(to_int32(f64::NAN), 0);
+        assert_eq!(to_int32(f64::INFINITY), 0);
+        assert_eq!(to_int32(f64::NEG_INFINITY), 0);
+
+        assert_eq!(to_int32(1.5), 1);
+        assert_eq!(to_int32(-1.5), -1);
+        assert_eq!(to_int32(0.999), 0);
+        assert_eq!(to_int32(-0.999), 0);
+
+        assert_eq!(to_int32(2147483647.0), i32::MAX);
+        assert_eq!(to_int32(2147483648.0), i32::MIN);
+        assert_eq!(to_int32(-2147483649.0), i32::MAX);
+        assert_eq!(to_int32(4294967296.0), 0);
+        assert_eq!(to_int32(4294967297.0), 1);
+        assert_eq!(to_int32(3735928559.0), -559038737); // 0xDEADBEEF
+
+        // 2^53 - 1: low 32 bits all set
+        assert_eq!(to_int32(9007199254740991.0), -1);
+        // Huge values have no bits below 2^32
+        assert_eq!(to_int32(1e100), 0);
+    }
+
+    #[test]
+    fn test_to_uint32() {
+        assert_eq!(to_uint32(-1.0), u32::MAX);
+        assert_eq!(to_uint32(4294967295.0), u32::MAX);
+        assert_eq!(to_uint32(4294967296.0), 0);
+        assert_eq!(to_uint32(4294967297.0), 1);
+        assert_eq!(to_uint32(-4294967295.0), 1);
+        assert_eq!(to_uint32(f64::NAN), 0);
+    }
+
+    #[test]
+    fn test_to_int32_matches_spec_transcription() {
+        use firefox_xorshift128plus::XorShift128PlusRNG;
+
+        for value in structured_samples() {
+            assert_eq!(
+                to_int32(value),
+                spec_to_int32(value),
+                "to_int32 diverges from the spec on {} ({:#018x})",
+                value,
+                value.to_bits()
+            );
+        }
+
+        let mut rng = XorShift128PlusRNG::from_seed_u64(0x7031_7032);
+        for _ in 0..200_000 {
+            let value = f64::from_bits(rng.next());
+            assert_eq!(
+                to_int32(value),
+                spec_to_int32(value),
+                "to_int32 diverges from the spec on bits {:#018x}",
+                value.to_bits()
+            );
+        }
+        // Scaled values concentrate on the interesting 2^31..2^53 band
+        for _ in 0..100_000 {
+            let value = (rng.next_double() - 0.5) * 2f64.powi(54);
+            assert_eq!(to_int32(value), spec_to_int32(value));
+        }
+    }
+
+    #[test]
+    fn test_specific_nan() {
+        let nan = specific_nan_f64(false, 1);
+        assert!(nan.is_nan());
+        assert!(nan.is_sign_positive());
+        assert_eq!(nan.to_bits(), 0x7FF0_0000_0000_0001);
+
+        let nan = specific_nan_f64(true, 0x000F_FFFF_FFFF_FFFF);
+        assert!(nan.is_nan());
+        assert_eq!(nan.to_bits(), 0xFFFF_FFFF_FFFF_FFFF);
+
+        let nan = specific_nan_f32(true, 0x40_0000);
+        assert!(nan.is_nan());
+        assert_eq!(nan.to_bits(), 0xFFC0_0000);
+    }
+
+    #[test]
+    #[should_panic(expected = "a zero significand would encode infinity")]
+    fn test_specific_nan_rejects_zero_significand() {
+        let _ = specific_nan_f64(false, 0);
+    }
+
+    #[test]
+    #[should_panic(expected = "significand must fit in 52 bits")]
+    fn test_specific_nan_rejects_wide_significand() {
+        let _ = specific_nan_f64(false, 1 << 52);
+    }
+
+    #[test]
+    fn test_is_exactly_representable_as_f64() {
+        // Everything through 2^53 is exact; the first casualty is 2^53 + 1
+        assert!(is_exactly_representable_as_f64(0i64));
+        assert!(is_exactly_representable_as_f64(-1i64));
+        assert!(is_exactly_representable_as_f64((1i64 << 53) - 1));
+        assert!(is_exactly_representable_as_f64(1i64 << 53));
+        assert!(!is_exactly_representable_as_f64((1i64 << 53) + 1));
+        assert!(is_exactly_representable_as_f64((1i64 << 53) + 2));
+        assert!(!is_exactly_representable_as_f64(-(1i64 << 53) - 1));
+
+        // Powers of two stay exact all the way up
+        assert!(is_exactly_representable_as_f64(1u64 << 63));
+        assert!(is_exactly_representable_as_f64(i64::MIN));
+        assert!(!is_exactly_representable_as_f64(i64::MAX));
+        assert!(!is_exactly_representable_as_f64(u64::MAX));
+
+        // Smaller widths always fit
+        assert!(is_exactly_representable_as_f64(i32::MAX));
+        assert!(is_exactly_representable_as_f64(u32::MAX));
+    }
+
+    #[test]
+    fn test_is_exactly_representable_as_f32() {
+        assert!(is_exactly_representable_as_f32((1i64 << 24) - 1));
+        assert!(is_exactly_representable_as_f32(1i64 << 24));
+        assert!(!is_exactly_representable_as_f32((1i64 << 24) + 1));
+        assert!(!is_exactly_representable_as_f32(-(1i64 << 24) - 1));
+        assert!(is_exactly_representable_as_f32(1u64 << 40));
+        assert!(!is_exactly_representable_as_f32(i32::MAX));
+        assert!(!is_exactly_representable_as_f32(u64::MAX));
+
+        // Anything f32-exact is also f64-exact
+        for value in [0i64, 1, -7, 1 << 24, 1 << 40, 123456] {
+            if is_exactly_representable_as_f32(value) {
+                assert!(is_exactly_representable_as_f64(value));
+            }
+        }
+    }
+
+    #[test]
+    fn test_unspecified_nan() {
+        assert!(unspecified_nan_f64().is_nan());
+        assert!(unspecified_nan_f64().is_sign_positive());
+        assert!(unspecified_nan_f32().is_nan());
+
+        assert!(is_canonical_nan_f64(unspecified_nan_f64()));
+        assert!(is_canonical_nan_f32(unspecified_nan_f32()));
+
+        // Other NaN encodings — including the hardware quiet NaN — are
+        // NaN but not canonical
+        assert!(f64::NAN.is_nan());
+        assert!(!is_canonical_nan_f64(f64::NAN));
+        assert!(!is_canonical_nan_f64(specific_nan_f64(true, 1)));
+        assert!(!is_canonical_nan_f32(specific_nan_f32(false, 1)));
+
+        // Non-NaN values are never canonical
+        assert!(!is_canonical_nan_f64(0.0));
+        assert!(!is_canonical_nan_f64(f64::INFINITY));
+    }
+
+    #[test]
+    fn test_fuzzy_equals_additive() {
+        let eps = FUZZY_EQUALS_EPSILON_F64;
+        assert!(fuzzy_equals_additive(1.0, 1.0, eps));
+        assert!(fuzzy_equals_additive(1.0, 1.0 + eps / 2.0, eps));
+        assert!(!fuzzy_equals_additive(1.0, 1.0 + eps * 2.0, eps));
+
+        // Absolute comparison ignores scale: any epsilon-sized window
+        // swallows tiny values entirely
+        assert!(fuzzy_equals_additive(1e-15, 2e-15, eps));
+
+        // NaN is equal to nothing
+        assert!(!fuzzy_equals_additive(f64::NAN, f64::NAN, eps));
+    }
+
+    #[test]
+    fn test_fuzzy_equals_multiplicative() {
+        let eps = FUZZY_EQUALS_EPSILON_F64;
+        assert!(fuzzy_equals_multiplicative(1.0, 1.0, eps));
+        assert!(fuzzy_equals_multiplicative(1e20, 1e20 * (1.0 + eps / 2.0), eps));
+        assert!(!fuzzy_equals_multiplicative(1e20, 1e20 * (1.0 + eps * 2.0), eps));
+
+        // Relative comparison scales down too
+        assert!(fuzzy_equals_multiplicative(1e-15, 1e-15 * (1.0 + eps / 2.0), eps));
+        assert!(!fuzzy_equals_multiplicative(1e-15, 2e-15, eps));
+
+        // Zero only fuzzy-equals zero: epsilon scales with the smaller
+        // magnitude, which is 0
+        assert!(fuzzy_equals_multiplicative(0.0, 0.0, eps));
+        assert!(!fuzzy_equals_multiplicative(0.0, 1e-300, eps));
+    }
+
     #[test]
     fn test_zero() {
         // Positive and negative zero
@@ -251,7 +1076,7 @@ mod tests {
             100.0,
             1000.0,
             -42.5,
-            3.14159_f32 as f64, // A float32 value cast to f64 is always representable
+            std::f32::consts::PI as f64, // A float32 value cast to f64 is always representable
         ];
 
         for &val in &representable {
@@ -284,6 +1109,149 @@ mod tests {
         }
     }
 
+    /// Structured inputs exercising every branch of both
+    /// is_float32_representable implementations: specials, zeros, every
+    /// power-of-two exponent, float32 values, and values perturbed by
+    /// one double ULP so the dropped-bits check has nonzero tails.
+    fn structured_samples() -> Vec<f64> {
+        let mut samples = vec![
+            0.0,
+            -0.0,
+            f64::NAN,
+            f64::INFINITY,
+            f64::NEG_INFINITY,
+            f64::MAX,
+            f64::MIN_POSITIVE,
+            f32::MAX as f64,
+            f32::MIN_POSITIVE as f64,
+            std::f64::consts::PI,
+            2147483647.0,
+            16777217.0,
+        ];
+        // Every power of two from the smallest f64 subnormal upward,
+        // each with one-ULP neighbors
+        for bit in 0..63 {
+            for base in [f64::from_bits(1u64 << bit), -f64::from_bits(1u64 << bit)] {
+                samples.push(base);
+                samples.push(f64::from_bits(base.to_bits() + 1));
+                samples.push(f64::from_bits(base.to_bits().wrapping_sub(1)));
+            }
+        }
+        for exp in -1022..=1023i32 {
+            let base = 2f64.powi(exp);
+            samples.push(base);
+            samples.push(f64::from_bits(base.to_bits() + 1));
+            samples.push(f64::from_bits(base.to_bits() - 1));
+        }
+        // A spread of exact float32 values and their double neighbors
+        for step in 0..1000u32 {
+            let as_f32 = f32::from_bits(step.wrapping_mul(0x0041_8421));
+            let as_f64 = as_f32 as f64;
+            samples.push(as_f64);
+            samples.push(f64::from_bits(as_f64.to_bits() ^ 1));
+        }
+        samples
+    }
+
+    #[test]
+    fn test_bits_impl_agrees_on_structured_samples() {
+        for value in structured_samples() {
+            assert_eq!(
+                is_float32_representable_bits(value),
+                is_float32_representable_roundtrip(value),
+                "implementations disagree on {} ({:#018x})",
+                value,
+                value.to_bits()
+            );
+        }
+    }
+
+    #[test]
+    fn test_bits_impl_agrees_on_random_bit_patterns() {
+        use firefox_xorshift128plus::XorShift128PlusRNG;
+
+        // Uniform random bit patterns hit NaNs, subnormals, and wild
+        // exponents far more often than uniform random values would
+        let mut rng = XorShift128PlusRNG::from_seed_u64(0xf10a_f10a);
+        for _ in 0..200_000 {
+            let value = f64::from_bits(rng.next());
+            assert_eq!(
+                is_float32_representable_bits(value),
+                is_float32_representable_roundtrip(value),
+                "implementations disagree on bits {:#018x}",
+                value.to_bits()
+            );
+        }
+        // And random values rounded through f32, which are all
+        // representable by construction
+        for _ in 0..50_000 {
+            let value = rng.next_double() as f32 as f64;
+            assert!(is_float32_representable_bits(value));
+        }
+    }
+
+    /// Rough timing comparison between the cast-based and bit-based
+    /// implementations; run with `cargo test -- --ignored --nocapture`.
+    /// On x86-64 the bit-based form wins clearly on random bit
+    /// patterns (~3x); the round-trip form is slightly ahead when
+    /// every input is representable and no early exit fires. The
+    /// bit-based form is the default for its better worst case.
+    #[test]
+    #[ignore]
+    fn bench_representable_implementations() {
+        use firefox_xorshift128plus::XorShift128PlusRNG;
+        use std::time::Instant;
+
+        let mut rng = XorShift128PlusRNG::from_seed_u64(0xbe9c_be9c);
+        let inputs: Vec<f64> = (0..1_000_000).map(|_| f64::from_bits(rng.next())).collect();
+
+        let start = Instant::now();
+        let cast_count = inputs
+            .iter()
+            .filter(|&&v| is_float32_representable_roundtrip(v))
+            .count();
+        let cast_time = start.elapsed();
+
+        let start = Instant::now();
+        let bits_count = inputs
+            .iter()
+            .filter(|&&v| is_float32_representable_bits(v))
+            .count();
+        let bits_time = start.elapsed();
+
+        assert_eq!(cast_count, bits_count);
+        println!(
+            "random bits — cast-based: {:?} for {} inputs; bit-based: {:?}",
+            cast_time,
+            inputs.len(),
+            bits_time
+        );
+
+        // A representable-only corpus removes the early-out advantage
+        // the random corpus gives the bit-based form
+        let representable: Vec<f64> = inputs.iter().map(|&v| v as f32 as f64).collect();
+
+        let start = Instant::now();
+        let cast_count = representable
+            .iter()
+            .filter(|&&v| is_float32_representable_roundtrip(v))
+            .count();
+        let cast_time = start.elapsed();
+
+        let start = Instant::now();
+        let bits_count = representable
+            .iter()
+            .filter(|&&v| is_float32_representable_bits(v))
+            .count();
+        let bits_time = start.elapsed();
+
+        assert_eq!(cast_count, bits_count);
+        println!(
+            "representable — cast-based: {:?}; bit-based: {:?}",
+            cast_time, bits_time
+        );
+    }
+
     #[test]
     fn test_edge_cases() {
         // Additional edge cases
@@ -0,0 +1,44 @@
+//! Throughput benchmark for hash_bytes on 64B–64KB inputs
+//!
+//! A dependency-free stand-in for a criterion harness (this tree builds
+//! without external dev-dependencies): times the public dispatching
+//! entry point against the scalar reference over the input sizes that
+//! matter for hash-table keys and cache blocks, and prints MB/s.
+//!
+//! Run with optimizations or the numbers are meaningless:
+//!
+//! ```text
+//! cargo run --release -p firefox_hashbytes --example hash_bench
+//! ```
+
+use firefox_hashbytes::{hash_bytes, hash_bytes_scalar};
+use std::time::Instant;
+
+/// Time `f` over `iters` calls and return MB/s for `size`-byte inputs
+fn throughput(size: usize, iters: u32, f: impl Fn(&[u8]) -> u32) -> f64 {
+    let data: Vec<u8> = (0..size).map(|i| (i % 251) as u8).collect();
+    // Warm up (and force feature detection off the timed path)
+    let mut sink = 0u32;
+    for _ in 0..100 {
+        sink = sink.wrapping_add(f(&data));
+    }
+    let start = Instant::now();
+    for _ in 0..iters {
+        sink = sink.wrapping_add(f(&data));
+    }
+    let elapsed = start.elapsed().as_secs_f64();
+    // Keep the results observable so the calls can't be optimized away
+    std::hint::black_box(sink);
+    (size as f64 * iters as f64) / elapsed / 1_000_000.0
+}
+
+fn main() {
+    println!("{:>8}  {:>12}  {:>12}", "size", "scalar MB/s", "dispatch MB/s");
+    for size in [64usize, 256, 1024, 4096, 16384, 65536] {
+        // Scale iteration counts so each row takes a comparable time
+        let iters = (64 * 1024 * 1024 / size) as u32;
+        let scalar = throughput(size, iters, |data| hash_bytes_scalar(data, 0));
+        let dispatch = throughput(size, iters, |data| hash_bytes(data, 0));
+        println!("{size:>8}  {scalar:>12.1}  {dispatch:>12.1}");
+    }
+}
@@ -76,9 +76,50 @@ pub unsafe extern "C" fn mozilla_HashBytes(
     result.unwrap_or(0)
 }
 
+/// FFI wrapper for the known-length string hash.
+///
+/// Matches mozilla::HashString(const char*, length) for the same buffer.
+///
+/// # Arguments
+///
+/// * `chars` - Pointer to character array (can be null if length is 0)
+/// * `length` - Number of characters to hash
+///
+/// # Returns
+///
+/// 32-bit hash value (0 on panic, which should not happen)
+///
+/// # Safety
+///
+/// Caller must ensure `chars` points to at least `length` valid bytes when
+/// `length > 0`; the memory must remain valid for the duration of the call.
+#[no_mangle]
+pub unsafe extern "C" fn mozilla_HashStringKnownLength(
+    chars: *const std::os::raw::c_char,
+    length: usize,
+) -> HashNumber {
+    let result = panic::catch_unwind(|| {
+        if length == 0 || chars.is_null() {
+            return 0;
+        }
+        // SAFETY: Caller guarantees chars points to at least length bytes
+        let slice = unsafe { slice::from_raw_parts(chars as *const u8, length) };
+        let mut hash = 0;
+        for &byte in slice {
+            hash = crate::add_u32_to_hash(hash, byte as u32);
+        }
+        hash
+    });
+    result.unwrap_or(0)
+}
+
 /// Alternative name for compatibility with different naming conventions.
 ///
 /// Some C++ code may use this name instead of mozilla_HashBytes.
+///
+/// # Safety
+///
+/// Same contract as mozilla_HashBytes.
 #[no_mangle]
 pub unsafe extern "C" fn HashBytes(
     bytes: *const u8,
@@ -88,6 +129,122 @@ pub unsafe extern "C" fn HashBytes(
     mozilla_HashBytes(bytes, length, starting_hash)
 }
 
+/// FFI wrapper for the 64-bit hash_bytes64 function.
+///
+/// Same contract as mozilla_HashBytes with a 64-bit result, for cache-key
+/// and fingerprinting consumers.
+///
+/// # Arguments
+///
+/// * `bytes` - Pointer to byte array (can be null if length is 0)
+/// * `length` - Number of bytes to hash
+/// * `starting_hash` - Starting hash value for chaining
+///
+/// # Returns
+///
+/// 64-bit hash value (0 on panic, which should not happen)
+///
+/// # Safety
+///
+/// Same contract as mozilla_HashBytes.
+#[no_mangle]
+pub unsafe extern "C" fn mozilla_HashBytes64(
+    bytes: *const u8,
+    length: usize,
+    starting_hash: u64,
+) -> u64 {
+    let result = panic::catch_unwind(|| {
+        if length == 0 || bytes.is_null() {
+            return starting_hash;
+        }
+        // SAFETY: Caller guarantees that bytes points to at least length bytes
+        let slice = unsafe { slice::from_raw_parts(bytes, length) };
+        crate::hash_bytes64(slice, starting_hash)
+    });
+    result.unwrap_or(0)
+}
+
+/// FFI wrapper for the null-terminated string hash.
+///
+/// Matches mozilla::HashString(const char*): hashes bytes up to but not
+/// including the terminating NUL, so C++ HashString call sites can switch
+/// to this symbol directly.
+///
+/// # Arguments
+///
+/// * `chars` - Pointer to a null-terminated character array (may be null)
+///
+/// # Returns
+///
+/// 32-bit hash value (0 for a null pointer, or on panic)
+///
+/// # Safety
+///
+/// Caller must ensure `chars` is either null or points to a valid
+/// null-terminated string that remains valid for the duration of the call.
+#[no_mangle]
+pub unsafe extern "C" fn mozilla_HashString(chars: *const std::os::raw::c_char) -> HashNumber {
+    let result = panic::catch_unwind(|| {
+        if chars.is_null() {
+            return 0;
+        }
+        // SAFETY: Caller guarantees chars is a valid null-terminated string
+        unsafe { crate::hash_cstring(chars) }
+    });
+    result.unwrap_or(0)
+}
+
+/// FFI wrapper for the known-length UTF-16 string hash.
+///
+/// Matches mozilla::HashString(const char16_t*, length): each 16-bit code
+/// unit is mixed as one step, so the result agrees with the C++
+/// implementation for the same buffer (and with hash_string only for
+/// ASCII).
+///
+/// # Arguments
+///
+/// * `chars` - Pointer to a UTF-16 code unit array (can be null if length is 0)
+/// * `length` - Number of code units to hash
+///
+/// # Returns
+///
+/// 32-bit hash value (0 on panic, which should not happen)
+///
+/// # Safety
+///
+/// Caller must ensure `chars` points to at least `length` valid u16 code
+/// units when `length > 0`; the memory must remain valid for the duration
+/// of the call.
+#[no_mangle]
+pub unsafe extern "C" fn mozilla_HashStringU16(chars: *const u16, length: usize) -> HashNumber {
+    let result = panic::catch_unwind(|| {
+        if length == 0 || chars.is_null() {
+            return 0;
+        }
+        // SAFETY: Caller guarantees chars points to at least length code units
+        let slice = unsafe { slice::from_raw_parts(chars, length) };
+        crate::hash_string_u16(slice)
+    });
+    result.unwrap_or(0)
+}
+
+/// FFI wrapper for the final hash-code scramble.
+///
+/// Matches mozilla::ScrambleHashCode; apply once before indexing a
+/// power-of-two-sized table.
+///
+/// # Arguments
+///
+/// * `hash` - The accumulated hash code
+///
+/// # Returns
+///
+/// The scrambled hash code
+#[no_mangle]
+pub extern "C" fn mozilla_ScrambleHashCode(hash: HashNumber) -> HashNumber {
+    crate::scramble_hash_code(hash)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -144,6 +301,62 @@ mod tests {
         assert_eq!(safe_hash, ffi_hash, "FFI wrapper should match safe implementation");
     }
 
+    #[test]
+    fn test_ffi_hash_bytes64() {
+        let data = b"cache key";
+        unsafe {
+            assert_eq!(
+                mozilla_HashBytes64(data.as_ptr(), data.len(), 0),
+                crate::hash_bytes64(data, 0)
+            );
+            assert_eq!(mozilla_HashBytes64(std::ptr::null(), 0, 7), 7);
+            assert_eq!(mozilla_HashBytes64(std::ptr::null(), 10, 7), 7);
+        }
+    }
+
+    #[test]
+    fn test_ffi_hash_string_known_length() {
+        let text = "hello";
+        let hash = unsafe {
+            mozilla_HashStringKnownLength(text.as_ptr() as *const std::os::raw::c_char, text.len())
+        };
+        assert_eq!(hash, crate::hash_string(text));
+
+        // Null or empty input hashes to the empty-string value
+        unsafe {
+            assert_eq!(mozilla_HashStringKnownLength(std::ptr::null(), 5), 0);
+            assert_eq!(
+                mozilla_HashStringKnownLength(text.as_ptr() as *const std::os::raw::c_char, 0),
+                0
+            );
+        }
+    }
+
+    #[test]
+    fn test_ffi_hash_string_null_terminated() {
+        let text = std::ffi::CString::new("hello world").unwrap();
+        let hash = unsafe { mozilla_HashString(text.as_ptr()) };
+        assert_eq!(hash, crate::hash_string("hello world"));
+
+        unsafe {
+            assert_eq!(mozilla_HashString(std::ptr::null()), 0);
+        }
+    }
+
+    #[test]
+    fn test_ffi_hash_string_u16() {
+        let units: Vec<u16> = "hello".encode_utf16().collect();
+        let hash = unsafe { mozilla_HashStringU16(units.as_ptr(), units.len()) };
+        assert_eq!(hash, crate::hash_string_u16(&units));
+        // ASCII input agrees with the narrow-string hash
+        assert_eq!(hash, crate::hash_string("hello"));
+
+        unsafe {
+            assert_eq!(mozilla_HashStringU16(std::ptr::null(), 5), 0);
+            assert_eq!(mozilla_HashStringU16(units.as_ptr(), 0), 0);
+        }
+    }
+
     #[test]
     fn test_ffi_alternative_name() {
         let data = b"test";
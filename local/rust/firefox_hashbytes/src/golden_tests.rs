@@ -0,0 +1,253 @@
+//! Golden-vector differential tests against the C++ implementation
+//!
+//! Compiled only with `--features golden-vectors`. Loads the checked-in
+//! `testdata/hash_golden.json` — (input, starting_hash, expected) tuples
+//! generated from the mfbt/HashFunctions.h implementation on a
+//! little-endian host — and verifies every exported hash function against
+//! it. Any change that moves an ABI-visible hash value fails here before
+//! it can land.
+//!
+//! To regenerate the vectors after an *intentional* hash change, rerun
+//! the C++ generator and replace the JSON file in the same commit; the
+//! expected values are plain integers so the diff shows exactly which
+//! inputs moved.
+//!
+//! The JSON subset used by the vector file (objects, arrays, strings
+//! without escapes, unsigned integers) is parsed by hand below — this
+//! crate deliberately has no external dependencies.
+
+use crate::{hash_bytes, hash_bytes64, hash_string, scramble_hash_code};
+use std::collections::HashMap;
+
+/// One parsed JSON value from the vector file
+#[derive(Debug, Clone, PartialEq)]
+enum Json {
+    Number(u64),
+    String(String),
+    Array(Vec<Json>),
+    Object(HashMap<String, Json>),
+}
+
+impl Json {
+    fn as_u64(&self) -> u64 {
+        match self {
+            Json::Number(n) => *n,
+            other => panic!("expected number, got {other:?}"),
+        }
+    }
+
+    fn as_str(&self) -> &str {
+        match self {
+            Json::String(s) => s,
+            other => panic!("expected string, got {other:?}"),
+        }
+    }
+
+    fn as_array(&self) -> &[Json] {
+        match self {
+            Json::Array(items) => items,
+            other => panic!("expected array, got {other:?}"),
+        }
+    }
+
+    fn field(&self, key: &str) -> &Json {
+        match self {
+            Json::Object(map) => map
+                .get(key)
+                .unwrap_or_else(|| panic!("missing field {key:?}")),
+            other => panic!("expected object, got {other:?}"),
+        }
+    }
+}
+
+/// Minimal parser for the subset of JSON the vector file uses
+struct Parser<'a> {
+    bytes: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> Parser<'a> {
+    fn new(text: &'a str) -> Self {
+        Parser {
+            bytes: text.as_bytes(),
+            pos: 0,
+        }
+    }
+
+    fn skip_whitespace(&mut self) {
+        while self.pos < self.bytes.len() && self.bytes[self.pos].is_ascii_whitespace() {
+            self.pos += 1;
+        }
+    }
+
+    fn peek(&mut self) -> u8 {
+        self.skip_whitespace();
+        self.bytes[self.pos]
+    }
+
+    fn expect(&mut self, byte: u8) {
+        let found = self.peek();
+        assert_eq!(found as char, byte as char, "at offset {}", self.pos);
+        self.pos += 1;
+    }
+
+    fn value(&mut self) -> Json {
+        match self.peek() {
+            b'{' => self.object(),
+            b'[' => self.array(),
+            b'"' => Json::String(self.string()),
+            b'0'..=b'9' => self.number(),
+            other => panic!("unexpected {:?} at offset {}", other as char, self.pos),
+        }
+    }
+
+    fn object(&mut self) -> Json {
+        self.expect(b'{');
+        let mut map = HashMap::new();
+        if self.peek() == b'}' {
+            self.pos += 1;
+            return Json::Object(map);
+        }
+        loop {
+            let key = self.string();
+            self.expect(b':');
+            map.insert(key, self.value());
+            match self.peek() {
+                b',' => self.pos += 1,
+                b'}' => {
+                    self.pos += 1;
+                    return Json::Object(map);
+                }
+                other => panic!("unexpected {:?} in object", other as char),
+            }
+        }
+    }
+
+    fn array(&mut self) -> Json {
+        self.expect(b'[');
+        let mut items = Vec::new();
+        if self.peek() == b']' {
+            self.pos += 1;
+            return Json::Array(items);
+        }
+        loop {
+            items.push(self.value());
+            match self.peek() {
+                b',' => self.pos += 1,
+                b']' => {
+                    self.pos += 1;
+                    return Json::Array(items);
+                }
+                other => panic!("unexpected {:?} in array", other as char),
+            }
+        }
+    }
+
+    fn string(&mut self) -> String {
+        self.expect(b'"');
+        let start = self.pos;
+        while self.bytes[self.pos] != b'"' {
+            assert_ne!(self.bytes[self.pos], b'\\', "escapes not supported");
+            self.pos += 1;
+        }
+        let text = std::str::from_utf8(&self.bytes[start..self.pos])
+            .expect("vector file is ASCII")
+            .to_string();
+        self.pos += 1;
+        text
+    }
+
+    fn number(&mut self) -> Json {
+        self.skip_whitespace();
+        let start = self.pos;
+        while self.pos < self.bytes.len() && self.bytes[self.pos].is_ascii_digit() {
+            self.pos += 1;
+        }
+        let text = std::str::from_utf8(&self.bytes[start..self.pos]).unwrap();
+        Json::Number(text.parse().expect("number fits in u64"))
+    }
+}
+
+/// Decode the hex-encoded `input` field into bytes
+fn decode_hex(hex: &str) -> Vec<u8> {
+    assert_eq!(hex.len() % 2, 0, "odd-length hex input");
+    (0..hex.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(&hex[i..i + 2], 16).expect("valid hex"))
+        .collect()
+}
+
+/// Load and parse the checked-in vector file
+fn load_vectors() -> Json {
+    let path = concat!(env!("CARGO_MANIFEST_DIR"), "/testdata/hash_golden.json");
+    let text = std::fs::read_to_string(path).expect("testdata/hash_golden.json is checked in");
+    let mut parser = Parser::new(&text);
+    let doc = parser.value();
+    parser.skip_whitespace();
+    assert_eq!(parser.pos, parser.bytes.len(), "trailing garbage");
+    doc
+}
+
+#[test]
+fn test_hash_bytes_matches_golden_vectors() {
+    let doc = load_vectors();
+    let vectors = doc.field("hash_bytes").as_array();
+    assert!(!vectors.is_empty());
+    for vector in vectors {
+        let input = decode_hex(vector.field("input").as_str());
+        let starting_hash = vector.field("starting_hash").as_u64() as u32;
+        let expected = vector.field("expected").as_u64() as u32;
+        assert_eq!(
+            hash_bytes(&input, starting_hash),
+            expected,
+            "input {:?} starting_hash {starting_hash:#x}",
+            vector.field("input").as_str()
+        );
+    }
+}
+
+#[test]
+fn test_hash_bytes64_matches_golden_vectors() {
+    let doc = load_vectors();
+    let vectors = doc.field("hash_bytes64").as_array();
+    assert!(!vectors.is_empty());
+    for vector in vectors {
+        let input = decode_hex(vector.field("input").as_str());
+        let starting_hash = vector.field("starting_hash").as_u64();
+        let expected = vector.field("expected").as_u64();
+        assert_eq!(
+            hash_bytes64(&input, starting_hash),
+            expected,
+            "input {:?} starting_hash {starting_hash:#x}",
+            vector.field("input").as_str()
+        );
+    }
+}
+
+#[test]
+fn test_hash_string_matches_golden_vectors() {
+    let doc = load_vectors();
+    let vectors = doc.field("hash_string").as_array();
+    assert!(!vectors.is_empty());
+    for vector in vectors {
+        let input = vector.field("input").as_str();
+        let expected = vector.field("expected").as_u64() as u32;
+        assert_eq!(hash_string(input), expected, "input {input:?}");
+    }
+}
+
+#[test]
+fn test_scramble_matches_golden_vectors() {
+    let doc = load_vectors();
+    let vectors = doc.field("scramble").as_array();
+    assert!(!vectors.is_empty());
+    for vector in vectors {
+        let input_hash = vector.field("input_hash").as_u64() as u32;
+        let expected = vector.field("expected").as_u64() as u32;
+        assert_eq!(
+            scramble_hash_code(input_hash),
+            expected,
+            "input_hash {input_hash:#x}"
+        );
+    }
+}
@@ -0,0 +1,147 @@
+//! std::hash adapter for the Firefox hash function
+//!
+//! Gecko components written in Rust often need a `HashMap` whose hash
+//! codes match what the surrounding C++ computes with
+//! `mozilla::HashBytes`, so keys can be compared against golden vectors
+//! (or handed across FFI) without re-hashing. [`MozHasher`] implements
+//! [`std::hash::Hasher`] on top of [`crate::add_u32_to_hash`], and
+//! [`MozBuildHasher`] plugs it into the standard collections.
+//!
+//! # Determinism
+//!
+//! Unlike the std `RandomState`, this hasher is fully deterministic — the
+//! same key always produces the same hash code, in line with the C++
+//! function. That also means it provides no protection against
+//! hash-flooding attacks; like `mozilla::HashBytes` itself, do not use it
+//! to hash attacker-controlled keys in security-sensitive tables.
+
+use crate::{hash_bytes, HashNumber};
+
+/// A [`std::hash::Hasher`] producing `mozilla::HashBytes`-compatible
+/// codes.
+///
+/// A single `write` of a buffer yields exactly `hash_bytes(buffer, 0)`
+/// (widened to `u64` by `finish`). Multiple writes chain the hash the
+/// same way repeated `mozilla::HashBytes` calls chain through their
+/// starting-hash parameter.
+///
+/// # Examples
+///
+/// ```
+/// use firefox_hashbytes::{hash_bytes, hasher::MozHasher};
+/// use std::hash::Hasher;
+///
+/// let mut hasher = MozHasher::new();
+/// hasher.write(b"hello");
+/// assert_eq!(hasher.finish(), hash_bytes(b"hello", 0) as u64);
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct MozHasher {
+    hash: HashNumber,
+}
+
+impl MozHasher {
+    /// Create a hasher with a starting hash of 0.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Create a hasher that chains from an existing hash code.
+    pub fn with_initial(hash: HashNumber) -> Self {
+        Self { hash }
+    }
+
+    /// The current 32-bit hash code (what C++ callers would receive).
+    pub fn hash_code(&self) -> HashNumber {
+        self.hash
+    }
+}
+
+impl std::hash::Hasher for MozHasher {
+    fn finish(&self) -> u64 {
+        self.hash as u64
+    }
+
+    fn write(&mut self, bytes: &[u8]) {
+        self.hash = hash_bytes(bytes, self.hash);
+    }
+}
+
+/// A [`std::hash::BuildHasher`] handing out [`MozHasher`]s, for use as the
+/// `S` parameter of `HashMap`/`HashSet`.
+///
+/// # Examples
+///
+/// ```
+/// use firefox_hashbytes::hasher::MozBuildHasher;
+/// use std::collections::HashMap;
+///
+/// let mut map: HashMap<String, u32, MozBuildHasher> = HashMap::default();
+/// map.insert("width".to_string(), 1280);
+/// assert_eq!(map.get("width"), Some(&1280));
+/// ```
+#[derive(Debug, Clone, Copy, Default)]
+pub struct MozBuildHasher;
+
+impl std::hash::BuildHasher for MozBuildHasher {
+    type Hasher = MozHasher;
+
+    fn build_hasher(&self) -> MozHasher {
+        MozHasher::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::hash::{BuildHasher, Hasher};
+
+    #[test]
+    fn test_single_write_matches_hash_bytes() {
+        let mut hasher = MozHasher::new();
+        hasher.write(b"hello world");
+        assert_eq!(hasher.hash_code(), hash_bytes(b"hello world", 0));
+        assert_eq!(hasher.finish(), hash_bytes(b"hello world", 0) as u64);
+    }
+
+    #[test]
+    fn test_writes_chain_like_starting_hash() {
+        let mut hasher = MozHasher::new();
+        hasher.write(b"hello");
+        hasher.write(b" world");
+
+        let chained = hash_bytes(b" world", hash_bytes(b"hello", 0));
+        assert_eq!(hasher.hash_code(), chained);
+    }
+
+    #[test]
+    fn test_with_initial_continues_a_cpp_hash() {
+        let from_cpp = hash_bytes(b"prefix", 0);
+        let mut hasher = MozHasher::with_initial(from_cpp);
+        hasher.write(b"suffix");
+        assert_eq!(hasher.hash_code(), hash_bytes(b"suffix", from_cpp));
+    }
+
+    #[test]
+    fn test_build_hasher_is_deterministic() {
+        // Two independent builders hash the same key identically (no
+        // per-instance random state)
+        assert_eq!(
+            MozBuildHasher.hash_one("some key"),
+            MozBuildHasher.hash_one("some key")
+        );
+    }
+
+    #[test]
+    fn test_hash_map_round_trip() {
+        let mut map: std::collections::HashMap<String, u32, MozBuildHasher> =
+            std::collections::HashMap::default();
+        for i in 0..100 {
+            map.insert(format!("key{i}"), i);
+        }
+        for i in 0..100 {
+            assert_eq!(map.get(&format!("key{i}")), Some(&i));
+        }
+        assert!(!map.contains_key("missing"));
+    }
+}
@@ -60,7 +60,7 @@ pub type HashNumber = u32;
 /// ```
 #[inline(always)]
 pub const fn rotate_left5(value: HashNumber) -> HashNumber {
-    (value << 5) | (value >> 27)
+    value.rotate_left(5)
 }
 
 /// Add a 32-bit value to a hash.
@@ -89,6 +89,78 @@ pub const fn add_u32_to_hash(hash: HashNumber, value: u32) -> HashNumber {
     GOLDEN_RATIO_U32.wrapping_mul(rotate_left5(hash) ^ value)
 }
 
+/// Add a 64-bit value to a hash, matching `mozilla::AddToHash` for
+/// 64-bit integers.
+///
+/// The low 32 bits are mixed first, then the high 32 bits, exactly like
+/// the C++ `AddU64ToHash` detail function, so a Rust `u64` and a C++
+/// `uint64_t` fold identically.
+///
+/// # Examples
+///
+/// ```
+/// use firefox_hashbytes::{add_u32_to_hash, add_u64_to_hash};
+///
+/// let value = 0x1122334455667788u64;
+/// assert_eq!(
+///     add_u64_to_hash(0, value),
+///     add_u32_to_hash(add_u32_to_hash(0, 0x55667788), 0x11223344)
+/// );
+/// ```
+#[inline(always)]
+pub const fn add_u64_to_hash(hash: HashNumber, value: u64) -> HashNumber {
+    add_u32_to_hash(add_u32_to_hash(hash, value as u32), (value >> 32) as u32)
+}
+
+/// Add a pointer-sized value to a hash, matching `mozilla::AddToHash` for
+/// `uintptr_t`.
+///
+/// Delegates to the 32- or 64-bit mixing path depending on the platform
+/// word size, like the C++ `AddUintptrToHash<sizeof(uintptr_t)>` dispatch.
+#[inline(always)]
+pub const fn add_usize_to_hash(hash: HashNumber, value: usize) -> HashNumber {
+    if std::mem::size_of::<usize>() == 8 {
+        add_u64_to_hash(hash, value as u64)
+    } else {
+        add_u32_to_hash(hash, value as u32)
+    }
+}
+
+/// Add a pointer's address to a hash, matching `mozilla::AddToHash` for
+/// pointer arguments.
+///
+/// Only the address is hashed, never the pointee, so null and dangling
+/// pointers are fine.
+#[inline(always)]
+pub fn add_pointer_to_hash<T>(hash: HashNumber, ptr: *const T) -> HashNumber {
+    add_usize_to_hash(hash, ptr as usize)
+}
+
+/// Scramble a hash code before using it as a table index, matching
+/// `mozilla::ScrambleHashCode`.
+///
+/// Hash codes whose low bits are poorly distributed (pointers, small
+/// enums) would cluster in power-of-two-sized tables like PLDHashTable;
+/// one final golden-ratio multiply avalanches the input into the high
+/// bits, which such tables shift down. Apply this exactly once, as the
+/// last step before indexing — it is already part of the C++ table
+/// implementations, not of the `AddToHash` chain itself.
+///
+/// # Examples
+///
+/// ```
+/// use firefox_hashbytes::{scramble_hash_code, GOLDEN_RATIO_U32};
+///
+/// assert_eq!(scramble_hash_code(1), GOLDEN_RATIO_U32);
+/// assert_eq!(scramble_hash_code(0), 0);
+/// ```
+#[inline(always)]
+pub const fn scramble_hash_code(hash: HashNumber) -> HashNumber {
+    // A single Fibonacci multiply spreads entropy into the high bits;
+    // wrapping matches C++ unsigned overflow semantics
+    hash.wrapping_mul(GOLDEN_RATIO_U32)
+}
+
 /// Hash a byte array into a 32-bit hash value.
 ///
 /// This function hashes arbitrary byte sequences using a fast, non-cryptographic
@@ -127,11 +199,30 @@ pub const fn add_u32_to_hash(hash: HashNumber, value: u32) -> HashNumber {
 /// - Unaligned memory reads for efficiency
 /// - Inline-friendly implementation
 ///
+/// Words are read little-endian regardless of host byte order, so the
+/// result is defined by the input bytes alone — big-endian targets
+/// produce the same hashes as x86 and ARM.
+///
 /// # Safety
 ///
 /// This function uses unsafe code for unaligned memory reads, but maintains
 /// safety through proper bounds checking via slice operations.
 pub fn hash_bytes(bytes: &[u8], starting_hash: HashNumber) -> HashNumber {
+    // Short inputs aren't worth the dispatch; everything else goes
+    // through the unrolled fast path, which is bit-identical to the
+    // scalar reference below
+    if bytes.len() < UNROLL_THRESHOLD {
+        return hash_bytes_scalar(bytes, starting_hash);
+    }
+    hash_bytes_dispatch(bytes, starting_hash)
+}
+
+/// The straightforward word-at-a-time reference implementation.
+///
+/// This is the ground truth the optimized paths are differentially tested
+/// against, and the path taken for inputs too short to amortize dispatch.
+/// Identical output to [`hash_bytes`] for every input.
+pub fn hash_bytes_scalar(bytes: &[u8], starting_hash: HashNumber) -> HashNumber {
     let mut hash = starting_hash;
     let len = bytes.len();
 
@@ -151,10 +242,14 @@ pub fn hash_bytes(bytes: &[u8], starting_hash: HashNumber) -> HashNumber {
         // SAFETY: We've calculated num_full_words to ensure we don't read past
         // the end of the slice. The unaligned read is safe because we're reading
         // from a valid slice.
-        let word = unsafe {
+        //
+        // from_le fixes the byte-to-word mapping so the hash is defined
+        // by the input bytes, not host endianness: a no-op on
+        // little-endian targets, a byte swap on big-endian ones.
+        let word = usize::from_le(unsafe {
             let ptr = bytes.as_ptr().add(offset);
             std::ptr::read_unaligned(ptr as *const usize)
-        };
+        });
 
         // Hash lower 32 bits first (matches C++ behavior)
         hash = add_u32_to_hash(hash, word as u32);
@@ -175,8 +270,607 @@ pub fn hash_bytes(bytes: &[u8], starting_hash: HashNumber) -> HashNumber {
     hash
 }
 
+/// Compile-time evaluable form of [`hash_bytes`].
+///
+/// Produces exactly the same value as `hash_bytes` for every input, but is
+/// a `const fn`, so static atom tables and switch-on-hash codegen can
+/// precompute hashes of literals the way `constexpr` HashBytes does in
+/// C++:
+///
+/// ```
+/// use firefox_hashbytes::{hash_bytes, hash_bytes_const};
+///
+/// const CLICK_HASH: u32 = hash_bytes_const(b"click", 0);
+/// assert_eq!(CLICK_HASH, hash_bytes(b"click", 0));
+/// ```
+///
+/// The runtime paths use unaligned pointer reads and CPU dispatch, neither
+/// of which is available in const evaluation, so this assembles each word
+/// from its bytes instead; `usize::from_le_bytes` matches the
+/// little-endian runtime reads. Const evaluation is byte-at-a-time slow
+/// — prefer `hash_bytes` for runtime inputs.
+pub const fn hash_bytes_const(bytes: &[u8], starting_hash: HashNumber) -> HashNumber {
+    let mut hash = starting_hash;
+    let word_size = std::mem::size_of::<usize>();
+    let num_full_words = bytes.len() / word_size;
+
+    let mut i = 0;
+    while i < num_full_words {
+        let offset = i * word_size;
+        let mut word_bytes = [0u8; std::mem::size_of::<usize>()];
+        let mut j = 0;
+        while j < word_size {
+            word_bytes[j] = bytes[offset + j];
+            j += 1;
+        }
+        let word = usize::from_le_bytes(word_bytes);
+
+        hash = add_u32_to_hash(hash, word as u32);
+        if word_size == 8 {
+            hash = add_u32_to_hash(hash, ((word as u64) >> 32) as u32);
+        }
+        i += 1;
+    }
+
+    let mut k = num_full_words * word_size;
+    while k < bytes.len() {
+        hash = add_u32_to_hash(hash, bytes[k] as u32);
+        k += 1;
+    }
+
+    hash
+}
+
+/// Hash a byte array with a platform-independent word size.
+///
+/// [`hash_bytes`] consumes the input in native words — 8 bytes at a time
+/// on 64-bit desktop, 4 bytes at a time on 32-bit Android — so the same
+/// bytes hash to *different* values across those builds. That is fine for
+/// in-memory hash tables, but not for anything that crosses an
+/// architecture boundary. Known Gecko consumers that need
+/// cross-architecture stability and should use this function instead:
+///
+/// - HTTP cache and startup-cache keys that may be read back by a
+///   differently-built binary after an OS or app migration
+/// - Telemetry and crash-annotation hashes compared server-side across
+///   the desktop and mobile populations
+/// - Sync/profile data fingerprints shared between devices
+///
+/// This variant always processes 4-byte little-endian words followed by a
+/// byte-at-a-time tail, so the result is a pure function of the bytes on
+/// every target. It equals `hash_bytes` on 32-bit builds. On 64-bit
+/// builds an 8-byte word mixed as its two 32-bit halves is the same as
+/// two consecutive 4-byte words, so the results agree too — except when
+/// the trailing partial word is 4 to 7 bytes, which the native path
+/// mixes byte-wise but this one mixes as a word plus tail.
+///
+/// # Examples
+///
+/// ```
+/// use firefox_hashbytes::hash_bytes_portable;
+///
+/// // Stable value, identical on every architecture
+/// assert_eq!(hash_bytes_portable(b"key", 0), hash_bytes_portable(b"key", 0));
+/// ```
+pub const fn hash_bytes_portable(bytes: &[u8], starting_hash: HashNumber) -> HashNumber {
+    let mut hash = starting_hash;
+    const WORD_SIZE: usize = 4;
+    let num_full_words = bytes.len() / WORD_SIZE;
+
+    let mut i = 0;
+    while i < num_full_words {
+        let offset = i * WORD_SIZE;
+        let word = u32::from_le_bytes([
+            bytes[offset],
+            bytes[offset + 1],
+            bytes[offset + 2],
+            bytes[offset + 3],
+        ]);
+        hash = add_u32_to_hash(hash, word);
+        i += 1;
+    }
+
+    let mut k = num_full_words * WORD_SIZE;
+    while k < bytes.len() {
+        hash = add_u32_to_hash(hash, bytes[k] as u32);
+        k += 1;
+    }
+
+    hash
+}
+
+/// Inputs at least this long take the unrolled path
+const UNROLL_THRESHOLD: usize = 64;
+
+/// The unrolled loop body, shared by every dispatch target.
+///
+/// Each mixing step depends on the previous hash, so the chain itself
+/// cannot be vectorized without changing the output; what the fast path
+/// buys is processing 4 words per iteration — fewer loop branches, and
+/// the next words' unaligned loads issued while the multiply chain
+/// retires. The words are mixed in exactly the scalar order, so the
+/// result is bit-identical to [`hash_bytes_scalar`].
+#[inline(always)]
+fn hash_bytes_unrolled_impl(bytes: &[u8], starting_hash: HashNumber) -> HashNumber {
+    let mut hash = starting_hash;
+    let word_size = std::mem::size_of::<usize>();
+    let num_full_words = bytes.len() / word_size;
+
+    #[inline(always)]
+    fn mix_word(hash: HashNumber, word: usize) -> HashNumber {
+        let mut hash = add_u32_to_hash(hash, word as u32);
+        if std::mem::size_of::<usize>() == 8 {
+            hash = add_u32_to_hash(hash, (word as u64 >> 32) as u32);
+        }
+        hash
+    }
+
+    // SAFETY (for all reads below): offsets stay within num_full_words
+    // words, which fit inside the slice; read_unaligned tolerates any
+    // alignment. from_le keeps the byte-to-word mapping endian-defined,
+    // matching hash_bytes_scalar.
+    let mut i = 0;
+    while i + 4 <= num_full_words {
+        let base = unsafe { bytes.as_ptr().add(i * word_size) };
+        let w0 = usize::from_le(unsafe { std::ptr::read_unaligned(base as *const usize) });
+        let w1 =
+            usize::from_le(unsafe { std::ptr::read_unaligned(base.add(word_size) as *const usize) });
+        let w2 = usize::from_le(unsafe {
+            std::ptr::read_unaligned(base.add(2 * word_size) as *const usize)
+        });
+        let w3 = usize::from_le(unsafe {
+            std::ptr::read_unaligned(base.add(3 * word_size) as *const usize)
+        });
+        hash = mix_word(hash, w0);
+        hash = mix_word(hash, w1);
+        hash = mix_word(hash, w2);
+        hash = mix_word(hash, w3);
+        i += 4;
+    }
+    while i < num_full_words {
+        let word = usize::from_le(unsafe {
+            std::ptr::read_unaligned(bytes.as_ptr().add(i * word_size) as *const usize)
+        });
+        hash = mix_word(hash, word);
+        i += 1;
+    }
+
+    for byte in &bytes[num_full_words * word_size..] {
+        hash = add_u32_to_hash(hash, *byte as u32);
+    }
+
+    hash
+}
+
+/// The unrolled body compiled for AVX2-capable cores.
+///
+/// The algorithm is still scalar (see [`hash_bytes_unrolled_impl`]); the
+/// target-feature attribute lets the compiler pick newer encodings and
+/// scheduling for the same instruction stream.
+///
+/// # Safety
+///
+/// Only call when the CPU supports AVX2 (checked at dispatch).
+#[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+#[target_feature(enable = "avx2")]
+unsafe fn hash_bytes_unrolled_avx2(bytes: &[u8], starting_hash: HashNumber) -> HashNumber {
+    hash_bytes_unrolled_impl(bytes, starting_hash)
+}
+
+/// Pick the best available implementation at runtime.
+///
+/// Feature detection is cached by the standard library, so the check is a
+/// single predictable branch after the first call.
+fn hash_bytes_dispatch(bytes: &[u8], starting_hash: HashNumber) -> HashNumber {
+    #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+    {
+        if std::is_x86_feature_detected!("avx2") {
+            // SAFETY: the AVX2 requirement was just verified
+            return unsafe { hash_bytes_unrolled_avx2(bytes, starting_hash) };
+        }
+    }
+    hash_bytes_unrolled_impl(bytes, starting_hash)
+}
+
+/// The golden ratio as a 64-bit fixed-point value, for the 64-bit hash
+/// variant (also SplitMix64's increment constant).
+pub const GOLDEN_RATIO_U64: u64 = 0x9E3779B97F4A7C15;
+
+/// Add a 64-bit value to a 64-bit hash.
+///
+/// The 64-bit analogue of [`add_u32_to_hash`]: rotate left by 5, XOR with
+/// the input, wrapping-multiply by the 64-bit golden ratio. Used by
+/// [`hash_bytes64`]; note this produces a different sequence than
+/// [`add_u64_to_hash`], which folds a 64-bit value into a *32-bit* hash.
+#[inline(always)]
+pub const fn add_u64_to_hash64(hash: u64, value: u64) -> u64 {
+    GOLDEN_RATIO_U64.wrapping_mul(hash.rotate_left(5) ^ value)
+}
+
+/// Hash a byte array into a 64-bit hash value.
+///
+/// The same shape as [`hash_bytes`] widened to 64 bits, for consumers —
+/// cache keys, fingerprinting — where 32 bits of hash space invites
+/// collisions. Processes the input in 64-bit words with unaligned reads
+/// and mixes any trailing bytes individually; supports chaining through
+/// `starting_hash` just like the 32-bit function.
+///
+/// Like the rest of this crate it is NOT cryptographic.
+///
+/// # Examples
+///
+/// ```
+/// use firefox_hashbytes::hash_bytes64;
+///
+/// let hash = hash_bytes64(b"cache key", 0);
+/// assert_ne!(hash, hash_bytes64(b"cache kez", 0));
+/// ```
+pub fn hash_bytes64(bytes: &[u8], starting_hash: u64) -> u64 {
+    let mut hash = starting_hash;
+    let len = bytes.len();
+
+    if len == 0 {
+        return hash;
+    }
+
+    // Always consume 8-byte words regardless of platform word size, so
+    // the result is identical on 32- and 64-bit targets
+    const WORD_SIZE: usize = std::mem::size_of::<u64>();
+    let num_full_words = len / WORD_SIZE;
+
+    for i in 0..num_full_words {
+        let offset = i * WORD_SIZE;
+        // SAFETY: num_full_words keeps every 8-byte read inside the
+        // slice; read_unaligned tolerates any alignment. from_le makes
+        // the result byte-order independent, like hash_bytes.
+        let word = u64::from_le(unsafe {
+            let ptr = bytes.as_ptr().add(offset);
+            std::ptr::read_unaligned(ptr as *const u64)
+        });
+        hash = add_u64_to_hash64(hash, word);
+    }
+
+    // Mix remaining bytes (less than one word) individually
+    let remaining_start = num_full_words * WORD_SIZE;
+    for byte in &bytes[remaining_start..] {
+        hash = add_u64_to_hash64(hash, *byte as u64);
+    }
+
+    hash
+}
+
+/// Hash a UTF-8 string, matching `mozilla::HashString(const char*, length)`.
+///
+/// Each byte is mixed individually with [`add_u32_to_hash`], exactly like
+/// the C++ known-length overload mixes each `char`, so a Rust `&str` and
+/// the equivalent C++ `char*` buffer produce identical hash codes.
+///
+/// # Examples
+///
+/// ```
+/// use firefox_hashbytes::hash_string;
+///
+/// assert_eq!(hash_string(""), 0);
+/// assert_ne!(hash_string("hello"), hash_string("Hello"));
+/// ```
+pub fn hash_string(s: &str) -> HashNumber {
+    let mut hash = 0;
+    for &byte in s.as_bytes() {
+        hash = add_u32_to_hash(hash, byte as u32);
+    }
+    hash
+}
+
+/// Hash UTF-16 code units, matching `mozilla::HashString(const char16_t*,
+/// length)`.
+///
+/// Each code unit is mixed individually, so the result matches C++ for the
+/// same `char16_t` buffer. Note this does NOT equal [`hash_string`] of the
+/// same text — the C++ overloads differ the same way.
+///
+/// # Examples
+///
+/// ```
+/// use firefox_hashbytes::hash_string_u16;
+///
+/// let units: Vec<u16> = "hello".encode_utf16().collect();
+/// assert_ne!(hash_string_u16(&units), 0);
+/// ```
+pub fn hash_string_u16(units: &[u16]) -> HashNumber {
+    // Fast path for Latin-1-only content, which dominates atom hashing:
+    // a code unit <= 0xFF mixes to exactly the same value as the byte it
+    // narrows to, so once the range check passes we can run the narrow
+    // byte loop. The all-<=0xFF scan and the u8 loop both vectorize,
+    // unlike the general loop over widening u16 loads. Hash values are
+    // identical on both paths; this mirrors the Latin-1 specializations
+    // of the C++ HashUntilZero/HashKnownLength helpers.
+    if units.iter().all(|&unit| unit <= 0xFF) {
+        let mut hash = 0;
+        for &unit in units {
+            hash = add_u32_to_hash(hash, unit as u8 as u32);
+        }
+        return hash;
+    }
+
+    let mut hash = 0;
+    for &unit in units {
+        hash = add_u32_to_hash(hash, unit as u32);
+    }
+    hash
+}
+
+/// Per-unit reference implementation of [`hash_string_u16`], without the
+/// Latin-1 fast path; the differential tests check the two agree.
+#[doc(hidden)]
+pub fn hash_string_u16_generic(units: &[u16]) -> HashNumber {
+    let mut hash = 0;
+    for &unit in units {
+        hash = add_u32_to_hash(hash, unit as u32);
+    }
+    hash
+}
+
+/// Hash a null-terminated C string, matching `mozilla::HashString(const
+/// char*)` (the until-zero overload).
+///
+/// The terminating NUL is not hashed, so the result equals
+/// [`hash_string`] of the same bytes.
+///
+/// # Safety
+///
+/// `ptr` must be non-null and point to a valid null-terminated string that
+/// remains valid for the duration of the call.
+pub unsafe fn hash_cstring(ptr: *const std::os::raw::c_char) -> HashNumber {
+    let mut hash = 0;
+    let mut cursor = ptr;
+    // SAFETY: The caller guarantees a valid null-terminated string, so
+    // every read up to and including the NUL is in bounds
+    unsafe {
+        while *cursor != 0 {
+            hash = add_u32_to_hash(hash, *cursor as u8 as u32);
+            cursor = cursor.add(1);
+        }
+    }
+    hash
+}
+
+/// Hash a large buffer in parallel, chunk by chunk.
+///
+/// Splits the input into `chunk_size`-byte chunks (the last may be
+/// shorter), hashes each chunk independently with [`hash_bytes`] starting
+/// from 0, and folds the per-chunk hashes together in order with
+/// [`hash_combine`]. Chunks are distributed across threads, but the
+/// combination order is always buffer order, so the result is a pure
+/// function of `(bytes, chunk_size)` regardless of thread count or
+/// scheduling.
+///
+/// This is a *distinct hashing mode*, not a faster `hash_bytes`: the
+/// result does NOT equal the serial hash of the same buffer, and changing
+/// `chunk_size` changes the result. Use it where both sides agree on the
+/// chunk size — multi-megabyte cache validation is the motivating
+/// consumer — and never mix it with serially computed hashes.
+///
+/// Inputs no longer than one chunk are hashed inline without spawning.
+///
+/// # Panics
+///
+/// Panics if `chunk_size` is 0.
+///
+/// # Examples
+///
+/// ```
+/// use firefox_hashbytes::{hash_bytes, hash_bytes_parallel, hash_combine};
+///
+/// let data = vec![0xabu8; 1 << 16];
+/// let parallel = hash_bytes_parallel(&data, 1 << 12);
+/// // Equivalent serial computation: per-chunk hashes combined in order
+/// let expected = data
+///     .chunks(1 << 12)
+///     .map(|chunk| hash_bytes(chunk, 0))
+///     .reduce(hash_combine)
+///     .unwrap();
+/// assert_eq!(parallel, expected);
+/// ```
+pub fn hash_bytes_parallel(bytes: &[u8], chunk_size: usize) -> HashNumber {
+    assert!(chunk_size > 0, "chunk_size must be non-zero");
+
+    if bytes.len() <= chunk_size {
+        return hash_bytes(bytes, 0);
+    }
+
+    let chunks: Vec<&[u8]> = bytes.chunks(chunk_size).collect();
+    let workers = std::thread::available_parallelism()
+        .map(|n| n.get())
+        .unwrap_or(1)
+        .min(chunks.len());
+
+    let chunk_hashes: Vec<HashNumber> = if workers <= 1 {
+        chunks.iter().map(|chunk| hash_bytes(chunk, 0)).collect()
+    } else {
+        // Give each worker a contiguous run of chunks and join in order,
+        // so the collected hashes come back in buffer order
+        let per_worker = chunks.len().div_ceil(workers);
+        std::thread::scope(|scope| {
+            let handles: Vec<_> = chunks
+                .chunks(per_worker)
+                .map(|group| {
+                    scope.spawn(move || {
+                        group
+                            .iter()
+                            .map(|chunk| hash_bytes(chunk, 0))
+                            .collect::<Vec<_>>()
+                    })
+                })
+                .collect();
+            handles
+                .into_iter()
+                .flat_map(|handle| handle.join().expect("hash worker panicked"))
+                .collect()
+        })
+    };
+
+    chunk_hashes
+        .into_iter()
+        .reduce(hash_combine)
+        .expect("at least one chunk")
+}
+
+/// A value that can be folded into a hash, for [`hash_generic!`].
+///
+/// Mirrors the scalar overload set of `mozilla::AddToHash`: every type no
+/// wider than 32 bits is zero/sign-extended to `u32` and mixed with
+/// [`add_u32_to_hash`], so mixed-type combinations hash identically to the
+/// C++ template.
+pub trait HashValue {
+    /// Fold `self` into `hash` and return the updated hash.
+    fn add_to_hash(self, hash: HashNumber) -> HashNumber;
+}
+
+macro_rules! impl_hash_value_via_u32 {
+    ($($ty:ty),+) => {
+        $(impl HashValue for $ty {
+            #[inline]
+            fn add_to_hash(self, hash: HashNumber) -> HashNumber {
+                add_u32_to_hash(hash, self as u32)
+            }
+        })+
+    };
+}
+
+impl_hash_value_via_u32!(u8, u16, u32, i8, i16, i32, char, bool);
+
+macro_rules! impl_hash_value_via_u64 {
+    ($($ty:ty),+) => {
+        $(impl HashValue for $ty {
+            #[inline]
+            fn add_to_hash(self, hash: HashNumber) -> HashNumber {
+                add_u64_to_hash(hash, self as u64)
+            }
+        })+
+    };
+}
+
+impl_hash_value_via_u64!(u64, i64);
+
+impl HashValue for usize {
+    #[inline]
+    fn add_to_hash(self, hash: HashNumber) -> HashNumber {
+        add_usize_to_hash(hash, self)
+    }
+}
+
+impl HashValue for isize {
+    #[inline]
+    fn add_to_hash(self, hash: HashNumber) -> HashNumber {
+        add_usize_to_hash(hash, self as usize)
+    }
+}
+
+impl<T> HashValue for *const T {
+    #[inline]
+    fn add_to_hash(self, hash: HashNumber) -> HashNumber {
+        add_pointer_to_hash(hash, self)
+    }
+}
+
+impl<T> HashValue for *mut T {
+    #[inline]
+    fn add_to_hash(self, hash: HashNumber) -> HashNumber {
+        add_pointer_to_hash(hash, self as *const T)
+    }
+}
+
+/// Hash any number of scalar values into one code, matching
+/// `mozilla::HashGeneric(...)`.
+///
+/// The values are folded left-to-right into a hash starting at 0, exactly
+/// as `HashGeneric` folds its arguments through `AddToHash`.
+///
+/// # Examples
+///
+/// ```
+/// use firefox_hashbytes::{add_u32_to_hash, hash_generic};
+///
+/// let hash = hash_generic!(1u32, 2u16, true);
+/// assert_eq!(
+///     hash,
+///     add_u32_to_hash(add_u32_to_hash(add_u32_to_hash(0, 1), 2), 1)
+/// );
+/// ```
+#[macro_export]
+macro_rules! hash_generic {
+    ($($value:expr),+ $(,)?) => {{
+        let mut hash: $crate::HashNumber = 0;
+        $(hash = $crate::HashValue::add_to_hash($value, hash);)+
+        hash
+    }};
+}
+
+/// Combine two hash codes into one.
+///
+/// This is the canonical way to merge independently computed hashes in
+/// this workspace — other crates should call this rather than invent
+/// their own combiners. It folds `h2` into `h1` through the same
+/// golden-ratio mixer every other entry point uses, so it is exactly
+/// `AddToHash(h1, h2)` in C++ terms: order matters, and
+/// `hash_combine(h, x)` equals hashing `x` as one more value of an
+/// ongoing hash.
+///
+/// # Examples
+///
+/// ```
+/// use firefox_hashbytes::{hash_bytes, hash_combine};
+///
+/// let name_hash = hash_bytes(b"name", 0);
+/// let value_hash = hash_bytes(b"value", 0);
+/// let entry_hash = hash_combine(name_hash, value_hash);
+/// assert_ne!(entry_hash, hash_combine(value_hash, name_hash));
+/// ```
+#[inline]
+pub const fn hash_combine(h1: HashNumber, h2: HashNumber) -> HashNumber {
+    add_u32_to_hash(h1, h2)
+}
+
+/// Combine any number of hash codes left-to-right with [`hash_combine`].
+///
+/// `hash_combine!(a, b, c)` is `hash_combine(hash_combine(a, b), c)`.
+/// Unlike [`hash_generic!`], the arguments here are already hash codes,
+/// not raw values, and the first one seeds the fold directly.
+///
+/// # Examples
+///
+/// ```
+/// use firefox_hashbytes::hash_combine;
+///
+/// let combined = firefox_hashbytes::hash_combine!(1, 2, 3);
+/// assert_eq!(combined, hash_combine(hash_combine(1, 2), 3));
+/// ```
+#[macro_export]
+macro_rules! hash_combine {
+    ($first:expr $(, $rest:expr)+ $(,)?) => {{
+        let mut hash: $crate::HashNumber = $first;
+        $(hash = $crate::hash_combine(hash, $rest);)+
+        hash
+    }};
+}
+
 // FFI layer for C++ interop
 pub mod ffi;
 
+// std::hash adapter (MozHasher / MozBuildHasher)
+pub mod hasher;
+
+pub use hasher::{MozBuildHasher, MozHasher};
+
+// Chunking-independent streaming hasher
+pub mod stream;
+
+pub use stream::HashBytesStream;
+
 #[cfg(test)]
 mod tests;
+
+// Differential tests against C++-generated golden vectors; see the
+// module docs for when and how to regenerate the vector file
+#[cfg(all(test, feature = "golden-vectors"))]
+mod golden_tests;
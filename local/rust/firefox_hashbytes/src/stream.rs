@@ -0,0 +1,206 @@
+//! Streaming incremental hashing
+//!
+//! The chaining form of [`crate::hash_bytes`] (passing the previous
+//! result as `starting_hash`) does NOT produce the same value as hashing
+//! the concatenated buffer: each call processes its own tail bytes
+//! individually, so word boundaries land differently. That's fine for
+//! combining distinct keys, but wrong for hashing one logical byte
+//! stream — a network body arriving in arbitrary chunks must hash the
+//! same however it was split.
+//!
+//! [`HashBytesStream`] fixes this by carrying sub-word bytes across
+//! `write` calls: words are only mixed once a full word's worth of bytes
+//! has arrived, and only `finish` mixes the final partial word. The
+//! result is guaranteed identical to `hash_bytes` of the concatenation.
+
+use crate::{add_u32_to_hash, HashNumber};
+
+/// Bytes per mixing word (platform word size, matching `hash_bytes`)
+const WORD_SIZE: usize = std::mem::size_of::<usize>();
+
+/// An incremental hasher whose result is independent of chunking.
+///
+/// # Examples
+///
+/// ```
+/// use firefox_hashbytes::{hash_bytes, HashBytesStream};
+///
+/// let mut stream = HashBytesStream::new();
+/// stream.write(b"hello ");
+/// stream.write(b"wor");
+/// stream.write(b"ld");
+/// assert_eq!(stream.finish(), hash_bytes(b"hello world", 0));
+/// ```
+#[derive(Debug, Clone)]
+pub struct HashBytesStream {
+    /// Hash over all complete words seen so far
+    hash: HashNumber,
+    /// Sub-word bytes carried to the next write (or to finish)
+    pending: [u8; WORD_SIZE],
+    pending_len: usize,
+}
+
+impl HashBytesStream {
+    /// Create a stream with a starting hash of 0.
+    pub fn new() -> Self {
+        Self::with_starting_hash(0)
+    }
+
+    /// Create a stream chaining from an existing hash value, like the
+    /// `starting_hash` parameter of [`crate::hash_bytes`].
+    pub fn with_starting_hash(starting_hash: HashNumber) -> Self {
+        Self {
+            hash: starting_hash,
+            pending: [0; WORD_SIZE],
+            pending_len: 0,
+        }
+    }
+
+    /// Mix one complete word, exactly like the word loop in `hash_bytes`
+    fn mix_word(&mut self, word: usize) {
+        self.hash = add_u32_to_hash(self.hash, word as u32);
+        if WORD_SIZE == 8 {
+            self.hash = add_u32_to_hash(self.hash, (word as u64 >> 32) as u32);
+        }
+    }
+
+    /// Feed the next chunk of the stream, in any size including empty.
+    pub fn write(&mut self, mut bytes: &[u8]) {
+        // Top up a partial word carried from earlier writes first
+        if self.pending_len > 0 {
+            let take = bytes.len().min(WORD_SIZE - self.pending_len);
+            self.pending[self.pending_len..self.pending_len + take]
+                .copy_from_slice(&bytes[..take]);
+            self.pending_len += take;
+            bytes = &bytes[take..];
+            if self.pending_len < WORD_SIZE {
+                return;
+            }
+            self.mix_word(usize::from_le_bytes(self.pending));
+            self.pending_len = 0;
+        }
+
+        // Mix complete words; from_le_bytes matches the little-endian
+        // word reads in hash_bytes
+        let mut chunks = bytes.chunks_exact(WORD_SIZE);
+        for chunk in &mut chunks {
+            self.mix_word(usize::from_le_bytes(chunk.try_into().unwrap()));
+        }
+
+        // Carry the tail to the next write
+        let tail = chunks.remainder();
+        self.pending[..tail.len()].copy_from_slice(tail);
+        self.pending_len = tail.len();
+    }
+
+    /// The hash of everything written so far, as if it had been one
+    /// buffer.
+    ///
+    /// Does not consume or reset the stream: more bytes can be written
+    /// afterwards, and a later `finish` reflects the longer stream.
+    pub fn finish(&self) -> HashNumber {
+        // The final partial word is mixed byte-by-byte, matching the
+        // tail loop in hash_bytes
+        let mut hash = self.hash;
+        for &byte in &self.pending[..self.pending_len] {
+            hash = add_u32_to_hash(hash, byte as u32);
+        }
+        hash
+    }
+
+    /// Total bytes currently carried (0 to one word's worth); mainly for
+    /// diagnostics.
+    pub fn pending_bytes(&self) -> usize {
+        self.pending_len
+    }
+}
+
+impl Default for HashBytesStream {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::hash_bytes;
+
+    /// Hash `data` through the stream split at every position in `splits`
+    fn hash_split(data: &[u8], splits: &[usize]) -> HashNumber {
+        let mut stream = HashBytesStream::new();
+        let mut start = 0;
+        for &split in splits {
+            stream.write(&data[start..split]);
+            start = split;
+        }
+        stream.write(&data[start..]);
+        stream.finish()
+    }
+
+    #[test]
+    fn test_matches_one_shot_for_every_split_point() {
+        let data: Vec<u8> = (0..64).map(|i| (i * 13 % 256) as u8).collect();
+        let expected = hash_bytes(&data, 0);
+        for split in 0..=data.len() {
+            assert_eq!(hash_split(&data, &[split]), expected, "split at {split}");
+        }
+    }
+
+    #[test]
+    fn test_matches_one_shot_for_many_small_chunks() {
+        let data: Vec<u8> = (0..1000).map(|i| (i % 251) as u8).collect();
+        let expected = hash_bytes(&data, 0);
+
+        // Byte-at-a-time
+        let mut stream = HashBytesStream::new();
+        for &byte in &data {
+            stream.write(std::slice::from_ref(&byte));
+        }
+        assert_eq!(stream.finish(), expected);
+
+        // Irregular chunk sizes, including empty writes
+        let splits: Vec<usize> = vec![1, 2, 10, 10, 17, 100, 500, 997];
+        assert_eq!(hash_split(&data, &splits), expected);
+    }
+
+    #[test]
+    fn test_empty_and_starting_hash() {
+        assert_eq!(HashBytesStream::new().finish(), hash_bytes(b"", 0));
+
+        let mut stream = HashBytesStream::with_starting_hash(42);
+        assert_eq!(stream.finish(), 42);
+        stream.write(b"data");
+        assert_eq!(stream.finish(), hash_bytes(b"data", 42));
+    }
+
+    #[test]
+    fn test_finish_is_non_destructive() {
+        let mut stream = HashBytesStream::new();
+        stream.write(b"hello ");
+        let mid = stream.finish();
+        assert_eq!(mid, hash_bytes(b"hello ", 0));
+        stream.write(b"world");
+        assert_eq!(stream.finish(), hash_bytes(b"hello world", 0));
+    }
+
+    #[test]
+    fn test_chaining_api_differs_on_word_straddling_splits() {
+        // Documents the problem this type exists to solve: plain
+        // chaining diverges when a split straddles a word boundary
+        let data: Vec<u8> = (0..32).collect();
+        let chained = hash_bytes(&data[5..], hash_bytes(&data[..5], 0));
+        assert_ne!(chained, hash_bytes(&data, 0));
+        assert_eq!(hash_split(&data, &[5]), hash_bytes(&data, 0));
+    }
+
+    #[test]
+    fn test_pending_bytes_reporting() {
+        let mut stream = HashBytesStream::new();
+        assert_eq!(stream.pending_bytes(), 0);
+        stream.write(b"abc");
+        assert_eq!(stream.pending_bytes(), 3);
+        stream.write(&[0u8; WORD_SIZE][..WORD_SIZE - 3]);
+        assert_eq!(stream.pending_bytes(), 0);
+    }
+}
@@ -45,11 +45,14 @@ fn test_rotate_left5() {
     assert_eq!(rotate_left5(1), 32); // 1 << 5
     assert_eq!(rotate_left5(0x80000000), 16); // High bit rotates: 0x8000_0000 >> 27 = 16
     
-    // Test that it's a true rotation (no bits lost)
+    // Test that it's a true rotation (no bits lost) against the C++
+    // shift-or formulation
+    #[allow(clippy::manual_rotate)]
+    fn reference(value: u32) -> u32 {
+        (value << 5) | (value >> 27)
+    }
     let value = 0x12345678;
-    let rotated = rotate_left5(value);
-    let expected = (value << 5) | (value >> 27);
-    assert_eq!(rotated, expected);
+    assert_eq!(rotate_left5(value), reference(value));
 }
 
 #[test]
@@ -158,7 +161,7 @@ fn test_large_buffer() {
 #[test]
 fn test_all_zeros() {
     let data = vec![0u8; 100];
-    let hash = hash_bytes(&data, 0);
+    let _hash = hash_bytes(&data, 0);
     // Actually, all zeros with starting_hash=0 produces 0 because:
     // add_u32_to_hash(0, 0) = GOLDEN_RATIO * (rotate_left5(0) ^ 0) = GOLDEN_RATIO * 0 = 0
     // This is correct behavior - the hash mixes the input, and zero input gives zero
@@ -208,8 +211,8 @@ fn test_wrapping_behavior() {
     // Use values that would overflow if not wrapped
     let data = vec![0xFFu8; 100];
     let hash = hash_bytes(&data, 0xFFFFFFFF);
-    // Should not panic and should produce some result
-    assert!(hash <= 0xFFFFFFFF); // Always true for u32, but validates no panic
+    // Should not panic and should produce a deterministic result
+    assert_eq!(hash, hash_bytes(&data, 0xFFFFFFFF));
 }
 
 /// Test to verify the implementation matches expected C++ behavior
@@ -236,6 +239,214 @@ fn test_known_values() {
     }
 }
 
+#[test]
+fn test_hash_string_matches_per_char_mixing() {
+    // The known-length C++ overload mixes one char at a time
+    let mut expected = 0;
+    for &byte in b"hello" {
+        expected = add_u32_to_hash(expected, byte as u32);
+    }
+    assert_eq!(hash_string("hello"), expected);
+    assert_eq!(hash_string(""), 0);
+    assert_ne!(hash_string("hello"), hash_string("hellp"));
+}
+
+#[test]
+fn test_hash_string_u16_matches_per_unit_mixing() {
+    let units: Vec<u16> = "héllo".encode_utf16().collect();
+    let mut expected = 0;
+    for &unit in &units {
+        expected = add_u32_to_hash(expected, unit as u32);
+    }
+    assert_eq!(hash_string_u16(&units), expected);
+    assert_eq!(hash_string_u16(&[]), 0);
+
+    // ASCII text hashes the same through both widths only if each unit
+    // equals the byte, which holds here
+    let ascii: Vec<u16> = "hash".encode_utf16().collect();
+    assert_eq!(hash_string_u16(&ascii), hash_string("hash"));
+}
+
+#[test]
+fn test_hash_cstring_matches_hash_string() {
+    let c_string = std::ffi::CString::new("hello world").unwrap();
+    let hash = unsafe { hash_cstring(c_string.as_ptr()) };
+    assert_eq!(hash, hash_string("hello world"));
+
+    let empty = std::ffi::CString::new("").unwrap();
+    assert_eq!(unsafe { hash_cstring(empty.as_ptr()) }, 0);
+}
+
+#[test]
+fn test_hash_generic_folds_left_to_right() {
+    assert_eq!(hash_generic!(42u32), add_u32_to_hash(0, 42));
+    assert_eq!(
+        hash_generic!(1u8, 2u16, 3u32),
+        add_u32_to_hash(add_u32_to_hash(add_u32_to_hash(0, 1), 2), 3)
+    );
+    // bool and char extend to u32 like the C++ template arguments
+    assert_eq!(
+        hash_generic!(true, 'A'),
+        add_u32_to_hash(add_u32_to_hash(0, 1), 65)
+    );
+    // Order matters
+    assert_ne!(hash_generic!(1u32, 2u32), hash_generic!(2u32, 1u32));
+}
+
+#[test]
+fn test_add_u64_to_hash_low_then_high() {
+    let value = 0x1122334455667788u64;
+    assert_eq!(
+        add_u64_to_hash(7, value),
+        add_u32_to_hash(add_u32_to_hash(7, 0x55667788), 0x11223344)
+    );
+    // A value that fits in 32 bits still mixes both halves (the zero
+    // high word changes the hash), matching the C++ 64-bit overload
+    assert_ne!(add_u64_to_hash(0, 42), add_u32_to_hash(0, 42));
+}
+
+#[test]
+fn test_add_usize_and_pointer_to_hash() {
+    let value = 0xDEADBEEFusize;
+    #[cfg(target_pointer_width = "64")]
+    assert_eq!(add_usize_to_hash(0, value), add_u64_to_hash(0, value as u64));
+    #[cfg(target_pointer_width = "32")]
+    assert_eq!(add_usize_to_hash(0, value), add_u32_to_hash(0, value as u32));
+
+    // Pointers hash by address only
+    let x = 5u32;
+    let ptr = &x as *const u32;
+    assert_eq!(add_pointer_to_hash(0, ptr), add_usize_to_hash(0, ptr as usize));
+    assert_eq!(
+        add_pointer_to_hash(0, std::ptr::null::<u32>()),
+        add_usize_to_hash(0, 0)
+    );
+}
+
+#[test]
+fn test_scramble_hash_code() {
+    assert_eq!(scramble_hash_code(0), 0);
+    assert_eq!(scramble_hash_code(1), GOLDEN_RATIO_U32);
+    assert_eq!(
+        scramble_hash_code(0x12345678),
+        0x12345678u32.wrapping_mul(GOLDEN_RATIO_U32)
+    );
+
+    // The point of the scramble: consecutive small codes (poor low-bit
+    // entropy) land in distinct high bits
+    let a = scramble_hash_code(1) >> 24;
+    let b = scramble_hash_code(2) >> 24;
+    let c = scramble_hash_code(3) >> 24;
+    assert!(a != b && b != c && a != c);
+}
+
+#[test]
+fn test_hash_generic_wide_values() {
+    // 64-bit and pointer-sized values fold through the two-halves path
+    assert_eq!(
+        hash_generic!(0x1122334455667788u64),
+        add_u64_to_hash(0, 0x1122334455667788)
+    );
+    assert_eq!(hash_generic!(7usize), add_usize_to_hash(0, 7));
+    let x = 1u8;
+    let ptr = &x as *const u8;
+    assert_eq!(hash_generic!(ptr), add_pointer_to_hash(0, ptr));
+    // Mixed widths chain in order
+    assert_eq!(
+        hash_generic!(1u32, 2u64),
+        add_u64_to_hash(add_u32_to_hash(0, 1), 2)
+    );
+}
+
+#[test]
+fn test_hash_bytes64_basics() {
+    // Empty input returns the starting hash unchanged
+    assert_eq!(hash_bytes64(b"", 0), 0);
+    assert_eq!(hash_bytes64(b"", 99), 99);
+
+    // Deterministic, input-sensitive, order-sensitive
+    assert_eq!(hash_bytes64(b"hello", 0), hash_bytes64(b"hello", 0));
+    assert_ne!(hash_bytes64(b"hello", 0), hash_bytes64(b"world", 0));
+    assert_ne!(hash_bytes64(b"ab", 0), hash_bytes64(b"ba", 0));
+    assert_ne!(hash_bytes64(b"x", 0), hash_bytes64(b"x", 1));
+}
+
+#[test]
+fn test_hash_bytes64_word_boundaries() {
+    // Exactly one 8-byte word equals one mixing step
+    let word = 0x1122334455667788u64.to_le_bytes();
+    assert_eq!(hash_bytes64(&word, 0), add_u64_to_hash64(0, 0x1122334455667788));
+
+    // Sub-word input mixes byte-by-byte
+    let mut expected = 0;
+    for &byte in b"abc" {
+        expected = add_u64_to_hash64(expected, byte as u64);
+    }
+    assert_eq!(hash_bytes64(b"abc", 0), expected);
+
+    // One word plus a tail combines both paths
+    let mut data = word.to_vec();
+    data.push(0x42);
+    let expected = add_u64_to_hash64(add_u64_to_hash64(0, 0x1122334455667788), 0x42);
+    assert_eq!(hash_bytes64(&data, 0), expected);
+}
+
+#[test]
+fn test_hash_bytes64_spreads_into_high_bits() {
+    // The point of the 64-bit variant: results actually use the upper
+    // word of the hash space
+    let hashes = [
+        hash_bytes64(b"cache key 1", 0),
+        hash_bytes64(b"cache key 2", 0),
+        hash_bytes64(b"cache key 3", 0),
+    ];
+    assert!(hashes.iter().any(|h| *h > u32::MAX as u64));
+}
+
+#[test]
+fn test_fast_path_matches_scalar_all_small_lengths() {
+    // Differential guard for the unrolled/dispatched path: every length
+    // around the word- and unroll-boundaries, with patterned bytes and a
+    // non-zero starting hash
+    for len in 0..=257usize {
+        let data: Vec<u8> = (0..len).map(|i| (i.wrapping_mul(31) ^ 0x5A) as u8).collect();
+        assert_eq!(
+            hash_bytes(&data, 0),
+            hash_bytes_scalar(&data, 0),
+            "length {len} diverged"
+        );
+        assert_eq!(
+            hash_bytes(&data, 0xDEADBEEF),
+            hash_bytes_scalar(&data, 0xDEADBEEF),
+            "length {len} with starting hash diverged"
+        );
+    }
+}
+
+#[test]
+fn test_fast_path_matches_scalar_large_buffers() {
+    // The benchmark sizes: 64B up to 64KB
+    for size in [64usize, 256, 1024, 4096, 16384, 65536] {
+        let data: Vec<u8> = (0..size).map(|i| (i % 251) as u8).collect();
+        assert_eq!(
+            hash_bytes(&data, 0),
+            hash_bytes_scalar(&data, 0),
+            "size {size} diverged"
+        );
+    }
+}
+
+#[test]
+fn test_fast_path_handles_unaligned_starts() {
+    // Slicing at odd offsets exercises the unaligned word reads in the
+    // unrolled loop
+    let backing: Vec<u8> = (0..4096).map(|i| (i * 7 % 256) as u8).collect();
+    for offset in 1..8 {
+        let data = &backing[offset..offset + 1024];
+        assert_eq!(hash_bytes(data, 0), hash_bytes_scalar(data, 0));
+    }
+}
+
 #[test]
 fn test_boundary_conditions() {
     // Test various boundary conditions
@@ -274,3 +485,233 @@ fn test_word_size_independence() {
     
     assert_eq!(hash, expected, "Small data should hash byte-by-byte");
 }
+
+#[test]
+fn test_const_hash_matches_runtime() {
+    // Evaluated at compile time
+    const LITERAL_HASH: HashNumber = hash_bytes_const(b"onreadystatechange", 0);
+    assert_eq!(LITERAL_HASH, hash_bytes(b"onreadystatechange", 0));
+
+    const CHAINED: HashNumber = hash_bytes_const(b"tail", hash_bytes_const(b"head", 0));
+    assert_eq!(CHAINED, hash_bytes(b"tail", hash_bytes(b"head", 0)));
+
+    const EMPTY: HashNumber = hash_bytes_const(b"", 7);
+    assert_eq!(EMPTY, 7);
+}
+
+#[test]
+fn test_const_hash_matches_runtime_all_lengths() {
+    // Cover word boundaries and both fast-path thresholds at runtime
+    let data: Vec<u8> = (0..200).map(|i| (i * 31 % 256) as u8).collect();
+    for len in 0..=data.len() {
+        assert_eq!(
+            hash_bytes_const(&data[..len], 5),
+            hash_bytes(&data[..len], 5),
+            "length {len}"
+        );
+    }
+}
+
+#[test]
+fn test_const_hash_usable_in_static_table() {
+    // The motivating use: a static table keyed by precomputed hashes
+    static ATOM_HASHES: [(HashNumber, &str); 3] = [
+        (hash_bytes_const(b"div", 0), "div"),
+        (hash_bytes_const(b"span", 0), "span"),
+        (hash_bytes_const(b"table", 0), "table"),
+    ];
+    for (hash, name) in ATOM_HASHES {
+        assert_eq!(hash, hash_bytes(name.as_bytes(), 0));
+    }
+}
+
+#[test]
+fn test_hash_combine_matches_mixer() {
+    // hash_combine is the same step every other entry point uses
+    assert_eq!(hash_combine(0, 5), add_u32_to_hash(0, 5));
+    assert_eq!(hash_combine(0xdead, 0xbeef), add_u32_to_hash(0xdead, 0xbeef));
+
+    // Order-sensitive, like AddToHash
+    assert_ne!(hash_combine(1, 2), hash_combine(2, 1));
+
+    // Usable in const contexts like the rest of the mixer family
+    const COMBINED: HashNumber = hash_combine(3, 4);
+    assert_eq!(COMBINED, add_u32_to_hash(3, 4));
+}
+
+#[test]
+fn test_hash_combine_macro_folds_left_to_right() {
+    let a = hash_bytes(b"a", 0);
+    let b = hash_bytes(b"b", 0);
+    let c = hash_bytes(b"c", 0);
+    assert_eq!(hash_combine!(a, b), hash_combine(a, b));
+    assert_eq!(hash_combine!(a, b, c), hash_combine(hash_combine(a, b), c));
+    // Trailing comma accepted, matching hash_generic!
+    assert_eq!(hash_combine!(a, b, c,), hash_combine!(a, b, c));
+}
+
+#[test]
+fn test_portable_hash_fixed_word_size() {
+    // Reference: 4-byte little-endian words, then byte tail
+    let data: Vec<u8> = (0..23).collect();
+    let mut expected = 9;
+    for chunk in data.chunks(4) {
+        if chunk.len() == 4 {
+            let word = u32::from_le_bytes(chunk.try_into().unwrap());
+            expected = add_u32_to_hash(expected, word);
+        } else {
+            for &byte in chunk {
+                expected = add_u32_to_hash(expected, byte as u32);
+            }
+        }
+    }
+    assert_eq!(hash_bytes_portable(&data, 9), expected);
+
+    // Sub-word inputs hash byte-by-byte, same as hash_bytes
+    for len in 0..4 {
+        assert_eq!(
+            hash_bytes_portable(&data[..len], 3),
+            hash_bytes(&data[..len], 3)
+        );
+    }
+
+    // On 64-bit targets the native-word hash diverges at 8+ bytes —
+    // exactly the instability this variant exists to avoid
+    if std::mem::size_of::<usize>() == 8 {
+        assert_ne!(hash_bytes_portable(&data, 0), hash_bytes(&data, 0));
+    }
+
+    // Usable in const contexts, like hash_bytes_const
+    const PORTABLE: HashNumber = hash_bytes_portable(b"stable key", 0);
+    assert_eq!(PORTABLE, hash_bytes_portable(b"stable key", 0));
+}
+
+#[test]
+fn test_u16_latin1_fast_path_matches_generic() {
+    // All-Latin-1 input takes the narrow fast path
+    let latin1: Vec<u16> = (0..=0xFFu16).collect();
+    assert_eq!(hash_string_u16(&latin1), hash_string_u16_generic(&latin1));
+
+    // ASCII content agrees with the narrow-string hash as before
+    let ascii: Vec<u16> = "onreadystatechange".encode_utf16().collect();
+    assert_eq!(hash_string_u16(&ascii), hash_string("onreadystatechange"));
+
+    // A single unit over 0xFF forces the generic path; placing it at
+    // either end catches a short-circuiting scan bug
+    for position in [0, 63] {
+        let mut units = vec![0x41u16; 64];
+        units[position] = 0x100;
+        assert_eq!(hash_string_u16(&units), hash_string_u16_generic(&units));
+    }
+
+    // Non-Latin-1 text (surrogates included) is unchanged
+    let wide: Vec<u16> = "héllo 世界 🦊".encode_utf16().collect();
+    assert!(wide.iter().any(|&unit| unit > 0xFF));
+    assert_eq!(hash_string_u16(&wide), hash_string_u16_generic(&wide));
+
+    assert_eq!(hash_string_u16(&[]), 0);
+}
+
+#[test]
+fn test_parallel_hash_matches_serial_combination() {
+    let data: Vec<u8> = (0..100_000).map(|i| (i % 251) as u8).collect();
+    for chunk_size in [1, 7, 4096, 65536] {
+        let expected = data
+            .chunks(chunk_size)
+            .map(|chunk| hash_bytes(chunk, 0))
+            .reduce(hash_combine)
+            .unwrap();
+        assert_eq!(
+            hash_bytes_parallel(&data, chunk_size),
+            expected,
+            "chunk_size {chunk_size}"
+        );
+    }
+}
+
+#[test]
+fn test_parallel_hash_mode_properties() {
+    let data: Vec<u8> = (0..10_000).map(|i| (i % 256) as u8).collect();
+
+    // Single-chunk inputs degenerate to the plain hash
+    assert_eq!(hash_bytes_parallel(&data, data.len()), hash_bytes(&data, 0));
+    assert_eq!(hash_bytes_parallel(&[], 4096), 0);
+
+    // A distinct mode: not equal to the serial hash, and chunk-size
+    // dependent — both documented, both load-bearing for consumers
+    assert_ne!(hash_bytes_parallel(&data, 1024), hash_bytes(&data, 0));
+    assert_ne!(
+        hash_bytes_parallel(&data, 1024),
+        hash_bytes_parallel(&data, 2048)
+    );
+
+    // Deterministic across repeated runs (thread scheduling must not
+    // leak into the result)
+    let first = hash_bytes_parallel(&data, 333);
+    for _ in 0..10 {
+        assert_eq!(hash_bytes_parallel(&data, 333), first);
+    }
+}
+
+#[test]
+#[should_panic(expected = "chunk_size must be non-zero")]
+fn test_parallel_hash_rejects_zero_chunk_size() {
+    hash_bytes_parallel(b"data", 0);
+}
+
+// Fixed expected values for the endianness tests below, valid on any
+// host byte order now that word reads are explicitly little-endian.
+// The hash_bytes values still depend on the native word size, so they
+// are only asserted on 64-bit targets; hash_bytes64 and
+// hash_bytes_portable use fixed word sizes and hold everywhere.
+
+#[test]
+#[cfg(target_pointer_width = "64")]
+fn test_endian_independent_vectors_hash_bytes() {
+    let ramp64: Vec<u8> = (0..64).collect();
+    let ramp16: Vec<u8> = (0..16).collect();
+    assert_eq!(hash_bytes(b"hello world, hash me", 0), 0x9726fac2);
+    assert_eq!(hash_bytes(&ramp64, 0), 0x96b6ff29);
+    assert_eq!(hash_bytes(&ramp16, 0xcafe), 0x7b1c8e84);
+    assert_eq!(hash_bytes(b"\x01\x02\x03\x04\x05\x06\x07\x08", 0), 0x8d1c883e);
+}
+
+#[test]
+fn test_endian_independent_vectors_hash_bytes64() {
+    let ramp64: Vec<u8> = (0..64).collect();
+    let ramp16: Vec<u8> = (0..16).collect();
+    assert_eq!(hash_bytes64(b"hello world, hash me", 0), 0xbb4fd06bc415ef43);
+    assert_eq!(hash_bytes64(&ramp64, 0), 0x5dd890c5c6a3f7d3);
+    assert_eq!(hash_bytes64(&ramp16, 0xcafe), 0xaecc23967bd5aae3);
+    assert_eq!(
+        hash_bytes64(b"\x01\x02\x03\x04\x05\x06\x07\x08", 0),
+        0xa8447ef0dc81a615
+    );
+}
+
+#[test]
+fn test_endian_independent_vectors_hash_bytes_portable() {
+    let ramp64: Vec<u8> = (0..64).collect();
+    let ramp16: Vec<u8> = (0..16).collect();
+    assert_eq!(hash_bytes_portable(b"hello world, hash me", 0), 0x11dc3d18);
+    assert_eq!(hash_bytes_portable(&ramp64, 0), 0x96b6ff29);
+    assert_eq!(hash_bytes_portable(&ramp16, 0xcafe), 0x7b1c8e84);
+    assert_eq!(
+        hash_bytes_portable(b"\x01\x02\x03\x04\x05\x06\x07\x08", 0),
+        0x8d1c883e
+    );
+}
+
+#[test]
+fn test_le_reads_consistent_across_all_paths() {
+    // Scalar, unrolled/dispatch, const, and streaming paths must all
+    // apply the same little-endian word mapping
+    let data: Vec<u8> = (0..300).map(|i| (i * 7 % 256) as u8).collect();
+    let expected = hash_bytes_scalar(&data, 1);
+    assert_eq!(hash_bytes(&data, 1), expected);
+    assert_eq!(hash_bytes_const(&data, 1), expected);
+    let mut stream = HashBytesStream::with_starting_hash(1);
+    stream.write(&data[..113]);
+    stream.write(&data[113..]);
+    assert_eq!(stream.finish(), expected);
+}
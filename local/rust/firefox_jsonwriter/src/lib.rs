@@ -74,7 +74,30 @@
 ///
 /// # Thread Safety
 /// This is a const lookup table, safe for concurrent access from multiple threads.
-pub static TWO_CHAR_ESCAPES: [i8; 256] = [
+///
+/// # Construction
+/// The table is built through the shared `CharTable` abstraction from
+/// `firefox_asciimask`, the single audited code path for the ported lookup
+/// tables. Only the seven populated entries are listed; everything else
+/// defaults to zero.
+pub static TWO_CHAR_ESCAPES: [i8; 256] = firefox_asciimask::CharTable::with_entries(
+    0,
+    &[
+        (0x08, b'b' as i8),  // \b backspace
+        (0x09, b't' as i8),  // \t tab
+        (0x0A, b'n' as i8),  // \n newline
+        (0x0C, b'f' as i8),  // \f form feed
+        (0x0D, b'r' as i8),  // \r carriage return
+        (0x22, b'"' as i8),  // \" double quote
+        (0x5C, b'\\' as i8), // \\ backslash
+    ],
+)
+.into_array();
+
+/// Historical expanded form of the table, kept as documentation of the full
+/// layout and verified equal to the `CharTable`-built version by the tests.
+#[cfg(test)]
+static TWO_CHAR_ESCAPES_EXPANDED: [i8; 256] = [
     // Row 0 (0x00-0x09): Control characters
     // 0x00-0x07: NULL through BEL - no two-char escape (use \uXXXX)
     0, 0, 0, 0, 0, 0, 0, 0,
@@ -160,6 +183,13 @@ mod tests {
         assert_eq!(std::mem::size_of_val(&TWO_CHAR_ESCAPES), 256);
     }
 
+    #[test]
+    fn test_table_matches_expanded_form() {
+        // The CharTable-built table must be byte-identical to the original
+        // fully written-out array
+        assert_eq!(TWO_CHAR_ESCAPES, TWO_CHAR_ESCAPES_EXPANDED);
+    }
+
     #[test]
     fn test_escape_mappings() {
         // Test all seven two-char escapes
@@ -0,0 +1,84 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+//! Differential fuzz target: firefox_nscrt::strtok vs a safe strtok_r
+//! reference. The deterministic slice of this coverage lives in the
+//! crate's `differential_tests` module; this target explores the input
+//! space with libFuzzer. Any divergence in token sequence or buffer
+//! mutation panics, which libFuzzer reports with the offending input.
+
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use std::ptr;
+
+/// The expected strtok_r behavior: non-empty tokens split on `delims`,
+/// and the buffer with each token's terminating delimiter NUL-ed.
+fn reference_tokenize(input: &[u8], delims: &[u8]) -> (Vec<Vec<u8>>, Vec<u8>) {
+    let mut expected_buf = input.to_vec();
+    let mut tokens = Vec::new();
+    let mut pos = 0;
+
+    loop {
+        while pos < input.len() && delims.contains(&input[pos]) {
+            pos += 1;
+        }
+        if pos == input.len() {
+            break;
+        }
+        let start = pos;
+        while pos < input.len() && !delims.contains(&input[pos]) {
+            pos += 1;
+        }
+        tokens.push(input[start..pos].to_vec());
+        if pos < input.len() {
+            expected_buf[pos] = 0;
+            pos += 1;
+        }
+    }
+
+    (tokens, expected_buf)
+}
+
+fuzz_target!(|data: &[u8]| {
+    // Layout: first byte is the delimiter count (1-4), then that many
+    // delimiter bytes, then the input. NUL bytes are dropped from both:
+    // they are string terminators in this API, not data.
+    if data.len() < 2 {
+        return;
+    }
+    let delim_count = 1 + (data[0] % 4) as usize;
+    if data.len() < 1 + delim_count {
+        return;
+    }
+    let delims: Vec<u8> = data[1..1 + delim_count]
+        .iter()
+        .copied()
+        .filter(|&b| b != 0)
+        .collect();
+    let input: Vec<u8> = data[1 + delim_count..]
+        .iter()
+        .copied()
+        .filter(|&b| b != 0)
+        .collect();
+
+    let (expected_tokens, expected_buf) = reference_tokenize(&input, &delims);
+
+    // Run the real pointer-based strtok loop
+    let mut buf: Vec<i8> = input.iter().map(|&b| b as i8).chain([0]).collect();
+    let delims_z: Vec<i8> = delims.iter().map(|&b| b as i8).chain([0]).collect();
+    let mut actual_tokens = Vec::new();
+    unsafe {
+        let mut new_str: *mut i8 = ptr::null_mut();
+        let mut token = firefox_nscrt::strtok(buf.as_mut_ptr(), delims_z.as_ptr(), &mut new_str);
+        while !token.is_null() {
+            actual_tokens.push(std::ffi::CStr::from_ptr(token).to_bytes().to_vec());
+            token = firefox_nscrt::strtok(new_str, delims_z.as_ptr(), &mut new_str);
+        }
+    }
+    let actual_buf: Vec<u8> = buf[..input.len()].iter().map(|&b| b as u8).collect();
+
+    assert_eq!(actual_tokens, expected_tokens);
+    assert_eq!(actual_buf, expected_buf);
+});
@@ -47,6 +47,39 @@ pub unsafe extern "C" fn nsCRT_strtok(
     }).unwrap_or(ptr::null_mut())
 }
 
+/// FFI export for the UTF-16 tokenizer (strtok for char16_t*)
+///
+/// Tokenizes a null-terminated UTF-16 string in-place, mirroring
+/// nsCRT_strtok for wide strings.
+///
+/// # Safety
+///
+/// - `string` must point to a valid, mutable, null-terminated UTF-16 string
+/// - `delims` must point to a valid, null-terminated UTF-16 string
+/// - `new_str` must point to a valid mutable pointer location
+/// - The input string will be modified (delimiters replaced with 0)
+///
+/// # C++ Usage:
+///
+/// ```cpp
+/// char16_t str[] = u"a,b,c";
+/// char16_t* newStr;
+/// char16_t* token = nsCRT_strtok_char16(str, u",", &newStr);
+/// while (token != nullptr) {
+///     token = nsCRT_strtok_char16(newStr, u",", &newStr);
+/// }
+/// ```
+#[no_mangle]
+pub unsafe extern "C" fn nsCRT_strtok_char16(
+    string: *mut u16,
+    delims: *const u16,
+    new_str: *mut *mut u16,
+) -> *mut u16 {
+    panic::catch_unwind(|| {
+        crate::strtok_char16(string, delims, new_str)
+    }).unwrap_or(ptr::null_mut())
+}
+
 /// FFI export for nsCRT::strcmp (char16_t* version)
 ///
 /// Compares two null-terminated UTF-16 strings.
@@ -73,6 +106,147 @@ pub unsafe extern "C" fn nsCRT_strcmp_char16(
     }).unwrap_or(0)
 }
 
+/// FFI export for nsCRT::strncmp (char16_t* version)
+///
+/// Compares at most `max` code units of two null-terminated UTF-16
+/// strings, stopping earlier at a NUL in either.
+///
+/// # Safety
+///
+/// - `str1` and `str2` must be null or point to UTF-16 strings that are
+///   null-terminated or at least `max` code units long
+/// - Returns: -1 if str1 < str2, 0 if equal through `max` units, 1 if str1 > str2
+///
+/// # C++ Usage:
+///
+/// ```cpp
+/// char16_t* s1 = u"https://a.example";
+/// char16_t* s2 = u"https://b.example";
+/// int32_t result = nsCRT_strncmp_char16(s1, s2, 8);  // 0: same scheme
+/// ```
+#[no_mangle]
+pub unsafe extern "C" fn nsCRT_strncmp_char16(
+    str1: *const u16,
+    str2: *const u16,
+    max: usize,
+) -> i32 {
+    panic::catch_unwind(|| {
+        crate::strncmp_char16(str1, str2, max)
+    }).unwrap_or(0)
+}
+
+/// FFI export for nsCRT::strcasecmp (char16_t* version)
+///
+/// Compares two null-terminated UTF-16 strings with ASCII-only case
+/// folding ('A'..'Z' equal 'a'..'z'; everything else exact).
+///
+/// # Safety
+///
+/// - `str1` and `str2` must be null or point to valid null-terminated UTF-16 strings
+/// - Returns: -1 if str1 < str2, 0 if equal, 1 if str1 > str2
+///
+/// # C++ Usage:
+///
+/// ```cpp
+/// char16_t* s1 = u"Hello";
+/// char16_t* s2 = u"hello";
+/// int32_t result = nsCRT_strcasecmp_char16(s1, s2);  // 0
+/// ```
+#[no_mangle]
+pub unsafe extern "C" fn nsCRT_strcasecmp_char16(
+    str1: *const u16,
+    str2: *const u16,
+) -> i32 {
+    panic::catch_unwind(|| {
+        crate::strcasecmp_char16(str1, str2)
+    }).unwrap_or(0)
+}
+
+/// FFI export for nsCRT::IsAsciiSpace
+///
+/// True for space, tab, CR, or LF; false for everything else, including
+/// form feed and non-ASCII whitespace.
+#[no_mangle]
+pub extern "C" fn nsCRT_IsAsciiSpace(ch: u16) -> bool {
+    crate::is_ascii_space(ch)
+}
+
+/// FFI export for nsCRT::IsAsciiDigit ('0'-'9')
+#[no_mangle]
+pub extern "C" fn nsCRT_IsAsciiDigit(ch: u16) -> bool {
+    crate::is_ascii_digit(ch)
+}
+
+/// FFI export for nsCRT::IsAsciiAlpha ('a'-'z', 'A'-'Z')
+#[no_mangle]
+pub extern "C" fn nsCRT_IsAsciiAlpha(ch: u16) -> bool {
+    crate::is_ascii_alpha(ch)
+}
+
+/// FFI export for nsCRT::IsUpper ('A'-'Z' only)
+#[no_mangle]
+pub extern "C" fn nsCRT_IsUpper(ch: u16) -> bool {
+    crate::is_upper(ch)
+}
+
+/// FFI export for nsCRT::IsLower ('a'-'z' only)
+#[no_mangle]
+pub extern "C" fn nsCRT_IsLower(ch: u16) -> bool {
+    crate::is_lower(ch)
+}
+
+/// FFI export for nsCRT::HashCode (char16_t* version)
+///
+/// Hashes code units up to the NUL terminator.
+///
+/// # Safety
+///
+/// - `str` must be null or point to a valid null-terminated UTF-16 string
+/// - Returns 0 for a null pointer or an empty string
+///
+/// # C++ Usage:
+///
+/// ```cpp
+/// char16_t* s = u"atom";
+/// uint32_t hash = nsCRT_HashCode_char16(s);
+/// ```
+#[no_mangle]
+pub unsafe extern "C" fn nsCRT_HashCode_char16(str: *const u16) -> u32 {
+    panic::catch_unwind(|| {
+        crate::hash_code_char16(str)
+    }).unwrap_or(0)
+}
+
+/// FFI export for the aHashCount variant of nsCRT::HashCode (char16_t*)
+///
+/// Like nsCRT_HashCode_char16, but also writes the number of code units
+/// hashed through `hash_count` (left untouched if it is null).
+///
+/// # Safety
+///
+/// - `str` must be null or point to a valid null-terminated UTF-16 string
+/// - `hash_count` must be null or point to writable memory for a uint32_t
+///
+/// # C++ Usage:
+///
+/// ```cpp
+/// uint32_t length;
+/// uint32_t hash = nsCRT_HashCode_char16_count(s, &length);
+/// ```
+#[no_mangle]
+pub unsafe extern "C" fn nsCRT_HashCode_char16_count(
+    str: *const u16,
+    hash_count: *mut u32,
+) -> u32 {
+    panic::catch_unwind(|| {
+        let (hash, count) = crate::hash_code_char16_counted(str);
+        if !hash_count.is_null() {
+            *hash_count = count;
+        }
+        hash
+    }).unwrap_or(0)
+}
+
 /// FFI export for nsCRT::atoll
 ///
 /// Converts a null-terminated C string to a 64-bit integer.
@@ -95,6 +269,326 @@ pub unsafe extern "C" fn nsCRT_atoll(str: *const i8) -> i64 {
     }).unwrap_or(0)
 }
 
+/// FFI export for UTF-16 substring search (nsString Find / strstr)
+///
+/// Returns the code-unit index of the first occurrence of the needle, or
+/// -1 if it does not occur. An empty needle matches at index 0.
+///
+/// # Safety
+///
+/// - `haystack` must point to at least `haystack_len` valid u16 units (may
+///   be null only if `haystack_len` is 0)
+/// - `needle` must point to at least `needle_len` valid u16 units (may be
+///   null only if `needle_len` is 0)
+///
+/// # C++ Usage:
+///
+/// ```cpp
+/// int64_t index = nsCRT_find_char16(hay, hayLen, needle, needleLen);
+/// if (index >= 0) { /* found */ }
+/// ```
+#[no_mangle]
+pub unsafe extern "C" fn nsCRT_find_char16(
+    haystack: *const u16,
+    haystack_len: usize,
+    needle: *const u16,
+    needle_len: usize,
+) -> i64 {
+    panic::catch_unwind(|| {
+        let haystack = slice_or_empty(haystack, haystack_len);
+        let needle = slice_or_empty(needle, needle_len);
+        match crate::find_char16(haystack, needle) {
+            Some(index) => index as i64,
+            None => -1,
+        }
+    }).unwrap_or(-1)
+}
+
+/// Case-insensitive variant of nsCRT_find_char16 (ASCII-only folding).
+///
+/// # Safety
+///
+/// Same contract as nsCRT_find_char16.
+#[no_mangle]
+pub unsafe extern "C" fn nsCRT_find_char16_ignore_case(
+    haystack: *const u16,
+    haystack_len: usize,
+    needle: *const u16,
+    needle_len: usize,
+) -> i64 {
+    panic::catch_unwind(|| {
+        let haystack = slice_or_empty(haystack, haystack_len);
+        let needle = slice_or_empty(needle, needle_len);
+        match crate::find_char16_ignore_case(haystack, needle) {
+            Some(index) => index as i64,
+            None => -1,
+        }
+    }).unwrap_or(-1)
+}
+
+/// Alternative name for nsCRT_HashCode_char16, matching the unqualified
+/// nsCRT::HashCode method name for shims generated from the header.
+///
+/// # Safety
+///
+/// Same contract as nsCRT_HashCode_char16.
+#[no_mangle]
+pub unsafe extern "C" fn nsCRT_HashCode(str: *const u16) -> u32 {
+    nsCRT_HashCode_char16(str)
+}
+
+/// Alternative name for nsCRT_find_char16, matching the FindChar16 shim
+/// naming used on the C++ side.
+///
+/// # Safety
+///
+/// Same contract as nsCRT_find_char16.
+#[no_mangle]
+pub unsafe extern "C" fn nsCRT_FindChar16(
+    haystack: *const u16,
+    haystack_len: usize,
+    needle: *const u16,
+    needle_len: usize,
+) -> i64 {
+    nsCRT_find_char16(haystack, haystack_len, needle, needle_len)
+}
+
+/// Treat a null or zero-length pointer as the empty slice.
+///
+/// # Safety
+///
+/// `ptr` must point to at least `len` valid u16 units when non-null and
+/// `len > 0`.
+unsafe fn slice_or_empty<'a>(ptr: *const u16, len: usize) -> &'a [u16] {
+    if ptr.is_null() || len == 0 {
+        &[]
+    } else {
+        std::slice::from_raw_parts(ptr, len)
+    }
+}
+
+/// FFI export for in-place ASCII lowercasing of a UTF-16 buffer
+///
+/// Only 'A'-'Z' change; all other code units are untouched
+/// (locale-independent, like ToLowerCaseASCII).
+///
+/// # Safety
+///
+/// - `buf` must point to at least `len` valid, writable u16 units (may be
+///   null only if `len` is 0)
+#[no_mangle]
+pub unsafe extern "C" fn nsCRT_ToLowerASCII_char16(buf: *mut u16, len: usize) {
+    if buf.is_null() || len == 0 {
+        return;
+    }
+    let _ = panic::catch_unwind(|| {
+        crate::to_lower_ascii_u16(std::slice::from_raw_parts_mut(buf, len));
+    });
+}
+
+/// FFI export for in-place ASCII uppercasing of a UTF-16 buffer
+///
+/// # Safety
+///
+/// Same contract as nsCRT_ToLowerASCII_char16.
+#[no_mangle]
+pub unsafe extern "C" fn nsCRT_ToUpperASCII_char16(buf: *mut u16, len: usize) {
+    if buf.is_null() || len == 0 {
+        return;
+    }
+    let _ = panic::catch_unwind(|| {
+        crate::to_upper_ascii_u16(std::slice::from_raw_parts_mut(buf, len));
+    });
+}
+
+// ============================================================================
+// strdup / strndup through the Gecko allocator
+// ============================================================================
+
+use std::alloc::Layout;
+use std::os::raw::{c_char, c_void};
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+/// Allocation hook signature (moz_xmalloc-compatible)
+pub type NsCrtAllocFn = unsafe extern "C" fn(usize) -> *mut c_void;
+
+/// Deallocation hook signature (free-compatible)
+pub type NsCrtFreeFn = unsafe extern "C" fn(*mut c_void);
+
+// The registered allocator, stored as raw function-pointer addresses
+// (0 = none). Default is the Rust global allocator; Gecko registers
+// moz_xmalloc/free at startup so duplicated strings live on the heap
+// C++ expects to own.
+static ALLOC_HOOK: AtomicUsize = AtomicUsize::new(0);
+static FREE_HOOK: AtomicUsize = AtomicUsize::new(0);
+
+/// Header stored in front of every duplicated string so nsCRT_free can
+/// release it with the same allocator that created it, even if the hook
+/// is swapped in between.
+#[repr(C)]
+struct AllocHeader {
+    /// Address of the free hook to use (0 = Rust global allocator)
+    free_fn: usize,
+    /// Total allocation size in bytes, including this header
+    total_size: usize,
+}
+
+const HEADER_SIZE: usize = std::mem::size_of::<AllocHeader>();
+const ALLOC_ALIGN: usize = std::mem::align_of::<AllocHeader>();
+
+/// Register the allocator pair used by the nsCRT_strdup family
+/// (typically moz_xmalloc and free). Passing null for either resets to
+/// the Rust global allocator. Strings already allocated remember their
+/// allocator, so registration order does not affect correctness of
+/// nsCRT_free.
+///
+/// # Safety
+///
+/// `alloc` and `free` must be a matching pair: memory returned by
+/// `alloc` must be releasable by `free`. `alloc` must return memory
+/// aligned for `usize` or null on failure.
+#[no_mangle]
+pub unsafe extern "C" fn nsCRT_set_allocator(
+    alloc: Option<NsCrtAllocFn>,
+    free: Option<NsCrtFreeFn>,
+) {
+    match (alloc, free) {
+        (Some(alloc), Some(free)) => {
+            ALLOC_HOOK.store(alloc as usize, Ordering::Release);
+            FREE_HOOK.store(free as usize, Ordering::Release);
+        }
+        _ => {
+            ALLOC_HOOK.store(0, Ordering::Release);
+            FREE_HOOK.store(0, Ordering::Release);
+        }
+    }
+}
+
+/// Allocate `payload_size` bytes through the registered allocator (or
+/// the Rust global allocator), returning a pointer to the payload; null
+/// on failure.
+unsafe fn alloc_with_header(payload_size: usize) -> *mut u8 {
+    let total_size = HEADER_SIZE + payload_size;
+    let alloc_fn = ALLOC_HOOK.load(Ordering::Acquire);
+    let free_fn = FREE_HOOK.load(Ordering::Acquire);
+
+    let base = if alloc_fn != 0 {
+        let alloc: NsCrtAllocFn = std::mem::transmute(alloc_fn);
+        alloc(total_size) as *mut u8
+    } else {
+        let layout = Layout::from_size_align(total_size, ALLOC_ALIGN)
+            .expect("allocation size overflow");
+        std::alloc::alloc(layout)
+    };
+    if base.is_null() {
+        return ptr::null_mut();
+    }
+
+    let header = base as *mut AllocHeader;
+    (*header).free_fn = if alloc_fn != 0 { free_fn } else { 0 };
+    (*header).total_size = total_size;
+    base.add(HEADER_SIZE)
+}
+
+/// Release memory obtained from the nsCRT_strdup family.
+///
+/// Dispatches to whichever allocator created the block, so it is always
+/// the matching free, regardless of hooks registered since.
+///
+/// # Safety
+///
+/// `str` must be null or a pointer previously returned by
+/// nsCRT_strdup/strndup (either width) that has not been freed.
+#[no_mangle]
+pub unsafe extern "C" fn nsCRT_free(str: *mut c_void) {
+    if str.is_null() {
+        return;
+    }
+    let base = (str as *mut u8).sub(HEADER_SIZE);
+    let header = base as *const AllocHeader;
+    let free_fn = (*header).free_fn;
+    if free_fn != 0 {
+        let free: NsCrtFreeFn = std::mem::transmute(free_fn);
+        free(base as *mut c_void);
+    } else {
+        let layout = Layout::from_size_align((*header).total_size, ALLOC_ALIGN)
+            .expect("header was written by alloc_with_header");
+        std::alloc::dealloc(base, layout);
+    }
+}
+
+/// Duplicate at most `max` elements of a null-terminated buffer,
+/// appending a terminator. Shared by both character widths.
+unsafe fn dup_impl<T: Copy + Default + PartialEq>(str: *const T, max: usize) -> *mut T {
+    if str.is_null() {
+        return ptr::null_mut();
+    }
+
+    // strndup semantics: copy up to max elements or the terminator,
+    // whichever comes first
+    let mut len = 0;
+    while len < max && *str.add(len) != T::default() {
+        len += 1;
+    }
+
+    let payload = alloc_with_header((len + 1) * std::mem::size_of::<T>()) as *mut T;
+    if payload.is_null() {
+        return ptr::null_mut();
+    }
+    ptr::copy_nonoverlapping(str, payload, len);
+    *payload.add(len) = T::default();
+    payload
+}
+
+/// FFI export for nsCRT::strdup (char version)
+///
+/// Duplicates a null-terminated C string via the registered allocator;
+/// release the result with nsCRT_free. Returns null for null input or
+/// allocation failure.
+///
+/// # Safety
+///
+/// `str` must be null or point to a valid null-terminated C string.
+#[no_mangle]
+pub unsafe extern "C" fn nsCRT_strdup(str: *const c_char) -> *mut c_char {
+    panic::catch_unwind(|| dup_impl(str, usize::MAX)).unwrap_or(ptr::null_mut())
+}
+
+/// FFI export for nsCRT::strndup (char version)
+///
+/// Duplicates at most `max` characters (stopping at a NUL), always
+/// NUL-terminating the copy. Release with nsCRT_free.
+///
+/// # Safety
+///
+/// `str` must be null or point to a string that is null-terminated or at
+/// least `max` characters long.
+#[no_mangle]
+pub unsafe extern "C" fn nsCRT_strndup(str: *const c_char, max: usize) -> *mut c_char {
+    panic::catch_unwind(|| dup_impl(str, max)).unwrap_or(ptr::null_mut())
+}
+
+/// FFI export for nsCRT::strdup (char16_t version)
+///
+/// # Safety
+///
+/// `str` must be null or point to a valid null-terminated UTF-16 string.
+#[no_mangle]
+pub unsafe extern "C" fn nsCRT_strdup_char16(str: *const u16) -> *mut u16 {
+    panic::catch_unwind(|| dup_impl(str, usize::MAX)).unwrap_or(ptr::null_mut())
+}
+
+/// FFI export for nsCRT::strndup (char16_t version)
+///
+/// # Safety
+///
+/// `str` must be null or point to a UTF-16 string that is null-terminated
+/// or at least `max` code units long.
+#[no_mangle]
+pub unsafe extern "C" fn nsCRT_strndup_char16(str: *const u16, max: usize) -> *mut u16 {
+    panic::catch_unwind(|| dup_impl(str, max)).unwrap_or(ptr::null_mut())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -127,6 +621,188 @@ mod tests {
         }
     }
 
+    fn utf16z(text: &str) -> Vec<u16> {
+        text.encode_utf16().chain(std::iter::once(0)).collect()
+    }
+
+    #[test]
+    fn test_ffi_strcasecmp_char16() {
+        unsafe {
+            let s1 = utf16z("Hello");
+            let s2 = utf16z("hello");
+            assert_eq!(nsCRT_strcasecmp_char16(s1.as_ptr(), s2.as_ptr()), 0);
+            assert_eq!(nsCRT_strcasecmp_char16(ptr::null(), s2.as_ptr()), -1);
+        }
+    }
+
+    #[test]
+    fn test_ffi_strncmp_char16() {
+        unsafe {
+            let s1 = utf16z("abcdef");
+            let s2 = utf16z("abcxyz");
+            assert_eq!(nsCRT_strncmp_char16(s1.as_ptr(), s2.as_ptr(), 3), 0);
+            assert_eq!(nsCRT_strncmp_char16(s1.as_ptr(), s2.as_ptr(), 4), -1);
+        }
+    }
+
+    #[test]
+    fn test_ffi_hash_code_names_agree() {
+        unsafe {
+            let s = utf16z("atom");
+            assert_eq!(nsCRT_HashCode(s.as_ptr()), nsCRT_HashCode_char16(s.as_ptr()));
+
+            let mut count = 0u32;
+            let hash = nsCRT_HashCode_char16_count(s.as_ptr(), &mut count);
+            assert_eq!(hash, nsCRT_HashCode(s.as_ptr()));
+            assert_eq!(count, 4);
+        }
+    }
+
+    #[test]
+    fn test_ffi_find_char16_names_agree() {
+        unsafe {
+            let haystack = utf16z("hello world");
+            let needle = utf16z("world");
+            // Lengths exclude the NUL terminator
+            assert_eq!(
+                nsCRT_FindChar16(haystack.as_ptr(), 11, needle.as_ptr(), 5),
+                6
+            );
+            assert_eq!(
+                nsCRT_find_char16(haystack.as_ptr(), 11, needle.as_ptr(), 5),
+                6
+            );
+            let missing = utf16z("moon");
+            assert_eq!(
+                nsCRT_FindChar16(haystack.as_ptr(), 11, missing.as_ptr(), 4),
+                -1
+            );
+            let upper = utf16z("WORLD");
+            assert_eq!(
+                nsCRT_find_char16_ignore_case(haystack.as_ptr(), 11, upper.as_ptr(), 5),
+                6
+            );
+        }
+    }
+
+    #[test]
+    fn test_strdup_roundtrip() {
+        unsafe {
+            let original = CString::new("duplicate me").unwrap();
+            let copy = nsCRT_strdup(original.as_ptr());
+            assert!(!copy.is_null());
+            assert_eq!(
+                std::ffi::CStr::from_ptr(copy).to_str().unwrap(),
+                "duplicate me"
+            );
+            // The copy is independent of the original's storage
+            drop(original);
+            assert_eq!(
+                std::ffi::CStr::from_ptr(copy).to_str().unwrap(),
+                "duplicate me"
+            );
+            nsCRT_free(copy as *mut c_void);
+
+            assert!(nsCRT_strdup(ptr::null()).is_null());
+            nsCRT_free(ptr::null_mut()); // must be a no-op
+        }
+    }
+
+    #[test]
+    fn test_strndup_truncates_and_terminates() {
+        unsafe {
+            let original = CString::new("abcdef").unwrap();
+
+            let copy = nsCRT_strndup(original.as_ptr(), 3);
+            assert_eq!(std::ffi::CStr::from_ptr(copy).to_str().unwrap(), "abc");
+            nsCRT_free(copy as *mut c_void);
+
+            // max beyond the NUL stops at the NUL
+            let copy = nsCRT_strndup(original.as_ptr(), 100);
+            assert_eq!(std::ffi::CStr::from_ptr(copy).to_str().unwrap(), "abcdef");
+            nsCRT_free(copy as *mut c_void);
+
+            let copy = nsCRT_strndup(original.as_ptr(), 0);
+            assert_eq!(std::ffi::CStr::from_ptr(copy).to_str().unwrap(), "");
+            nsCRT_free(copy as *mut c_void);
+        }
+    }
+
+    #[test]
+    fn test_strdup_char16() {
+        unsafe {
+            let original = utf16z("wide copy");
+            let copy = nsCRT_strdup_char16(original.as_ptr());
+            assert!(!copy.is_null());
+            assert_eq!(nsCRT_strcmp_char16(copy, original.as_ptr()), 0);
+            nsCRT_free(copy as *mut c_void);
+
+            let copy = nsCRT_strndup_char16(original.as_ptr(), 4);
+            let expected = utf16z("wide");
+            assert_eq!(nsCRT_strcmp_char16(copy, expected.as_ptr()), 0);
+            nsCRT_free(copy as *mut c_void);
+        }
+    }
+
+    // A hook pair that prefixes each block with its size so test_free can
+    // release through the Rust allocator, plus counters proving the hook
+    // was actually used
+    static HOOK_ALLOCS: std::sync::atomic::AtomicUsize =
+        std::sync::atomic::AtomicUsize::new(0);
+    static HOOK_FREES: std::sync::atomic::AtomicUsize =
+        std::sync::atomic::AtomicUsize::new(0);
+
+    unsafe extern "C" fn test_alloc(size: usize) -> *mut c_void {
+        HOOK_ALLOCS.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+        let layout = Layout::from_size_align(size + HEADER_SIZE, ALLOC_ALIGN).unwrap();
+        let base = std::alloc::alloc(layout);
+        if base.is_null() {
+            return ptr::null_mut();
+        }
+        *(base as *mut usize) = size;
+        base.add(HEADER_SIZE) as *mut c_void
+    }
+
+    unsafe extern "C" fn test_free(ptr: *mut c_void) {
+        HOOK_FREES.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+        let base = (ptr as *mut u8).sub(HEADER_SIZE);
+        let size = *(base as *const usize);
+        let layout = Layout::from_size_align(size + HEADER_SIZE, ALLOC_ALIGN).unwrap();
+        std::alloc::dealloc(base, layout);
+    }
+
+    #[test]
+    fn test_registered_allocator_hook_used_and_matched() {
+        unsafe {
+            let original = CString::new("hooked").unwrap();
+
+            nsCRT_set_allocator(Some(test_alloc), Some(test_free));
+            let hooked_copy = nsCRT_strdup(original.as_ptr());
+            nsCRT_set_allocator(None, None);
+
+            // Allocated through the hook...
+            assert!(HOOK_ALLOCS.load(std::sync::atomic::Ordering::SeqCst) >= 1);
+            assert_eq!(
+                std::ffi::CStr::from_ptr(hooked_copy).to_str().unwrap(),
+                "hooked"
+            );
+
+            // ...and freed through it, even though the hook has since
+            // been unregistered
+            let frees_before = HOOK_FREES.load(std::sync::atomic::Ordering::SeqCst);
+            nsCRT_free(hooked_copy as *mut c_void);
+            assert!(HOOK_FREES.load(std::sync::atomic::Ordering::SeqCst) > frees_before);
+
+            // Default-allocator copies record no hook in their header, so
+            // they free through the Rust path (checked via the header
+            // rather than the counters, which other threads may bump)
+            let plain_copy = nsCRT_strdup(original.as_ptr());
+            let base = (plain_copy as *mut u8).sub(HEADER_SIZE);
+            assert_eq!((*(base as *const AllocHeader)).free_fn, 0);
+            nsCRT_free(plain_copy as *mut c_void);
+        }
+    }
+
     #[test]
     fn test_ffi_atoll() {
         unsafe {
@@ -18,6 +18,11 @@
 
 pub mod ffi;
 
+// Property tests pitting strtok against a strtok_r reference; the fuzz
+// target in fuzz/ extends the same comparison with libFuzzer inputs
+#[cfg(test)]
+mod differential_tests;
+
 use std::ptr;
 
 const DELIM_TABLE_SIZE: usize = 32;
@@ -139,6 +144,172 @@ pub unsafe fn strtok(
     }
 }
 
+/// Iterator over the tokens of a byte buffer, created by [`tokens`].
+///
+/// Wraps the same in-place tokenization logic as [`strtok`]: each
+/// delimiter that terminates a token is overwritten with `'\0'` in the
+/// underlying buffer as iteration advances, so the buffer ends up in the
+/// same state a C++ strtok loop would leave it in.
+pub struct Tokens<'a> {
+    remaining: &'a mut [u8],
+    delim_table: [u8; DELIM_TABLE_SIZE],
+}
+
+impl<'a> Iterator for Tokens<'a> {
+    type Item = &'a [u8];
+
+    fn next(&mut self) -> Option<&'a [u8]> {
+        let buf = std::mem::take(&mut self.remaining);
+
+        // Skip leading delimiters
+        let mut start = 0;
+        while start < buf.len() && is_delim(&self.delim_table, buf[start]) {
+            start += 1;
+        }
+        if start == buf.len() {
+            return None;
+        }
+
+        // Find the end of the token
+        let mut end = start;
+        while end < buf.len() && !is_delim(&self.delim_table, buf[end]) {
+            end += 1;
+        }
+
+        if end == buf.len() {
+            // Last token runs to the end of the buffer; nothing to overwrite
+            return Some(&buf[start..]);
+        }
+
+        // Replace the terminating delimiter with '\0', like strtok, and
+        // keep the rest of the buffer for the next call
+        buf[end] = 0;
+        let (token_part, rest) = buf.split_at_mut(end + 1);
+        self.remaining = rest;
+        Some(&token_part[start..end])
+    }
+}
+
+/// Tokenize a byte buffer in place, yielding each token as a subslice.
+///
+/// The safe, slice-based counterpart to [`strtok`] for Rust consumers:
+/// same delimiter-bitmap skipping, same in-place `'\0'` writes, but
+/// bounded by the slice length instead of a NUL terminator and with no
+/// raw pointers. Runs of delimiters are skipped, so no empty tokens are
+/// produced; a buffer containing only delimiters (or nothing) yields no
+/// tokens. A `'\0'` byte in the buffer is an ordinary byte unless listed
+/// in `delims`.
+///
+/// # Examples
+///
+/// ```
+/// use firefox_nscrt::tokens;
+///
+/// let mut buf = *b"a,,b,c";
+/// let collected: Vec<&[u8]> = tokens(&mut buf, b",").collect();
+/// assert_eq!(collected, [b"a", b"b", b"c"]);
+/// ```
+pub fn tokens<'a>(buf: &'a mut [u8], delims: &[u8]) -> Tokens<'a> {
+    Tokens {
+        remaining: buf,
+        delim_table: build_delim_table(delims),
+    }
+}
+
+/// One bit per possible u16 code unit (65536 bits = 8 KiB), the wide
+/// counterpart of the 32-byte ASCII delimiter table
+const DELIM_TABLE_SIZE_U16: usize = 8192;
+
+/// Build a delimiter bitmap over the full u16 range.
+#[inline]
+fn build_delim_table_u16(delims: &[u16]) -> Box<[u8; DELIM_TABLE_SIZE_U16]> {
+    // Boxed: 8 KiB is too large to keep copying across stack frames
+    let mut table = Box::new([0u8; DELIM_TABLE_SIZE_U16]);
+    for &ch in delims {
+        table[(ch >> 3) as usize] |= 1 << (ch & 7);
+    }
+    table
+}
+
+/// Check if a code unit is in the u16 delimiter bitmap.
+#[inline]
+fn is_delim_u16(table: &[u8; DELIM_TABLE_SIZE_U16], ch: u16) -> bool {
+    (table[(ch >> 3) as usize] & (1 << (ch & 7))) != 0
+}
+
+/// UTF-16 string tokenizer, mirroring [`strtok`] for char16_t buffers
+///
+/// Tokenizes a null-terminated UTF-16 string in place: leading
+/// delimiters are skipped, the delimiter ending the token is overwritten
+/// with 0, and `new_str` is updated to the continuation point, so
+/// char16_t consumers get the strtok loop without narrowing first.
+///
+/// # Safety
+///
+/// This function is unsafe because it:
+/// - Dereferences raw pointers
+/// - Modifies the input string in-place
+/// - Assumes null-terminated UTF-16 strings
+///
+/// # Arguments
+///
+/// * `string` - Mutable pointer to the string to tokenize (must be non-null)
+/// * `delims` - Pointer to null-terminated UTF-16 delimiter string
+/// * `new_str` - Output pointer to update with continuation point
+///
+/// # Returns
+///
+/// Pointer to the next token, or null if no more tokens found.
+pub unsafe fn strtok_char16(
+    string: *mut u16,
+    delims: *const u16,
+    new_str: *mut *mut u16,
+) -> *mut u16 {
+    debug_assert!(!string.is_null(), "string must not be null");
+
+    if string.is_null() || delims.is_null() || new_str.is_null() {
+        return ptr::null_mut();
+    }
+
+    // Build delimiter bitmap
+    let delim_table = {
+        let mut delims_vec = Vec::new();
+        let mut delim_ptr = delims;
+        while *delim_ptr != 0 {
+            delims_vec.push(*delim_ptr);
+            delim_ptr = delim_ptr.offset(1);
+        }
+        build_delim_table_u16(&delims_vec)
+    };
+
+    // Skip to beginning (skip leading delimiters)
+    let mut str_ptr = string;
+    while *str_ptr != 0 && is_delim_u16(&delim_table, *str_ptr) {
+        str_ptr = str_ptr.offset(1);
+    }
+    let result = str_ptr;
+
+    // Fix up the end of the token
+    while *str_ptr != 0 {
+        if is_delim_u16(&delim_table, *str_ptr) {
+            *str_ptr = 0; // Replace delimiter with null terminator
+            str_ptr = str_ptr.offset(1);
+            break;
+        }
+        str_ptr = str_ptr.offset(1);
+    }
+
+    // Update continuation pointer
+    *new_str = str_ptr;
+
+    // Return null if no token found (result == str_ptr means empty)
+    if str_ptr == result {
+        ptr::null_mut()
+    } else {
+        result
+    }
+}
+
 /// UTF-16 string comparison (Rust implementation of nsCRT::strcmp for char16_t*)
 ///
 /// Compares two null-terminated UTF-16 strings lexicographically.
@@ -206,6 +377,244 @@ pub unsafe fn strcmp_char16(str1: *const u16, str2: *const u16) -> i32 {
     0
 }
 
+/// Bounded UTF-16 string comparison (nsCRT::strncmp for char16_t*)
+///
+/// Compares at most `max` code units of two null-terminated UTF-16
+/// strings, stopping earlier at a NUL in either string. Many callers
+/// compare fixed-length prefixes — scheme checks, attribute names —
+/// without wanting to walk to the terminator.
+///
+/// # Safety
+///
+/// `str1` and `str2` must each be null or point to UTF-16 strings that
+/// are either null-terminated or at least `max` code units long.
+///
+/// # Returns
+///
+/// - `-1` if str1 < str2 within the first `max` units
+/// - `0` if they are equal through `max` units (or through a shared NUL)
+/// - `1` if str1 > str2
+///
+/// `max == 0` always compares equal. Null handling matches
+/// [`strcmp_char16`]: both null → 0, a null pointer compares less than
+/// any string.
+pub unsafe fn strncmp_char16(str1: *const u16, str2: *const u16, max: usize) -> i32 {
+    if str1.is_null() && str2.is_null() {
+        return 0;
+    }
+    if str1.is_null() {
+        return -1;
+    }
+    if str2.is_null() {
+        return 1;
+    }
+
+    let mut s1 = str1;
+    let mut s2 = str2;
+    let mut remaining = max;
+    while remaining > 0 {
+        let c1 = *s1;
+        let c2 = *s2;
+
+        if c1 != c2 {
+            return if c1 < c2 { -1 } else { 1 };
+        }
+
+        if c1 == 0 {
+            break;
+        }
+
+        s1 = s1.offset(1);
+        s2 = s2.offset(1);
+        remaining -= 1;
+    }
+
+    0
+}
+
+/// Fold an ASCII uppercase code unit to lowercase, leaving everything
+/// else (including non-ASCII letters) untouched — the same folding the
+/// C++ nsCRT::strcasecmp performs.
+#[inline]
+fn fold_ascii_case_u16(ch: u16) -> u16 {
+    if (b'A' as u16..=b'Z' as u16).contains(&ch) {
+        ch + (b'a' - b'A') as u16
+    } else {
+        ch
+    }
+}
+
+/// Case-insensitive UTF-16 string comparison (nsCRT::strcasecmp for
+/// char16_t*)
+///
+/// Compares two null-terminated UTF-16 strings with ASCII-only case
+/// folding: `'A'..='Z'` compare equal to `'a'..='z'`, and all other code
+/// units — including accented and non-Latin letters — compare exactly.
+/// This matches the C++ implementation, which deliberately does not do
+/// Unicode case folding.
+///
+/// # Safety
+///
+/// `str1` and `str2` must each be null or point to a valid
+/// null-terminated UTF-16 string.
+///
+/// # Returns
+///
+/// - `-1` if str1 < str2 (after folding)
+/// - `0` if equal
+/// - `1` if str1 > str2
+///
+/// Null handling matches [`strcmp_char16`]: both null → 0, a null
+/// pointer compares less than any string.
+pub unsafe fn strcasecmp_char16(str1: *const u16, str2: *const u16) -> i32 {
+    if str1.is_null() && str2.is_null() {
+        return 0;
+    }
+    if str1.is_null() {
+        return -1;
+    }
+    if str2.is_null() {
+        return 1;
+    }
+
+    let mut s1 = str1;
+    let mut s2 = str2;
+    loop {
+        let c1 = fold_ascii_case_u16(*s1);
+        let c2 = fold_ascii_case_u16(*s2);
+
+        if c1 != c2 {
+            return if c1 < c2 { -1 } else { 1 };
+        }
+
+        if c1 == 0 {
+            break;
+        }
+
+        s1 = s1.offset(1);
+        s2 = s2.offset(1);
+    }
+
+    0
+}
+
+/// Safe slice entry point for [`strcasecmp_char16`].
+///
+/// Compares the full slices (no NUL terminator needed) with the same
+/// ASCII-only folding; when one slice is a folded prefix of the other,
+/// the shorter compares less, as if both were NUL-terminated at their
+/// ends.
+pub fn strcasecmp_char16_slices(str1: &[u16], str2: &[u16]) -> i32 {
+    let len = str1.len().min(str2.len());
+    for i in 0..len {
+        let c1 = fold_ascii_case_u16(str1[i]);
+        let c2 = fold_ascii_case_u16(str2[i]);
+        if c1 != c2 {
+            return if c1 < c2 { -1 } else { 1 };
+        }
+    }
+    match str1.len().cmp(&str2.len()) {
+        std::cmp::Ordering::Less => -1,
+        std::cmp::Ordering::Equal => 0,
+        std::cmp::Ordering::Greater => 1,
+    }
+}
+
+// ============================================================================
+// ASCII character classification (nsCRT::IsAscii* / IsUpper / IsLower)
+// ============================================================================
+
+use firefox_asciimask::{
+    is_masked_u16, mask_from_chars, ASCIIMaskArray, ALPHA_MASK, ZERO_TO_NINE_MASK,
+};
+
+/// The four characters nsCRT::IsAsciiSpace accepts — deliberately NOT
+/// the five-character `WHITESPACE_MASK` from firefox_asciimask, which
+/// also includes form feed
+static ASCII_SPACE_MASK: ASCIIMaskArray = mask_from_chars(b" \t\r\n");
+
+/// Mask for ASCII uppercase letters: A-Z
+static UPPER_MASK: ASCIIMaskArray = mask_from_chars(b"ABCDEFGHIJKLMNOPQRSTUVWXYZ");
+
+/// Mask for ASCII lowercase letters: a-z
+static LOWER_MASK: ASCIIMaskArray = mask_from_chars(b"abcdefghijklmnopqrstuvwxyz");
+
+/// nsCRT::IsAsciiSpace — space, tab, CR, or LF (not form feed)
+#[inline]
+pub fn is_ascii_space(ch: u16) -> bool {
+    is_masked_u16(&ASCII_SPACE_MASK, ch)
+}
+
+/// nsCRT::IsAsciiDigit — '0' through '9'
+#[inline]
+pub fn is_ascii_digit(ch: u16) -> bool {
+    is_masked_u16(&ZERO_TO_NINE_MASK, ch)
+}
+
+/// nsCRT::IsAsciiAlpha — 'a'-'z' or 'A'-'Z'
+#[inline]
+pub fn is_ascii_alpha(ch: u16) -> bool {
+    is_masked_u16(&ALPHA_MASK, ch)
+}
+
+/// nsCRT::IsUpper — 'A' through 'Z' only; non-ASCII is never upper here
+#[inline]
+pub fn is_upper(ch: u16) -> bool {
+    is_masked_u16(&UPPER_MASK, ch)
+}
+
+/// nsCRT::IsLower — 'a' through 'z' only; non-ASCII is never lower here
+#[inline]
+pub fn is_lower(ch: u16) -> bool {
+    is_masked_u16(&LOWER_MASK, ch)
+}
+
+/// UTF-16 string hash (Rust implementation of nsCRT::HashCode for
+/// char16_t*)
+///
+/// Hashes the code units up to (not including) the NUL terminator,
+/// mixing each one with the golden-ratio primitive from
+/// `firefox_hashbytes` — the same step `mozilla::HashString` uses, so
+/// the value agrees with `hash_string_u16` of the same units.
+///
+/// # Safety
+///
+/// `str` must be null or point to a valid null-terminated UTF-16 string.
+///
+/// # Returns
+///
+/// The hash code; 0 for a null pointer or an empty string.
+pub unsafe fn hash_code_char16(str: *const u16) -> u32 {
+    hash_code_char16_counted(str).0
+}
+
+/// The `aHashCount` variant of [`hash_code_char16`]: also reports how
+/// many code units were hashed, which callers use as a cheap strlen.
+///
+/// # Safety
+///
+/// Same contract as [`hash_code_char16`].
+///
+/// # Returns
+///
+/// `(hash, count)` — the hash code and the number of code units before
+/// the NUL; both 0 for a null pointer.
+pub unsafe fn hash_code_char16_counted(str: *const u16) -> (u32, u32) {
+    if str.is_null() {
+        return (0, 0);
+    }
+
+    let mut hash = 0u32;
+    let mut count = 0u32;
+    let mut cursor = str;
+    while *cursor != 0 {
+        hash = firefox_hashbytes::add_u32_to_hash(hash, *cursor as u32);
+        count += 1;
+        cursor = cursor.offset(1);
+    }
+    (hash, count)
+}
+
 /// String to 64-bit integer conversion (Rust implementation of nsCRT::atoll)
 ///
 /// Parses a null-terminated C string as a decimal integer.
@@ -254,6 +663,242 @@ pub unsafe fn atoll(str: *const i8) -> i64 {
     result
 }
 
+// ============================================================================
+// ASCII case conversion over UTF-16 buffers (nsCRT / nsUnicharUtils)
+// ============================================================================
+
+/// Fold an ASCII lowercase code unit to uppercase; the inverse of
+/// [`fold_ascii_case_u16`], with the same ASCII-only scope.
+#[inline]
+fn raise_ascii_case_u16(ch: u16) -> u16 {
+    if (b'a' as u16..=b'z' as u16).contains(&ch) {
+        ch - (b'a' - b'A') as u16
+    } else {
+        ch
+    }
+}
+
+/// Lowercase the ASCII letters of a UTF-16 buffer in place.
+///
+/// Locale-independent by construction: only `'A'..='Z'` change, every
+/// other code unit — including non-ASCII letters — passes through
+/// untouched, matching the ToLowerCaseASCII conversions
+/// nsCRT/nsUnicharUtils callers rely on for protocol text.
+pub fn to_lower_ascii_u16(units: &mut [u16]) {
+    for unit in units {
+        *unit = fold_ascii_case_u16(*unit);
+    }
+}
+
+/// Uppercase the ASCII letters of a UTF-16 buffer in place; see
+/// [`to_lower_ascii_u16`] for scope.
+pub fn to_upper_ascii_u16(units: &mut [u16]) {
+    for unit in units {
+        *unit = raise_ascii_case_u16(*unit);
+    }
+}
+
+/// Copying variant of [`to_lower_ascii_u16`].
+pub fn to_lower_ascii_u16_copy(units: &[u16]) -> Vec<u16> {
+    units.iter().map(|&unit| fold_ascii_case_u16(unit)).collect()
+}
+
+/// Copying variant of [`to_upper_ascii_u16`].
+pub fn to_upper_ascii_u16_copy(units: &[u16]) -> Vec<u16> {
+    units.iter().map(|&unit| raise_ascii_case_u16(unit)).collect()
+}
+
+// ============================================================================
+// UTF-16 substring search (nsString Find / strstr)
+// ============================================================================
+
+/// Boyer-Moore-Horspool search over UTF-16 code units, parameterized by
+/// the per-unit fold applied before comparing.
+///
+/// The bad-character shift table is indexed by the low byte of each
+/// (folded) unit; units sharing a low byte just shift conservatively, so
+/// the worst case degrades toward the naive scan but matches stay exact.
+#[inline]
+fn find_char16_with_fold(
+    haystack: &[u16],
+    needle: &[u16],
+    fold: impl Fn(u16) -> u16,
+) -> Option<usize> {
+    if needle.is_empty() {
+        return Some(0);
+    }
+    if needle.len() > haystack.len() {
+        return None;
+    }
+
+    if needle.len() == 1 {
+        let target = fold(needle[0]);
+        return haystack.iter().position(|&unit| fold(unit) == target);
+    }
+
+    let mut shift = [needle.len(); 256];
+    for (i, &unit) in needle[..needle.len() - 1].iter().enumerate() {
+        shift[(fold(unit) & 0xFF) as usize] = needle.len() - 1 - i;
+    }
+
+    let mut pos = 0;
+    while pos + needle.len() <= haystack.len() {
+        let window = &haystack[pos..pos + needle.len()];
+        if window
+            .iter()
+            .zip(needle)
+            .all(|(&h, &n)| fold(h) == fold(n))
+        {
+            return Some(pos);
+        }
+        let last = fold(window[needle.len() - 1]);
+        pos += shift[(last & 0xFF) as usize];
+    }
+
+    None
+}
+
+/// Find the first occurrence of `needle` in `haystack` (nsString Find /
+/// UTF-16 strstr), returning the code-unit index of the match.
+///
+/// Uses Boyer-Moore-Horspool, so typical searches skip most of the
+/// haystack. An empty needle matches at index 0, like strstr.
+///
+/// # Examples
+///
+/// ```
+/// use firefox_nscrt::find_char16;
+///
+/// let haystack: Vec<u16> = "hello world".encode_utf16().collect();
+/// let needle: Vec<u16> = "world".encode_utf16().collect();
+/// assert_eq!(find_char16(&haystack, &needle), Some(6));
+/// ```
+pub fn find_char16(haystack: &[u16], needle: &[u16]) -> Option<usize> {
+    find_char16_with_fold(haystack, needle, |unit| unit)
+}
+
+/// Case-insensitive [`find_char16`], with the same ASCII-only folding as
+/// [`strcasecmp_char16`]: `'A'..='Z'` match `'a'..='z'`, everything else
+/// matches exactly.
+pub fn find_char16_ignore_case(haystack: &[u16], needle: &[u16]) -> Option<usize> {
+    find_char16_with_fold(haystack, needle, fold_ascii_case_u16)
+}
+
+// ============================================================================
+// Configurable integer parsing (extended atoll)
+// ============================================================================
+
+/// Options for [`parse_i64`].
+///
+/// The default is bug-for-bug [`atoll`] behavior: digits only from the
+/// first byte, no sign, no whitespace skipping, and silent wrap-around on
+/// overflow. Each option opts into stricter or more permissive handling.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ParseOptions {
+    allow_sign: bool,
+    skip_leading_whitespace: bool,
+    detect_overflow: bool,
+}
+
+impl ParseOptions {
+    /// The legacy `atoll`-compatible defaults.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Accept a single leading `'+'` or `'-'`.
+    pub fn allow_sign(mut self) -> Self {
+        self.allow_sign = true;
+        self
+    }
+
+    /// Skip leading ASCII whitespace (space, tab, CR, LF) before the
+    /// number, like libc `atoll` does and nsCRT's does not.
+    pub fn skip_leading_whitespace(mut self) -> Self {
+        self.skip_leading_whitespace = true;
+        self
+    }
+
+    /// Return [`ParseIntError::Overflow`] when the value does not fit in
+    /// an `i64` instead of silently wrapping.
+    pub fn detect_overflow(mut self) -> Self {
+        self.detect_overflow = true;
+        self
+    }
+}
+
+/// Errors from [`parse_i64`]. Only produced by options that opt into
+/// them; the default configuration never fails.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ParseIntError {
+    /// The digits overflowed an `i64` (requires
+    /// [`ParseOptions::detect_overflow`]).
+    Overflow,
+}
+
+/// Parse a decimal integer from a byte slice with configurable behavior.
+///
+/// With default [`ParseOptions`] this is exactly the safe-slice form of
+/// [`atoll`]: consume digits from the start until the first non-digit
+/// (or the end), with wrap-around on overflow and 0 for input that
+/// starts with a non-digit. Options add a leading sign, leading
+/// whitespace skipping, and checked overflow.
+///
+/// Like `atoll`, input with no digits at the parse position — including
+/// a bare sign — yields `Ok(0)`.
+///
+/// # Examples
+///
+/// ```
+/// use firefox_nscrt::{parse_i64, ParseOptions};
+///
+/// assert_eq!(parse_i64(b"123abc", ParseOptions::new()), Ok(123));
+/// assert_eq!(
+///     parse_i64(b"  -42", ParseOptions::new().allow_sign().skip_leading_whitespace()),
+///     Ok(-42)
+/// );
+/// ```
+pub fn parse_i64(bytes: &[u8], options: ParseOptions) -> Result<i64, ParseIntError> {
+    let mut pos = 0;
+
+    if options.skip_leading_whitespace {
+        while pos < bytes.len() && is_ascii_space(bytes[pos] as u16) {
+            pos += 1;
+        }
+    }
+
+    let mut negative = false;
+    if options.allow_sign && pos < bytes.len() && (bytes[pos] == b'+' || bytes[pos] == b'-') {
+        negative = bytes[pos] == b'-';
+        pos += 1;
+    }
+
+    // Accumulate as a negative magnitude so that i64::MIN, whose
+    // absolute value does not fit in an i64, still parses under
+    // overflow detection
+    let mut result: i64 = 0;
+    while pos < bytes.len() && bytes[pos].is_ascii_digit() {
+        let digit = (bytes[pos] - b'0') as i64;
+        if options.detect_overflow {
+            result = result
+                .checked_mul(10)
+                .and_then(|value| value.checked_sub(digit))
+                .ok_or(ParseIntError::Overflow)?;
+        } else {
+            result = result.wrapping_mul(10).wrapping_sub(digit);
+        }
+        pos += 1;
+    }
+
+    if negative {
+        Ok(result)
+    } else if options.detect_overflow {
+        result.checked_neg().ok_or(ParseIntError::Overflow)
+    } else {
+        Ok(result.wrapping_neg())
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -322,6 +967,118 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_tokens_basic() {
+        let mut buf = *b"a,b,c";
+        let collected: Vec<&[u8]> = tokens(&mut buf, b",").collect();
+        assert_eq!(collected, [b"a", b"b", b"c"]);
+    }
+
+    #[test]
+    fn test_tokens_multiple_delimiters_and_runs() {
+        let mut buf = *b"  a \tb\t\t c ";
+        let collected: Vec<&[u8]> = tokens(&mut buf, b" \t").collect();
+        assert_eq!(collected, [b"a", b"b", b"c"]);
+    }
+
+    #[test]
+    fn test_tokens_empty_and_all_delimiters() {
+        let mut empty: [u8; 0] = [];
+        assert_eq!(tokens(&mut empty, b",").count(), 0);
+
+        let mut only_delims = *b",,,";
+        assert_eq!(tokens(&mut only_delims, b",").count(), 0);
+    }
+
+    #[test]
+    fn test_tokens_writes_nul_in_place() {
+        // Matches strtok: the delimiter after each token becomes '\0',
+        // but the trailing token's end (buffer end) is untouched
+        let mut buf = *b"a,b,c";
+        let _ = tokens(&mut buf, b",").count();
+        assert_eq!(&buf, b"a\0b\0c");
+    }
+
+    #[test]
+    fn test_tokens_matches_strtok() {
+        // The slice API and the pointer API tokenize identically
+        let mut buf = *b"red;;green;blue";
+        let slice_tokens: Vec<Vec<u8>> = tokens(&mut buf, b";")
+            .map(|token| token.to_vec())
+            .collect();
+
+        unsafe {
+            let input = CString::new("red;;green;blue").unwrap().into_raw();
+            let delims = CString::new(";").unwrap().into_raw();
+            let mut new_str: *mut i8 = ptr::null_mut();
+            let mut pointer_tokens = Vec::new();
+            let mut token = strtok(input, delims, &mut new_str);
+            while !token.is_null() {
+                pointer_tokens
+                    .push(std::ffi::CStr::from_ptr(token).to_bytes().to_vec());
+                token = strtok(new_str, delims, &mut new_str);
+            }
+            assert_eq!(slice_tokens, pointer_tokens);
+            let _ = CString::from_raw(input);
+            let _ = CString::from_raw(delims);
+        }
+    }
+
+    #[test]
+    fn test_strtok_char16_basic() {
+        unsafe {
+            let mut buf = utf16z("a,b,c");
+            let delims = utf16z(",");
+            let mut new_str: *mut u16 = ptr::null_mut();
+
+            let mut collected = Vec::new();
+            let mut token = strtok_char16(buf.as_mut_ptr(), delims.as_ptr(), &mut new_str);
+            while !token.is_null() {
+                let mut units = Vec::new();
+                let mut cursor = token as *const u16;
+                while *cursor != 0 {
+                    units.push(*cursor);
+                    cursor = cursor.offset(1);
+                }
+                collected.push(String::from_utf16(&units).unwrap());
+                token = strtok_char16(new_str, delims.as_ptr(), &mut new_str);
+            }
+            assert_eq!(collected, ["a", "b", "c"]);
+        }
+    }
+
+    #[test]
+    fn test_strtok_char16_leading_delims_and_wide_delimiters() {
+        unsafe {
+            // A non-ASCII delimiter ('、' U+3001) exercises the full-range bitmap
+            let mut buf = utf16z("、、犬、猫");
+            let delims = utf16z("、");
+            let mut new_str: *mut u16 = ptr::null_mut();
+
+            let token = strtok_char16(buf.as_mut_ptr(), delims.as_ptr(), &mut new_str);
+            assert!(!token.is_null());
+            assert_eq!(*token, "犬".encode_utf16().next().unwrap());
+
+            let token = strtok_char16(new_str, delims.as_ptr(), &mut new_str);
+            assert!(!token.is_null());
+            assert_eq!(*token, "猫".encode_utf16().next().unwrap());
+
+            let token = strtok_char16(new_str, delims.as_ptr(), &mut new_str);
+            assert!(token.is_null());
+        }
+    }
+
+    #[test]
+    fn test_strtok_char16_no_tokens() {
+        unsafe {
+            let mut buf = utf16z(",,,");
+            let delims = utf16z(",");
+            let mut new_str: *mut u16 = ptr::null_mut();
+            let token = strtok_char16(buf.as_mut_ptr(), delims.as_ptr(), &mut new_str);
+            assert!(token.is_null());
+        }
+    }
+
     #[test]
     fn test_strcmp_char16_equal() {
         unsafe {
@@ -374,6 +1131,163 @@ mod tests {
         }
     }
 
+    fn utf16z(text: &str) -> Vec<u16> {
+        text.encode_utf16().chain(std::iter::once(0)).collect()
+    }
+
+    #[test]
+    fn test_strncmp_char16_prefix_bounded() {
+        unsafe {
+            let s1 = utf16z("https://a.example");
+            let s2 = utf16z("https://b.example");
+            // Equal through the shared scheme prefix, different beyond it
+            assert_eq!(strncmp_char16(s1.as_ptr(), s2.as_ptr(), 8), 0);
+            assert_eq!(strncmp_char16(s1.as_ptr(), s2.as_ptr(), 9), -1);
+            assert_eq!(strncmp_char16(s2.as_ptr(), s1.as_ptr(), 9), 1);
+        }
+    }
+
+    #[test]
+    fn test_strncmp_char16_stops_at_nul() {
+        unsafe {
+            // Shorter string's NUL ends the comparison before max
+            let s1 = utf16z("abc");
+            let s2 = utf16z("abcdef");
+            assert_eq!(strncmp_char16(s1.as_ptr(), s2.as_ptr(), 100), -1);
+            assert_eq!(strncmp_char16(s1.as_ptr(), s1.as_ptr(), 100), 0);
+        }
+    }
+
+    #[test]
+    fn test_strncmp_char16_zero_max_and_nulls() {
+        unsafe {
+            let s1 = utf16z("abc");
+            let s2 = utf16z("xyz");
+            assert_eq!(strncmp_char16(s1.as_ptr(), s2.as_ptr(), 0), 0);
+
+            assert_eq!(strncmp_char16(ptr::null(), ptr::null(), 5), 0);
+            assert_eq!(strncmp_char16(ptr::null(), s1.as_ptr(), 5), -1);
+            assert_eq!(strncmp_char16(s1.as_ptr(), ptr::null(), 5), 1);
+        }
+    }
+
+    #[test]
+    fn test_strcasecmp_char16_ascii_folding() {
+        unsafe {
+            let s1 = utf16z("Hello World");
+            let s2 = utf16z("hello world");
+            assert_eq!(strcasecmp_char16(s1.as_ptr(), s2.as_ptr()), 0);
+
+            let s3 = utf16z("ABC");
+            let s4 = utf16z("abd");
+            assert_eq!(strcasecmp_char16(s3.as_ptr(), s4.as_ptr()), -1);
+            assert_eq!(strcasecmp_char16(s4.as_ptr(), s3.as_ptr()), 1);
+        }
+    }
+
+    #[test]
+    fn test_strcasecmp_char16_non_ascii_not_folded() {
+        unsafe {
+            // Only ASCII letters fold: 'É' (U+00C9) != 'é' (U+00E9)
+            let upper = utf16z("É");
+            let lower = utf16z("é");
+            assert_ne!(strcasecmp_char16(upper.as_ptr(), lower.as_ptr()), 0);
+        }
+    }
+
+    #[test]
+    fn test_strcasecmp_char16_null_handling() {
+        unsafe {
+            let s = utf16z("hello");
+            assert_eq!(strcasecmp_char16(ptr::null(), ptr::null()), 0);
+            assert_eq!(strcasecmp_char16(ptr::null(), s.as_ptr()), -1);
+            assert_eq!(strcasecmp_char16(s.as_ptr(), ptr::null()), 1);
+        }
+    }
+
+    #[test]
+    fn test_strcasecmp_char16_slices() {
+        let a: Vec<u16> = "CaseLESS".encode_utf16().collect();
+        let b: Vec<u16> = "caseless".encode_utf16().collect();
+        assert_eq!(strcasecmp_char16_slices(&a, &b), 0);
+
+        // A folded prefix compares less than the longer string
+        let prefix: Vec<u16> = "case".encode_utf16().collect();
+        assert_eq!(strcasecmp_char16_slices(&prefix, &b), -1);
+        assert_eq!(strcasecmp_char16_slices(&b, &prefix), 1);
+
+        assert_eq!(strcasecmp_char16_slices(&[], &[]), 0);
+    }
+
+    #[test]
+    fn test_is_ascii_space() {
+        for ch in [b' ', b'\t', b'\r', b'\n'] {
+            assert!(is_ascii_space(ch as u16), "{ch:?}");
+        }
+        // Form feed is whitespace to ASCIIMask but not to nsCRT
+        assert!(!is_ascii_space(0x0C));
+        assert!(!is_ascii_space(b'a' as u16));
+        // Non-ASCII whitespace (NBSP, ideographic space) is not ASCII space
+        assert!(!is_ascii_space(0x00A0));
+        assert!(!is_ascii_space(0x3000));
+    }
+
+    #[test]
+    fn test_is_ascii_digit_and_alpha() {
+        for ch in b'0'..=b'9' {
+            assert!(is_ascii_digit(ch as u16));
+            assert!(!is_ascii_alpha(ch as u16));
+        }
+        for ch in (b'a'..=b'z').chain(b'A'..=b'Z') {
+            assert!(is_ascii_alpha(ch as u16));
+            assert!(!is_ascii_digit(ch as u16));
+        }
+        // Fullwidth digit '０' (U+FF10) is not an ASCII digit
+        assert!(!is_ascii_digit(0xFF10));
+    }
+
+    #[test]
+    fn test_is_upper_and_lower() {
+        for ch in b'A'..=b'Z' {
+            assert!(is_upper(ch as u16));
+            assert!(!is_lower(ch as u16));
+        }
+        for ch in b'a'..=b'z' {
+            assert!(is_lower(ch as u16));
+            assert!(!is_upper(ch as u16));
+        }
+        // 'É' (U+00C9) is upper in Unicode but not to these ASCII checks
+        assert!(!is_upper(0x00C9));
+        assert!(!is_lower(0x00E9));
+        assert!(!is_upper(b'5' as u16));
+    }
+
+    #[test]
+    fn test_hash_code_char16_matches_hashbytes() {
+        unsafe {
+            let s = utf16z("DOM Worker");
+            let units: Vec<u16> = "DOM Worker".encode_utf16().collect();
+            assert_eq!(
+                hash_code_char16(s.as_ptr()),
+                firefox_hashbytes::hash_string_u16(&units)
+            );
+        }
+    }
+
+    #[test]
+    fn test_hash_code_char16_counted() {
+        unsafe {
+            let s = utf16z("hello");
+            let (hash, count) = hash_code_char16_counted(s.as_ptr());
+            assert_eq!(count, 5);
+            assert_eq!(hash, hash_code_char16(s.as_ptr()));
+
+            let empty = utf16z("");
+            assert_eq!(hash_code_char16_counted(empty.as_ptr()), (0, 0));
+            assert_eq!(hash_code_char16_counted(ptr::null()), (0, 0));
+        }
+    }
+
     #[test]
     fn test_atoll_basic() {
         unsafe {
@@ -421,6 +1335,164 @@ mod tests {
         }
     }
 
+    fn utf16(text: &str) -> Vec<u16> {
+        text.encode_utf16().collect()
+    }
+
+    #[test]
+    fn test_to_lower_upper_ascii_u16_in_place() {
+        let mut buf = utf16("Hello, World! 123");
+        to_lower_ascii_u16(&mut buf);
+        assert_eq!(buf, utf16("hello, world! 123"));
+        to_upper_ascii_u16(&mut buf);
+        assert_eq!(buf, utf16("HELLO, WORLD! 123"));
+    }
+
+    #[test]
+    fn test_case_conversion_is_ascii_only() {
+        // 'é' (U+00E9) and 'Σ' (U+03A3) must not change
+        let mut buf = utf16("éΣa");
+        to_upper_ascii_u16(&mut buf);
+        assert_eq!(buf, utf16("éΣA"));
+        to_lower_ascii_u16(&mut buf);
+        assert_eq!(buf, utf16("éΣa"));
+    }
+
+    #[test]
+    fn test_case_conversion_copying_variants() {
+        let original = utf16("MiXeD");
+        assert_eq!(to_lower_ascii_u16_copy(&original), utf16("mixed"));
+        assert_eq!(to_upper_ascii_u16_copy(&original), utf16("MIXED"));
+        // Source untouched
+        assert_eq!(original, utf16("MiXeD"));
+
+        assert!(to_lower_ascii_u16_copy(&[]).is_empty());
+    }
+
+    #[test]
+    fn test_case_conversion_agrees_with_strcasecmp() {
+        // Lowercasing both sides is exactly what strcasecmp folding does
+        let a = utf16("Content-LENGTH");
+        let b = utf16("content-length");
+        assert_eq!(to_lower_ascii_u16_copy(&a), to_lower_ascii_u16_copy(&b));
+        assert_eq!(strcasecmp_char16_slices(&a, &b), 0);
+    }
+
+    #[test]
+    fn test_find_char16_basic() {
+        let haystack = utf16("the quick brown fox");
+        assert_eq!(find_char16(&haystack, &utf16("quick")), Some(4));
+        assert_eq!(find_char16(&haystack, &utf16("fox")), Some(16));
+        assert_eq!(find_char16(&haystack, &utf16("the")), Some(0));
+        assert_eq!(find_char16(&haystack, &utf16("cat")), None);
+
+        // Empty needle matches at 0; needle longer than haystack never does
+        assert_eq!(find_char16(&haystack, &[]), Some(0));
+        assert_eq!(find_char16(&utf16("ab"), &utf16("abc")), None);
+    }
+
+    #[test]
+    fn test_find_char16_first_of_repeated_matches() {
+        let haystack = utf16("abababab");
+        assert_eq!(find_char16(&haystack, &utf16("abab")), Some(0));
+        assert_eq!(find_char16(&haystack, &utf16("bab")), Some(1));
+    }
+
+    #[test]
+    fn test_find_char16_matches_naive_scan() {
+        // BMH against the obvious implementation over awkward inputs:
+        // repeated units and units sharing a low byte (0x0041 vs 0x0141)
+        let haystack: Vec<u16> =
+            "aabaabaaab".encode_utf16().chain([0x0141, 0x0041]).collect();
+        let needles: Vec<Vec<u16>> = vec![
+            utf16("aab"),
+            utf16("aaab"),
+            vec![0x0141, 0x0041],
+            vec![0x0041, 0x0141],
+            vec![0x0141],
+        ];
+        for needle in &needles {
+            let naive = (0..=haystack.len().saturating_sub(needle.len()))
+                .find(|&i| haystack[i..i + needle.len()] == needle[..]);
+            assert_eq!(find_char16(&haystack, needle), naive, "needle {needle:?}");
+        }
+    }
+
+    #[test]
+    fn test_find_char16_ignore_case() {
+        let haystack = utf16("Content-Type: TEXT/html");
+        assert_eq!(
+            find_char16_ignore_case(&haystack, &utf16("text/HTML")),
+            Some(14)
+        );
+        assert_eq!(
+            find_char16_ignore_case(&haystack, &utf16("content-type")),
+            Some(0)
+        );
+        // Folding is ASCII-only: 'É' does not match 'é'
+        assert_eq!(
+            find_char16_ignore_case(&utf16("café"), &utf16("CAFÉ")),
+            None
+        );
+        // The case-sensitive search would miss this one
+        assert_eq!(find_char16(&haystack, &utf16("text/HTML")), None);
+    }
+
+    #[test]
+    fn test_parse_i64_default_matches_atoll() {
+        for input in ["12345", "0", "123abc", "abc", "", "-5", " 7", "99999999999999999999"] {
+            let expected = unsafe {
+                let cstr = CString::new(input).unwrap();
+                atoll(cstr.as_ptr())
+            };
+            assert_eq!(
+                parse_i64(input.as_bytes(), ParseOptions::new()),
+                Ok(expected),
+                "input {input:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_parse_i64_sign_and_whitespace() {
+        let options = ParseOptions::new().allow_sign().skip_leading_whitespace();
+        assert_eq!(parse_i64(b"-42", options), Ok(-42));
+        assert_eq!(parse_i64(b"+42", options), Ok(42));
+        assert_eq!(parse_i64(b" \t\r\n-7xyz", options), Ok(-7));
+
+        // A bare sign has no digits, like a bare non-digit: 0
+        assert_eq!(parse_i64(b"-", options), Ok(0));
+
+        // Sign without the option is just a non-digit
+        assert_eq!(parse_i64(b"-42", ParseOptions::new()), Ok(0));
+        // Whitespace without the option stops the parse immediately
+        assert_eq!(parse_i64(b" 42", ParseOptions::new()), Ok(0));
+    }
+
+    #[test]
+    fn test_parse_i64_overflow_detection() {
+        let checked = ParseOptions::new().allow_sign().detect_overflow();
+        assert_eq!(
+            parse_i64(b"9223372036854775807", checked),
+            Ok(i64::MAX)
+        );
+        assert_eq!(
+            parse_i64(b"-9223372036854775808", checked),
+            Ok(i64::MIN)
+        );
+        assert_eq!(
+            parse_i64(b"9223372036854775808", checked),
+            Err(ParseIntError::Overflow)
+        );
+        assert_eq!(
+            parse_i64(b"-9223372036854775809", checked),
+            Err(ParseIntError::Overflow)
+        );
+
+        // Without detection, overflow silently wraps (legacy behavior)
+        assert!(parse_i64(b"99999999999999999999", ParseOptions::new()).is_ok());
+    }
+
     #[test]
     fn test_build_delim_table() {
         let delims = b",;:";
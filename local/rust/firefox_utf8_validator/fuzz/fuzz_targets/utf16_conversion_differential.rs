@@ -0,0 +1,46 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+//! Differential fuzz target: UTF-8 ↔ UTF-16 conversion vs encoding_rs.
+//! These are the conversions behind ConvertUtf8toUtf16_RUST /
+//! ConvertUtf16toUtf8_RUST; both directions are lossy with U+FFFD
+//! replacement, so the outputs must match encoding_rs unit for unit.
+//! The input buffer doubles as both a UTF-8 candidate and (re-read as
+//! 16-bit units) a potentially ill-formed UTF-16 candidate.
+
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    // UTF-8 → UTF-16
+    let converted = firefox_utf8_validator::convert_utf8_to_utf16(data);
+    let mut reference = vec![0u16; data.len() + 1];
+    let written = encoding_rs::mem::convert_utf8_to_utf16(data, &mut reference);
+    assert_eq!(converted, reference[..written]);
+
+    // The length helper predicts the conversion exactly on valid input
+    if let Some(length) = firefox_utf8_validator::utf8_to_utf16_length(data) {
+        assert_eq!(length, converted.len());
+    } else {
+        assert!(!firefox_utf8_validator::is_valid_utf8(data));
+    }
+
+    // UTF-16 → UTF-8, reinterpreting the input as units
+    let units: Vec<u16> = data
+        .chunks_exact(2)
+        .map(|pair| u16::from_le_bytes([pair[0], pair[1]]))
+        .collect();
+    let converted = firefox_utf8_validator::convert_utf16_to_utf8(&units);
+    let mut reference = vec![0u8; units.len() * 3 + 1];
+    let written = encoding_rs::mem::convert_utf16_to_utf8(&units, &mut reference);
+    assert_eq!(converted.as_bytes(), &reference[..written]);
+
+    // Well-formedness agrees with the repair path: repair changes
+    // nothing exactly when the buffer was already valid
+    let mut repaired = units.clone();
+    let replaced = firefox_utf8_validator::ensure_valid_utf16_lossy(&mut repaired);
+    assert_eq!(firefox_utf8_validator::is_valid_utf16(&units), replaced == 0);
+    assert!(firefox_utf8_validator::is_valid_utf16(&repaired));
+});
@@ -0,0 +1,47 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+//! Differential fuzz target: UTF-8 validation and lossy repair vs
+//! encoding_rs. The validator behind `IsValidUtf8_RUST` must agree
+//! with encoding_rs on every verdict, and the lossy/repair paths must
+//! produce encoding_rs's replacement output byte for byte — the WHATWG
+//! algorithm leaves no room for interpretation. To also compare
+//! against the C++ implementation, link a Gecko build and an extern
+//! "C" shim the way firefox_floatingpoint's gecko-differential test
+//! does; this target covers the Rust-vs-encoding_rs half without one.
+
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    let (reference, had_errors) =
+        encoding_rs::UTF_8.decode_without_bom_handling(data);
+
+    // Verdicts must agree
+    assert_eq!(firefox_utf8_validator::is_valid_utf8(data), !had_errors);
+    assert_eq!(
+        firefox_utf8_validator::validate_utf8(data).is_ok(),
+        !had_errors
+    );
+
+    // Lossy conversion must match encoding_rs's replacement output
+    let lossy = firefox_utf8_validator::to_valid_utf8_lossy(data);
+    assert_eq!(lossy, reference);
+
+    // In-place repair produces the same bytes and reports one
+    // replacement per U+FFFD it introduced
+    let mut repaired = data.to_vec();
+    let replacements = firefox_utf8_validator::repair_utf8_in_place(&mut repaired);
+    assert_eq!(repaired, reference.as_bytes());
+    if !had_errors {
+        assert!(replacements.is_empty());
+    }
+
+    // The counting helpers agree with the converted output
+    assert_eq!(
+        firefox_utf8_validator::count_code_points(data),
+        reference.chars().count()
+    );
+});
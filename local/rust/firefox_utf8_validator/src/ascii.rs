@@ -0,0 +1,240 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+//! ASCII scanning.
+//!
+//! Ports of Gecko's `mozilla::IsAscii` / first-non-ASCII guards from
+//! TextUtils.h. These checks run before nearly every string conversion
+//! — an all-ASCII buffer takes a memcpy fast path instead of a real
+//! decode — so the scans here are word-at-a-time, with AVX2-compiled
+//! variants dispatched at runtime on x86-64 the same way the hashing
+//! crate does.
+
+/// High bit of every byte in a word; a set bit marks a non-ASCII byte.
+const BYTE_HIGH_BITS: u64 = 0x8080_8080_8080_8080;
+
+/// Bits above 0x7F in every 16-bit lane; a set bit marks a non-ASCII
+/// code unit.
+const UNIT_HIGH_BITS: u64 = 0xFF80_FF80_FF80_FF80;
+
+#[inline(always)]
+pub(crate) fn first_non_ascii_impl(bytes: &[u8]) -> Option<usize> {
+    let chunks = bytes.chunks_exact(8);
+    let remainder_start = bytes.len() - chunks.remainder().len();
+
+    for (chunk_index, chunk) in chunks.enumerate() {
+        let word = u64::from_ne_bytes(chunk.try_into().unwrap());
+        if word & BYTE_HIGH_BITS != 0 {
+            // Fall back to a byte scan only inside the offending word
+            let offset = chunk
+                .iter()
+                .position(|&byte| byte >= 0x80)
+                .unwrap();
+            return Some(chunk_index * 8 + offset);
+        }
+    }
+
+    bytes[remainder_start..]
+        .iter()
+        .position(|&byte| byte >= 0x80)
+        .map(|offset| remainder_start + offset)
+}
+
+#[inline(always)]
+fn first_non_ascii_utf16_impl(units: &[u16]) -> Option<usize> {
+    let chunks = units.chunks_exact(4);
+    let remainder_start = units.len() - chunks.remainder().len();
+
+    for (chunk_index, chunk) in chunks.enumerate() {
+        let word = chunk
+            .iter()
+            .enumerate()
+            .fold(0u64, |word, (lane, &unit)| {
+                word | (unit as u64) << (16 * lane)
+            });
+        if word & UNIT_HIGH_BITS != 0 {
+            let offset = chunk
+                .iter()
+                .position(|&unit| unit >= 0x80)
+                .unwrap();
+            return Some(chunk_index * 4 + offset);
+        }
+    }
+
+    units[remainder_start..]
+        .iter()
+        .position(|&unit| unit >= 0x80)
+        .map(|offset| remainder_start + offset)
+}
+
+#[cfg(all(feature = "std", target_arch = "x86_64"))]
+#[target_feature(enable = "avx2")]
+unsafe fn first_non_ascii_avx2(bytes: &[u8]) -> Option<usize> {
+    first_non_ascii_impl(bytes)
+}
+
+#[cfg(all(feature = "std", target_arch = "x86_64"))]
+#[target_feature(enable = "avx2")]
+unsafe fn first_non_ascii_utf16_avx2(units: &[u16]) -> Option<usize> {
+    first_non_ascii_utf16_impl(units)
+}
+
+/// Returns the offset of the first non-ASCII byte, if any.
+///
+/// `None` means the whole buffer is ASCII; callers that only need the
+/// verdict should use [`is_ascii`]. The offset is where a conversion
+/// fast path can stop copying and a real decoder must take over.
+///
+/// # Examples
+///
+/// ```
+/// use firefox_utf8_validator::first_non_ascii;
+///
+/// assert_eq!(first_non_ascii(b"plain"), None);
+/// assert_eq!(first_non_ascii("abcé".as_bytes()), Some(3));
+/// ```
+pub fn first_non_ascii(bytes: &[u8]) -> Option<usize> {
+    #[cfg(all(feature = "std", target_arch = "x86_64"))]
+    {
+        if std::is_x86_feature_detected!("avx2") {
+            // SAFETY: AVX2 support was just verified at runtime
+            return unsafe { first_non_ascii_avx2(bytes) };
+        }
+    }
+
+    first_non_ascii_impl(bytes)
+}
+
+/// Returns whether every byte is ASCII (< 0x80).
+///
+/// The Rust counterpart of `mozilla::IsAscii` over a byte span; the
+/// guard Gecko runs before choosing a string conversion path.
+///
+/// # Examples
+///
+/// ```
+/// use firefox_utf8_validator::is_ascii;
+///
+/// assert!(is_ascii(b"Hello, world!"));
+/// assert!(!is_ascii("Café".as_bytes()));
+/// ```
+#[inline]
+pub fn is_ascii(bytes: &[u8]) -> bool {
+    first_non_ascii(bytes).is_none()
+}
+
+/// Returns the offset of the first non-ASCII UTF-16 code unit, if any.
+///
+/// The `char16_t` counterpart of [`first_non_ascii`]: a unit is
+/// non-ASCII when its value is 0x80 or above.
+///
+/// # Examples
+///
+/// ```
+/// use firefox_utf8_validator::first_non_ascii_utf16;
+///
+/// assert_eq!(first_non_ascii_utf16(&[0x61, 0x62]), None);
+/// assert_eq!(first_non_ascii_utf16(&[0x61, 0xE9]), Some(1));
+/// ```
+pub fn first_non_ascii_utf16(units: &[u16]) -> Option<usize> {
+    #[cfg(all(feature = "std", target_arch = "x86_64"))]
+    {
+        if std::is_x86_feature_detected!("avx2") {
+            // SAFETY: AVX2 support was just verified at runtime
+            return unsafe { first_non_ascii_utf16_avx2(units) };
+        }
+    }
+
+    first_non_ascii_utf16_impl(units)
+}
+
+/// Returns whether every UTF-16 code unit is ASCII (< 0x80).
+///
+/// # Examples
+///
+/// ```
+/// use firefox_utf8_validator::is_ascii_utf16;
+///
+/// assert!(is_ascii_utf16(&[0x48, 0x69]));
+/// assert!(!is_ascii_utf16(&[0x48, 0x20AC]));
+/// ```
+#[inline]
+pub fn is_ascii_utf16(units: &[u16]) -> bool {
+    first_non_ascii_utf16(units).is_none()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_is_ascii_bytes() {
+        assert!(is_ascii(b""));
+        assert!(is_ascii(b"Hello, world!"));
+        assert!(is_ascii(&(0u8..0x80).collect::<Vec<_>>()));
+        assert!(!is_ascii(&[0x80]));
+        assert!(!is_ascii(&[0xFF]));
+        assert!(!is_ascii("Café".as_bytes()));
+    }
+
+    #[test]
+    fn test_first_non_ascii_bytes() {
+        assert_eq!(first_non_ascii(b""), None);
+        assert_eq!(first_non_ascii(b"plain"), None);
+        assert_eq!(first_non_ascii(&[0x80]), Some(0));
+        assert_eq!(first_non_ascii("abcé".as_bytes()), Some(3));
+        // Earliest of several
+        assert_eq!(first_non_ascii(&[0x41, 0xC2, 0xA0, 0xFF]), Some(1));
+    }
+
+    #[test]
+    fn test_first_non_ascii_every_position() {
+        // The word-at-a-time scan must find the right offset at every
+        // alignment, including inside the remainder tail
+        for len in [1, 7, 8, 9, 15, 16, 17, 40] {
+            for position in 0..len {
+                let mut bytes = vec![0x41u8; len];
+                bytes[position] = 0x80;
+                assert_eq!(first_non_ascii(&bytes), Some(position), "len {len}");
+                assert!(!is_ascii(&bytes));
+            }
+            assert_eq!(first_non_ascii(&vec![0x41u8; len]), None);
+        }
+    }
+
+    #[test]
+    fn test_is_ascii_utf16_units() {
+        assert!(is_ascii_utf16(&[]));
+        assert!(is_ascii_utf16(&[0x00, 0x48, 0x7F]));
+        assert!(!is_ascii_utf16(&[0x80]));
+        assert!(!is_ascii_utf16(&[0x48, 0x20AC]));
+        assert!(!is_ascii_utf16(&[0xD800]));
+    }
+
+    #[test]
+    fn test_first_non_ascii_utf16_every_position() {
+        for len in [1, 3, 4, 5, 7, 8, 9, 20] {
+            for position in 0..len {
+                let mut units = vec![0x41u16; len];
+                units[position] = 0x80;
+                assert_eq!(
+                    first_non_ascii_utf16(&units),
+                    Some(position),
+                    "len {len}"
+                );
+            }
+            assert_eq!(first_non_ascii_utf16(&vec![0x41u16; len]), None);
+        }
+    }
+
+    #[test]
+    fn test_agrees_with_std() {
+        let mixed: Vec<u8> = (0u8..=255).cycle().take(1000).collect();
+        assert_eq!(is_ascii(&mixed), mixed.is_ascii());
+        assert_eq!(
+            first_non_ascii(&mixed),
+            mixed.iter().position(|&b| b >= 0x80)
+        );
+    }
+}
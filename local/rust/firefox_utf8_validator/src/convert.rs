@@ -0,0 +1,430 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+//! UTF-8 ↔ UTF-16 conversion and lossy UTF-8 sanitization.
+//!
+//! Ports of Gecko's `ConvertUtf8toUtf16` / `ConvertUtf16toUtf8` entry
+//! points. Both directions convert lossily with Gecko's replacement
+//! semantics: invalid UTF-8 subparts and unpaired UTF-16 surrogates
+//! become U+FFFD, never an error. The safe APIs allocate and therefore
+//! need the `alloc` feature; the length-counting helpers below work
+//! with core alone. With the `std` feature the FFI exports in
+//! [`ffi`](crate::ffi) fill caller-provided buffers and return the
+//! written length.
+
+#[cfg(feature = "alloc")]
+use alloc::{borrow::Cow, string::String, vec::Vec};
+
+/// Converts UTF-8 bytes to UTF-16 code units.
+///
+/// Invalid sequences are replaced with U+FFFD per maximal subpart, so
+/// every input converts. Valid input round-trips exactly. The output
+/// never exceeds `bytes.len()` units, which C++ callers use to
+/// pre-size buffers.
+///
+/// # Examples
+///
+/// ```
+/// use firefox_utf8_validator::convert_utf8_to_utf16;
+///
+/// assert_eq!(convert_utf8_to_utf16(b"Hi"), vec![0x48, 0x69]);
+/// assert_eq!(convert_utf8_to_utf16("🦀".as_bytes()), vec![0xD83E, 0xDD80]);
+/// assert_eq!(convert_utf8_to_utf16(&[0xFF]), vec![0xFFFD]);
+/// ```
+#[cfg(feature = "alloc")]
+pub fn convert_utf8_to_utf16(bytes: &[u8]) -> Vec<u16> {
+    String::from_utf8_lossy(bytes).encode_utf16().collect()
+}
+
+/// Converts UTF-16 code units to UTF-8.
+///
+/// Unpaired surrogates are replaced with U+FFFD, matching Gecko's
+/// conversion of potentially ill-formed UTF-16 strings. The output
+/// never exceeds `3 * units.len()` bytes (a lone unit encodes to at
+/// most 3 bytes; a surrogate pair's 2 units to 4).
+///
+/// # Examples
+///
+/// ```
+/// use firefox_utf8_validator::convert_utf16_to_utf8;
+///
+/// assert_eq!(convert_utf16_to_utf8(&[0x48, 0x69]), "Hi");
+/// assert_eq!(convert_utf16_to_utf8(&[0xD83E, 0xDD80]), "🦀");
+/// assert_eq!(convert_utf16_to_utf8(&[0xD800]), "\u{FFFD}");
+/// ```
+#[cfg(feature = "alloc")]
+pub fn convert_utf16_to_utf8(units: &[u16]) -> String {
+    String::from_utf16_lossy(units)
+}
+
+/// Returns the input as valid UTF-8, replacing invalid sequences with
+/// U+FFFD.
+///
+/// Uses the WHATWG replacement algorithm (one U+FFFD per maximal
+/// subpart, the same convention as [`validate_utf8`]'s
+/// `invalid_length`). Valid input borrows — no allocation, no copy —
+/// so the common case costs one validation scan.
+///
+/// # Examples
+///
+/// ```
+/// use std::borrow::Cow;
+/// use firefox_utf8_validator::to_valid_utf8_lossy;
+///
+/// assert!(matches!(to_valid_utf8_lossy(b"ok"), Cow::Borrowed("ok")));
+/// assert_eq!(to_valid_utf8_lossy(b"a\xFFb"), "a\u{FFFD}b");
+/// ```
+///
+/// [`validate_utf8`]: crate::validate_utf8
+#[cfg(feature = "alloc")]
+pub fn to_valid_utf8_lossy(bytes: &[u8]) -> Cow<'_, str> {
+    String::from_utf8_lossy(bytes)
+}
+
+/// Counts the code points in a UTF-8 buffer.
+///
+/// Invalid input still gets an answer: each invalid maximal subpart
+/// counts as the one U+FFFD a lossy conversion would produce, so the
+/// result always equals `to_valid_utf8_lossy(bytes).chars().count()`
+/// without allocating. Valid portions are counted by scanning, not
+/// decoding.
+///
+/// # Examples
+///
+/// ```
+/// use firefox_utf8_validator::count_code_points;
+///
+/// assert_eq!(count_code_points(b"abc"), 3);
+/// assert_eq!(count_code_points("é🦀".as_bytes()), 2);
+/// assert_eq!(count_code_points(&[0x61, 0xFF, 0xFF]), 3);
+/// ```
+pub fn count_code_points(bytes: &[u8]) -> usize {
+    let mut count = 0;
+    let mut offset = 0;
+
+    loop {
+        match core::str::from_utf8(&bytes[offset..]) {
+            Ok(valid) => {
+                count += valid.chars().count();
+                return count;
+            }
+            Err(error) => {
+                count += bytes[offset..offset + error.valid_up_to()]
+                    .iter()
+                    .filter(|&&byte| byte & 0xC0 != 0x80)
+                    .count();
+                // The invalid maximal subpart becomes one U+FFFD
+                count += 1;
+                let skip = error
+                    .error_len()
+                    .unwrap_or(bytes.len() - offset - error.valid_up_to());
+                offset += error.valid_up_to() + skip;
+            }
+        }
+    }
+}
+
+/// Returns the UTF-16 length a valid UTF-8 buffer converts to.
+///
+/// `None` if the buffer is not valid UTF-8 — callers that tolerate
+/// invalid input should size by `bytes.len()` (the lossy worst case)
+/// instead. On valid input this is exactly
+/// [`convert_utf8_to_utf16`]`(bytes).len()`, computed in one scan so
+/// buffers can be sized before converting, matching the C++
+/// `Utf8ToUtf16` length-computation step.
+///
+/// # Examples
+///
+/// ```
+/// use firefox_utf8_validator::utf8_to_utf16_length;
+///
+/// assert_eq!(utf8_to_utf16_length(b"abc"), Some(3));
+/// assert_eq!(utf8_to_utf16_length("🦀".as_bytes()), Some(2)); // surrogate pair
+/// assert_eq!(utf8_to_utf16_length(&[0xFF]), None);
+/// ```
+pub fn utf8_to_utf16_length(bytes: &[u8]) -> Option<usize> {
+    if core::str::from_utf8(bytes).is_err() {
+        return None;
+    }
+
+    // In valid UTF-8 every code point contributes exactly one
+    // non-continuation byte, and the astral ones (4-byte sequences,
+    // lead 0xF0-0xF4) contribute a second UTF-16 unit for the
+    // surrogate pair.
+    let length = bytes
+        .iter()
+        .map(|&byte| match byte {
+            0xF0.. => 2,
+            byte if byte & 0xC0 != 0x80 => 1,
+            _ => 0,
+        })
+        .sum();
+    Some(length)
+}
+
+/// Repairs a buffer to valid UTF-8 in place, reporting each
+/// replacement.
+///
+/// Every invalid maximal subpart is replaced with U+FFFD (WHATWG
+/// replacement algorithm). The returned offsets locate each replaced
+/// subpart in the *original* buffer, so diagnostics can point at the
+/// bytes that were thrown away; the count of replacements is the
+/// vector's length. A valid buffer is left untouched and reports no
+/// replacements.
+///
+/// # Examples
+///
+/// ```
+/// use firefox_utf8_validator::repair_utf8_in_place;
+///
+/// let mut buf = b"a\xFF\xFEb".to_vec();
+/// let replaced = repair_utf8_in_place(&mut buf);
+/// assert_eq!(buf, "a\u{FFFD}\u{FFFD}b".as_bytes());
+/// assert_eq!(replaced, vec![1, 2]);
+/// ```
+#[cfg(feature = "alloc")]
+pub fn repair_utf8_in_place(bytes: &mut Vec<u8>) -> Vec<usize> {
+    const REPLACEMENT: &[u8] = "\u{FFFD}".as_bytes();
+
+    // Scan ahead before touching the buffer: the valid case must not
+    // reallocate
+    if core::str::from_utf8(bytes).is_ok() {
+        return Vec::new();
+    }
+
+    let mut repaired = Vec::with_capacity(bytes.len() + REPLACEMENT.len());
+    let mut replacements = Vec::new();
+    let mut offset = 0;
+
+    loop {
+        match core::str::from_utf8(&bytes[offset..]) {
+            Ok(_) => {
+                repaired.extend_from_slice(&bytes[offset..]);
+                break;
+            }
+            Err(error) => {
+                let valid_up_to = error.valid_up_to();
+                repaired.extend_from_slice(&bytes[offset..offset + valid_up_to]);
+                replacements.push(offset + valid_up_to);
+                repaired.extend_from_slice(REPLACEMENT);
+                // error_len is None only at end of input
+                let skip = error
+                    .error_len()
+                    .unwrap_or(bytes.len() - offset - valid_up_to);
+                offset += valid_up_to + skip;
+            }
+        }
+    }
+
+    *bytes = repaired;
+    replacements
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_utf8_to_utf16_basic() {
+        assert_eq!(convert_utf8_to_utf16(b""), Vec::<u16>::new());
+        assert_eq!(convert_utf8_to_utf16(b"ASCII"), b"ASCII".map(u16::from));
+        assert_eq!(convert_utf8_to_utf16("é".as_bytes()), vec![0xE9]);
+        assert_eq!(convert_utf8_to_utf16("€".as_bytes()), vec![0x20AC]);
+        // Astral code points become surrogate pairs
+        assert_eq!(
+            convert_utf8_to_utf16("𐍈".as_bytes()),
+            vec![0xD800, 0xDF48]
+        );
+    }
+
+    #[test]
+    fn test_utf8_to_utf16_replacement() {
+        assert_eq!(convert_utf8_to_utf16(&[0xFF]), vec![0xFFFD]);
+        // Each maximal subpart gets its own replacement
+        assert_eq!(
+            convert_utf8_to_utf16(&[0x41, 0xED, 0xA0, 0x80, 0x42]),
+            vec![0x41, 0xFFFD, 0xFFFD, 0xFFFD, 0x42]
+        );
+        // Truncated trailing sequence
+        assert_eq!(
+            convert_utf8_to_utf16(&[0x41, 0xF0, 0x9F]),
+            vec![0x41, 0xFFFD]
+        );
+    }
+
+    #[test]
+    fn test_utf16_to_utf8_basic() {
+        assert_eq!(convert_utf16_to_utf8(&[]), "");
+        assert_eq!(convert_utf16_to_utf8(&[0x48, 0x69]), "Hi");
+        assert_eq!(convert_utf16_to_utf8(&[0x20AC]), "€");
+        assert_eq!(convert_utf16_to_utf8(&[0xD800, 0xDF48]), "𐍈");
+    }
+
+    #[test]
+    fn test_utf16_to_utf8_unpaired_surrogates() {
+        assert_eq!(convert_utf16_to_utf8(&[0xD800]), "\u{FFFD}");
+        assert_eq!(convert_utf16_to_utf8(&[0xDFFF]), "\u{FFFD}");
+        // High surrogate followed by a non-low unit: only the high one
+        // is replaced
+        assert_eq!(convert_utf16_to_utf8(&[0xD800, 0x41]), "\u{FFFD}A");
+        // Low before high is two lone surrogates
+        assert_eq!(
+            convert_utf16_to_utf8(&[0xDC00, 0xD800]),
+            "\u{FFFD}\u{FFFD}"
+        );
+    }
+
+    #[test]
+    fn test_round_trips() {
+        for text in ["", "plain", "Café ☕", "日本語", "🦀🎉", "mixed 𐍈 text"] {
+            let units = convert_utf8_to_utf16(text.as_bytes());
+            assert_eq!(convert_utf16_to_utf8(&units), text);
+
+            let bytes = convert_utf16_to_utf8(&text.encode_utf16().collect::<Vec<_>>());
+            assert_eq!(bytes, text);
+        }
+    }
+
+    #[test]
+    fn test_lossy_borrows_when_valid() {
+        for text in ["", "plain", "Café ☕", "🦀"] {
+            match to_valid_utf8_lossy(text.as_bytes()) {
+                Cow::Borrowed(s) => assert_eq!(s, text),
+                Cow::Owned(_) => panic!("valid input {text:?} should borrow"),
+            }
+        }
+        assert!(matches!(
+            to_valid_utf8_lossy(&[0xFF]),
+            Cow::Owned(_)
+        ));
+    }
+
+    #[test]
+    fn test_lossy_matches_conversion_semantics() {
+        // Same replacement algorithm as the UTF-16 conversion: one
+        // U+FFFD per maximal subpart
+        for bytes in [
+            &[0x41, 0xED, 0xA0, 0x80, 0x42][..],
+            &[0x41, 0xF0, 0x9F],
+            &[0xFF, 0xFE],
+            &[0xC2],
+        ] {
+            let lossy = to_valid_utf8_lossy(bytes);
+            assert_eq!(
+                lossy.encode_utf16().collect::<Vec<_>>(),
+                convert_utf8_to_utf16(bytes)
+            );
+        }
+    }
+
+    #[test]
+    fn test_count_code_points() {
+        assert_eq!(count_code_points(b""), 0);
+        assert_eq!(count_code_points(b"abc"), 3);
+        assert_eq!(count_code_points("Café ☕ 🦀".as_bytes()), 8);
+        // Invalid subparts count as one replacement each
+        assert_eq!(count_code_points(&[0x61, 0xED, 0xA0, 0x80, 0x62]), 5);
+        assert_eq!(count_code_points(&[0xF0, 0x9F]), 1);
+    }
+
+    #[test]
+    fn test_count_matches_lossy() {
+        let inputs: Vec<Vec<u8>> = vec![
+            b"plain".to_vec(),
+            "🦀🎉".as_bytes().to_vec(),
+            vec![0xFF],
+            vec![0xC2, 0xA0, 0xC2],
+            (0u8..=255).collect(),
+        ];
+        for input in inputs {
+            assert_eq!(
+                count_code_points(&input),
+                to_valid_utf8_lossy(&input).chars().count(),
+                "input {input:02X?}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_utf16_length() {
+        assert_eq!(utf8_to_utf16_length(b""), Some(0));
+        assert_eq!(utf8_to_utf16_length(b"abc"), Some(3));
+        assert_eq!(utf8_to_utf16_length("é€".as_bytes()), Some(2));
+        assert_eq!(utf8_to_utf16_length("🦀".as_bytes()), Some(2));
+        assert_eq!(utf8_to_utf16_length(&[0xFF]), None);
+        assert_eq!(utf8_to_utf16_length(&[0xF0, 0x9F]), None);
+    }
+
+    #[test]
+    fn test_utf16_length_matches_conversion() {
+        for text in ["", "plain", "Café ☕", "日本語", "🦀🎉", "mixed 𐍈 text"] {
+            assert_eq!(
+                utf8_to_utf16_length(text.as_bytes()),
+                Some(convert_utf8_to_utf16(text.as_bytes()).len())
+            );
+        }
+    }
+
+    #[test]
+    fn test_repair_valid_untouched() {
+        let mut buf = "Café 🦀".as_bytes().to_vec();
+        let original = buf.clone();
+        assert_eq!(repair_utf8_in_place(&mut buf), Vec::<usize>::new());
+        assert_eq!(buf, original);
+    }
+
+    #[test]
+    fn test_repair_reports_original_offsets() {
+        // Surrogate encoding: three maximal subparts at offsets 1, 2, 3
+        let mut buf = vec![0x41, 0xED, 0xA0, 0x80, 0x42];
+        assert_eq!(repair_utf8_in_place(&mut buf), vec![1, 2, 3]);
+        assert_eq!(buf, "A\u{FFFD}\u{FFFD}\u{FFFD}B".as_bytes());
+
+        // Truncated trailing sequence is one replacement
+        let mut buf = vec![0x41, 0xF0, 0x9F];
+        assert_eq!(repair_utf8_in_place(&mut buf), vec![1]);
+        assert_eq!(buf, "A\u{FFFD}".as_bytes());
+
+        // Two independent bad bytes with valid text between them
+        let mut buf = vec![0xFF, 0x61, 0x62, 0xFE, 0x63];
+        assert_eq!(repair_utf8_in_place(&mut buf), vec![0, 3]);
+        assert_eq!(buf, "\u{FFFD}ab\u{FFFD}c".as_bytes());
+    }
+
+    #[test]
+    fn test_repair_agrees_with_lossy() {
+        let inputs: Vec<Vec<u8>> = vec![
+            b"plain".to_vec(),
+            vec![0xFF],
+            vec![0xC2, 0xA0, 0xC2],
+            vec![0xED, 0xA0, 0x80, 0xF4, 0x90, 0x80, 0x80],
+            (0u8..=255).collect(),
+        ];
+        for input in inputs {
+            let mut buf = input.clone();
+            let replacements = repair_utf8_in_place(&mut buf);
+            assert_eq!(buf, to_valid_utf8_lossy(&input).as_bytes());
+            // One U+FFFD in the output per reported replacement
+            let fffd_count = to_valid_utf8_lossy(&input)
+                .chars()
+                .filter(|&c| c == '\u{FFFD}')
+                .count();
+            assert_eq!(replacements.len(), fffd_count);
+            // Offsets are strictly increasing and in range
+            assert!(replacements.windows(2).all(|w| w[0] < w[1]));
+            assert!(replacements.iter().all(|&o| o < input.len()));
+        }
+    }
+
+    #[test]
+    fn test_output_size_bounds() {
+        let worst_utf8: Vec<u8> = (0..200u8).map(|i| if i % 2 == 0 { 0xFF } else { i % 0x7F }).collect();
+        let units = convert_utf8_to_utf16(&worst_utf8);
+        assert!(units.len() <= worst_utf8.len());
+
+        let worst_utf16: Vec<u16> = (0..200u32).map(|i| 0xD800 + (i % 0x800) as u16).collect();
+        let bytes = convert_utf16_to_utf8(&worst_utf16);
+        assert!(bytes.len() <= 3 * worst_utf16.len());
+    }
+}
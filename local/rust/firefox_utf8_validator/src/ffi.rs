@@ -102,10 +102,806 @@ pub unsafe extern "C" fn IsValidUtf8_RUST(a_code_units: *const u8, a_count: usiz
     result.unwrap_or(false)
 }
 
+/// Status codes for `DecodeOneUtf8CodePoint_RUST`, mirroring
+/// [`Utf8DecodeError`](crate::Utf8DecodeError) for C++ callers.
+pub mod decode_status {
+    /// Decoding succeeded.
+    pub const OK: i32 = 0;
+    /// The first byte cannot begin a sequence.
+    pub const BAD_LEAD_UNIT: i32 = 1;
+    /// The input ends before the sequence does.
+    pub const NOT_ENOUGH_UNITS: i32 = 2;
+    /// A non-continuation byte inside the sequence.
+    pub const BAD_TRAILING_UNIT: i32 = 3;
+    /// The sequence decodes to a surrogate.
+    pub const SURROGATE: i32 = 4;
+    /// The sequence decodes beyond U+10FFFF.
+    pub const TOO_LARGE: i32 = 5;
+    /// Overlong encoding.
+    pub const NOT_SHORTEST_FORM: i32 = 6;
+}
+
+/// FFI export: decodes the first UTF-8 code point in a buffer.
+///
+/// On success returns [`decode_status::OK`] and writes the scalar
+/// value through `a_code_point` and the number of bytes consumed
+/// through `a_length`; on failure returns the matching
+/// `decode_status` code and leaves both out-params untouched. Either
+/// out-param may be null if the caller does not need it.
+///
+/// # Safety
+///
+/// The caller must ensure:
+/// - `a_code_units` points to at least `a_count` readable bytes, or is
+///   null with `a_count` 0
+/// - `a_code_point` and `a_length` are each null or writable
+///
+/// # C++ Signature
+///
+/// ```cpp
+/// extern "C" int32_t DecodeOneUtf8CodePoint_RUST(
+///     const uint8_t* a_code_units, size_t a_count,
+///     uint32_t* a_code_point, size_t* a_length);
+/// ```
+#[no_mangle]
+pub unsafe extern "C" fn DecodeOneUtf8CodePoint_RUST(
+    a_code_units: *const u8,
+    a_count: usize,
+    a_code_point: *mut u32,
+    a_length: *mut usize,
+) -> i32 {
+    let result = panic::catch_unwind(|| {
+        let bytes = if a_code_units.is_null() {
+            &[][..]
+        } else {
+            // SAFETY: caller guarantees a_code_units covers a_count bytes
+            unsafe { std::slice::from_raw_parts(a_code_units, a_count) }
+        };
+
+        match crate::decode_one(bytes) {
+            Ok((code_point, length)) => {
+                if !a_code_point.is_null() {
+                    // SAFETY: caller guarantees a_code_point is writable
+                    unsafe { *a_code_point = code_point as u32 };
+                }
+                if !a_length.is_null() {
+                    // SAFETY: caller guarantees a_length is writable
+                    unsafe { *a_length = length };
+                }
+                decode_status::OK
+            }
+            Err(crate::Utf8DecodeError::BadLeadUnit) => decode_status::BAD_LEAD_UNIT,
+            Err(crate::Utf8DecodeError::NotEnoughUnits { .. }) => decode_status::NOT_ENOUGH_UNITS,
+            Err(crate::Utf8DecodeError::BadTrailingUnit { .. }) => decode_status::BAD_TRAILING_UNIT,
+            Err(crate::Utf8DecodeError::Surrogate { .. }) => decode_status::SURROGATE,
+            Err(crate::Utf8DecodeError::TooLarge { .. }) => decode_status::TOO_LARGE,
+            Err(crate::Utf8DecodeError::NotShortestForm { .. }) => {
+                decode_status::NOT_SHORTEST_FORM
+            }
+        }
+    });
+
+    result.unwrap_or(decode_status::BAD_LEAD_UNIT)
+}
+
+/// C-compatible error report for `IsValidUtf8WithError_RUST`.
+///
+/// The FFI mirror of [`Utf8ErrorInfo`](crate::Utf8ErrorInfo); `status`
+/// uses the [`decode_status`] codes.
+#[repr(C)]
+pub struct Utf8ErrorInfoFFI {
+    /// Byte offset of the first invalid sequence.
+    pub offset: usize,
+    /// Length in bytes of the invalid sequence.
+    pub invalid_length: usize,
+    /// A [`decode_status`] code describing the failure.
+    pub status: i32,
+}
+
+/// FFI export: validates UTF-8 and fills a caller-provided error
+/// report on failure.
+///
+/// Returns the same verdict as `IsValidUtf8_RUST`; when the buffer is
+/// invalid and `a_error` is non-null, the struct receives the offset,
+/// length, and status of the first invalid sequence so C++ callers can
+/// produce a useful diagnostic. On a valid buffer the struct is left
+/// untouched.
+///
+/// # Safety
+///
+/// The caller must ensure:
+/// - `a_code_units` points to at least `a_count` readable bytes, or is
+///   null with `a_count` 0
+/// - `a_error` is null or points to writable memory for a
+///   [`Utf8ErrorInfoFFI`]
+///
+/// # C++ Signature
+///
+/// ```cpp
+/// struct Utf8ErrorInfo { size_t offset; size_t invalid_length; int32_t status; };
+/// extern "C" bool IsValidUtf8WithError_RUST(
+///     const uint8_t* a_code_units, size_t a_count, Utf8ErrorInfo* a_error);
+/// ```
+#[no_mangle]
+pub unsafe extern "C" fn IsValidUtf8WithError_RUST(
+    a_code_units: *const u8,
+    a_count: usize,
+    a_error: *mut Utf8ErrorInfoFFI,
+) -> bool {
+    let result = panic::catch_unwind(|| {
+        if a_code_units.is_null() {
+            return a_count == 0;
+        }
+
+        // SAFETY: caller guarantees a_code_units covers a_count bytes
+        let bytes = unsafe { std::slice::from_raw_parts(a_code_units, a_count) };
+
+        match crate::validate_utf8(bytes) {
+            Ok(()) => true,
+            Err(info) => {
+                if !a_error.is_null() {
+                    let status = match info.kind {
+                        crate::Utf8DecodeError::BadLeadUnit => decode_status::BAD_LEAD_UNIT,
+                        crate::Utf8DecodeError::NotEnoughUnits { .. } => {
+                            decode_status::NOT_ENOUGH_UNITS
+                        }
+                        crate::Utf8DecodeError::BadTrailingUnit { .. } => {
+                            decode_status::BAD_TRAILING_UNIT
+                        }
+                        crate::Utf8DecodeError::Surrogate { .. } => decode_status::SURROGATE,
+                        crate::Utf8DecodeError::TooLarge { .. } => decode_status::TOO_LARGE,
+                        crate::Utf8DecodeError::NotShortestForm { .. } => {
+                            decode_status::NOT_SHORTEST_FORM
+                        }
+                    };
+                    // SAFETY: caller guarantees a_error is writable
+                    unsafe {
+                        *a_error = Utf8ErrorInfoFFI {
+                            offset: info.offset,
+                            invalid_length: info.invalid_length,
+                            status,
+                        };
+                    }
+                }
+                false
+            }
+        }
+    });
+
+    result.unwrap_or(false)
+}
+
+/// FFI export: converts UTF-8 to UTF-16 into a caller-provided buffer.
+///
+/// Returns the number of UTF-16 code units the full conversion
+/// produces. If that fits in `a_dest_capacity` the units are written to
+/// `a_dest`; otherwise nothing is written and the caller should retry
+/// with a buffer of the returned size. Sizing `a_dest_capacity` to
+/// `a_src_count` always suffices. Conversion is lossy: invalid UTF-8
+/// becomes U+FFFD, matching
+/// [`convert_utf8_to_utf16`](crate::convert_utf8_to_utf16).
+///
+/// # Safety
+///
+/// The caller must ensure:
+/// - `a_src` points to at least `a_src_count` readable bytes, or is
+///   null with `a_src_count` 0
+/// - `a_dest` points to at least `a_dest_capacity` writable `char16_t`,
+///   or is null with `a_dest_capacity` 0
+///
+/// # C++ Signature
+///
+/// ```cpp
+/// extern "C" size_t ConvertUtf8toUtf16_RUST(
+///     const uint8_t* a_src, size_t a_src_count,
+///     char16_t* a_dest, size_t a_dest_capacity);
+/// ```
+#[no_mangle]
+pub unsafe extern "C" fn ConvertUtf8toUtf16_RUST(
+    a_src: *const u8,
+    a_src_count: usize,
+    a_dest: *mut u16,
+    a_dest_capacity: usize,
+) -> usize {
+    let result = panic::catch_unwind(|| {
+        let bytes = if a_src.is_null() {
+            &[][..]
+        } else {
+            // SAFETY: caller guarantees a_src covers a_src_count bytes
+            unsafe { std::slice::from_raw_parts(a_src, a_src_count) }
+        };
+
+        let units = crate::convert_utf8_to_utf16(bytes);
+        if !a_dest.is_null() && units.len() <= a_dest_capacity {
+            // SAFETY: caller guarantees a_dest holds a_dest_capacity
+            // units, and we just checked the output fits
+            unsafe { std::ptr::copy_nonoverlapping(units.as_ptr(), a_dest, units.len()) };
+        }
+        units.len()
+    });
+
+    result.unwrap_or(0)
+}
+
+/// FFI export: converts UTF-16 to UTF-8 into a caller-provided buffer.
+///
+/// Returns the number of UTF-8 bytes the full conversion produces,
+/// writing them only when they fit in `a_dest_capacity` (sizing it to
+/// `3 * a_src_count` always suffices). Unpaired surrogates become
+/// U+FFFD, matching
+/// [`convert_utf16_to_utf8`](crate::convert_utf16_to_utf8).
+///
+/// # Safety
+///
+/// The caller must ensure:
+/// - `a_src` points to at least `a_src_count` readable `char16_t`, or
+///   is null with `a_src_count` 0
+/// - `a_dest` points to at least `a_dest_capacity` writable bytes, or
+///   is null with `a_dest_capacity` 0
+///
+/// # C++ Signature
+///
+/// ```cpp
+/// extern "C" size_t ConvertUtf16toUtf8_RUST(
+///     const char16_t* a_src, size_t a_src_count,
+///     uint8_t* a_dest, size_t a_dest_capacity);
+/// ```
+#[no_mangle]
+pub unsafe extern "C" fn ConvertUtf16toUtf8_RUST(
+    a_src: *const u16,
+    a_src_count: usize,
+    a_dest: *mut u8,
+    a_dest_capacity: usize,
+) -> usize {
+    let result = panic::catch_unwind(|| {
+        let units = if a_src.is_null() {
+            &[][..]
+        } else {
+            // SAFETY: caller guarantees a_src covers a_src_count units
+            unsafe { std::slice::from_raw_parts(a_src, a_src_count) }
+        };
+
+        let bytes = crate::convert_utf16_to_utf8(units);
+        if !a_dest.is_null() && bytes.len() <= a_dest_capacity {
+            // SAFETY: caller guarantees a_dest holds a_dest_capacity
+            // bytes, and we just checked the output fits
+            unsafe { std::ptr::copy_nonoverlapping(bytes.as_ptr(), a_dest, bytes.len()) };
+        }
+        bytes.len()
+    });
+
+    result.unwrap_or(0)
+}
+
+/// FFI export: returns whether every byte is ASCII.
+///
+/// The exported form of [`is_ascii`](crate::is_ascii); an empty or
+/// null-with-zero-count buffer is ASCII.
+///
+/// # Safety
+///
+/// The caller must ensure `a_chars` points to at least `a_count`
+/// readable bytes, or is null with `a_count` 0.
+///
+/// # C++ Signature
+///
+/// ```cpp
+/// extern "C" bool IsAscii_RUST(const uint8_t* a_chars, size_t a_count);
+/// ```
+#[no_mangle]
+pub unsafe extern "C" fn IsAscii_RUST(a_chars: *const u8, a_count: usize) -> bool {
+    let result = panic::catch_unwind(|| {
+        if a_chars.is_null() {
+            return a_count == 0;
+        }
+
+        // SAFETY: caller guarantees a_chars covers a_count bytes
+        let bytes = unsafe { std::slice::from_raw_parts(a_chars, a_count) };
+        crate::is_ascii(bytes)
+    });
+
+    result.unwrap_or(false)
+}
+
+/// FFI export: returns whether every UTF-16 code unit is ASCII.
+///
+/// The `char16_t` counterpart of `IsAscii_RUST`.
+///
+/// # Safety
+///
+/// The caller must ensure `a_chars` points to at least `a_count`
+/// readable `char16_t`, or is null with `a_count` 0.
+///
+/// # C++ Signature
+///
+/// ```cpp
+/// extern "C" bool IsAscii16_RUST(const char16_t* a_chars, size_t a_count);
+/// ```
+#[no_mangle]
+pub unsafe extern "C" fn IsAscii16_RUST(a_chars: *const u16, a_count: usize) -> bool {
+    let result = panic::catch_unwind(|| {
+        if a_chars.is_null() {
+            return a_count == 0;
+        }
+
+        // SAFETY: caller guarantees a_chars covers a_count units
+        let units = unsafe { std::slice::from_raw_parts(a_chars, a_count) };
+        crate::is_ascii_utf16(units)
+    });
+
+    result.unwrap_or(false)
+}
+
+/// FFI export: finds the first non-ASCII byte.
+///
+/// Returns `true` and writes the offset through `a_index` when a
+/// non-ASCII byte exists; returns `false` with `a_index` untouched
+/// when the buffer is all ASCII. `a_index` may be null if only the
+/// verdict matters.
+///
+/// # Safety
+///
+/// The caller must ensure:
+/// - `a_chars` points to at least `a_count` readable bytes, or is null
+///   with `a_count` 0
+/// - `a_index` is null or writable
+///
+/// # C++ Signature
+///
+/// ```cpp
+/// extern "C" bool FirstNonAscii_RUST(
+///     const uint8_t* a_chars, size_t a_count, size_t* a_index);
+/// ```
+#[no_mangle]
+pub unsafe extern "C" fn FirstNonAscii_RUST(
+    a_chars: *const u8,
+    a_count: usize,
+    a_index: *mut usize,
+) -> bool {
+    let result = panic::catch_unwind(|| {
+        let bytes = if a_chars.is_null() {
+            &[][..]
+        } else {
+            // SAFETY: caller guarantees a_chars covers a_count bytes
+            unsafe { std::slice::from_raw_parts(a_chars, a_count) }
+        };
+
+        match crate::first_non_ascii(bytes) {
+            Some(index) => {
+                if !a_index.is_null() {
+                    // SAFETY: caller guarantees a_index is writable
+                    unsafe { *a_index = index };
+                }
+                true
+            }
+            None => false,
+        }
+    });
+
+    result.unwrap_or(false)
+}
+
+/// FFI export: finds the first non-ASCII UTF-16 code unit.
+///
+/// The `char16_t` counterpart of `FirstNonAscii_RUST`, with the same
+/// out-param contract.
+///
+/// # Safety
+///
+/// The caller must ensure:
+/// - `a_chars` points to at least `a_count` readable `char16_t`, or is
+///   null with `a_count` 0
+/// - `a_index` is null or writable
+///
+/// # C++ Signature
+///
+/// ```cpp
+/// extern "C" bool FirstNonAscii16_RUST(
+///     const char16_t* a_chars, size_t a_count, size_t* a_index);
+/// ```
+#[no_mangle]
+pub unsafe extern "C" fn FirstNonAscii16_RUST(
+    a_chars: *const u16,
+    a_count: usize,
+    a_index: *mut usize,
+) -> bool {
+    let result = panic::catch_unwind(|| {
+        let units = if a_chars.is_null() {
+            &[][..]
+        } else {
+            // SAFETY: caller guarantees a_chars covers a_count units
+            unsafe { std::slice::from_raw_parts(a_chars, a_count) }
+        };
+
+        match crate::first_non_ascii_utf16(units) {
+            Some(index) => {
+                if !a_index.is_null() {
+                    // SAFETY: caller guarantees a_index is writable
+                    unsafe { *a_index = index };
+                }
+                true
+            }
+            None => false,
+        }
+    });
+
+    result.unwrap_or(false)
+}
+
+/// FFI export: returns whether a buffer is UTF-8 narrowable to
+/// Latin-1.
+///
+/// The exported form of [`is_utf8_latin1`](crate::is_utf8_latin1).
+///
+/// # Safety
+///
+/// The caller must ensure `a_code_units` points to at least `a_count`
+/// readable bytes, or is null with `a_count` 0.
+///
+/// # C++ Signature
+///
+/// ```cpp
+/// extern "C" bool IsUtf8Latin1_RUST(const uint8_t* a_code_units, size_t a_count);
+/// ```
+#[no_mangle]
+pub unsafe extern "C" fn IsUtf8Latin1_RUST(a_code_units: *const u8, a_count: usize) -> bool {
+    let result = panic::catch_unwind(|| {
+        if a_code_units.is_null() {
+            return a_count == 0;
+        }
+
+        // SAFETY: caller guarantees a_code_units covers a_count bytes
+        let bytes = unsafe { std::slice::from_raw_parts(a_code_units, a_count) };
+        crate::is_utf8_latin1(bytes)
+    });
+
+    result.unwrap_or(false)
+}
+
+/// FFI export: truncates UTF-16 to Latin-1 into a caller-provided
+/// buffer.
+///
+/// The output is always exactly `a_src_count` bytes — one per unit —
+/// and that is the returned value. The bytes are written only when
+/// `a_dest_capacity` is large enough, following the same
+/// retry-with-bigger-buffer contract as the UTF conversions. Matches
+/// [`lossy_convert_utf16_to_latin1`](crate::lossy_convert_utf16_to_latin1):
+/// units above U+00FF lose their high byte.
+///
+/// # Safety
+///
+/// The caller must ensure:
+/// - `a_src` points to at least `a_src_count` readable `char16_t`, or
+///   is null with `a_src_count` 0
+/// - `a_dest` points to at least `a_dest_capacity` writable bytes, or
+///   is null with `a_dest_capacity` 0
+///
+/// # C++ Signature
+///
+/// ```cpp
+/// extern "C" size_t LossyConvertUtf16toLatin1_RUST(
+///     const char16_t* a_src, size_t a_src_count,
+///     uint8_t* a_dest, size_t a_dest_capacity);
+/// ```
+#[no_mangle]
+pub unsafe extern "C" fn LossyConvertUtf16toLatin1_RUST(
+    a_src: *const u16,
+    a_src_count: usize,
+    a_dest: *mut u8,
+    a_dest_capacity: usize,
+) -> usize {
+    let result = panic::catch_unwind(|| {
+        let units = if a_src.is_null() {
+            &[][..]
+        } else {
+            // SAFETY: caller guarantees a_src covers a_src_count units
+            unsafe { std::slice::from_raw_parts(a_src, a_src_count) }
+        };
+
+        let bytes = crate::lossy_convert_utf16_to_latin1(units);
+        if !a_dest.is_null() && bytes.len() <= a_dest_capacity {
+            // SAFETY: caller guarantees a_dest holds a_dest_capacity
+            // bytes, and we just checked the output fits
+            unsafe { std::ptr::copy_nonoverlapping(bytes.as_ptr(), a_dest, bytes.len()) };
+        }
+        bytes.len()
+    });
+
+    result.unwrap_or(0)
+}
+
+/// FFI export: converts Latin-1 to UTF-8 into a caller-provided
+/// buffer.
+///
+/// Returns the number of UTF-8 bytes the full conversion produces,
+/// writing them only when they fit in `a_dest_capacity` (sizing it to
+/// `2 * a_src_count` always suffices). Matches
+/// [`convert_latin1_to_utf8`](crate::convert_latin1_to_utf8); the
+/// conversion cannot fail.
+///
+/// # Safety
+///
+/// The caller must ensure:
+/// - `a_src` points to at least `a_src_count` readable bytes, or is
+///   null with `a_src_count` 0
+/// - `a_dest` points to at least `a_dest_capacity` writable bytes, or
+///   is null with `a_dest_capacity` 0
+///
+/// # C++ Signature
+///
+/// ```cpp
+/// extern "C" size_t ConvertLatin1toUtf8_RUST(
+///     const uint8_t* a_src, size_t a_src_count,
+///     uint8_t* a_dest, size_t a_dest_capacity);
+/// ```
+#[no_mangle]
+pub unsafe extern "C" fn ConvertLatin1toUtf8_RUST(
+    a_src: *const u8,
+    a_src_count: usize,
+    a_dest: *mut u8,
+    a_dest_capacity: usize,
+) -> usize {
+    let result = panic::catch_unwind(|| {
+        let bytes = if a_src.is_null() {
+            &[][..]
+        } else {
+            // SAFETY: caller guarantees a_src covers a_src_count bytes
+            unsafe { std::slice::from_raw_parts(a_src, a_src_count) }
+        };
+
+        let utf8 = crate::convert_latin1_to_utf8(bytes);
+        if !a_dest.is_null() && utf8.len() <= a_dest_capacity {
+            // SAFETY: caller guarantees a_dest holds a_dest_capacity
+            // bytes, and we just checked the output fits
+            unsafe { std::ptr::copy_nonoverlapping(utf8.as_ptr(), a_dest, utf8.len()) };
+        }
+        utf8.len()
+    });
+
+    result.unwrap_or(0)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_ffi_is_utf8_latin1() {
+        let latin1 = "Café".as_bytes();
+        let wide = "€".as_bytes();
+        unsafe {
+            assert!(IsUtf8Latin1_RUST(latin1.as_ptr(), latin1.len()));
+            assert!(!IsUtf8Latin1_RUST(wide.as_ptr(), wide.len()));
+            assert!(IsUtf8Latin1_RUST(std::ptr::null(), 0));
+            assert!(!IsUtf8Latin1_RUST(std::ptr::null(), 3));
+        }
+    }
+
+    #[test]
+    fn test_ffi_utf16_to_latin1() {
+        let src: Vec<u16> = vec![0x48, 0xE9, 0x20AC];
+        let mut dest = [0u8; 8];
+        unsafe {
+            let written = LossyConvertUtf16toLatin1_RUST(
+                src.as_ptr(),
+                src.len(),
+                dest.as_mut_ptr(),
+                dest.len(),
+            );
+            assert_eq!(written, 3);
+            assert_eq!(&dest[..3], &[0x48, 0xE9, 0xAC]);
+
+            // Too-small buffer: required size returned, nothing written
+            let mut small = [0xABu8; 2];
+            let required = LossyConvertUtf16toLatin1_RUST(
+                src.as_ptr(),
+                src.len(),
+                small.as_mut_ptr(),
+                small.len(),
+            );
+            assert_eq!(required, 3);
+            assert_eq!(small, [0xAB, 0xAB]);
+        }
+    }
+
+    #[test]
+    fn test_ffi_latin1_to_utf8() {
+        let src = [0x43u8, 0xE9];
+        let mut dest = [0u8; 8];
+        unsafe {
+            let written =
+                ConvertLatin1toUtf8_RUST(src.as_ptr(), src.len(), dest.as_mut_ptr(), dest.len());
+            assert_eq!(&dest[..written], "Cé".as_bytes());
+
+            // Null dest with zero capacity acts as a length query
+            assert_eq!(
+                ConvertLatin1toUtf8_RUST(src.as_ptr(), src.len(), std::ptr::null_mut(), 0),
+                3
+            );
+        }
+    }
+
+    #[test]
+    fn test_ffi_is_ascii() {
+        let ascii = b"plain text";
+        let non_ascii = "Café".as_bytes();
+        unsafe {
+            assert!(IsAscii_RUST(ascii.as_ptr(), ascii.len()));
+            assert!(!IsAscii_RUST(non_ascii.as_ptr(), non_ascii.len()));
+            assert!(IsAscii_RUST(std::ptr::null(), 0));
+            assert!(!IsAscii_RUST(std::ptr::null(), 5));
+        }
+
+        let units: Vec<u16> = vec![0x48, 0x69];
+        let wide: Vec<u16> = vec![0x48, 0x20AC];
+        unsafe {
+            assert!(IsAscii16_RUST(units.as_ptr(), units.len()));
+            assert!(!IsAscii16_RUST(wide.as_ptr(), wide.len()));
+            assert!(IsAscii16_RUST(std::ptr::null(), 0));
+        }
+    }
+
+    #[test]
+    fn test_ffi_first_non_ascii() {
+        let mixed = "abcé".as_bytes();
+        let ascii = b"abc";
+        let mut index = 99usize;
+        unsafe {
+            assert!(FirstNonAscii_RUST(mixed.as_ptr(), mixed.len(), &mut index));
+            assert_eq!(index, 3);
+
+            // All-ASCII: false, out-param untouched
+            index = 99;
+            assert!(!FirstNonAscii_RUST(ascii.as_ptr(), ascii.len(), &mut index));
+            assert_eq!(index, 99);
+
+            // Null out-param is tolerated
+            assert!(FirstNonAscii_RUST(
+                mixed.as_ptr(),
+                mixed.len(),
+                std::ptr::null_mut()
+            ));
+        }
+
+        let units: Vec<u16> = vec![0x61, 0x62, 0xD800];
+        unsafe {
+            index = 99;
+            assert!(FirstNonAscii16_RUST(units.as_ptr(), units.len(), &mut index));
+            assert_eq!(index, 2);
+
+            assert!(!FirstNonAscii16_RUST(std::ptr::null(), 0, &mut index));
+        }
+    }
+
+    #[test]
+    fn test_ffi_convert_utf8_to_utf16() {
+        let src = "a🦀".as_bytes();
+        let mut dest = [0u16; 8];
+        unsafe {
+            let written =
+                ConvertUtf8toUtf16_RUST(src.as_ptr(), src.len(), dest.as_mut_ptr(), dest.len());
+            assert_eq!(written, 3);
+            assert_eq!(&dest[..3], &[0x61, 0xD83E, 0xDD80]);
+
+            // Too-small buffer: required size returned, nothing written
+            let mut small = [0xABCDu16; 2];
+            let required =
+                ConvertUtf8toUtf16_RUST(src.as_ptr(), src.len(), small.as_mut_ptr(), small.len());
+            assert_eq!(required, 3);
+            assert_eq!(small, [0xABCD, 0xABCD]);
+
+            // Null dest with zero capacity acts as a length query
+            assert_eq!(
+                ConvertUtf8toUtf16_RUST(src.as_ptr(), src.len(), std::ptr::null_mut(), 0),
+                3
+            );
+        }
+    }
+
+    #[test]
+    fn test_ffi_convert_utf16_to_utf8() {
+        let src: Vec<u16> = vec![0x61, 0xD800, 0x62]; // lone surrogate
+        let mut dest = [0u8; 16];
+        unsafe {
+            let written =
+                ConvertUtf16toUtf8_RUST(src.as_ptr(), src.len(), dest.as_mut_ptr(), dest.len());
+            assert_eq!(&dest[..written], "a\u{FFFD}b".as_bytes());
+
+            assert_eq!(
+                ConvertUtf16toUtf8_RUST(std::ptr::null(), 0, dest.as_mut_ptr(), dest.len()),
+                0
+            );
+        }
+    }
+
+    #[test]
+    fn test_ffi_validate_with_error() {
+        unsafe {
+            let mut info = Utf8ErrorInfoFFI {
+                offset: 77,
+                invalid_length: 77,
+                status: 77,
+            };
+
+            // Valid input: true, struct untouched
+            let valid = "Café".as_bytes();
+            assert!(IsValidUtf8WithError_RUST(
+                valid.as_ptr(),
+                valid.len(),
+                &mut info
+            ));
+            assert_eq!(info.offset, 77);
+
+            // Invalid input: false, struct filled
+            let invalid = b"Hi\xF4\x90\x80\x80";
+            assert!(!IsValidUtf8WithError_RUST(
+                invalid.as_ptr(),
+                invalid.len(),
+                &mut info
+            ));
+            assert_eq!(info.offset, 2);
+            assert_eq!(info.status, decode_status::TOO_LARGE);
+
+            // Null error struct is tolerated
+            assert!(!IsValidUtf8WithError_RUST(
+                invalid.as_ptr(),
+                invalid.len(),
+                std::ptr::null_mut()
+            ));
+        }
+    }
+
+    #[test]
+    fn test_ffi_decode_one_success() {
+        let data = "é rest".as_bytes();
+        let mut code_point = 0u32;
+        let mut length = 0usize;
+        unsafe {
+            let status = DecodeOneUtf8CodePoint_RUST(
+                data.as_ptr(),
+                data.len(),
+                &mut code_point,
+                &mut length,
+            );
+            assert_eq!(status, decode_status::OK);
+        }
+        assert_eq!(code_point, 0xE9);
+        assert_eq!(length, 2);
+    }
+
+    #[test]
+    fn test_ffi_decode_one_errors() {
+        unsafe {
+            let mut code_point = 99u32;
+            let mut length = 99usize;
+
+            let truncated = [0xE2u8, 0x82];
+            let status = DecodeOneUtf8CodePoint_RUST(
+                truncated.as_ptr(),
+                truncated.len(),
+                &mut code_point,
+                &mut length,
+            );
+            assert_eq!(status, decode_status::NOT_ENOUGH_UNITS);
+            // Out-params untouched on failure
+            assert_eq!(code_point, 99);
+            assert_eq!(length, 99);
+
+            let surrogate = [0xEDu8, 0xA0, 0x80];
+            let status = DecodeOneUtf8CodePoint_RUST(
+                surrogate.as_ptr(),
+                surrogate.len(),
+                std::ptr::null_mut(),
+                std::ptr::null_mut(),
+            );
+            assert_eq!(status, decode_status::SURROGATE);
+
+            let status =
+                DecodeOneUtf8CodePoint_RUST(std::ptr::null(), 0, &mut code_point, &mut length);
+            assert_eq!(status, decode_status::NOT_ENOUGH_UNITS);
+        }
+    }
+
     #[test]
     fn test_ffi_null_pointer_zero_length() {
         // Null pointer with zero length should return true (empty string)
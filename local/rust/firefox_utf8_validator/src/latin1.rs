@@ -0,0 +1,136 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+//! Latin-1 detection and conversion.
+//!
+//! Ports of the xpcom string Latin-1 entry points. Gecko narrows
+//! strings whose code points all fit in U+0000-U+00FF to one byte per
+//! character; these helpers answer whether a UTF-8 buffer is narrowable
+//! and convert between the narrow and UTF-8 representations. Detection
+//! works with core alone; the conversions allocate and need the
+//! `alloc` feature.
+
+#[cfg(feature = "alloc")]
+use alloc::{string::String, vec::Vec};
+
+/// Returns whether a buffer is valid UTF-8 whose code points all fit
+/// in Latin-1 (U+0000-U+00FF).
+///
+/// This is the check behind Gecko's string-narrowing optimization: a
+/// `true` answer means the text can be stored one byte per character.
+/// Invalid UTF-8 is `false`, not an error — it cannot be narrowed
+/// either way.
+///
+/// # Examples
+///
+/// ```
+/// use firefox_utf8_validator::is_utf8_latin1;
+///
+/// assert!(is_utf8_latin1(b"plain"));
+/// assert!(is_utf8_latin1("Café".as_bytes())); // é is U+00E9
+/// assert!(!is_utf8_latin1("€".as_bytes())); // U+20AC
+/// assert!(!is_utf8_latin1(&[0xFF])); // not UTF-8
+/// ```
+pub fn is_utf8_latin1(bytes: &[u8]) -> bool {
+    match core::str::from_utf8(bytes) {
+        Ok(text) => text.chars().all(|c| (c as u32) <= 0xFF),
+        Err(_) => false,
+    }
+}
+
+/// Converts UTF-16 to Latin-1 by truncating each unit to its low byte.
+///
+/// The port of `LossyConvertUtf16toLatin1`: units above U+00FF —
+/// including surrogates — simply lose their high byte, matching the
+/// C++ behavior exactly. Callers are expected to know the input is
+/// Latin-1 text; the lossy truncation is the documented contract, not
+/// a validation failure mode.
+///
+/// # Examples
+///
+/// ```
+/// use firefox_utf8_validator::lossy_convert_utf16_to_latin1;
+///
+/// assert_eq!(lossy_convert_utf16_to_latin1(&[0x48, 0xE9]), vec![0x48, 0xE9]);
+/// // U+20AC truncates to 0xAC
+/// assert_eq!(lossy_convert_utf16_to_latin1(&[0x20AC]), vec![0xAC]);
+/// ```
+#[cfg(feature = "alloc")]
+pub fn lossy_convert_utf16_to_latin1(units: &[u16]) -> Vec<u8> {
+    units.iter().map(|&unit| unit as u8).collect()
+}
+
+/// Converts Latin-1 bytes to UTF-8.
+///
+/// Every byte is the code point of the same value, so this conversion
+/// is total and exact: ASCII bytes copy through and 0x80-0xFF become
+/// two-byte sequences. The output is at most `2 * bytes.len()` bytes,
+/// which C++ callers use to pre-size buffers.
+///
+/// # Examples
+///
+/// ```
+/// use firefox_utf8_validator::convert_latin1_to_utf8;
+///
+/// assert_eq!(convert_latin1_to_utf8(b"plain"), "plain");
+/// assert_eq!(convert_latin1_to_utf8(&[0x43, 0xE9]), "Cé");
+/// ```
+#[cfg(feature = "alloc")]
+pub fn convert_latin1_to_utf8(bytes: &[u8]) -> String {
+    bytes.iter().map(|&byte| byte as char).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_is_utf8_latin1() {
+        assert!(is_utf8_latin1(b""));
+        assert!(is_utf8_latin1(b"plain ASCII"));
+        assert!(is_utf8_latin1("Café ÿ ñ".as_bytes()));
+        // U+00FF is the last Latin-1 code point; U+0100 is out
+        assert!(is_utf8_latin1("\u{FF}".as_bytes()));
+        assert!(!is_utf8_latin1("\u{100}".as_bytes()));
+        assert!(!is_utf8_latin1("€".as_bytes()));
+        assert!(!is_utf8_latin1("🦀".as_bytes()));
+        // Invalid UTF-8 is not Latin-1, even when the raw bytes would be
+        assert!(!is_utf8_latin1(&[0xE9]));
+        assert!(!is_utf8_latin1(&[0xC0, 0x80]));
+    }
+
+    #[test]
+    fn test_lossy_utf16_to_latin1() {
+        assert_eq!(lossy_convert_utf16_to_latin1(&[]), Vec::<u8>::new());
+        assert_eq!(
+            lossy_convert_utf16_to_latin1(&[0x48, 0x69, 0xE9]),
+            vec![0x48, 0x69, 0xE9]
+        );
+        // Out-of-range units truncate to the low byte
+        assert_eq!(lossy_convert_utf16_to_latin1(&[0x20AC]), vec![0xAC]);
+        assert_eq!(lossy_convert_utf16_to_latin1(&[0xD800]), vec![0x00]);
+        assert_eq!(lossy_convert_utf16_to_latin1(&[0x0100]), vec![0x00]);
+    }
+
+    #[test]
+    fn test_latin1_to_utf8() {
+        assert_eq!(convert_latin1_to_utf8(b""), "");
+        assert_eq!(convert_latin1_to_utf8(b"ASCII"), "ASCII");
+        assert_eq!(convert_latin1_to_utf8(&[0xE9]), "\u{E9}");
+        // Every Latin-1 byte value converts and stays Latin-1
+        let all: Vec<u8> = (0u8..=255).collect();
+        let utf8 = convert_latin1_to_utf8(&all);
+        assert!(is_utf8_latin1(utf8.as_bytes()));
+        assert!(utf8.len() <= 2 * all.len());
+    }
+
+    #[test]
+    fn test_latin1_round_trip() {
+        // Latin-1 → UTF-8 → UTF-16 → Latin-1 is the identity
+        let all: Vec<u8> = (0u8..=255).collect();
+        let utf8 = convert_latin1_to_utf8(&all);
+        let units = crate::convert_utf8_to_utf16(utf8.as_bytes());
+        assert_eq!(lossy_convert_utf16_to_latin1(&units), all);
+    }
+}
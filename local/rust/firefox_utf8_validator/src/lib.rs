@@ -21,7 +21,7 @@
 //!
 //! # Implementation
 //!
-//! This implementation leverages Rust's standard library `std::str::from_utf8()`
+//! This implementation leverages Rust's standard library `core::str::from_utf8()`
 //! which provides production-grade, well-tested UTF-8 validation. The Rust
 //! implementation validates the same UTF-8 standard (RFC 3629) as Firefox's C++
 //! implementation.
@@ -31,11 +31,51 @@
 //! The core validation logic is safe Rust code. The FFI layer requires unsafe
 //! code to construct slices from raw pointers, but includes comprehensive
 //! safety checks (null pointer checks, bounds validation).
+//!
+//! # Features
+//!
+//! The crate is `no_std`-capable so it can be linked into minimal
+//! targets and the standalone JS shell:
+//!
+//! - `std` (default): the FFI layer and runtime CPU feature detection
+//!   for the vectorized ASCII scans
+//! - `alloc` (implied by `std`): the conversion and lossy-repair APIs
+//!
+//! With no features enabled, validation, decoding, ASCII/Latin-1
+//! detection, and length counting remain available on core alone.
 
+#![cfg_attr(not(feature = "std"), no_std)]
 #![deny(unsafe_op_in_unsafe_fn)]
 #![warn(missing_docs)]
 
+#[cfg(feature = "alloc")]
+extern crate alloc;
+
+pub mod ascii;
+pub mod convert;
+#[cfg(feature = "std")]
 pub mod ffi;
+pub mod latin1;
+#[cfg(feature = "std")]
+pub mod parallel;
+#[cfg(feature = "simd")]
+pub mod simd;
+pub mod sniff;
+pub mod utf16;
+
+pub use ascii::{first_non_ascii, first_non_ascii_utf16, is_ascii, is_ascii_utf16};
+#[cfg(feature = "alloc")]
+pub use convert::{
+    convert_utf16_to_utf8, convert_utf8_to_utf16, repair_utf8_in_place, to_valid_utf8_lossy,
+};
+pub use convert::{count_code_points, utf8_to_utf16_length};
+#[cfg(feature = "alloc")]
+pub use latin1::{convert_latin1_to_utf8, lossy_convert_utf16_to_latin1};
+pub use latin1::is_utf8_latin1;
+#[cfg(feature = "std")]
+pub use parallel::is_valid_utf8_parallel;
+pub use sniff::{detect_bom, probably_utf8, strip_bom, Encoding};
+pub use utf16::{ensure_valid_utf16_lossy, is_valid_utf16};
 
 #[cfg(test)]
 mod tests;
@@ -81,12 +121,211 @@ mod tests;
 ///
 /// # Performance
 ///
-/// This function uses Rust's `std::str::from_utf8()` which is highly optimized
+/// This function uses Rust's `core::str::from_utf8()` which is highly optimized
 /// and may use SIMD instructions on supported platforms. Performance is
 /// expected to be equal to or better than the C++ implementation.
 #[inline]
 pub fn is_valid_utf8(bytes: &[u8]) -> bool {
-    std::str::from_utf8(bytes).is_ok()
+    #[cfg(feature = "simd")]
+    {
+        simd::is_valid_utf8_simd(bytes)
+    }
+    #[cfg(not(feature = "simd"))]
+    {
+        core::str::from_utf8(bytes).is_ok()
+    }
+}
+
+/// Why a byte sequence failed to decode as one UTF-8 code point.
+///
+/// Mirrors the distinctions the C++ `DecodeOneUtf8CodePoint` reports
+/// through its callback parameters (`aOnBadLeadUnit`,
+/// `aOnNotEnoughUnits`, `aOnBadTrailingUnit`, `aOnBadCodePoint`,
+/// `aOnNotShortestForm`), so diagnostics can say *what* was wrong
+/// rather than just "invalid".
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Utf8DecodeError {
+    /// The first byte can never begin a sequence: a bare continuation
+    /// byte (0x80-0xBF) or 0xF8-0xFF.
+    BadLeadUnit,
+    /// The lead byte promised more bytes than the input holds.
+    NotEnoughUnits {
+        /// Total sequence length the lead byte called for.
+        required: usize,
+        /// Bytes actually available, including the lead byte.
+        available: usize,
+    },
+    /// A byte inside the sequence is not a continuation byte.
+    BadTrailingUnit {
+        /// Offset of the offending byte from the start of the sequence.
+        index: usize,
+    },
+    /// The sequence decodes to a surrogate (U+D800-U+DFFF), which is
+    /// not a valid scalar value.
+    Surrogate {
+        /// The decoded surrogate value.
+        value: u32,
+    },
+    /// The sequence decodes beyond U+10FFFF.
+    TooLarge {
+        /// The decoded out-of-range value.
+        value: u32,
+    },
+    /// Overlong encoding: the code point has a shorter encoding, which
+    /// UTF-8 requires.
+    NotShortestForm {
+        /// The decoded value that should have been encoded shorter.
+        value: u32,
+    },
+}
+
+/// Decodes the first UTF-8 code point in `bytes`.
+///
+/// The Rust counterpart of `mozilla::DecodeOneUtf8CodePoint`: on
+/// success returns the scalar value and how many bytes it consumed
+/// (1-4); on failure a [`Utf8DecodeError`] saying exactly what was
+/// malformed. Empty input reports `NotEnoughUnits`.
+///
+/// [`is_valid_utf8`] remains the fast path for whole-buffer checks;
+/// this entry point is for callers that walk a buffer code point by
+/// code point or need precise diagnostics.
+///
+/// # Examples
+///
+/// ```
+/// use firefox_utf8_validator::{decode_one, Utf8DecodeError};
+///
+/// assert_eq!(decode_one("é!".as_bytes()), Ok(('é', 2)));
+/// assert_eq!(decode_one(&[0xC3]), Err(Utf8DecodeError::NotEnoughUnits {
+///     required: 2,
+///     available: 1,
+/// }));
+/// assert_eq!(decode_one(&[0xC0, 0x80]),
+///            Err(Utf8DecodeError::NotShortestForm { value: 0 }));
+/// ```
+pub fn decode_one(bytes: &[u8]) -> Result<(char, usize), Utf8DecodeError> {
+    let lead = match bytes.first() {
+        Some(&lead) => lead,
+        None => {
+            return Err(Utf8DecodeError::NotEnoughUnits {
+                required: 1,
+                available: 0,
+            })
+        }
+    };
+
+    if lead < 0x80 {
+        return Ok((lead as char, 1));
+    }
+
+    // Sequence length and the smallest code point that needs it. As in
+    // the C++ decoder, 0xC0/0xC1 and 0xF5-0xF7 count as leads here and
+    // fail the shortest-form / range checks below, not BadLeadUnit.
+    let (required, min_value) = match lead {
+        0x80..=0xBF => return Err(Utf8DecodeError::BadLeadUnit),
+        0xC0..=0xDF => (2, 0x80),
+        0xE0..=0xEF => (3, 0x800),
+        0xF0..=0xF7 => (4, 0x10000),
+        _ => return Err(Utf8DecodeError::BadLeadUnit),
+    };
+
+    if bytes.len() < required {
+        return Err(Utf8DecodeError::NotEnoughUnits {
+            required,
+            available: bytes.len(),
+        });
+    }
+
+    // Accumulate the payload bits, validating each continuation byte
+    let mut value = (lead as u32) & (0x7F >> required);
+    for (index, &byte) in bytes.iter().enumerate().take(required).skip(1) {
+        if byte & 0xC0 != 0x80 {
+            return Err(Utf8DecodeError::BadTrailingUnit { index });
+        }
+        value = (value << 6) | (byte as u32 & 0x3F);
+    }
+
+    if value < min_value {
+        return Err(Utf8DecodeError::NotShortestForm { value });
+    }
+    if (0xD800..=0xDFFF).contains(&value) {
+        return Err(Utf8DecodeError::Surrogate { value });
+    }
+    if value > 0x10FFFF {
+        return Err(Utf8DecodeError::TooLarge { value });
+    }
+
+    // All the scalar-value exclusions were checked above
+    Ok((char::from_u32(value).unwrap(), required))
+}
+
+/// Where and how a buffer failed UTF-8 validation.
+///
+/// Produced by [`validate_utf8`]; pairs the byte offset of the first
+/// invalid sequence with its length (how many bytes a lossy converter
+/// would replace) and the detailed [`Utf8DecodeError`] for the
+/// sequence.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Utf8ErrorInfo {
+    /// Byte offset of the first invalid sequence; everything before it
+    /// is valid UTF-8.
+    pub offset: usize,
+    /// Length in bytes of the invalid sequence. For a buffer that ends
+    /// mid-sequence this runs to the end of the buffer.
+    pub invalid_length: usize,
+    /// What was wrong with the sequence.
+    pub kind: Utf8DecodeError,
+}
+
+/// Validates a buffer, reporting where validation failed.
+///
+/// The diagnostic companion to [`is_valid_utf8`]: the same
+/// accept/reject decision, but a rejection carries the offset, length,
+/// and kind of the first invalid sequence so callers can point at the
+/// offending bytes instead of reporting a bare false. The fast
+/// whole-buffer scan runs first; the detailed decode happens only on
+/// the error path.
+///
+/// `invalid_length` follows the Unicode "maximal subpart" convention —
+/// the bytes a lossy converter would replace with one U+FFFD. A lead
+/// byte whose continuation range excludes the next byte therefore
+/// reports length 1, even when the detailed [`kind`](Utf8ErrorInfo)
+/// describes the longer sequence the lead byte promised.
+///
+/// # Examples
+///
+/// ```
+/// use firefox_utf8_validator::{validate_utf8, Utf8DecodeError};
+///
+/// assert!(validate_utf8(b"Hello").is_ok());
+///
+/// let err = validate_utf8(b"Hi\xED\xA0\x80!").unwrap_err();
+/// assert_eq!(err.offset, 2);
+/// assert_eq!(err.invalid_length, 1); // ED cannot be followed by A0
+/// assert_eq!(err.kind, Utf8DecodeError::Surrogate { value: 0xD800 });
+/// ```
+pub fn validate_utf8(bytes: &[u8]) -> Result<(), Utf8ErrorInfo> {
+    let error = match core::str::from_utf8(bytes) {
+        Ok(_) => return Ok(()),
+        Err(error) => error,
+    };
+
+    let offset = error.valid_up_to();
+    // error_len is None when the buffer ends mid-sequence; report the
+    // remaining bytes as the invalid span in that case
+    let invalid_length = error.error_len().unwrap_or(bytes.len() - offset);
+    // Re-decode the offending sequence for the detailed kind. decode_one
+    // must fail here: from_utf8 just rejected this position.
+    let kind = match decode_one(&bytes[offset..]) {
+        Err(kind) => kind,
+        Ok(_) => unreachable!("from_utf8 and decode_one disagree at offset {}", offset),
+    };
+
+    Err(Utf8ErrorInfo {
+        offset,
+        invalid_length,
+        kind,
+    })
 }
 
 /// Validates UTF-8 with explicit length (alternative API).
@@ -120,6 +359,6 @@ pub unsafe fn is_valid_utf8_ptr(ptr: *const u8, len: usize) -> bool {
     }
 
     // SAFETY: Caller guarantees ptr is valid for len bytes
-    let bytes = unsafe { std::slice::from_raw_parts(ptr, len) };
+    let bytes = unsafe { core::slice::from_raw_parts(ptr, len) };
     is_valid_utf8(bytes)
 }
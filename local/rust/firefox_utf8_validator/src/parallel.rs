@@ -0,0 +1,172 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+//! Chunked parallel validation for large buffers.
+//!
+//! Cache and downloads code validates multi-megabyte files before
+//! handing them to JS; a single serial scan leaves every other core
+//! idle. Validation parallelizes trivially once the chunk boundaries
+//! sit on code point boundaries: a buffer is valid exactly when each
+//! aligned chunk is valid on its own. Threading follows the hashing
+//! crate's `hash_bytes_parallel` — scoped threads, contiguous runs per
+//! worker, no pool kept alive between calls.
+
+/// Chunk size for [`is_valid_utf8_parallel`]; inputs no longer than
+/// this are validated inline without spawning.
+const PARALLEL_CHUNK_SIZE: usize = 1 << 20;
+
+/// Splits `bytes` into independently validatable chunks of roughly
+/// `chunk_size` bytes, nudging each split forward onto a code point
+/// boundary. Returns `None` when no boundary exists within the 3-byte
+/// lookahead — more than three continuation bytes in a row — which
+/// already proves the buffer invalid.
+fn aligned_chunks(bytes: &[u8], chunk_size: usize) -> Option<Vec<&[u8]>> {
+    let mut chunks = Vec::with_capacity(bytes.len() / chunk_size + 1);
+    let mut start = 0;
+
+    while start < bytes.len() {
+        let mut end = (start + chunk_size).min(bytes.len());
+        // A valid continuation run is at most 3 bytes, so a boundary
+        // must appear within that lookahead
+        let mut lookahead = 0;
+        while end < bytes.len() && bytes[end] & 0xC0 == 0x80 {
+            end += 1;
+            lookahead += 1;
+            if lookahead > 3 {
+                return None;
+            }
+        }
+        chunks.push(&bytes[start..end]);
+        start = end;
+    }
+
+    Some(chunks)
+}
+
+fn is_valid_utf8_chunked(bytes: &[u8], chunk_size: usize) -> bool {
+    if bytes.len() <= chunk_size {
+        return crate::is_valid_utf8(bytes);
+    }
+
+    let chunks = match aligned_chunks(bytes, chunk_size) {
+        Some(chunks) => chunks,
+        None => return false,
+    };
+
+    let workers = std::thread::available_parallelism()
+        .map(|n| n.get())
+        .unwrap_or(1)
+        .min(chunks.len());
+    if workers <= 1 {
+        return chunks.iter().all(|chunk| crate::is_valid_utf8(chunk));
+    }
+
+    // Give each worker a contiguous run of chunks; the verdict is
+    // order-independent so joining collects a simple conjunction
+    let per_worker = chunks.len().div_ceil(workers);
+    std::thread::scope(|scope| {
+        let handles: Vec<_> = chunks
+            .chunks(per_worker)
+            .map(|group| {
+                scope.spawn(move || group.iter().all(|chunk| crate::is_valid_utf8(chunk)))
+            })
+            .collect();
+        handles
+            .into_iter()
+            .all(|handle| handle.join().expect("validation worker panicked"))
+    })
+}
+
+/// Validates a large buffer across the available cores.
+///
+/// Returns exactly the same verdict as
+/// [`is_valid_utf8`](crate::is_valid_utf8): the input is split into
+/// 1 MiB chunks aligned to code point boundaries and the chunks are
+/// validated concurrently on scoped threads. Inputs of one chunk or
+/// less are validated inline, so small callers pay nothing for the
+/// parallel entry point.
+///
+/// # Examples
+///
+/// ```
+/// use firefox_utf8_validator::is_valid_utf8_parallel;
+///
+/// let big = "Café 🦀 ".repeat(1 << 18);
+/// assert!(is_valid_utf8_parallel(big.as_bytes()));
+/// assert!(!is_valid_utf8_parallel(&[0xFF]));
+/// ```
+pub fn is_valid_utf8_parallel(bytes: &[u8]) -> bool {
+    is_valid_utf8_chunked(bytes, PARALLEL_CHUNK_SIZE)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_aligned_chunks_land_on_boundaries() {
+        let text = "Café 🦀 日本語 ".repeat(40);
+        let bytes = text.as_bytes();
+        for chunk_size in [1, 3, 7, 16, 64] {
+            let chunks = aligned_chunks(bytes, chunk_size).unwrap();
+            // Chunks partition the buffer and each is valid on its own
+            assert_eq!(chunks.iter().map(|c| c.len()).sum::<usize>(), bytes.len());
+            for chunk in &chunks {
+                assert!(core::str::from_utf8(chunk).is_ok());
+            }
+        }
+    }
+
+    #[test]
+    fn test_aligned_chunks_reject_long_continuation_runs() {
+        // Five continuation bytes cannot straddle any boundary
+        let mut bytes = vec![0x41u8; 8];
+        bytes.extend([0x80; 5]);
+        bytes.extend([0x41; 8]);
+        assert_eq!(aligned_chunks(&bytes, 8), None);
+        assert!(!is_valid_utf8_chunked(&bytes, 8));
+    }
+
+    #[test]
+    fn test_chunked_agrees_with_serial() {
+        let valid = "ASCII and Café and 日本語 and 🦀🎉 ".repeat(50);
+        let mut corrupted = valid.as_bytes().to_vec();
+        corrupted[valid.len() / 2] = 0xFF;
+        // Still valid: the last character is an ASCII space
+        let truncated = &valid.as_bytes()[..valid.len() - 1];
+        // Invalid: cut two bytes into the crab's 4-byte sequence
+        let mut mid_sequence = valid.as_bytes().to_vec();
+        mid_sequence.truncate(valid.find('🦀').unwrap() + 2);
+
+        for input in [
+            &b""[..],
+            b"small",
+            valid.as_bytes(),
+            &corrupted,
+            truncated,
+            &mid_sequence,
+        ] {
+            for chunk_size in [1, 5, 64, 1 << 20] {
+                assert_eq!(
+                    is_valid_utf8_chunked(input, chunk_size),
+                    crate::is_valid_utf8(input),
+                    "chunk_size {chunk_size}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_parallel_entry_point() {
+        // Over one chunk so the threaded path actually runs
+        let big = "Café 🦀 ".repeat(1 << 18);
+        assert!(big.len() > PARALLEL_CHUNK_SIZE);
+        assert!(is_valid_utf8_parallel(big.as_bytes()));
+
+        let mut corrupted = big.as_bytes().to_vec();
+        let position = corrupted.len() - 2;
+        corrupted[position] = 0xFF;
+        assert!(!is_valid_utf8_parallel(&corrupted));
+    }
+}
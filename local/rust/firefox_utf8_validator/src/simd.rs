@@ -0,0 +1,173 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+//! Vectorized UTF-8 validation with runtime dispatch.
+//!
+//! A simdutf-style fast path: ASCII runs — the overwhelming majority
+//! of real-world input — are skipped word-at-a-time with the scan from
+//! [`ascii`](crate::ascii), and only the non-ASCII sequences go
+//! through the full decoder. The whole validator is compiled once per
+//! instruction set (`AVX2` and `SSE4.2` on x86-64, baseline `NEON` on
+//! aarch64) and the best supported copy is picked at runtime, the same
+//! dispatch scheme the ASCII scans and the hashing crate use.
+//!
+//! The scalar [`is_valid_utf8`](crate::is_valid_utf8) decision is the
+//! specification: the differential tests below require bit-identical
+//! accept/reject verdicts on every input.
+
+use crate::ascii::first_non_ascii_impl;
+
+#[inline(always)]
+fn is_valid_utf8_simd_impl(bytes: &[u8]) -> bool {
+    let mut offset = 0;
+    while offset < bytes.len() {
+        // Skip the ASCII run starting here in bulk
+        match first_non_ascii_impl(&bytes[offset..]) {
+            None => return true,
+            Some(skip) => offset += skip,
+        }
+        // Decode exactly one non-ASCII sequence, then go back to
+        // skipping ASCII
+        match crate::decode_one(&bytes[offset..]) {
+            Ok((_, length)) => offset += length,
+            Err(_) => return false,
+        }
+    }
+    true
+}
+
+#[cfg(target_arch = "x86_64")]
+#[target_feature(enable = "avx2")]
+unsafe fn is_valid_utf8_avx2(bytes: &[u8]) -> bool {
+    is_valid_utf8_simd_impl(bytes)
+}
+
+#[cfg(target_arch = "x86_64")]
+#[target_feature(enable = "sse4.2")]
+unsafe fn is_valid_utf8_sse42(bytes: &[u8]) -> bool {
+    is_valid_utf8_simd_impl(bytes)
+}
+
+#[cfg(target_arch = "aarch64")]
+#[target_feature(enable = "neon")]
+unsafe fn is_valid_utf8_neon(bytes: &[u8]) -> bool {
+    is_valid_utf8_simd_impl(bytes)
+}
+
+/// Validates UTF-8 through the vectorized fast path.
+///
+/// Returns exactly the same verdict as
+/// [`is_valid_utf8`](crate::is_valid_utf8) on every input — the two
+/// implementations differ only in how fast they reject or accept.
+/// [`is_valid_utf8`] itself routes here when the `simd` feature is
+/// enabled, so most callers never name this function.
+///
+/// # Examples
+///
+/// ```
+/// use firefox_utf8_validator::simd::is_valid_utf8_simd;
+///
+/// assert!(is_valid_utf8_simd("Café 🦀".as_bytes()));
+/// assert!(!is_valid_utf8_simd(&[0xED, 0xA0, 0x80]));
+/// ```
+///
+/// [`is_valid_utf8`]: crate::is_valid_utf8
+pub fn is_valid_utf8_simd(bytes: &[u8]) -> bool {
+    #[cfg(target_arch = "x86_64")]
+    {
+        if std::is_x86_feature_detected!("avx2") {
+            // SAFETY: AVX2 support was just verified at runtime
+            return unsafe { is_valid_utf8_avx2(bytes) };
+        }
+        if std::is_x86_feature_detected!("sse4.2") {
+            // SAFETY: SSE4.2 support was just verified at runtime
+            return unsafe { is_valid_utf8_sse42(bytes) };
+        }
+    }
+    #[cfg(target_arch = "aarch64")]
+    {
+        if std::arch::is_aarch64_feature_detected!("neon") {
+            // SAFETY: NEON support was just verified at runtime
+            return unsafe { is_valid_utf8_neon(bytes) };
+        }
+    }
+
+    is_valid_utf8_simd_impl(bytes)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// The scalar verdict the fast path must reproduce exactly.
+    fn scalar(bytes: &[u8]) -> bool {
+        core::str::from_utf8(bytes).is_ok()
+    }
+
+    #[test]
+    fn test_known_verdicts() {
+        assert!(is_valid_utf8_simd(b""));
+        assert!(is_valid_utf8_simd(b"Hello, world!"));
+        assert!(is_valid_utf8_simd("Café ☕ 日本語 🦀".as_bytes()));
+        assert!(!is_valid_utf8_simd(&[0xFF]));
+        assert!(!is_valid_utf8_simd(&[0xC0, 0x80]));
+        assert!(!is_valid_utf8_simd(&[0xED, 0xA0, 0x80]));
+        assert!(!is_valid_utf8_simd(&[0xF4, 0x90, 0x80, 0x80]));
+        assert!(!is_valid_utf8_simd(&[0xC3])); // truncated
+    }
+
+    #[test]
+    fn test_differential_exhaustive_short() {
+        // Every 1-3 byte combination of the interesting byte values
+        // must get the scalar verdict
+        let interesting = [
+            0x00u8, 0x41, 0x7F, 0x80, 0xBF, 0xC0, 0xC2, 0xDF, 0xE0, 0xED, 0xEF, 0xF0, 0xF4,
+            0xF5, 0xFF,
+        ];
+        for &a in &interesting {
+            assert_eq!(is_valid_utf8_simd(&[a]), scalar(&[a]));
+            for &b in &interesting {
+                assert_eq!(is_valid_utf8_simd(&[a, b]), scalar(&[a, b]));
+                for &c in &interesting {
+                    let input = [a, b, c];
+                    assert_eq!(is_valid_utf8_simd(&input), scalar(&input), "{input:02X?}");
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_differential_random() {
+        use firefox_xorshift128plus::XorShift128PlusRNG;
+
+        let mut rng = XorShift128PlusRNG::from_seed_u64(0x51D0_11FE);
+
+        // Random byte soup: overwhelmingly invalid, exercises rejection
+        // at every alignment
+        for _ in 0..2_000 {
+            let len = (rng.next() % 64) as usize;
+            let bytes: Vec<u8> = (0..len).map(|_| rng.next() as u8).collect();
+            assert_eq!(is_valid_utf8_simd(&bytes), scalar(&bytes), "{bytes:02X?}");
+        }
+
+        // Valid text with a single corrupted byte: exercises acceptance
+        // and near-miss rejection
+        let text = "ASCII runs, Café, 日本語, 🦀🎉, and more ASCII padding";
+        for _ in 0..2_000 {
+            let mut bytes = text.as_bytes().to_vec();
+            let position = (rng.next() % bytes.len() as u64) as usize;
+            bytes[position] = rng.next() as u8;
+            assert_eq!(is_valid_utf8_simd(&bytes), scalar(&bytes), "{bytes:02X?}");
+        }
+    }
+
+    #[test]
+    fn test_routed_through_is_valid_utf8() {
+        // With the feature on, the public entry point gives the same
+        // answers (it routes here)
+        for input in [&b"plain"[..], "🦀".as_bytes(), &[0xFF], &[0xED, 0xA0, 0x80]] {
+            assert_eq!(crate::is_valid_utf8(input), is_valid_utf8_simd(input));
+        }
+    }
+}
@@ -0,0 +1,184 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+//! BOM detection and encoding sniffing.
+//!
+//! File-loading call sites hand-roll the same three byte comparisons
+//! before deciding how to decode; these helpers centralize them. BOM
+//! detection covers the encodings Gecko actually distinguishes by BOM
+//! (UTF-8, UTF-16LE, UTF-16BE), and [`probably_utf8`] answers the
+//! sniffing question those call sites ask about a file *prefix*, where
+//! the last code point may be cut off mid-sequence. Everything here
+//! works with core alone.
+
+/// An encoding identified by its byte order mark.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Encoding {
+    /// UTF-8, marked by `EF BB BF`.
+    Utf8,
+    /// UTF-16 little-endian, marked by `FF FE`.
+    Utf16Le,
+    /// UTF-16 big-endian, marked by `FE FF`.
+    Utf16Be,
+}
+
+impl Encoding {
+    /// Length in bytes of this encoding's BOM.
+    pub const fn bom_length(self) -> usize {
+        match self {
+            Encoding::Utf8 => 3,
+            Encoding::Utf16Le | Encoding::Utf16Be => 2,
+        }
+    }
+}
+
+/// Detects a byte order mark at the start of a buffer.
+///
+/// `None` means no recognized BOM — which says nothing about the
+/// encoding; BOM-less UTF-8 is the common case on the web.
+///
+/// # Examples
+///
+/// ```
+/// use firefox_utf8_validator::{detect_bom, Encoding};
+///
+/// assert_eq!(detect_bom(&[0xEF, 0xBB, 0xBF, 0x41]), Some(Encoding::Utf8));
+/// assert_eq!(detect_bom(&[0xFF, 0xFE, 0x41, 0x00]), Some(Encoding::Utf16Le));
+/// assert_eq!(detect_bom(b"plain"), None);
+/// ```
+pub fn detect_bom(bytes: &[u8]) -> Option<Encoding> {
+    match bytes {
+        [0xEF, 0xBB, 0xBF, ..] => Some(Encoding::Utf8),
+        [0xFF, 0xFE, ..] => Some(Encoding::Utf16Le),
+        [0xFE, 0xFF, ..] => Some(Encoding::Utf16Be),
+        _ => None,
+    }
+}
+
+/// Returns the buffer with any recognized BOM removed.
+///
+/// A buffer without a BOM comes back unchanged, so this is safe to
+/// apply unconditionally before decoding.
+///
+/// # Examples
+///
+/// ```
+/// use firefox_utf8_validator::strip_bom;
+///
+/// assert_eq!(strip_bom(&[0xEF, 0xBB, 0xBF, 0x41]), &[0x41]);
+/// assert_eq!(strip_bom(b"plain"), b"plain");
+/// ```
+pub fn strip_bom(bytes: &[u8]) -> &[u8] {
+    match detect_bom(bytes) {
+        Some(encoding) => &bytes[encoding.bom_length()..],
+        None => bytes,
+    }
+}
+
+/// Heuristic: does this buffer look like UTF-8 text?
+///
+/// Intended for sniffing a fixed-size prefix of a larger file, so the
+/// answer tolerates exactly one truncation artifact: a buffer whose
+/// only flaw is an incomplete sequence at the very end still scores as
+/// probably UTF-8. A UTF-8 BOM decides immediately; any other invalid
+/// sequence scores `false`, and so does a NUL byte — NUL is
+/// technically a valid code point, but in practice it signals binary
+/// data or UTF-16, whose ASCII text is byte-wise valid UTF-8 full of
+/// NULs. Pure NUL-free ASCII is `true`: it decodes correctly as UTF-8
+/// whatever the author intended.
+///
+/// # Examples
+///
+/// ```
+/// use firefox_utf8_validator::probably_utf8;
+///
+/// assert!(probably_utf8("Café".as_bytes()));
+/// assert!(probably_utf8(&"Café".as_bytes()[..4])); // é cut in half
+/// assert!(!probably_utf8(&[0x41, 0x00, 0x42, 0x00])); // UTF-16LE "AB"
+/// ```
+pub fn probably_utf8(bytes: &[u8]) -> bool {
+    if detect_bom(bytes) == Some(Encoding::Utf8) {
+        return true;
+    }
+
+    // UTF-16 ASCII text and most binary formats are ruled out here,
+    // not by UTF-8 validation
+    if bytes.contains(&0x00) {
+        return false;
+    }
+
+    match core::str::from_utf8(bytes) {
+        Ok(_) => true,
+        // error_len() is None exactly when the buffer ends inside a
+        // sequence that was valid so far — the truncated-prefix case
+        Err(error) => error.error_len().is_none(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_detect_bom() {
+        assert_eq!(detect_bom(&[0xEF, 0xBB, 0xBF]), Some(Encoding::Utf8));
+        assert_eq!(detect_bom(&[0xFF, 0xFE]), Some(Encoding::Utf16Le));
+        assert_eq!(detect_bom(&[0xFE, 0xFF]), Some(Encoding::Utf16Be));
+        assert_eq!(detect_bom(&[0xEF, 0xBB, 0xBF, 0x41]), Some(Encoding::Utf8));
+
+        assert_eq!(detect_bom(b""), None);
+        assert_eq!(detect_bom(b"plain"), None);
+        // Prefixes of the UTF-8 BOM are not a BOM
+        assert_eq!(detect_bom(&[0xEF]), None);
+        assert_eq!(detect_bom(&[0xEF, 0xBB]), None);
+    }
+
+    #[test]
+    fn test_bom_lengths_match_detection() {
+        let bodies: [&[u8]; 3] = [&[0xEF, 0xBB, 0xBF], &[0xFF, 0xFE], &[0xFE, 0xFF]];
+        for body in bodies {
+            let encoding = detect_bom(body).unwrap();
+            assert_eq!(encoding.bom_length(), body.len());
+        }
+    }
+
+    #[test]
+    fn test_strip_bom() {
+        assert_eq!(strip_bom(&[0xEF, 0xBB, 0xBF, 0x41, 0x42]), b"AB");
+        assert_eq!(strip_bom(&[0xFF, 0xFE, 0x41, 0x00]), &[0x41, 0x00]);
+        assert_eq!(strip_bom(&[0xFE, 0xFF]), b"");
+        assert_eq!(strip_bom(b"plain"), b"plain");
+        assert_eq!(strip_bom(b""), b"");
+    }
+
+    #[test]
+    fn test_probably_utf8() {
+        assert!(probably_utf8(b""));
+        assert!(probably_utf8(b"pure ASCII"));
+        assert!(probably_utf8("Café 日本語 🦀".as_bytes()));
+        // BOM decides even when the rest is garbage
+        assert!(probably_utf8(&[0xEF, 0xBB, 0xBF, 0xFF]));
+
+        assert!(!probably_utf8(&[0xFF, 0x41]));
+        assert!(!probably_utf8(&[0xC0, 0x80]));
+        // UTF-16LE ASCII is byte-wise valid UTF-8; the NUL rule
+        // catches it
+        assert!(!probably_utf8(&[0x41, 0x00, 0x42, 0x00]));
+        assert!(!probably_utf8(&[0x00]));
+    }
+
+    #[test]
+    fn test_probably_utf8_truncated_prefixes() {
+        // Every prefix of valid UTF-8 text sniffs as probably UTF-8,
+        // even the ones that cut a sequence in half
+        let text = "a é € 🦀".as_bytes();
+        for length in 0..=text.len() {
+            assert!(probably_utf8(&text[..length]), "prefix length {length}");
+        }
+        // But truncation in the middle does not excuse the error
+        let mut corrupted = text.to_vec();
+        corrupted[1] = 0xC3; // é's lead byte, now mid-text with no continuation
+        assert!(!probably_utf8(&corrupted));
+    }
+}
@@ -206,6 +206,220 @@ fn test_property_length_preserved() {
     assert_eq!(len_before, len_after, "Length should not change");
 }
 
+mod decode_one {
+    use crate::{decode_one, Utf8DecodeError};
+
+    #[test]
+    fn test_decode_valid_sequences() {
+        assert_eq!(decode_one(b"A"), Ok(('A', 1)));
+        assert_eq!(decode_one("é".as_bytes()), Ok(('é', 2)));
+        assert_eq!(decode_one("€".as_bytes()), Ok(('€', 3)));
+        assert_eq!(decode_one("🦀".as_bytes()), Ok(('🦀', 4)));
+
+        // Only the first code point is consumed
+        assert_eq!(decode_one("日本語".as_bytes()), Ok(('日', 3)));
+
+        // Boundary code points
+        assert_eq!(decode_one(&[0xED, 0x9F, 0xBF]), Ok(('\u{D7FF}', 3)));
+        assert_eq!(decode_one(&[0xEE, 0x80, 0x80]), Ok(('\u{E000}', 3)));
+        assert_eq!(decode_one(&[0xF4, 0x8F, 0xBF, 0xBF]), Ok(('\u{10FFFF}', 4)));
+    }
+
+    #[test]
+    fn test_decode_bad_lead_unit() {
+        assert_eq!(decode_one(&[0x80]), Err(Utf8DecodeError::BadLeadUnit));
+        assert_eq!(decode_one(&[0xBF]), Err(Utf8DecodeError::BadLeadUnit));
+        assert_eq!(decode_one(&[0xF8]), Err(Utf8DecodeError::BadLeadUnit));
+        assert_eq!(decode_one(&[0xFF]), Err(Utf8DecodeError::BadLeadUnit));
+    }
+
+    #[test]
+    fn test_decode_not_enough_units() {
+        assert_eq!(
+            decode_one(&[]),
+            Err(Utf8DecodeError::NotEnoughUnits {
+                required: 1,
+                available: 0
+            })
+        );
+        assert_eq!(
+            decode_one(&[0xC3]),
+            Err(Utf8DecodeError::NotEnoughUnits {
+                required: 2,
+                available: 1
+            })
+        );
+        assert_eq!(
+            decode_one(&[0xF0, 0x9F, 0xA6]),
+            Err(Utf8DecodeError::NotEnoughUnits {
+                required: 4,
+                available: 3
+            })
+        );
+    }
+
+    #[test]
+    fn test_decode_bad_trailing_unit() {
+        assert_eq!(
+            decode_one(&[0xC3, 0xC0]),
+            Err(Utf8DecodeError::BadTrailingUnit { index: 1 })
+        );
+        assert_eq!(
+            decode_one(&[0xE2, 0x82, 0xFF]),
+            Err(Utf8DecodeError::BadTrailingUnit { index: 2 })
+        );
+        // The first bad byte is reported, even if later ones are bad too
+        assert_eq!(
+            decode_one(&[0xF0, 0x40, 0x40, 0x40]),
+            Err(Utf8DecodeError::BadTrailingUnit { index: 1 })
+        );
+    }
+
+    #[test]
+    fn test_decode_bad_code_points() {
+        assert_eq!(
+            decode_one(&[0xED, 0xA0, 0x80]),
+            Err(Utf8DecodeError::Surrogate { value: 0xD800 })
+        );
+        assert_eq!(
+            decode_one(&[0xED, 0xBF, 0xBF]),
+            Err(Utf8DecodeError::Surrogate { value: 0xDFFF })
+        );
+        assert_eq!(
+            decode_one(&[0xF4, 0x90, 0x80, 0x80]),
+            Err(Utf8DecodeError::TooLarge { value: 0x110000 })
+        );
+        assert_eq!(
+            decode_one(&[0xC0, 0x80]),
+            Err(Utf8DecodeError::NotShortestForm { value: 0 })
+        );
+        assert_eq!(
+            decode_one(&[0xF0, 0x82, 0x82, 0xAC]),
+            Err(Utf8DecodeError::NotShortestForm { value: 0x20AC })
+        );
+    }
+
+    #[test]
+    fn test_decode_agrees_with_validation() {
+        // Walking a buffer with decode_one accepts exactly the buffers
+        // is_valid_utf8 accepts — checked over every 1-3 byte input
+        // drawn from a byte set covering all lead/continuation classes
+        let interesting = [
+            0x00u8, 0x41, 0x7F, 0x80, 0xBF, 0xC0, 0xC2, 0xDF, 0xE0, 0xED, 0xEF, 0xF0, 0xF4, 0xF5,
+            0xFF,
+        ];
+        let check = |bytes: &[u8]| {
+            let mut rest = bytes;
+            let mut decodes = true;
+            while !rest.is_empty() {
+                match decode_one(rest) {
+                    Ok((_, consumed)) => rest = &rest[consumed..],
+                    Err(_) => {
+                        decodes = false;
+                        break;
+                    }
+                }
+            }
+            assert_eq!(
+                decodes,
+                crate::is_valid_utf8(bytes),
+                "decode_one and is_valid_utf8 disagree on {:02x?}",
+                bytes
+            );
+        };
+        for &a in &interesting {
+            check(&[a]);
+            for &b in &interesting {
+                check(&[a, b]);
+                for &c in &interesting {
+                    check(&[a, b, c]);
+                }
+            }
+        }
+    }
+}
+
+mod validate_utf8 {
+    use crate::{validate_utf8, Utf8DecodeError};
+
+    #[test]
+    fn test_valid_buffers() {
+        assert!(validate_utf8(b"").is_ok());
+        assert!(validate_utf8(b"Hello").is_ok());
+        assert!(validate_utf8("Café 日本語 🦀".as_bytes()).is_ok());
+    }
+
+    #[test]
+    fn test_error_offset_and_length() {
+        // Invalid lead after valid prefix
+        let err = validate_utf8(b"Hello\xFFWorld").unwrap_err();
+        assert_eq!(err.offset, 5);
+        assert_eq!(err.invalid_length, 1);
+        assert_eq!(err.kind, Utf8DecodeError::BadLeadUnit);
+
+        // Surrogate: the maximal subpart is just the ED lead byte, but
+        // the kind still identifies the surrogate it would have encoded
+        let err = validate_utf8(b"ab\xED\xA0\x80cd").unwrap_err();
+        assert_eq!(err.offset, 2);
+        assert_eq!(err.invalid_length, 1);
+        assert_eq!(err.kind, Utf8DecodeError::Surrogate { value: 0xD800 });
+
+        // An overlong 4-byte sequence: maximal subpart is the lead only
+        let err = validate_utf8(b"\xF0\x82\x82\xAC").unwrap_err();
+        assert_eq!(err.offset, 0);
+        assert_eq!(err.invalid_length, 1);
+        assert_eq!(
+            err.kind,
+            Utf8DecodeError::NotShortestForm { value: 0x20AC }
+        );
+
+        // Truncated final sequence runs to the end of the buffer
+        let err = validate_utf8(b"abc\xF0\x9F").unwrap_err();
+        assert_eq!(err.offset, 3);
+        assert_eq!(err.invalid_length, 2);
+        assert_eq!(
+            err.kind,
+            Utf8DecodeError::NotEnoughUnits {
+                required: 4,
+                available: 2
+            }
+        );
+    }
+
+    #[test]
+    fn test_agrees_with_is_valid_utf8() {
+        let cases: &[&[u8]] = &[
+            b"",
+            b"plain",
+            "🦀".as_bytes(),
+            b"\x80",
+            b"\xC0\x80",
+            b"\xED\xA0\x80",
+            b"\xF4\x90\x80\x80",
+            b"ok\xC3",
+            b"\xE2\x82",
+        ];
+        for &bytes in cases {
+            assert_eq!(
+                validate_utf8(bytes).is_ok(),
+                crate::is_valid_utf8(bytes),
+                "verdicts disagree on {:02x?}",
+                bytes
+            );
+        }
+    }
+
+    #[test]
+    fn test_prefix_before_offset_is_valid() {
+        let bytes = b"abc\xE4\xB8\xADdef\xFFtail";
+        let err = validate_utf8(bytes).unwrap_err();
+        assert!(crate::is_valid_utf8(&bytes[..err.offset]));
+        assert!(!crate::is_valid_utf8(
+            &bytes[err.offset..err.offset + err.invalid_length]
+        ));
+    }
+}
+
 #[test]
 fn test_deterministic() {
     // Same input always produces same output
@@ -0,0 +1,181 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+//! UTF-16 validation.
+//!
+//! Gecko strings are potentially ill-formed UTF-16 — lone surrogates
+//! can and do appear — but IPC and serialization boundaries need to
+//! know when a buffer is well-formed and to repair it when it is not.
+//! These are the UTF-16 counterparts of [`is_valid_utf8`] and the
+//! lossy UTF-8 repair in [`convert`](crate::convert).
+//!
+//! [`is_valid_utf8`]: crate::is_valid_utf8
+
+/// Returns whether `unit` is a lead surrogate (U+D800-U+DBFF).
+#[inline]
+fn is_lead_surrogate(unit: u16) -> bool {
+    (0xD800..=0xDBFF).contains(&unit)
+}
+
+/// Returns whether `unit` is a trail surrogate (U+DC00-U+DFFF).
+#[inline]
+fn is_trail_surrogate(unit: u16) -> bool {
+    (0xDC00..=0xDFFF).contains(&unit)
+}
+
+/// Returns whether a buffer is well-formed UTF-16.
+///
+/// Well-formed means every lead surrogate is immediately followed by a
+/// trail surrogate and no trail surrogate appears on its own. An empty
+/// buffer is valid.
+///
+/// # Examples
+///
+/// ```
+/// use firefox_utf8_validator::is_valid_utf16;
+///
+/// assert!(is_valid_utf16(&[0x48, 0x69]));
+/// assert!(is_valid_utf16(&[0xD83E, 0xDD80])); // 🦀 as a pair
+/// assert!(!is_valid_utf16(&[0xD83E])); // lone lead
+/// assert!(!is_valid_utf16(&[0xDD80, 0xD83E])); // reversed pair
+/// ```
+pub fn is_valid_utf16(units: &[u16]) -> bool {
+    let mut index = 0;
+    while index < units.len() {
+        let unit = units[index];
+        if is_lead_surrogate(unit) {
+            if index + 1 >= units.len() || !is_trail_surrogate(units[index + 1]) {
+                return false;
+            }
+            index += 2;
+        } else if is_trail_surrogate(unit) {
+            return false;
+        } else {
+            index += 1;
+        }
+    }
+    true
+}
+
+/// Repairs a UTF-16 buffer in place, replacing each lone surrogate
+/// with U+FFFD.
+///
+/// Returns the number of units replaced; zero means the buffer was
+/// already well-formed. Replacement never changes the length — a lone
+/// surrogate and U+FFFD are both one unit — so surrogate pairs and
+/// everything else keep their positions.
+///
+/// # Examples
+///
+/// ```
+/// use firefox_utf8_validator::ensure_valid_utf16_lossy;
+///
+/// let mut units = vec![0x61, 0xD800, 0x62];
+/// assert_eq!(ensure_valid_utf16_lossy(&mut units), 1);
+/// assert_eq!(units, vec![0x61, 0xFFFD, 0x62]);
+/// ```
+pub fn ensure_valid_utf16_lossy(units: &mut [u16]) -> usize {
+    let mut replaced = 0;
+    let mut index = 0;
+    while index < units.len() {
+        let unit = units[index];
+        if is_lead_surrogate(unit) && index + 1 < units.len() && is_trail_surrogate(units[index + 1])
+        {
+            index += 2;
+        } else if is_lead_surrogate(unit) || is_trail_surrogate(unit) {
+            units[index] = 0xFFFD;
+            replaced += 1;
+            index += 1;
+        } else {
+            index += 1;
+        }
+    }
+    replaced
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_is_valid_utf16() {
+        assert!(is_valid_utf16(&[]));
+        assert!(is_valid_utf16(&[0x48, 0x69]));
+        assert!(is_valid_utf16(&[0xD7FF, 0xE000])); // neighbors of the gap
+        assert!(is_valid_utf16(&[0xD800, 0xDC00])); // minimal pair
+        assert!(is_valid_utf16(&[0xDBFF, 0xDFFF])); // maximal pair
+        assert!(is_valid_utf16(&[0x61, 0xD83E, 0xDD80, 0x62]));
+
+        assert!(!is_valid_utf16(&[0xD800])); // lone lead at end
+        assert!(!is_valid_utf16(&[0xDC00])); // lone trail
+        assert!(!is_valid_utf16(&[0xD800, 0x41])); // lead before non-trail
+        assert!(!is_valid_utf16(&[0xDC00, 0xD800])); // reversed
+        assert!(!is_valid_utf16(&[0xD800, 0xD800, 0xDC00])); // lead before pair
+    }
+
+    #[test]
+    fn test_agrees_with_decode_utf16() {
+        let cases: Vec<Vec<u16>> = vec![
+            vec![],
+            vec![0x48, 0x69],
+            vec![0xD83E, 0xDD80],
+            vec![0xD800],
+            vec![0xDC00, 0xD800],
+            vec![0x61, 0xD800, 0xDC00, 0xDFFF],
+            (0xD7F0..0xE010).collect(),
+        ];
+        for units in cases {
+            assert_eq!(
+                is_valid_utf16(&units),
+                char::decode_utf16(units.iter().copied()).all(|r| r.is_ok()),
+                "units {units:04X?}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_ensure_valid_well_formed_untouched() {
+        let mut units = vec![0x61, 0xD83E, 0xDD80, 0x62];
+        let original = units.clone();
+        assert_eq!(ensure_valid_utf16_lossy(&mut units), 0);
+        assert_eq!(units, original);
+    }
+
+    #[test]
+    fn test_ensure_valid_replaces_lone_surrogates() {
+        let mut units = vec![0x61, 0xD800, 0x62];
+        assert_eq!(ensure_valid_utf16_lossy(&mut units), 1);
+        assert_eq!(units, vec![0x61, 0xFFFD, 0x62]);
+
+        // A lead before a pair is lone; the pair survives
+        let mut units = vec![0xD800, 0xD83E, 0xDD80];
+        assert_eq!(ensure_valid_utf16_lossy(&mut units), 1);
+        assert_eq!(units, vec![0xFFFD, 0xD83E, 0xDD80]);
+
+        // Reversed pair is two lone surrogates
+        let mut units = vec![0xDC00, 0xD800];
+        assert_eq!(ensure_valid_utf16_lossy(&mut units), 2);
+        assert_eq!(units, vec![0xFFFD, 0xFFFD]);
+    }
+
+    #[test]
+    fn test_ensure_valid_matches_lossy_conversion() {
+        // After repair the buffer is valid and agrees with what
+        // from_utf16_lossy would have produced
+        let cases: Vec<Vec<u16>> = vec![
+            vec![0xD800],
+            vec![0x61, 0xDC00, 0xD83E, 0xDD80],
+            vec![0xD800, 0x41, 0xDBFF, 0xDFFF, 0xDC00],
+            (0xD7F0..0xE010).collect(),
+        ];
+        for mut units in cases {
+            let expected: Vec<u16> = crate::convert_utf16_to_utf8(&units)
+                .encode_utf16()
+                .collect();
+            ensure_valid_utf16_lossy(&mut units);
+            assert!(is_valid_utf16(&units));
+            assert_eq!(units, expected);
+        }
+    }
+}
@@ -19,7 +19,7 @@
 //!
 //! All FFI functions are prefixed with `xorshift128plus_` to avoid naming conflicts.
 
-use crate::XorShift128PlusRNG;
+use crate::{Xoroshiro128Plus, Xoshiro256PlusPlus, XorShift128PlusRNG};
 use std::panic;
 
 /// FFI-safe constructor: Create new XorShift128+ RNG
@@ -54,6 +54,33 @@ pub extern "C" fn xorshift128plus_new(initial0: u64, initial1: u64) -> *mut XorS
     }
 }
 
+/// FFI-safe constructor: Create new XorShift128+ RNG from a single u64 seed
+///
+/// Seeds via SplitMix64 as recommended in the xorshift128+ docs, so a single
+/// low-entropy seed (e.g. a counter or timestamp) still produces a
+/// well-mixed initial state. Any seed value is valid, including zero.
+///
+/// # Safety
+///
+/// This function is safe to call from C++. Returns a heap-allocated RNG
+/// instance. Caller must call `xorshift128plus_destroy` when done.
+///
+/// # Returns
+///
+/// Pointer to newly allocated RNG, or null on allocation failure
+#[no_mangle]
+pub extern "C" fn xorshift128plus_new_from_u64_seed(seed: u64) -> *mut XorShift128PlusRNG {
+    // Catch panics to prevent unwinding into C++
+    let result = panic::catch_unwind(|| {
+        Box::into_raw(Box::new(XorShift128PlusRNG::from_seed_u64(seed)))
+    });
+
+    match result {
+        Ok(ptr) => ptr,
+        Err(_) => std::ptr::null_mut(),
+    }
+}
+
 /// FFI-safe destructor: Destroy XorShift128+ RNG
 ///
 /// # Safety
@@ -114,6 +141,30 @@ pub unsafe extern "C" fn xorshift128plus_next_double(rng: *mut XorShift128PlusRN
     result.unwrap_or(0.0)
 }
 
+/// FFI-safe nextFloat: Generate next pseudo-random float in [0, 1)
+///
+/// Single-precision variant of `xorshift128plus_next_double`, using 24
+/// mantissa bits. Intended for Float32 math paths.
+///
+/// # Safety
+///
+/// `rng` must be a valid pointer to an XorShift128PlusRNG instance.
+///
+/// # Returns
+///
+/// Next pseudo-random f32 value in [0.0, 1.0), or 0.0 if rng is null or panic occurs
+#[no_mangle]
+pub unsafe extern "C" fn xorshift128plus_next_float(rng: *mut XorShift128PlusRNG) -> f32 {
+    if rng.is_null() {
+        return 0.0;
+    }
+
+    // Catch panics
+    let result = panic::catch_unwind(|| unsafe { (*rng).next_float() });
+
+    result.unwrap_or(0.0)
+}
+
 /// FFI-safe setState: Set RNG state to specific values
 ///
 /// # Safety
@@ -139,6 +190,43 @@ pub unsafe extern "C" fn xorshift128plus_set_state(
     });
 }
 
+/// FFI-safe state getter: Read state[0] without advancing the RNG
+///
+/// Paired with `xorshift128plus_get_state1` and `xorshift128plus_set_state`
+/// this lets C++ harnesses checkpoint and replay RNG state.
+///
+/// # Safety
+///
+/// `rng` must be a valid pointer to an XorShift128PlusRNG instance.
+///
+/// # Returns
+///
+/// Current state[0], or 0 if rng is null
+#[no_mangle]
+pub unsafe extern "C" fn xorshift128plus_get_state0(rng: *const XorShift128PlusRNG) -> u64 {
+    if rng.is_null() {
+        return 0;
+    }
+    unsafe { (*rng).state()[0] }
+}
+
+/// FFI-safe state getter: Read state[1] without advancing the RNG
+///
+/// # Safety
+///
+/// `rng` must be a valid pointer to an XorShift128PlusRNG instance.
+///
+/// # Returns
+///
+/// Current state[1], or 0 if rng is null
+#[no_mangle]
+pub unsafe extern "C" fn xorshift128plus_get_state1(rng: *const XorShift128PlusRNG) -> u64 {
+    if rng.is_null() {
+        return 0;
+    }
+    unsafe { (*rng).state()[1] }
+}
+
 /// FFI-safe offsetOfState0: Get byte offset of state[0]
 ///
 /// This is used by JIT code for direct memory access.
@@ -163,6 +251,85 @@ pub extern "C" fn xorshift128plus_offset_of_state1() -> usize {
     XorShift128PlusRNG::offset_of_state1()
 }
 
+/// FFI: Construct an RNG into caller-provided storage
+///
+/// The JIT embeds the RNG state inline (e.g. in the compartment's realm
+/// data) rather than holding a heap handle, so this placement-construction
+/// entry point writes a fully initialized `XorShift128PlusRNG` into `buf`.
+/// The buffer is validated before writing: `len` must be at least
+/// `xorshift128plus_size_of()` (16) and `buf` must be 8-byte aligned, the
+/// alignment of the struct's u64 state words.
+///
+/// On success the buffer can be passed to `xorshift128plus_next` et al. as
+/// a `XorShift128PlusRNG*`. Do NOT pass it to `xorshift128plus_destroy`;
+/// the storage belongs to the caller.
+///
+/// # Safety
+///
+/// `buf` must be valid for writes of `len` bytes, or null (which fails
+/// cleanly).
+///
+/// # Returns
+///
+/// `true` if the RNG was constructed, `false` if `buf` is null, too small,
+/// or misaligned
+///
+/// # Note
+///
+/// At least one of s0, s1 should be non-zero for proper operation.
+#[no_mangle]
+pub unsafe extern "C" fn xorshift128plus_init_in_place(
+    buf: *mut u8,
+    len: usize,
+    s0: u64,
+    s1: u64,
+) -> bool {
+    if buf.is_null()
+        || len < std::mem::size_of::<XorShift128PlusRNG>()
+        || !(buf as usize).is_multiple_of(std::mem::align_of::<XorShift128PlusRNG>())
+    {
+        return false;
+    }
+
+    // Catch panics to prevent unwinding into C++
+    let result = panic::catch_unwind(|| unsafe {
+        (buf as *mut XorShift128PlusRNG).write(XorShift128PlusRNG::new(s0, s1));
+    });
+
+    result.is_ok()
+}
+
+/// FFI: Draw the next u64 from the process-global shared RNG
+///
+/// The global instance is lazily seeded on first use and internally
+/// synchronized, so this is safe to call from any thread without external
+/// locking. For deterministic sequences in tests, call
+/// `xorshift128plus_global_set_state` first.
+#[no_mangle]
+pub extern "C" fn xorshift128plus_global_next() -> u64 {
+    let result = panic::catch_unwind(|| crate::SyncXorShift128PlusRNG::global().next());
+    result.unwrap_or(0)
+}
+
+/// FFI: Draw the next f64 in [0, 1) from the process-global shared RNG
+#[no_mangle]
+pub extern "C" fn xorshift128plus_global_next_double() -> f64 {
+    let result = panic::catch_unwind(|| crate::SyncXorShift128PlusRNG::global().next_double());
+    result.unwrap_or(0.0)
+}
+
+/// FFI: Reset the process-global shared RNG to a known state
+///
+/// # Note
+///
+/// At least one of state0, state1 should be non-zero for proper operation.
+#[no_mangle]
+pub extern "C" fn xorshift128plus_global_set_state(state0: u64, state1: u64) {
+    let _ = panic::catch_unwind(|| {
+        crate::SyncXorShift128PlusRNG::global().set_state(state0, state1);
+    });
+}
+
 // Additional FFI helpers for C++ compatibility
 
 /// Get the size of XorShift128PlusRNG struct
@@ -177,6 +344,310 @@ pub extern "C" fn xorshift128plus_size_of() -> usize {
     std::mem::size_of::<XorShift128PlusRNG>()
 }
 
+// ============================================================================
+// xoshiro256++ FFI (see crate::xoshiro)
+// ============================================================================
+
+/// FFI-safe constructor: Create new xoshiro256++ RNG from explicit state
+///
+/// # Safety
+///
+/// This function is safe to call from C++. Returns a heap-allocated RNG
+/// instance. Caller must call `xoshiro256plusplus_destroy` when done.
+///
+/// # Returns
+///
+/// Pointer to newly allocated RNG, or null on allocation failure
+///
+/// # Note
+///
+/// At least one of s0..s3 should be non-zero for proper operation.
+#[no_mangle]
+pub extern "C" fn xoshiro256plusplus_new(
+    s0: u64,
+    s1: u64,
+    s2: u64,
+    s3: u64,
+) -> *mut Xoshiro256PlusPlus {
+    // Catch panics to prevent unwinding into C++
+    let result = panic::catch_unwind(|| {
+        Box::into_raw(Box::new(Xoshiro256PlusPlus::new(s0, s1, s2, s3)))
+    });
+
+    match result {
+        Ok(ptr) => ptr,
+        Err(_) => std::ptr::null_mut(),
+    }
+}
+
+/// FFI-safe constructor: Create new xoshiro256++ RNG from a single u64 seed
+///
+/// Seeds via SplitMix64; any seed value is valid, including zero.
+///
+/// # Safety
+///
+/// This function is safe to call from C++. Caller must call
+/// `xoshiro256plusplus_destroy` when done.
+#[no_mangle]
+pub extern "C" fn xoshiro256plusplus_new_from_u64_seed(seed: u64) -> *mut Xoshiro256PlusPlus {
+    let result = panic::catch_unwind(|| {
+        Box::into_raw(Box::new(Xoshiro256PlusPlus::from_seed_u64(seed)))
+    });
+
+    match result {
+        Ok(ptr) => ptr,
+        Err(_) => std::ptr::null_mut(),
+    }
+}
+
+/// FFI-safe destructor: Destroy xoshiro256++ RNG
+///
+/// # Safety
+///
+/// `rng` must be a valid pointer returned from `xoshiro256plusplus_new` and
+/// not previously destroyed. Passing null is safe (no-op).
+#[no_mangle]
+pub unsafe extern "C" fn xoshiro256plusplus_destroy(rng: *mut Xoshiro256PlusPlus) {
+    if !rng.is_null() {
+        let _ = panic::catch_unwind(|| {
+            unsafe {
+                let _ = Box::from_raw(rng);
+            }
+        });
+    }
+}
+
+/// FFI-safe next: Generate next pseudo-random 64-bit number
+///
+/// # Safety
+///
+/// `rng` must be a valid pointer to a Xoshiro256PlusPlus instance.
+///
+/// # Returns
+///
+/// Next pseudo-random u64 value, or 0 if rng is null or panic occurs
+#[no_mangle]
+pub unsafe extern "C" fn xoshiro256plusplus_next(rng: *mut Xoshiro256PlusPlus) -> u64 {
+    if rng.is_null() {
+        return 0;
+    }
+
+    let result = panic::catch_unwind(|| unsafe { (*rng).next() });
+
+    result.unwrap_or(0)
+}
+
+/// FFI-safe nextDouble: Generate next pseudo-random double in [0, 1)
+///
+/// # Safety
+///
+/// `rng` must be a valid pointer to a Xoshiro256PlusPlus instance.
+#[no_mangle]
+pub unsafe extern "C" fn xoshiro256plusplus_next_double(rng: *mut Xoshiro256PlusPlus) -> f64 {
+    if rng.is_null() {
+        return 0.0;
+    }
+
+    let result = panic::catch_unwind(|| unsafe { (*rng).next_double() });
+
+    result.unwrap_or(0.0)
+}
+
+/// FFI-safe setState: Set RNG state to specific values
+///
+/// # Safety
+///
+/// `rng` must be a valid pointer to a Xoshiro256PlusPlus instance.
+///
+/// # Note
+///
+/// At least one of s0..s3 should be non-zero for proper operation.
+#[no_mangle]
+pub unsafe extern "C" fn xoshiro256plusplus_set_state(
+    rng: *mut Xoshiro256PlusPlus,
+    s0: u64,
+    s1: u64,
+    s2: u64,
+    s3: u64,
+) {
+    if rng.is_null() {
+        return;
+    }
+
+    let _ = panic::catch_unwind(|| unsafe {
+        (*rng).set_state(s0, s1, s2, s3);
+    });
+}
+
+/// FFI-safe offsetOfState: Get byte offset of state[i]
+///
+/// This is used by JIT code for direct memory access.
+///
+/// # Returns
+///
+/// Byte offset of state[i] from start of struct (i * 8)
+#[no_mangle]
+pub extern "C" fn xoshiro256plusplus_offset_of_state(i: usize) -> usize {
+    Xoshiro256PlusPlus::offset_of_state(i)
+}
+
+/// Get the size of the Xoshiro256PlusPlus struct
+///
+/// Used by C++ code to verify struct size matches expectations.
+///
+/// # Returns
+///
+/// Size in bytes (always 32)
+#[no_mangle]
+pub extern "C" fn xoshiro256plusplus_size_of() -> usize {
+    std::mem::size_of::<Xoshiro256PlusPlus>()
+}
+
+// ============================================================================
+// xoroshiro128+ FFI (see crate::xoshiro)
+// ============================================================================
+
+/// FFI-safe constructor: Create new xoroshiro128+ RNG from explicit state
+///
+/// # Safety
+///
+/// This function is safe to call from C++. Caller must call
+/// `xoroshiro128plus_destroy` when done.
+///
+/// # Note
+///
+/// At least one of initial0, initial1 should be non-zero for proper operation.
+#[no_mangle]
+pub extern "C" fn xoroshiro128plus_new(initial0: u64, initial1: u64) -> *mut Xoroshiro128Plus {
+    let result = panic::catch_unwind(|| {
+        Box::into_raw(Box::new(Xoroshiro128Plus::new(initial0, initial1)))
+    });
+
+    match result {
+        Ok(ptr) => ptr,
+        Err(_) => std::ptr::null_mut(),
+    }
+}
+
+/// FFI-safe constructor: Create new xoroshiro128+ RNG from a single u64 seed
+///
+/// Seeds via SplitMix64; any seed value is valid, including zero.
+///
+/// # Safety
+///
+/// This function is safe to call from C++. Caller must call
+/// `xoroshiro128plus_destroy` when done.
+#[no_mangle]
+pub extern "C" fn xoroshiro128plus_new_from_u64_seed(seed: u64) -> *mut Xoroshiro128Plus {
+    let result = panic::catch_unwind(|| {
+        Box::into_raw(Box::new(Xoroshiro128Plus::from_seed_u64(seed)))
+    });
+
+    match result {
+        Ok(ptr) => ptr,
+        Err(_) => std::ptr::null_mut(),
+    }
+}
+
+/// FFI-safe destructor: Destroy xoroshiro128+ RNG
+///
+/// # Safety
+///
+/// `rng` must be a valid pointer returned from `xoroshiro128plus_new` and not
+/// previously destroyed. Passing null is safe (no-op).
+#[no_mangle]
+pub unsafe extern "C" fn xoroshiro128plus_destroy(rng: *mut Xoroshiro128Plus) {
+    if !rng.is_null() {
+        let _ = panic::catch_unwind(|| {
+            unsafe {
+                let _ = Box::from_raw(rng);
+            }
+        });
+    }
+}
+
+/// FFI-safe next: Generate next pseudo-random 64-bit number
+///
+/// # Safety
+///
+/// `rng` must be a valid pointer to a Xoroshiro128Plus instance.
+///
+/// # Returns
+///
+/// Next pseudo-random u64 value, or 0 if rng is null or panic occurs
+#[no_mangle]
+pub unsafe extern "C" fn xoroshiro128plus_next(rng: *mut Xoroshiro128Plus) -> u64 {
+    if rng.is_null() {
+        return 0;
+    }
+
+    let result = panic::catch_unwind(|| unsafe { (*rng).next() });
+
+    result.unwrap_or(0)
+}
+
+/// FFI-safe nextDouble: Generate next pseudo-random double in [0, 1)
+///
+/// # Safety
+///
+/// `rng` must be a valid pointer to a Xoroshiro128Plus instance.
+#[no_mangle]
+pub unsafe extern "C" fn xoroshiro128plus_next_double(rng: *mut Xoroshiro128Plus) -> f64 {
+    if rng.is_null() {
+        return 0.0;
+    }
+
+    let result = panic::catch_unwind(|| unsafe { (*rng).next_double() });
+
+    result.unwrap_or(0.0)
+}
+
+/// FFI-safe setState: Set RNG state to specific values
+///
+/// # Safety
+///
+/// `rng` must be a valid pointer to a Xoroshiro128Plus instance.
+///
+/// # Note
+///
+/// At least one of state0, state1 should be non-zero for proper operation.
+#[no_mangle]
+pub unsafe extern "C" fn xoroshiro128plus_set_state(
+    rng: *mut Xoroshiro128Plus,
+    state0: u64,
+    state1: u64,
+) {
+    if rng.is_null() {
+        return;
+    }
+
+    let _ = panic::catch_unwind(|| unsafe {
+        (*rng).set_state(state0, state1);
+    });
+}
+
+/// FFI-safe offsetOfState0: Get byte offset of state[0]
+#[no_mangle]
+pub extern "C" fn xoroshiro128plus_offset_of_state0() -> usize {
+    Xoroshiro128Plus::offset_of_state0()
+}
+
+/// FFI-safe offsetOfState1: Get byte offset of state[1]
+#[no_mangle]
+pub extern "C" fn xoroshiro128plus_offset_of_state1() -> usize {
+    Xoroshiro128Plus::offset_of_state1()
+}
+
+/// Get the size of the Xoroshiro128Plus struct
+///
+/// # Returns
+///
+/// Size in bytes (always 16)
+#[no_mangle]
+pub extern "C" fn xoroshiro128plus_size_of() -> usize {
+    std::mem::size_of::<Xoroshiro128Plus>()
+}
+
 #[cfg(test)]
 mod ffi_tests {
     use super::*;
@@ -199,13 +670,37 @@ mod ffi_tests {
 
             // Test nextDouble()
             let d = xorshift128plus_next_double(rng);
-            assert!(d >= 0.0 && d < 1.0);
+            assert!((0.0..1.0).contains(&d));
+
+            // Test nextFloat()
+            let f = xorshift128plus_next_float(rng);
+            assert!((0.0..1.0).contains(&f));
 
             // Test destructor
             xorshift128plus_destroy(rng);
         }
     }
 
+    #[test]
+    fn test_ffi_from_u64_seed() {
+        unsafe {
+            let rng = xorshift128plus_new_from_u64_seed(42);
+            assert!(!rng.is_null());
+
+            // Matches the Rust-side constructor exactly
+            let mut expected = XorShift128PlusRNG::from_seed_u64(42);
+            assert_eq!(xorshift128plus_next(rng), expected.next());
+            assert_eq!(xorshift128plus_next(rng), expected.next());
+
+            xorshift128plus_destroy(rng);
+
+            // Zero seed is valid
+            let rng = xorshift128plus_new_from_u64_seed(0);
+            assert!(!rng.is_null());
+            xorshift128plus_destroy(rng);
+        }
+    }
+
     #[test]
     fn test_ffi_null_safety() {
         // Test that null pointers don't crash
@@ -216,12 +711,160 @@ mod ffi_tests {
             let d = xorshift128plus_next_double(std::ptr::null_mut());
             assert_eq!(d, 0.0);
 
+            let f = xorshift128plus_next_float(std::ptr::null_mut());
+            assert_eq!(f, 0.0);
+
             xorshift128plus_set_state(std::ptr::null_mut(), 1, 2);
 
             xorshift128plus_destroy(std::ptr::null_mut());
         }
     }
 
+    #[test]
+    fn test_ffi_init_in_place() {
+        unsafe {
+            // u64 storage guarantees the 8-byte alignment the struct needs
+            let mut storage = [0u64; 2];
+            let buf = storage.as_mut_ptr() as *mut u8;
+
+            assert!(xorshift128plus_init_in_place(buf, 16, 1, 4));
+
+            // The buffer now behaves exactly like a heap-allocated RNG
+            let rng = buf as *mut XorShift128PlusRNG;
+            assert_eq!(xorshift128plus_next(rng), 0x800049);
+            assert_eq!(xorshift128plus_next(rng), 0x3000186);
+
+            // Oversized storage is fine; only a minimum is enforced
+            let mut big = [0u64; 4];
+            assert!(xorshift128plus_init_in_place(big.as_mut_ptr() as *mut u8, 32, 1, 4));
+        }
+    }
+
+    #[test]
+    fn test_ffi_init_in_place_rejects_bad_buffers() {
+        unsafe {
+            let mut storage = [0u64; 3];
+            let buf = storage.as_mut_ptr() as *mut u8;
+
+            // Null buffer
+            assert!(!xorshift128plus_init_in_place(std::ptr::null_mut(), 16, 1, 4));
+
+            // Too small
+            assert!(!xorshift128plus_init_in_place(buf, 15, 1, 4));
+
+            // Misaligned (offset by one byte from an 8-byte boundary)
+            assert!(!xorshift128plus_init_in_place(buf.add(1), 16, 1, 4));
+        }
+    }
+
+    #[test]
+    fn test_ffi_global() {
+        // The only test that draws from the process-global RNG, so the
+        // sequence assertions cannot race with other tests
+        xorshift128plus_global_set_state(1, 4);
+        assert_eq!(xorshift128plus_global_next(), 0x800049);
+        assert_eq!(xorshift128plus_global_next(), 0x3000186);
+
+        let d = xorshift128plus_global_next_double();
+        assert!((0.0..1.0).contains(&d));
+    }
+
+    #[test]
+    fn test_ffi_state_getters() {
+        unsafe {
+            let rng = xorshift128plus_new(1, 4);
+            assert_eq!(xorshift128plus_get_state0(rng), 1);
+            assert_eq!(xorshift128plus_get_state1(rng), 4);
+
+            // Reading state does not advance the sequence
+            assert_eq!(xorshift128plus_next(rng), 0x800049);
+
+            // Checkpoint/replay round trip
+            let s0 = xorshift128plus_get_state0(rng);
+            let s1 = xorshift128plus_get_state1(rng);
+            let expected = xorshift128plus_next(rng);
+            xorshift128plus_set_state(rng, s0, s1);
+            assert_eq!(xorshift128plus_next(rng), expected);
+
+            xorshift128plus_destroy(rng);
+
+            // Null pointers yield 0
+            assert_eq!(xorshift128plus_get_state0(std::ptr::null()), 0);
+            assert_eq!(xorshift128plus_get_state1(std::ptr::null()), 0);
+        }
+    }
+
+    #[test]
+    fn test_ffi_xoshiro256pp() {
+        unsafe {
+            let rng = xoshiro256plusplus_new(1, 2, 3, 4);
+            assert!(!rng.is_null());
+
+            // Reference sequence (see xoshiro module tests)
+            assert_eq!(xoshiro256plusplus_next(rng), 0x2800001);
+            assert_eq!(xoshiro256plusplus_next(rng), 0x3800067);
+
+            // setState resets the sequence
+            xoshiro256plusplus_set_state(rng, 1, 2, 3, 4);
+            assert_eq!(xoshiro256plusplus_next(rng), 0x2800001);
+
+            let d = xoshiro256plusplus_next_double(rng);
+            assert!((0.0..1.0).contains(&d));
+
+            xoshiro256plusplus_destroy(rng);
+
+            // Single-seed constructor matches the Rust-side one
+            let rng = xoshiro256plusplus_new_from_u64_seed(42);
+            let mut expected = Xoshiro256PlusPlus::from_seed_u64(42);
+            assert_eq!(xoshiro256plusplus_next(rng), expected.next());
+            xoshiro256plusplus_destroy(rng);
+
+            // Null safety
+            assert_eq!(xoshiro256plusplus_next(std::ptr::null_mut()), 0);
+            assert_eq!(xoshiro256plusplus_next_double(std::ptr::null_mut()), 0.0);
+            xoshiro256plusplus_set_state(std::ptr::null_mut(), 1, 2, 3, 4);
+            xoshiro256plusplus_destroy(std::ptr::null_mut());
+        }
+
+        assert_eq!(xoshiro256plusplus_offset_of_state(2), 16);
+        assert_eq!(xoshiro256plusplus_size_of(), 32);
+    }
+
+    #[test]
+    fn test_ffi_xoroshiro128p() {
+        unsafe {
+            let rng = xoroshiro128plus_new(1, 2);
+            assert!(!rng.is_null());
+
+            // Reference sequence (see xoshiro module tests)
+            assert_eq!(xoroshiro128plus_next(rng), 0x3);
+            assert_eq!(xoroshiro128plus_next(rng), 0x6001030003);
+
+            xoroshiro128plus_set_state(rng, 1, 2);
+            assert_eq!(xoroshiro128plus_next(rng), 0x3);
+
+            let d = xoroshiro128plus_next_double(rng);
+            assert!((0.0..1.0).contains(&d));
+
+            xoroshiro128plus_destroy(rng);
+
+            let rng = xoroshiro128plus_new_from_u64_seed(42);
+            let mut expected = Xoroshiro128Plus::from_seed_u64(42);
+            assert_eq!(xoroshiro128plus_next(rng), expected.next());
+            xoroshiro128plus_destroy(rng);
+
+            // Null safety
+            assert_eq!(xoroshiro128plus_next(std::ptr::null_mut()), 0);
+            assert_eq!(xoroshiro128plus_next_double(std::ptr::null_mut()), 0.0);
+            xoroshiro128plus_set_state(std::ptr::null_mut(), 1, 2);
+            xoroshiro128plus_destroy(std::ptr::null_mut());
+        }
+
+        assert_eq!(xoroshiro128plus_offset_of_state0(), 0);
+        assert_eq!(xoroshiro128plus_offset_of_state1(), 8);
+        assert_eq!(xoroshiro128plus_size_of(), 16);
+    }
+
     #[test]
     fn test_ffi_offsets() {
         // Verify offset functions return correct values
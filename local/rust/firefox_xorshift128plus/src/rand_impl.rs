@@ -0,0 +1,157 @@
+// -*- Mode: rust; tab-width: 4; indent-tabs-mode: nil; c-basic-offset: 2 -*-
+// vim: set ts=4 sts=2 et sw=2 tw=80:
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+//! rand ecosystem integration (feature = "rand")
+//!
+//! Implements `rand_core::RngCore` and `rand_core::SeedableRng` for
+//! [`XorShift128PlusRNG`] so Rust-side Gecko components can plug the shared
+//! generator into rand's distributions, shuffles, and samplers without
+//! wrapping it by hand. The impls are feature-gated so the production
+//! staticlib keeps zero external dependencies.
+
+use crate::{SplitMix64, XorShift128PlusRNG};
+use rand_core::{impls, Error, RngCore, SeedableRng};
+
+impl RngCore for XorShift128PlusRNG {
+    #[inline]
+    fn next_u32(&mut self) -> u32 {
+        // xorshift128+'s weakest bits are the low ones; hand out the high
+        // half when only 32 bits are wanted.
+        (self.next() >> 32) as u32
+    }
+
+    #[inline]
+    fn next_u64(&mut self) -> u64 {
+        self.next()
+    }
+
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        impls::fill_bytes_via_next(self, dest)
+    }
+
+    fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), Error> {
+        self.fill_bytes(dest);
+        Ok(())
+    }
+}
+
+impl SeedableRng for XorShift128PlusRNG {
+    type Seed = [u8; 16];
+
+    /// Build from 16 seed bytes interpreted as two little-endian u64 state
+    /// words. An all-zero seed (which the raw constructor rejects) is
+    /// expanded through SplitMix64 instead, matching `seed_from_u64(0)`.
+    fn from_seed(seed: Self::Seed) -> Self {
+        let state0 = u64::from_le_bytes(seed[..8].try_into().unwrap());
+        let state1 = u64::from_le_bytes(seed[8..].try_into().unwrap());
+        if state0 == 0 && state1 == 0 {
+            Self::from_seed_u64(0)
+        } else {
+            Self::new(state0, state1)
+        }
+    }
+
+    /// Delegates to [`XorShift128PlusRNG::from_seed_u64`], i.e. SplitMix64
+    /// expansion, rather than rand_core's default PCG-based expansion, so
+    /// Rust and C++ callers seeding from the same u64 agree.
+    fn seed_from_u64(state: u64) -> Self {
+        Self::from_seed_u64(state)
+    }
+}
+
+impl RngCore for SplitMix64 {
+    #[inline]
+    fn next_u32(&mut self) -> u32 {
+        (self.next() >> 32) as u32
+    }
+
+    #[inline]
+    fn next_u64(&mut self) -> u64 {
+        self.next()
+    }
+
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        impls::fill_bytes_via_next(self, dest)
+    }
+
+    fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), Error> {
+        self.fill_bytes(dest);
+        Ok(())
+    }
+}
+
+impl SeedableRng for SplitMix64 {
+    type Seed = [u8; 8];
+
+    fn from_seed(seed: Self::Seed) -> Self {
+        Self::new(u64::from_le_bytes(seed))
+    }
+
+    fn seed_from_u64(state: u64) -> Self {
+        Self::new(state)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_next_u64_matches_next() {
+        let mut a = XorShift128PlusRNG::new(1, 4);
+        let mut b = XorShift128PlusRNG::new(1, 4);
+        for _ in 0..10 {
+            assert_eq!(RngCore::next_u64(&mut a), b.next());
+        }
+    }
+
+    #[test]
+    fn test_next_u32_uses_high_bits() {
+        let mut a = XorShift128PlusRNG::new(1, 4);
+        let mut b = XorShift128PlusRNG::new(1, 4);
+        assert_eq!(a.next_u32(), (b.next() >> 32) as u32);
+    }
+
+    #[test]
+    fn test_from_seed_round_trip() {
+        let mut seed = [0u8; 16];
+        seed[..8].copy_from_slice(&1u64.to_le_bytes());
+        seed[8..].copy_from_slice(&4u64.to_le_bytes());
+        let mut rng = XorShift128PlusRNG::from_seed(seed);
+        assert_eq!(rng.next(), 0x800049); // same as new(1, 4)
+    }
+
+    #[test]
+    fn test_from_seed_all_zero_is_valid() {
+        let mut rng = XorShift128PlusRNG::from_seed([0u8; 16]);
+        let mut expected = XorShift128PlusRNG::from_seed_u64(0);
+        assert_eq!(rng.next(), expected.next());
+    }
+
+    #[test]
+    fn test_seed_from_u64_matches_from_seed_u64() {
+        let mut a = <XorShift128PlusRNG as SeedableRng>::seed_from_u64(7);
+        let mut b = XorShift128PlusRNG::from_seed_u64(7);
+        assert_eq!(a.next(), b.next());
+    }
+
+    #[test]
+    fn test_fill_bytes() {
+        let mut rng = XorShift128PlusRNG::new(1, 4);
+        let mut buf = [0u8; 37]; // deliberately not a multiple of 8
+        rng.fill_bytes(&mut buf);
+        assert!(buf.iter().any(|&b| b != 0));
+
+        let mut rng = XorShift128PlusRNG::new(1, 4);
+        assert!(rng.try_fill_bytes(&mut buf).is_ok());
+    }
+
+    #[test]
+    fn test_splitmix_rngcore() {
+        let mut a = <SplitMix64 as SeedableRng>::seed_from_u64(0);
+        assert_eq!(RngCore::next_u64(&mut a), 0xE220A8397B1DCDAF);
+    }
+}
@@ -0,0 +1,305 @@
+// -*- Mode: rust; tab-width: 4; indent-tabs-mode: nil; c-basic-offset: 2 -*-
+// vim: set ts=4 sts=2 et sw=2 tw=80:
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+//! Built-in statistical self-tests (feature = "verify")
+//!
+//! A transcription slip in a shift constant or scrambler still yields a
+//! generator that "looks random" to the eye, so algorithm regressions and
+//! new generators need to be caught mechanically. This module runs a small
+//! battery of classical statistical tests over each generator in the crate:
+//!
+//! - **monobit**: overall one-bit fraction should be near 1/2
+//! - **runs**: count of maximal same-bit runs should match expectation
+//! - **bit-population**: each of the 64 bit positions should be balanced
+//! - **chi-squared**: `next_u64_below` buckets should be uniform
+//!
+//! These are smoke tests, not a substitute for TestU01/PractRand: thresholds
+//! are loose (roughly 5-sigma on fixed seeds) so the battery is fully
+//! deterministic and never flakes in CI, yet a wrong constant fails it
+//! immediately.
+//!
+//! ## Usage
+//! ```text
+//! cargo test --features verify
+//! ```
+//! or programmatically:
+//! ```
+//! use firefox_xorshift128plus::verify::verify_all;
+//!
+//! let report = verify_all();
+//! assert!(report.passed());
+//! println!("{}", report.to_json());
+//! ```
+
+use crate::{SplitMix64, Xoroshiro128Plus, Xoshiro256PlusPlus, XorShift128PlusRNG};
+
+/// Number of 64-bit words drawn per generator for the bit-level tests
+const SAMPLE_WORDS: usize = 4096;
+
+/// Number of bounded draws and bucket count for the chi-squared test
+const CHI_DRAWS: usize = 100_000;
+const CHI_BUCKETS: u64 = 16;
+
+/// Result of one statistical test on one generator.
+pub struct StatCheck {
+    /// Generator name, e.g. `"XorShift128PlusRNG"`.
+    pub generator: &'static str,
+    /// Test name: `"monobit"`, `"runs"`, `"bit_population"`, or
+    /// `"chi_squared"`.
+    pub test: &'static str,
+    /// The computed test statistic (|z| for the z-tests, chi^2 for the
+    /// bucket test).
+    pub statistic: f64,
+    /// The pass threshold the statistic was compared against.
+    pub threshold: f64,
+    /// Whether the statistic stayed under the threshold.
+    pub passed: bool,
+}
+
+/// Aggregate report over every generator and test.
+pub struct VerificationReport {
+    pub checks: Vec<StatCheck>,
+}
+
+impl VerificationReport {
+    /// True if every individual check passed.
+    pub fn passed(&self) -> bool {
+        self.checks.iter().all(|c| c.passed)
+    }
+
+    /// Render the report as JSON. No external dependencies: the shape is
+    /// flat and every value is a name, bool, or number.
+    pub fn to_json(&self) -> String {
+        let mut out = String::from("{\"passed\":");
+        out.push_str(if self.passed() { "true" } else { "false" });
+        out.push_str(",\"checks\":[");
+        for (i, check) in self.checks.iter().enumerate() {
+            if i > 0 {
+                out.push(',');
+            }
+            out.push_str("{\"generator\":\"");
+            out.push_str(check.generator);
+            out.push_str("\",\"test\":\"");
+            out.push_str(check.test);
+            out.push_str("\",\"statistic\":");
+            out.push_str(&format!("{:.4}", check.statistic));
+            out.push_str(",\"threshold\":");
+            out.push_str(&format!("{:.4}", check.threshold));
+            out.push_str(",\"passed\":");
+            out.push_str(if check.passed { "true" } else { "false" });
+            out.push('}');
+        }
+        out.push_str("]}");
+        out
+    }
+}
+
+/// Monobit test: the one-bit fraction over the sample, as a z-score against
+/// the binomial expectation n/2.
+fn monobit(generator: &'static str, words: &[u64]) -> StatCheck {
+    let ones: u64 = words.iter().map(|w| w.count_ones() as u64).sum();
+    let n = (words.len() * 64) as f64;
+    let z = (ones as f64 - n / 2.0).abs() / (n / 4.0).sqrt();
+    let threshold = 5.0;
+    StatCheck { generator, test: "monobit", statistic: z, threshold, passed: z < threshold }
+}
+
+/// Runs test: number of maximal runs of equal bits across the concatenated
+/// bit stream, as a z-score. For unbiased bits the expected count is
+/// (n + 1) / 2 with variance ~ n / 4.
+fn runs(generator: &'static str, words: &[u64]) -> StatCheck {
+    let mut run_count = 1u64;
+    let mut prev = words[0] & 1;
+    let mut seen = 0usize;
+    for &w in words {
+        for bit_index in 0..64 {
+            let bit = (w >> bit_index) & 1;
+            if seen > 0 && bit != prev {
+                run_count += 1;
+            }
+            prev = bit;
+            seen += 1;
+        }
+    }
+    let n = seen as f64;
+    let z = (run_count as f64 - (n + 1.0) / 2.0).abs() / (n / 4.0).sqrt();
+    let threshold = 5.0;
+    StatCheck { generator, test: "runs", statistic: z, threshold, passed: z < threshold }
+}
+
+/// Bit-population test: every one of the 64 bit positions should be set in
+/// about half the sampled words. Reports the worst per-position z-score,
+/// which is exactly the test that catches a weak or stuck output bit.
+fn bit_population(generator: &'static str, words: &[u64]) -> StatCheck {
+    let n = words.len() as f64;
+    let mut worst = 0.0f64;
+    for bit_index in 0..64 {
+        let pop = words.iter().filter(|&&w| (w >> bit_index) & 1 == 1).count();
+        let z = (pop as f64 - n / 2.0).abs() / (n / 4.0).sqrt();
+        worst = worst.max(z);
+    }
+    // 64 positions tested, so allow a little more room than a single z-test
+    let threshold = 5.5;
+    StatCheck {
+        generator,
+        test: "bit_population",
+        statistic: worst,
+        threshold,
+        passed: worst < threshold,
+    }
+}
+
+/// Chi-squared test on bounded outputs: bucket counts from the unbiased
+/// bounded generator against the uniform expectation. With 15 degrees of
+/// freedom the 99.999th percentile is ~44, so 60 is a deterministic-safe
+/// margin that still catches modulo-bias-sized defects.
+fn chi_squared(generator: &'static str, draw: impl FnMut() -> u64) -> StatCheck {
+    let mut draw = draw;
+    let mut counts = [0u64; CHI_BUCKETS as usize];
+    for _ in 0..CHI_DRAWS {
+        counts[draw() as usize] += 1;
+    }
+    let expected = CHI_DRAWS as f64 / CHI_BUCKETS as f64;
+    let chi2: f64 = counts
+        .iter()
+        .map(|&c| {
+            let d = c as f64 - expected;
+            d * d / expected
+        })
+        .sum();
+    let threshold = 60.0;
+    StatCheck {
+        generator,
+        test: "chi_squared",
+        statistic: chi2,
+        threshold,
+        passed: chi2 < threshold,
+    }
+}
+
+/// Run the full battery against one generator's word stream and bounded
+/// draws.
+fn check_generator(
+    generator: &'static str,
+    mut next: impl FnMut() -> u64,
+    below: impl FnMut() -> u64,
+) -> Vec<StatCheck> {
+    let words: Vec<u64> = (0..SAMPLE_WORDS).map(|_| next()).collect();
+    vec![
+        monobit(generator, &words),
+        runs(generator, &words),
+        bit_population(generator, &words),
+        chi_squared(generator, below),
+    ]
+}
+
+/// Verify every generator shipped by this crate.
+///
+/// Seeds are fixed so the report is fully deterministic.
+pub fn verify_all() -> VerificationReport {
+    let mut checks = Vec::new();
+
+    let mut a = XorShift128PlusRNG::from_seed_u64(0x5EED);
+    let mut a2 = XorShift128PlusRNG::from_seed_u64(0x5EED);
+    checks.extend(check_generator(
+        "XorShift128PlusRNG",
+        || a.next(),
+        || a2.next_u64_below(CHI_BUCKETS),
+    ));
+
+    let mut b = SplitMix64::new(0x5EED);
+    let mut b2 = SplitMix64::new(0x5EED);
+    checks.extend(check_generator(
+        "SplitMix64",
+        || b.next(),
+        // SplitMix64 has no bounded draw; bucket via the strong high bits
+        || b2.next() >> (64 - CHI_BUCKETS.trailing_zeros()),
+    ));
+
+    let mut c = Xoshiro256PlusPlus::from_seed_u64(0x5EED);
+    let mut c2 = Xoshiro256PlusPlus::from_seed_u64(0x5EED);
+    checks.extend(check_generator(
+        "Xoshiro256PlusPlus",
+        || c.next(),
+        || c2.next() >> (64 - CHI_BUCKETS.trailing_zeros()),
+    ));
+
+    let mut d = Xoroshiro128Plus::from_seed_u64(0x5EED);
+    let mut d2 = Xoroshiro128Plus::from_seed_u64(0x5EED);
+    checks.extend(check_generator(
+        "Xoroshiro128Plus",
+        || d.next(),
+        || d2.next() >> (64 - CHI_BUCKETS.trailing_zeros()),
+    ));
+
+    VerificationReport { checks }
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_verify_all_passes() {
+        let report = verify_all();
+        assert!(report.passed(), "report: {}", report.to_json());
+        // 4 generators x 4 tests
+        assert_eq!(report.checks.len(), 16);
+    }
+
+    #[test]
+    fn test_report_json_shape() {
+        let report = verify_all();
+        let json = report.to_json();
+        assert!(json.starts_with("{\"passed\":true"));
+        assert!(json.contains("\"generator\":\"XorShift128PlusRNG\""));
+        assert!(json.contains("\"test\":\"monobit\""));
+        assert!(json.ends_with("]}"));
+    }
+
+    #[test]
+    fn test_battery_catches_broken_generator() {
+        // A counter passes monobit-in-the-large but has a stuck-at-zero
+        // high bit, which bit_population must flag
+        let mut counter = 0u64;
+        let mut counter2 = 0u64;
+        let checks = check_generator(
+            "Counter",
+            || {
+                counter += 1;
+                counter
+            },
+            || {
+                counter2 += 1;
+                counter2 % CHI_BUCKETS
+            },
+        );
+        assert!(
+            checks.iter().any(|c| !c.passed),
+            "battery failed to flag a counter as non-random"
+        );
+    }
+
+    #[test]
+    fn test_failing_check_is_reported() {
+        let mut report = verify_all();
+        report.checks.push(StatCheck {
+            generator: "Broken",
+            test: "monobit",
+            statistic: 99.0,
+            threshold: 5.0,
+            passed: false,
+        });
+        assert!(!report.passed());
+        let json = report.to_json();
+        assert!(json.starts_with("{\"passed\":false"));
+        assert!(json.contains("\"generator\":\"Broken\""));
+    }
+}
@@ -0,0 +1,340 @@
+// -*- Mode: rust; tab-width: 4; indent-tabs-mode: nil; c-basic-offset: 2 -*-
+// vim: set ts=4 sts=2 et sw=2 tw=80:
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+//! Newer Vigna generators: xoshiro256++ and xoroshiro128+
+//!
+//! xorshift128+'s low output bits are linearly weak, which matters for
+//! consumers that mask off the bottom bits (hash table probing, JIT
+//! Math.random specializations). These generators from the same family fix
+//! that (xoshiro256++) or keep the same footprint with a better state
+//! transition (xoroshiro128+):
+//!
+//! Blackman, David and Vigna, Sebastiano (2018). "Scrambled Linear
+//! Pseudorandom Number Generators" (http://prng.di.unimi.it/)
+//!
+//! Both follow the same discipline as [`XorShift128PlusRNG`]: `#[repr(C)]`
+//! layout with compile-time size/offset assertions, state offset accessors
+//! for JIT embedding, SplitMix64 single-u64 seeding, and matching FFI
+//! naming (`xoshiro256plusplus_*`, `xoroshiro128plus_*` in the ffi module).
+//!
+//! [`XorShift128PlusRNG`]: crate::XorShift128PlusRNG
+
+use crate::SplitMix64;
+use std::mem::size_of;
+
+/// xoshiro256++ pseudo-random number generator
+///
+/// 256 bits of state, period 2^256 - 1, and a `++` output scrambler that
+/// removes the weak low bits xorshift128+ exhibits. Preferred for new
+/// consumers that slice the output arbitrarily.
+///
+/// # Thread Safety
+///
+/// Like the other generators in this crate, NOT thread-safe.
+///
+/// # Example
+///
+/// ```
+/// use firefox_xorshift128plus::Xoshiro256PlusPlus;
+///
+/// let mut rng = Xoshiro256PlusPlus::from_seed_u64(42);
+/// let v = rng.next();
+/// let d = rng.next_double();
+/// assert!(d >= 0.0 && d < 1.0);
+/// # let _ = v;
+/// ```
+#[repr(C)]
+#[derive(Debug, Clone, Copy)]
+pub struct Xoshiro256PlusPlus {
+    /// Internal state: four 64-bit values, not all zero
+    state: [u64; 4],
+}
+
+impl Xoshiro256PlusPlus {
+    /// Construct from explicit state words
+    ///
+    /// # Panics
+    ///
+    /// Panics in debug mode if all four values are zero.
+    pub const fn new(s0: u64, s1: u64, s2: u64, s3: u64) -> Self {
+        debug_assert!(
+            s0 != 0 || s1 != 0 || s2 != 0 || s3 != 0,
+            "Xoshiro256PlusPlus: At least one state value must be non-zero"
+        );
+        Self { state: [s0, s1, s2, s3] }
+    }
+
+    /// Construct from a single 64-bit seed via four SplitMix64 outputs,
+    /// per the reference seeding recommendation. Any seed is valid.
+    pub const fn from_seed_u64(seed: u64) -> Self {
+        let mut sm = SplitMix64::new(seed);
+        Self::new(sm.next(), sm.next(), sm.next(), sm.next())
+    }
+
+    /// Generate the next pseudo-random 64-bit number
+    #[inline]
+    #[allow(clippy::should_implement_trait)] // mirrors the C++ next() API
+    pub fn next(&mut self) -> u64 {
+        // Algorithm from the xoshiro256plusplus.c reference implementation
+        let result = self.state[0]
+            .wrapping_add(self.state[3])
+            .rotate_left(23)
+            .wrapping_add(self.state[0]);
+
+        let t = self.state[1] << 17;
+        self.state[2] ^= self.state[0];
+        self.state[3] ^= self.state[1];
+        self.state[1] ^= self.state[2];
+        self.state[0] ^= self.state[3];
+        self.state[2] ^= t;
+        self.state[3] = self.state[3].rotate_left(45);
+
+        result
+    }
+
+    /// Generate a pseudo-random f64 in [0, 1) using 53 mantissa bits,
+    /// same construction as [`XorShift128PlusRNG::next_double`].
+    ///
+    /// [`XorShift128PlusRNG::next_double`]: crate::XorShift128PlusRNG::next_double
+    #[inline]
+    pub fn next_double(&mut self) -> f64 {
+        const MANTISSA_BITS: u32 = 53;
+        let mantissa = self.next() & ((1u64 << MANTISSA_BITS) - 1);
+        (mantissa as f64) / ((1u64 << MANTISSA_BITS) as f64)
+    }
+
+    /// Get a copy of the current state
+    #[inline]
+    pub const fn state(&self) -> [u64; 4] {
+        self.state
+    }
+
+    /// Set the state to specific values
+    ///
+    /// # Panics
+    ///
+    /// Panics in debug mode if all four values are zero.
+    pub const fn set_state(&mut self, s0: u64, s1: u64, s2: u64, s3: u64) {
+        debug_assert!(
+            s0 != 0 || s1 != 0 || s2 != 0 || s3 != 0,
+            "Xoshiro256PlusPlus: At least one state value must be non-zero"
+        );
+        self.state = [s0, s1, s2, s3];
+    }
+
+    /// Byte offset of state[i] within the struct, for JIT direct access
+    #[inline]
+    pub const fn offset_of_state(i: usize) -> usize {
+        i * size_of::<u64>()
+    }
+}
+
+/// xoroshiro128+ pseudo-random number generator
+///
+/// Same 16-byte footprint as xorshift128+ with a stronger state transition.
+/// The `+` scrambler still leaves the lowest bits weak, so prefer
+/// [`Xoshiro256PlusPlus`] unless the 16-byte layout is required; this one
+/// exists for call sites that embed exactly two words but want the better
+/// transition.
+///
+/// # Example
+///
+/// ```
+/// use firefox_xorshift128plus::Xoroshiro128Plus;
+///
+/// let mut rng = Xoroshiro128Plus::new(1, 2);
+/// assert_eq!(rng.next(), 3); // first output is s0 + s1
+/// ```
+#[repr(C)]
+#[derive(Debug, Clone, Copy)]
+pub struct Xoroshiro128Plus {
+    /// Internal state: two 64-bit values, not both zero
+    state: [u64; 2],
+}
+
+impl Xoroshiro128Plus {
+    /// Construct from explicit state words
+    ///
+    /// # Panics
+    ///
+    /// Panics in debug mode if both values are zero.
+    pub const fn new(initial0: u64, initial1: u64) -> Self {
+        debug_assert!(
+            initial0 != 0 || initial1 != 0,
+            "Xoroshiro128Plus: At least one state value must be non-zero"
+        );
+        Self { state: [initial0, initial1] }
+    }
+
+    /// Construct from a single 64-bit seed via SplitMix64. Any seed is
+    /// valid.
+    pub const fn from_seed_u64(seed: u64) -> Self {
+        let mut sm = SplitMix64::new(seed);
+        Self::new(sm.next(), sm.next())
+    }
+
+    /// Generate the next pseudo-random 64-bit number
+    #[inline]
+    #[allow(clippy::should_implement_trait)] // mirrors the C++ next() API
+    pub fn next(&mut self) -> u64 {
+        // Algorithm from the xoroshiro128plus.c reference implementation
+        let s0 = self.state[0];
+        let mut s1 = self.state[1];
+        let result = s0.wrapping_add(s1);
+
+        s1 ^= s0;
+        self.state[0] = s0.rotate_left(24) ^ s1 ^ (s1 << 16);
+        self.state[1] = s1.rotate_left(37);
+
+        result
+    }
+
+    /// Generate a pseudo-random f64 in [0, 1) using 53 mantissa bits
+    #[inline]
+    pub fn next_double(&mut self) -> f64 {
+        const MANTISSA_BITS: u32 = 53;
+        let mantissa = self.next() & ((1u64 << MANTISSA_BITS) - 1);
+        (mantissa as f64) / ((1u64 << MANTISSA_BITS) as f64)
+    }
+
+    /// Get a copy of the current state
+    #[inline]
+    pub const fn state(&self) -> [u64; 2] {
+        self.state
+    }
+
+    /// Set the state to specific values
+    ///
+    /// # Panics
+    ///
+    /// Panics in debug mode if both values are zero.
+    pub const fn set_state(&mut self, state0: u64, state1: u64) {
+        debug_assert!(
+            state0 != 0 || state1 != 0,
+            "Xoroshiro128Plus: At least one state value must be non-zero"
+        );
+        self.state = [state0, state1];
+    }
+
+    /// Byte offset of state[0] within the struct, for JIT direct access
+    #[inline]
+    pub const fn offset_of_state0() -> usize {
+        0
+    }
+
+    /// Byte offset of state[1] within the struct, for JIT direct access
+    #[inline]
+    pub const fn offset_of_state1() -> usize {
+        size_of::<u64>()
+    }
+}
+
+// Compile-time assertions to ensure struct layout matches C++
+const _: () = {
+    assert!(size_of::<Xoshiro256PlusPlus>() == 4 * size_of::<u64>());
+    assert!(Xoshiro256PlusPlus::offset_of_state(0) == 0);
+    assert!(Xoshiro256PlusPlus::offset_of_state(3) == 24);
+
+    assert!(size_of::<Xoroshiro128Plus>() == 2 * size_of::<u64>());
+    assert!(Xoroshiro128Plus::offset_of_state0() == 0);
+    assert!(Xoroshiro128Plus::offset_of_state1() == 8);
+};
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_struct_sizes() {
+        assert_eq!(size_of::<Xoshiro256PlusPlus>(), 32);
+        assert_eq!(size_of::<Xoroshiro128Plus>(), 16);
+    }
+
+    #[test]
+    fn test_xoshiro256pp_reference_sequence() {
+        // Reference outputs of xoshiro256plusplus.c for state {1, 2, 3, 4}
+        let mut rng = Xoshiro256PlusPlus::new(1, 2, 3, 4);
+        assert_eq!(rng.next(), 0x2800001);
+        assert_eq!(rng.next(), 0x3800067);
+        assert_eq!(rng.next(), 0xcc00003800067);
+        assert_eq!(rng.next(), 0xcc201994400b2);
+        assert_eq!(rng.next(), 0x8012a2019ac433cd);
+    }
+
+    #[test]
+    fn test_xoroshiro128p_reference_sequence() {
+        // Reference outputs of xoroshiro128plus.c for state {1, 2}
+        let mut rng = Xoroshiro128Plus::new(1, 2);
+        assert_eq!(rng.next(), 0x3);
+        assert_eq!(rng.next(), 0x6001030003);
+        assert_eq!(rng.next(), 0x20c102c302000c03);
+        assert_eq!(rng.next(), 0x810180670d23ad61);
+        assert_eq!(rng.next(), 0x26d13a4941333a42);
+    }
+
+    #[test]
+    fn test_from_seed_u64_uses_splitmix() {
+        let mut sm = SplitMix64::new(99);
+        let mut expected = Xoshiro256PlusPlus::new(sm.next(), sm.next(), sm.next(), sm.next());
+        assert_eq!(Xoshiro256PlusPlus::from_seed_u64(99).next(), expected.next());
+
+        let mut sm = SplitMix64::new(99);
+        let mut expected = Xoroshiro128Plus::new(sm.next(), sm.next());
+        assert_eq!(Xoroshiro128Plus::from_seed_u64(99).next(), expected.next());
+    }
+
+    #[test]
+    fn test_state_round_trip() {
+        let mut rng = Xoshiro256PlusPlus::from_seed_u64(1);
+        let checkpoint = rng.state();
+        let log: Vec<u64> = (0..5).map(|_| rng.next()).collect();
+        rng.set_state(checkpoint[0], checkpoint[1], checkpoint[2], checkpoint[3]);
+        for expected in log {
+            assert_eq!(rng.next(), expected);
+        }
+
+        let mut rng = Xoroshiro128Plus::from_seed_u64(2);
+        let checkpoint = rng.state();
+        let log: Vec<u64> = (0..5).map(|_| rng.next()).collect();
+        rng.set_state(checkpoint[0], checkpoint[1]);
+        for expected in log {
+            assert_eq!(rng.next(), expected);
+        }
+    }
+
+    #[test]
+    fn test_next_double_range() {
+        let mut a = Xoshiro256PlusPlus::from_seed_u64(3);
+        let mut b = Xoroshiro128Plus::from_seed_u64(3);
+        for _ in 0..1000 {
+            assert!((0.0..1.0).contains(&a.next_double()));
+            assert!((0.0..1.0).contains(&b.next_double()));
+        }
+    }
+
+    #[test]
+    fn test_const_construction() {
+        const A: Xoshiro256PlusPlus = Xoshiro256PlusPlus::from_seed_u64(7);
+        const B: Xoroshiro128Plus = Xoroshiro128Plus::new(1, 2);
+
+        let mut a = A;
+        let mut expected = Xoshiro256PlusPlus::from_seed_u64(7);
+        assert_eq!(a.next(), expected.next());
+
+        let mut b = B;
+        assert_eq!(b.next(), 0x3);
+    }
+
+    #[test]
+    fn test_offsets() {
+        assert_eq!(Xoshiro256PlusPlus::offset_of_state(0), 0);
+        assert_eq!(Xoshiro256PlusPlus::offset_of_state(1), 8);
+        assert_eq!(Xoshiro256PlusPlus::offset_of_state(2), 16);
+        assert_eq!(Xoshiro256PlusPlus::offset_of_state(3), 24);
+        assert_eq!(Xoroshiro128Plus::offset_of_state0(), 0);
+        assert_eq!(Xoroshiro128Plus::offset_of_state1(), 8);
+    }
+}